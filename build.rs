@@ -0,0 +1,83 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// Collects every module's `LESSONS` into $OUT_DIR/lessons.rs, which `registry` includes. A module with lessons
+// says where they're taught with a `TAUGHT` right above its list, that's all it takes to register them:
+//
+//     pub const TAUGHT: Taught = Taught { day: 6, order: 4 };
+//     pub static LESSONS: &[LessonDef] = &[ ... ];
+//
+// The list comes out in curriculum order, by day and then by `order`, each entry behind the same #[cfg] as its
+// `pub mod` so a feature that's off leaves its lessons out.
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    let mut modules = Vec::new();
+    modules.extend(lesson_modules(Path::new("src"), "crate", "src/lib.rs"));
+    modules.extend(lesson_modules(Path::new("src/days"), "crate::days", "src/days/mod.rs"));
+    modules.sort_by_key(|module| module.taught);
+    for pair in modules.windows(2) {
+        if pair[0].taught == pair[1].taught {
+            panic!("{} and {} are both taught at day {}, order {}", pair[0].path, pair[1].path, pair[0].taught.0, pair[0].taught.1);
+        }
+    }
+
+    let mut list = String::from("// Generated by build.rs, every module's lessons in curriculum order\n&[\n");
+    for module in &modules {
+        if let Some(cfg) = &module.cfg {
+            list.push_str(&format!("    {cfg}\n"));
+        }
+        list.push_str(&format!("    ({0}::TAUGHT, {0}::LESSONS),\n", module.path));
+    }
+    list.push_str("]\n");
+    let out = Path::new(&env::var_os("OUT_DIR").expect("cargo sets OUT_DIR")).join("lessons.rs");
+    fs::write(out, list).expect("can't write the lesson list");
+}
+
+struct LessonModule {
+    // e.g. `crate::days::day1`
+    path: String,
+    // The attribute on its `pub mod`, if there is one
+    cfg: Option<String>,
+    // (day, order)
+    taught: (u8, u8),
+}
+
+// The modules in `dir` that have a `LESSONS` list, `parent` is the file declaring them
+fn lesson_modules(dir: &Path, prefix: &str, parent: &str) -> Vec<LessonModule> {
+    let declarations = fs::read_to_string(parent).unwrap_or_else(|err| panic!("can't read {parent}: {err}"));
+    let mut modules = Vec::new();
+    for entry in fs::read_dir(dir).unwrap_or_else(|err| panic!("can't read {}: {err}", dir.display())) {
+        let file = entry.expect("can't read a directory entry").path();
+        if file.extension().is_none_or(|extension| extension != "rs") {
+            continue;
+        }
+        let source = fs::read_to_string(&file).unwrap_or_else(|err| panic!("can't read {}: {err}", file.display()));
+        let lines: Vec<&str> = source.lines().collect();
+        let Some(at) = lines.iter().position(|line| line.starts_with("pub static LESSONS: &[LessonDef]")) else {
+            continue;
+        };
+        let name = file.file_stem().unwrap().to_string_lossy().into_owned();
+        let taught = at.checked_sub(1).and_then(|above| taught(lines[above])).unwrap_or_else(|| {
+            panic!("{} has lessons but no `pub const TAUGHT: Taught = Taught {{ day: N, order: N }};` right above them", file.display())
+        });
+        let declaration = declarations.lines().position(|line| line == format!("pub mod {name};"));
+        let Some(declaration) = declaration else {
+            panic!("{} has lessons but {parent} has no `pub mod {name};`", file.display());
+        };
+        let cfg = declaration
+            .checked_sub(1)
+            .map(|above| declarations.lines().nth(above).unwrap().trim())
+            .filter(|line| line.starts_with("#[cfg("))
+            .map(str::to_string);
+        modules.push(LessonModule { path: format!("{prefix}::{name}"), cfg, taught });
+    }
+    modules
+}
+
+// (day, order) from a `TAUGHT` line
+fn taught(line: &str) -> Option<(u8, u8)> {
+    let fields = line.strip_prefix("pub const TAUGHT: Taught = Taught { day: ")?.strip_suffix(" };")?;
+    let (day, order) = fields.split_once(", order: ")?;
+    Some((day.parse().ok()?, order.parse().ok()?))
+}
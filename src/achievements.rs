@@ -0,0 +1,181 @@
+use crate::exercises::{Exercise, ExerciseSet, Mode};
+use crate::registry;
+use crate::progress::Progress;
+
+// Something to unlock for a milestone, e.g. passing every slice exercise. Once unlocked it stays unlocked,
+// even if the exercises change later.
+pub struct Achievement {
+    // Stored in the progress file, never change it once released
+    pub id: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    earned: fn(&Progress, &ExerciseSet) -> bool,
+}
+
+// "10 borrow exercises" is more than some exercise sets have (the built-in one has 5), so every one of them
+// is enough there. The description has to say so too.
+const BORROW_MASTER_COUNT: usize = 10;
+
+pub static ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "first_move",
+        title: "First move",
+        description: "Pass an exercise about moves",
+        earned: |state, set| set.iter().any(|exercise| exercise.info.topic == "moves" && passed(state, exercise)),
+    },
+    Achievement {
+        id: "borrow_master",
+        title: "Borrow master",
+        description: "Pass 10 borrowing exercises without a hint, or every one if there are fewer",
+        earned: |state, set| {
+            let borrowing: Vec<&Exercise> = set.iter().filter(|exercise| exercise.info.topic == "references").collect();
+            let unaided = borrowing
+                .iter()
+                .filter(|exercise| passed(state, exercise) && state.hints.get(&exercise.info.name).is_none_or(|&hints| hints == 0))
+                .count();
+            !borrowing.is_empty() && unaided >= BORROW_MASTER_COUNT.min(borrowing.len())
+        },
+    },
+    Achievement {
+        id: "slice_and_dice",
+        title: "Slice and dice",
+        description: "Pass every slice exercise",
+        earned: |state, set| every(state, set, |exercise| exercise.info.topic == "slices"),
+    },
+    Achievement {
+        id: "compiler_whisperer",
+        title: "Compiler whisperer",
+        description: "Break every compile-fail exercise with the right error",
+        earned: |state, set| every(state, set, |exercise| exercise.info.mode == Mode::CompileFail),
+    },
+    Achievement {
+        id: "bookworm",
+        title: "Bookworm",
+        description: "Run every lesson to the end",
+        earned: |state, _| registry::lessons().all(|lesson| state.lessons.contains(lesson.name)),
+    },
+];
+
+fn passed(state: &Progress, exercise: &Exercise) -> bool {
+    state.exercises.contains(&exercise.info.name)
+}
+
+// At least one exercise matches and every one that does has passed
+fn every(state: &Progress, set: &ExerciseSet, matches: fn(&Exercise) -> bool) -> bool {
+    let mut matching = set.iter().filter(|exercise| matches(exercise)).peekable();
+    matching.peek().is_some() && matching.all(|exercise| passed(state, exercise))
+}
+
+// Adds every achievement that's been earned to the state, returning the ones that weren't unlocked before
+pub fn unlock(state: &mut Progress, set: &ExerciseSet) -> Vec<&'static Achievement> {
+    let earned: Vec<&'static Achievement> = ACHIEVEMENTS
+        .iter()
+        .filter(|achievement| !state.achievements.contains(achievement.id) && (achievement.earned)(state, set))
+        .collect();
+    state.achievements.extend(earned.iter().map(|achievement| achievement.id.to_string()));
+    earned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exercises::ExerciseInfo;
+    use crate::lesson::Difficulty;
+
+    fn exercise(name: &str, topic: &str, mode: Mode) -> Exercise {
+        let info = ExerciseInfo {
+            name: name.to_string(),
+            path: format!("{name}.rs"),
+            topic: topic.to_string(),
+            mode,
+            difficulty: Difficulty::default(),
+            error: None,
+            hints: Vec::new(),
+            solution: None,
+        };
+        Exercise { info, skeleton: None, solution: None }
+    }
+
+    fn references(count: usize) -> Vec<Exercise> {
+        (0..count).map(|index| exercise(&format!("borrow{index}"), "references", Mode::Compile)).collect()
+    }
+
+    fn pass(state: &mut Progress, names: impl IntoIterator<Item = String>) {
+        state.exercises.extend(names);
+    }
+
+    fn unlocked(state: &mut Progress, exercises: Vec<Exercise>) -> Vec<&'static str> {
+        unlock(state, &ExerciseSet::new("exercises", exercises)).iter().map(|achievement| achievement.id).collect()
+    }
+
+    #[test]
+    fn borrow_master_text_matches_its_threshold() {
+        let borrow_master = ACHIEVEMENTS.iter().find(|achievement| achievement.id == "borrow_master").unwrap();
+        assert!(borrow_master.description.contains(&BORROW_MASTER_COUNT.to_string()), "{}", borrow_master.description);
+    }
+
+    #[test]
+    fn borrow_master_needs_ten_unaided_passes() {
+        let mut state = Progress::default();
+        pass(&mut state, (0..BORROW_MASTER_COUNT - 1).map(|index| format!("borrow{index}")));
+        assert!(unlocked(&mut state, references(12)).is_empty());
+
+        // A hint on one of them doesn't count
+        pass(&mut state, [format!("borrow{}", BORROW_MASTER_COUNT - 1)]);
+        state.hints.insert("borrow0".to_string(), 1);
+        assert!(unlocked(&mut state, references(12)).is_empty());
+
+        // but a hint that was never shown does
+        state.hints.insert("borrow0".to_string(), 0);
+        assert_eq!(unlocked(&mut state, references(12)), ["borrow_master"]);
+    }
+
+    #[test]
+    fn borrow_master_with_fewer_than_ten_needs_all_of_them() {
+        let mut state = Progress::default();
+        pass(&mut state, (0..4).map(|index| format!("borrow{index}")));
+        assert!(unlocked(&mut state, references(5)).is_empty());
+        pass(&mut state, ["borrow4".to_string()]);
+        assert_eq!(unlocked(&mut state, references(5)), ["borrow_master"]);
+        assert!(unlocked(&mut Progress::default(), Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn every_exercise_of_a_kind_has_to_pass() {
+        let exercises = || vec![
+            exercise("slice1", "slices", Mode::Test),
+            exercise("slice2", "slices", Mode::Compile),
+            exercise("move1", "moves", Mode::CompileFail),
+        ];
+        let mut state = Progress::default();
+        pass(&mut state, ["slice1".to_string()]);
+        assert!(unlocked(&mut state, exercises()).is_empty());
+        pass(&mut state, ["slice2".to_string(), "move1".to_string()]);
+        assert_eq!(unlocked(&mut state, exercises()), ["first_move", "slice_and_dice", "compiler_whisperer"]);
+    }
+
+    #[test]
+    fn achievements_unlock_once_and_stay_unlocked() {
+        let mut state = Progress::default();
+        pass(&mut state, ["move1".to_string()]);
+        assert_eq!(unlocked(&mut state, vec![exercise("move1", "moves", Mode::Compile)]), ["first_move"]);
+        assert!(unlocked(&mut state, vec![exercise("move1", "moves", Mode::Compile)]).is_empty());
+        assert!(state.achievements.contains("first_move"));
+
+        // The exercise going away later doesn't take it back
+        state.exercises.clear();
+        assert!(unlocked(&mut state, Vec::new()).is_empty());
+        assert!(state.achievements.contains("first_move"));
+    }
+
+    #[test]
+    fn bookworm_needs_every_lesson() {
+        let mut state = Progress::default();
+        let mut lessons: Vec<&str> = registry::lessons().map(|lesson| lesson.name).collect();
+        let last = lessons.pop().unwrap();
+        state.lessons.extend(lessons.into_iter().map(str::to_string));
+        assert!(unlocked(&mut state, Vec::new()).is_empty());
+        state.lessons.insert(last.to_string());
+        assert_eq!(unlocked(&mut state, Vec::new()), ["bookworm"]);
+    }
+}
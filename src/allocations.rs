@@ -0,0 +1,119 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Counts every allocation the program makes, then hands it to the system allocator. It's only installed with the
+// count-allocations feature, so a `clone()` showing up as one more allocation and a `&String` as none is
+// something you can watch happen:
+//
+//     cargo run --features count-allocations -- run stack_and_heap
+//
+// The runner reads the counters before and after each lesson. What the reporter does with the demo's messages
+// (keeping them, printing them) isn't counted. Building them is the demo's own work and is, a `format!` or an
+// OwnershipEvent allocates too, so compare lessons rather than expect zero.
+pub struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static REALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static FREES: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    // Set by `pause`, a const initialiser so reading it never allocates
+    static PAUSED: Cell<bool> = const { Cell::new(false) };
+}
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn counting() -> bool {
+    // A thread that's shutting down has no thread locals left, count it
+    !PAUSED.try_with(Cell::get).unwrap_or(false)
+}
+
+// The only unsafe code in the crate, GlobalAlloc can't be implemented without it. Everything is passed straight
+// to System, so its safety rules are the caller's, as they would be without the counting.
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if counting() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if counting() {
+            FREES.fetch_add(1, Ordering::Relaxed);
+        }
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if counting() {
+            REALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            BYTES.fetch_add(new_size.saturating_sub(layout.size()), Ordering::Relaxed);
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+// How many times memory was allocated, grown or shrunk in place (a String outgrowing its capacity) and freed.
+// `bytes` is what was asked for, growing counts the difference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Allocations {
+    pub allocations: usize,
+    pub reallocations: usize,
+    pub frees: usize,
+    pub bytes: usize,
+}
+
+impl Allocations {
+    // What happened between `earlier` and these counts
+    pub fn since(&self, earlier: &Allocations) -> Allocations {
+        Allocations {
+            allocations: self.allocations.saturating_sub(earlier.allocations),
+            reallocations: self.reallocations.saturating_sub(earlier.reallocations),
+            frees: self.frees.saturating_sub(earlier.frees),
+            bytes: self.bytes.saturating_sub(earlier.bytes),
+        }
+    }
+}
+
+impl fmt::Display for Allocations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let times = |count: usize| if count == 1 { "once".to_string() } else { format!("{count} times") };
+        write!(f, "this lesson allocated {}, freed {}", times(self.allocations), times(self.frees))?;
+        if self.reallocations > 0 {
+            write!(f, ", and grew or shrank an allocation {}", times(self.reallocations))?;
+        }
+        write!(f, " ({} bytes asked for)", self.bytes)
+    }
+}
+
+// The counts so far, None when the crate wasn't built with count-allocations and nothing is counted
+pub fn counts() -> Option<Allocations> {
+    cfg!(feature = "count-allocations").then(|| Allocations {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        reallocations: REALLOCATIONS.load(Ordering::Relaxed),
+        frees: FREES.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+    })
+}
+
+// Stops counting on this thread until the guard is dropped, pausing again inside leaves it paused
+pub fn pause() -> Paused {
+    Paused { was: PAUSED.with(|paused| paused.replace(true)) }
+}
+
+pub struct Paused {
+    was: bool,
+}
+
+impl Drop for Paused {
+    fn drop(&mut self) {
+        PAUSED.with(|paused| paused.set(self.was));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::flashcards::Card;
+use crate::quiz::{Question, QuestionKind};
+
+// Anki's plain text import: one note per line, front, back and tags separated by tabs. Fields are HTML, so code
+// goes in a <pre> block to keep its indentation and the line breaks become <br>, a newline would end the note.
+// The header lines at the top tell Anki all that, nothing needs setting in the import dialog.
+const HEADER: &str = "#separator:tab\n#html:true\n#tags column:3\n";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Note {
+    pub front: String,
+    pub back: String,
+    pub tags: Vec<String>,
+}
+
+impl Note {
+    pub fn from_card(card: &Card) -> Self {
+        let mut back = text(&card.back);
+        if let Some(code) = &card.code {
+            back.push_str(&pre(code));
+        }
+        Self { front: text(&card.front), back, tags: tags(&card.topic, "flashcard") }
+    }
+
+    // The question as it's asked in `quiz`, the back has the right answer and the explanation
+    pub fn from_question(question: &Question) -> Self {
+        let mut front = text(&question.prompt);
+        let answer = match &question.kind {
+            QuestionKind::MultipleChoice { choices, answer } => {
+                front.push_str("<ol type=\"a\">");
+                for choice in choices {
+                    front.push_str(&format!("<li>{}</li>", text(choice)));
+                }
+                front.push_str("</ol>");
+                format!("{}) {}", (b'a' + *answer as u8) as char, text(&choices[*answer]))
+            }
+            QuestionKind::TrueFalse { .. } => {
+                front.push_str("<br><i>true or false?</i>");
+                text(&question.expected())
+            }
+            QuestionKind::PredictOutput { code, output } => {
+                front.push_str(&pre(code));
+                pre(output)
+            }
+            QuestionKind::FillInBlank { code, answers } => {
+                front.push_str(&pre(code));
+                answers.iter().map(|answer| format!("<code>{}</code>", escape(answer))).collect::<Vec<_>>().join(" or ")
+            }
+            QuestionKind::WillItCompile { code, error } => {
+                front.push_str(&pre(code));
+                match error {
+                    None => "yes".to_string(),
+                    Some(error) => format!("no, <code>error[{}]: {}</code>", escape(&error.code), escape(&error.message)),
+                }
+            }
+        };
+        let back = format!("<b>{answer}</b><br><br>{}", text(&question.explanation));
+        Self { front, back, tags: tags(&question.topic, "quiz") }
+    }
+}
+
+// The whole file, header included
+pub fn tsv(notes: &[Note]) -> String {
+    let mut file = HEADER.to_string();
+    for note in notes {
+        file.push_str(&format!("{}\t{}\t{}\n", note.front, note.back, note.tags.join(" ")));
+    }
+    file
+}
+
+// Tags can't have spaces, Anki would split them into two
+fn tags(topic: &str, kind: &str) -> Vec<String> {
+    vec!["rust_day_6".to_string(), topic.replace(' ', "_"), kind.to_string()]
+}
+
+// Prose, with `code spans` as <code>
+fn text(text: &str) -> String {
+    let mut html = String::new();
+    for (index, part) in text.split('`').enumerate() {
+        if index % 2 == 1 {
+            html.push_str(&format!("<code>{}</code>", escape(part)));
+        } else {
+            html.push_str(&escape(part));
+        }
+    }
+    html.replace('\n', "<br>")
+}
+
+fn pre(code: &str) -> String {
+    format!("<pre><code>{}</code></pre>", escape(code.trim_end()).replace('\n', "<br>"))
+}
+
+// Also takes out tabs, which would start a new field, and quotes, which Anki would read as CSV quoting
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\t', "    ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quiz::CompileError;
+
+    fn question(kind: QuestionKind) -> Question {
+        Question {
+            id: "moves-test".to_string(),
+            topic: "moves".to_string(),
+            prompt: "What does `s1` hold?".to_string(),
+            kind,
+            explanation: "It was moved.".to_string(),
+            lesson: None,
+        }
+    }
+
+    #[test]
+    fn prose_gets_code_spans_and_escapes() {
+        assert_eq!(text("use `Vec<T>` & more\nnext"), "use <code>Vec&lt;T&gt;</code> &amp; more<br>next");
+        assert_eq!(escape("a\t\"b\""), "a    &quot;b&quot;");
+        assert_eq!(pre("fn main() {\n    x();\n}\n\n"), "<pre><code>fn main() {<br>    x();<br>}</code></pre>");
+    }
+
+    #[test]
+    fn cards_keep_their_code_and_tags() {
+        let card = Card {
+            id: "box-size".to_string(),
+            topic: "smart pointers".to_string(),
+            front: "How big is a `Box<T>`?".to_string(),
+            back: "One pointer".to_string(),
+            code: Some("let b = Box::new(5);".to_string()),
+        };
+        assert_eq!(
+            Note::from_card(&card),
+            Note {
+                front: "How big is a <code>Box&lt;T&gt;</code>?".to_string(),
+                back: "One pointer<pre><code>let b = Box::new(5);</code></pre>".to_string(),
+                tags: vec!["rust_day_6".to_string(), "smart_pointers".to_string(), "flashcard".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn each_kind_of_question_puts_its_answer_on_the_back() {
+        let choice = Note::from_question(&question(QuestionKind::MultipleChoice { choices: vec!["nothing".to_string(), "hello".to_string()], answer: 1 }));
+        assert_eq!(choice.front, "What does <code>s1</code> hold?<ol type=\"a\"><li>nothing</li><li>hello</li></ol>");
+        assert_eq!(choice.back, "<b>b) hello</b><br><br>It was moved.");
+
+        let true_false = Note::from_question(&question(QuestionKind::TrueFalse { answer: false }));
+        assert!(true_false.front.ends_with("<br><i>true or false?</i>"));
+        assert!(true_false.back.starts_with("<b>false</b>"));
+
+        let output = Note::from_question(&question(QuestionKind::PredictOutput { code: "println!(\"hi\");".to_string(), output: "hi\n".to_string() }));
+        assert!(output.front.ends_with("<pre><code>println!(&quot;hi&quot;);</code></pre>"));
+        assert!(output.back.starts_with("<b><pre><code>hi</code></pre></b>"));
+
+        let blank = Note::from_question(&question(QuestionKind::FillInBlank { code: "let r = ___s;".to_string(), answers: vec!["&".to_string(), "& ".to_string()] }));
+        assert!(blank.back.starts_with("<b><code>&amp;</code> or <code>&amp; </code></b>"));
+
+        let error = CompileError { code: "E0382".to_string(), message: "borrow of moved value: `s1`".to_string() };
+        let rejected = Note::from_question(&question(QuestionKind::WillItCompile { code: "let s2 = s1;".to_string(), error: Some(error) }));
+        assert!(rejected.back.starts_with("<b>no, <code>error[E0382]: borrow of moved value: `s1`</code></b>"));
+        let compiles = Note::from_question(&question(QuestionKind::WillItCompile { code: "let x = 5;".to_string(), error: None }));
+        assert!(compiles.back.starts_with("<b>yes</b>"));
+        assert_eq!(compiles.tags, ["rust_day_6", "moves", "quiz"]);
+    }
+
+    #[test]
+    fn the_file_is_the_header_then_a_line_per_note() {
+        let note = Note { front: "front".to_string(), back: "back".to_string(), tags: vec!["a".to_string(), "b".to_string()] };
+        assert_eq!(tsv(&[note.clone(), note]), format!("{HEADER}front\tback\ta b\nfront\tback\ta b\n"));
+    }
+
+    #[test]
+    fn no_built_in_card_or_question_breaks_a_line() {
+        let cards = crate::flashcards::cards();
+        let questions = crate::quiz::bank();
+        for note in cards.iter().map(Note::from_card).chain(questions.iter().map(Note::from_question)) {
+            for field in [&note.front, &note.back] {
+                assert!(!field.contains('\t') && !field.contains('\n'), "{field}");
+            }
+        }
+    }
+}
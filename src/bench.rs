@@ -0,0 +1,138 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use crate::json::{Json, ToJson};
+
+// Two ways of doing the same thing, timed against each other
+pub struct Experiment {
+    pub name: &'static str,
+    // The claim from the notes being checked
+    pub claim: &'static str,
+    pub baseline: (&'static str, fn(u64)),
+    pub contender: (&'static str, fn(u64)),
+}
+
+// How long each side of an experiment took per iteration, in nanoseconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    pub name: &'static str,
+    pub claim: &'static str,
+    pub baseline: (&'static str, f64),
+    pub contender: (&'static str, f64),
+}
+
+impl BenchResult {
+    // How many times slower the contender was than the baseline, None when the baseline was too fast to measure
+    pub fn ratio(&self) -> Option<f64> {
+        (self.baseline.1 >= 0.05).then(|| self.contender.1 / self.baseline.1)
+    }
+}
+
+impl ToJson for BenchResult {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("name", self.name.into()),
+            ("claim", self.claim.into()),
+            ("baseline", self.baseline.0.into()),
+            ("baseline_ns", self.baseline.1.into()),
+            ("contender", self.contender.0.into()),
+            ("contender_ns", self.contender.1.into()),
+            ("ratio", self.ratio().into()),
+        ])
+    }
+}
+
+#[allow(clippy::ptr_arg)] // Borrows exactly the String the clone copies, so the two sides measure the same thing
+fn length_of(s: &String) -> usize {
+    s.len()
+}
+
+fn length_of_owned(s: String) -> usize {
+    s.len()
+}
+
+pub static EXPERIMENTS: &[Experiment] = &[
+    Experiment {
+        name: "reference vs clone",
+        claim: "Passing a reference doesn't copy anything, clone copies the heap data",
+        baseline: ("&String", |iterations| {
+            let s = String::from("hello world, this is a heap allocated string");
+            for _ in 0..iterations {
+                black_box(length_of(black_box(&s)));
+            }
+        }),
+        contender: ("clone()", |iterations| {
+            let s = String::from("hello world, this is a heap allocated string");
+            for _ in 0..iterations {
+                black_box(length_of_owned(black_box(&s).clone()));
+            }
+        }),
+    },
+    Experiment {
+        name: "move vs clone",
+        claim: "A move copies only the pointer, length and capacity, clone allocates",
+        baseline: ("move", |iterations| {
+            let mut s = String::from("hello");
+            for _ in 0..iterations {
+                let moved = black_box(s); // Moves s, no allocation
+                s = moved;
+            }
+            black_box(s);
+        }),
+        contender: ("clone()", |iterations| {
+            let s = String::from("hello");
+            for _ in 0..iterations {
+                black_box(black_box(&s).clone());
+            }
+        }),
+    },
+    Experiment {
+        name: "stack vs heap",
+        claim: "Values of a known size live on the stack, which is cheaper than asking the allocator",
+        baseline: ("i32", |iterations| {
+            for i in 0..iterations {
+                black_box(black_box(i as i32));
+            }
+        }),
+        contender: ("Box<i32>", |iterations| {
+            for i in 0..iterations {
+                black_box(Box::new(black_box(i as i32)));
+            }
+        }),
+    },
+    Experiment {
+        name: "literal vs String::from",
+        claim: "A string literal is a slice into the binary, String::from copies it onto the heap",
+        baseline: ("&str", |iterations| {
+            for _ in 0..iterations {
+                black_box(black_box("hello world").len());
+            }
+        }),
+        contender: ("String::from", |iterations| {
+            for _ in 0..iterations {
+                black_box(String::from(black_box("hello world")).len());
+            }
+        }),
+    },
+];
+
+// Nanoseconds per iteration
+fn time(iterations: u64, run: fn(u64)) -> f64 {
+    run(iterations / 10 + 1); // Warm up caches and the allocator
+    let started = Instant::now();
+    run(iterations);
+    started.elapsed().as_secs_f64() * 1e9 / iterations.max(1) as f64
+}
+
+// Runs every experiment for `iterations` iterations per side
+pub fn run(iterations: u64) -> Vec<BenchResult> {
+    EXPERIMENTS
+        .iter()
+        .map(|experiment| BenchResult {
+            name: experiment.name,
+            claim: experiment.claim,
+            baseline: (experiment.baseline.0, time(iterations, experiment.baseline.1)),
+            contender: (experiment.contender.0, time(iterations, experiment.contender.1)),
+        })
+        .collect()
+}
@@ -0,0 +1,126 @@
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+use crate::simulator::Program;
+
+// The reference rules are in `notes` (`rust_day_6 notes references`)
+
+pub const TAUGHT: Taught = Taught { day: 6, order: 2 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(reference_demo {
+        name: "reference",
+        summary: "Immutable references read a value without owning it",
+        topics: &["borrowing", "references"],
+        prerequisites: &["takes_and_gives_back"],
+        explanation: "A reference (`&s5`) lets a function use a value without taking ownership of it, so the caller can still use it afterwards. References must always be valid: they can't outlive the value they point to, so there are no dangling references.",
+    }),
+    lesson!(mutate_reference_demo {
+        name: "mutate_reference",
+        summary: "Mutable references change a value without owning it",
+        topics: &["borrowing", "references"],
+        prerequisites: &["reference"],
+        explanation: "References are immutable by default, `&mut` makes a reference that can change the value. In any scope you can have either one mutable reference or any number of immutable ones, which is how Rust prevents data races at compile time.",
+    }),
+    lesson!(borrow_checker_demo {
+        name: "borrow_checker",
+        summary: "Which small programs the borrow checker accepts, and what it says about the rest",
+        topics: &["borrowing", "references"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["mutate_reference"],
+        explanation: "A borrow lasts from the `&` until the reference is last used, not until the end of its scope, so a `&mut` can follow shared references that are done. Moving a value out while a reference to it is still going to be used, or letting the value go out of scope before its reference, breaks the reference rules and is rejected.",
+    }),
+];
+
+// Uses reference to the value, so can use without taking ownership, a &String or a literal
+pub fn reference(my_string: &str) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(my_string);
+    output
+}
+
+// References are immutable by default, but it is possible to make them mutable
+pub fn mutate_reference(my_mut_string: &mut String) -> DemoOutput {
+    my_mut_string.push_str("(added) world!");
+    let mut output = DemoOutput::new();
+    output.value("my_mut_string", &my_mut_string);
+    output
+}
+
+// Borrowing a value lets a function read it without taking ownership
+pub fn reference_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let s5 = String::from("world");
+    reporter.event(&OwnershipEvent::borrowed("s5", "my_string"));
+    reporter.output(&reference(&s5));
+    reporter.message(&format!("s5: {}", s5));
+    Ok(())
+}
+
+// A mutable reference lets a function change a value it doesn't own
+pub fn mutate_reference_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let mut my_mut_string = String::from("Hello world");
+    reporter.event(&OwnershipEvent::borrowed_mut("my_mut_string", "my_mut_string"));
+    reporter.output(&mutate_reference(&mut my_mut_string));
+    reporter.message(&my_mut_string); // You can only have one mutable reference to a value at a time, prevents race conditions
+    Ok(())
+}
+
+// Programs for the borrow checker simulator, accepted or not
+const BORROW_CHECKER_PROGRAMS: &[&str] = &[
+    "let mut s = String::from(\"hello\");\nlet r1 = &s;\nprintln!(\"{}\", r1);\nlet r2 = &mut s; // r1 isn't used again, its borrow is over\nprintln!(\"{}\", r2);",
+    "let s1 = String::from(\"hello\");\nlet r = &s1;\nlet s2 = s1;\nprintln!(\"{}\", r);",
+    "let mut s = String::from(\"hello\");\nlet r1 = &mut s;\nlet r2 = &mut s;\nprintln!(\"{}, {}\", r1, r2);",
+    "let r;\n{\n    let x = String::from(\"inner\");\n    r = &x;\n}\nprintln!(\"{}\", r);",
+];
+
+// Runs each program through `simulator`, the rustc-like error for the ones it rejects
+pub fn borrow_checker_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    for (index, source) in BORROW_CHECKER_PROGRAMS.iter().enumerate() {
+        if index > 0 {
+            reporter.message("");
+        }
+        let program = Program::parse(source)?;
+        for line in &program.lines {
+            reporter.message(&format!("    {line}"));
+        }
+        match program.check() {
+            Ok(()) => reporter.message("compiles"),
+            Err(rejection) => {
+                for line in program.render(&rejection).lines() {
+                    reporter.message(line);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutate_reference_changes_the_caller_string() {
+        let mut s = String::from("Hello world");
+        let output = mutate_reference(&mut s);
+        assert_eq!(s, "Hello world(added) world!");
+        assert_eq!(output.values, [("my_mut_string".to_string(), s)]);
+    }
+
+    #[test]
+    fn mutate_reference_demo_borrows_mutably_once() {
+        let mut output = DemoOutput::new();
+        mutate_reference_demo(&mut output).unwrap();
+        assert_eq!(output.messages, ["Hello world(added) world!"]);
+        assert_eq!(output.events, [OwnershipEvent::borrowed_mut("my_mut_string", "my_mut_string")]);
+    }
+
+    #[test]
+    fn reference_demo_leaves_s5_usable() {
+        let mut output = DemoOutput::new();
+        reference_demo(&mut output).unwrap();
+        assert_eq!(output.messages, ["world", "s5: world"]);
+        assert_eq!(output.events, [OwnershipEvent::borrowed("s5", "my_string")]);
+    }
+}
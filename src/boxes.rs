@@ -0,0 +1,335 @@
+use std::fmt;
+use std::ptr;
+
+use crate::error::LessonError;
+use crate::layout::{self, Layout};
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+use crate::tracked::{Tracked, Tracker};
+
+// A Box<T> owns one T on the heap and is itself just the pointer to it, so moving a Box moves a pointer however
+// big the T is, and dropping the Box drops the T and frees its block. That single pointer is also what lets a type
+// contain itself: a List holding a List would be infinitely big, a List holding a Box<List> is two words.
+
+pub const TAUGHT: Taught = Taught { day: 11, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(box_basics_demo {
+        name: "box_basics",
+        summary: "Box::new puts one value on the heap, and the Box owns it",
+        topics: &["smart-pointers", "ownership"],
+        prerequisites: &["take_ownership", "layout"],
+        explanation: "`Box::new(value)` moves the value into a heap block and returns a Box, one pointer on the stack, that owns it. `*boxed` gets at the value, and most methods reach through the Box without it. Moving the Box, into a variable or a function, copies only the pointer, the value stays where it is on the heap. `*boxed` can also move the value back out, which uses the Box up and frees its block.",
+    }),
+    lesson!(recursive_types_demo {
+        name: "recursive_types",
+        summary: "A type can't contain itself, but it can contain a Box of itself",
+        topics: &["smart-pointers", "ownership"],
+        prerequisites: &["box_basics"],
+        explanation: "rustc needs to know every type's size, and `enum List { Cons(i32, List), Nil }` would need room for a List inside a List inside a List, forever (E0072). A `Box<List>` is one pointer whatever the List it points to, so `Cons(i32, Box<List>)` has a size and the rest of the list lives on the heap. Trees work the same way: each node has an `Option<Box<Node>>` per child, None where there isn't one, and the niche keeps that Option as small as the Box.",
+    }),
+    lesson!(box_drop_demo {
+        name: "box_drop",
+        summary: "Dropping a Box drops what it owns, all the way down a recursive list",
+        topics: &["smart-pointers", "ownership"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["recursive_types", "tracked"],
+        explanation: "When a Box goes out of scope it drops its value and then frees the heap block. A recursive type drops like any other struct or enum, its fields in the order they're declared, so dropping the head of a list drops its value, then its Box, which drops the next link's value, then the next Box, down to the end. Moving a link's Box out to a new owner cuts the list there: the new owner decides when the rest is dropped.",
+    }),
+];
+
+// The box lesson's types, measured with the layout lesson's table
+pub fn box_layouts() -> Vec<Layout> {
+    vec![
+        Layout::of::<i32>("i32", "the value itself"),
+        Layout::of::<Box<i32>>("Box<i32>", "a pointer to an i32 on the heap"),
+        Layout::of::<Box<String>>("Box<String>", "still one pointer, the String's three words are on the heap"),
+        Layout::of::<Box<[i32]>>("Box<[i32]>", "a boxed slice, pointer and length"),
+        Layout::of::<List>("List", "an i32 and a Box<List>, or Nil"),
+        Layout::of::<Box<List>>("Box<List>", "one pointer, whatever the list behind it"),
+        Layout::of::<Node>("Node", "an i32 and two Option<Box<Node>>s"),
+        Layout::of::<Box<Node>>("Box<Node>", "a child, its Option is None as the null pointer, a Box never is"),
+    ]
+}
+
+// Prints the rows of the layout table for the named types
+fn layout_rows(reporter: &mut dyn Reporter, names: &[&str]) {
+    let layouts: Vec<Layout> = box_layouts().into_iter().filter(|layout| names.contains(&layout.name)).collect();
+    for line in layout::table(&layouts) {
+        reporter.message(&line);
+    }
+}
+
+// Takes a Box by value, only the pointer moves in
+pub fn shout(boxed: Box<String>) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(boxed.to_uppercase()).value("boxed", &boxed);
+    output
+} // boxed dropped here, the String and its heap block with it
+
+// Boxes a String, moves the Box around, checks the value never moved, then moves it back out
+pub fn box_basics_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    layout_rows(reporter, &["i32", "Box<i32>", "Box<String>", "Box<[i32]>"]);
+    reporter.message("");
+
+    let number = Box::new(5); // The 5 lives on the heap, number is the pointer to it
+    reporter.message(&format!("number: {number}, *number + 1: {}", *number + 1));
+
+    let name = Box::new(String::from("ferris"));
+    let address: *const String = &*name; // Where the String is on the heap
+    let owner = name; // Moves the Box, the pointer is copied and name can't be used
+    reporter.event(&OwnershipEvent::moved("name", "owner"));
+    reporter.message(&format!("name: error, owner: {owner}, the String hasn't moved: {}", ptr::eq(address, &*owner)));
+
+    let copy = owner.clone(); // A new Box with its own heap String
+    reporter.event(&OwnershipEvent::cloned("owner", "copy"));
+    reporter.message(&format!("copy is a new block: {}", !ptr::eq(address, &*copy)));
+    reporter.event(&OwnershipEvent::moved("copy", "boxed"));
+    reporter.output(&shout(copy));
+
+    let unboxed: String = *owner; // Moves the String out, owner is used up and its heap block freed
+    reporter.event(&OwnershipEvent::moved("*owner", "unboxed"));
+    reporter.message(&format!("unboxed: {unboxed}, owner: error"));
+    Ok(())
+}
+
+// A list of i32s, each Cons holding the rest of the list in a Box so List has a size
+pub enum List {
+    Cons(i32, Box<List>),
+    Nil,
+}
+
+impl List {
+    // Builds the list from the back, each element wrapping what's been built so far
+    pub fn from_slice(values: &[i32]) -> List {
+        values.iter().rev().fold(List::Nil, |rest, &value| List::Cons(value, Box::new(rest)))
+    }
+
+    // Walks the list through references, one Box at a time
+    pub fn sum(&self) -> i32 {
+        match self {
+            List::Cons(value, rest) => value + rest.sum(),
+            List::Nil => 0,
+        }
+    }
+}
+
+impl fmt::Display for List {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            List::Cons(value, rest) => write!(f, "Cons({value}, {rest})"),
+            List::Nil => write!(f, "Nil"),
+        }
+    }
+}
+
+// A binary search tree node, each child is either missing or a Box owning the whole subtree
+pub struct Node {
+    pub value: i32,
+    pub left: Option<Box<Node>>,
+    pub right: Option<Box<Node>>,
+}
+
+impl Node {
+    pub fn new(value: i32) -> Self {
+        Node { value, left: None, right: None }
+    }
+
+    // Walks down through &mut Option<Box<Node>> until it finds an empty spot, and boxes the new node there
+    pub fn insert(&mut self, value: i32) {
+        let child = if value < self.value { &mut self.left } else { &mut self.right };
+        match child {
+            Some(node) => node.insert(value),
+            None => *child = Some(Box::new(Node::new(value))),
+        }
+    }
+
+    // The values smallest first, left subtree, this node, right subtree
+    pub fn in_order(&self, values: &mut Vec<i32>) {
+        if let Some(left) = &self.left {
+            left.in_order(values);
+        }
+        values.push(self.value);
+        if let Some(right) = &self.right {
+            right.in_order(values);
+        }
+    }
+
+    // The number of nodes on the longest path down
+    pub fn depth(&self) -> usize {
+        let left = self.left.as_ref().map_or(0, |node| node.depth());
+        let right = self.right.as_ref().map_or(0, |node| node.depth());
+        1 + left.max(right)
+    }
+}
+
+const INFINITE_LIST: (&str, &str) = (
+    "enum List {\n    Cons(i32, List),\n    Nil,\n}",
+    "error[E0072]: recursive type `List` has infinite size\n --> src/main.rs:1:1\n  |\n1 | enum List {\n  | ^^^^^^^^^\n2 |     Cons(i32, List),\n  |               ---- recursive without indirection",
+);
+
+// The rejected List, the boxed one that works and a tree built from boxed nodes, with their sizes
+pub fn recursive_types_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let (code, error) = INFINITE_LIST;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    let list = List::from_slice(&[1, 2, 3]);
+    reporter.message(&format!("list: {list}, sum: {}", list.sum()));
+
+    let mut tree = Node::new(5);
+    for value in [3, 8, 1, 4, 9] {
+        tree.insert(value); // Each new node is boxed and moved into its parent's empty Option
+    }
+    let mut values = Vec::new();
+    tree.in_order(&mut values);
+    reporter.message(&format!("tree in order: {values:?}, depth: {}", tree.depth()));
+    reporter.message("");
+
+    layout_rows(reporter, &["List", "Box<List>", "Node", "Box<Node>"]);
+    Ok(())
+}
+
+// A list whose values log their own drops
+pub enum Chain {
+    Link(Tracked<String>, Box<Chain>),
+    End,
+}
+
+impl Chain {
+    // A link for each name, the first name at the head
+    pub fn from_names(tracker: &Tracker, names: &[&str]) -> Chain {
+        names.iter().rev().fold(Chain::End, |rest, name| Chain::Link(tracker.track(name, name.to_string()), Box::new(rest)))
+    }
+
+    // Moves the rest of the chain out, leaving End in its place
+    pub fn cut(&mut self) -> Chain {
+        match self {
+            Chain::Link(_, rest) => std::mem::replace(rest.as_mut(), Chain::End),
+            Chain::End => Chain::End,
+        }
+    }
+}
+
+// Drops a whole chain and watches the order, then cuts one in two and drops the halves separately
+pub fn box_drop_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let tracker = Tracker::new();
+    let chain = Chain::from_names(&tracker, &["head", "middle", "tail"]);
+    drop(chain); // head's String, then its Box, which drops middle's String, then its Box...
+    reporter.event(&OwnershipEvent::dropped("chain"));
+    tracker.report(reporter);
+    reporter.message("");
+
+    let mut front = Chain::from_names(&tracker, &["first", "second", "third"]);
+    let back = front.cut(); // Everything after first moves to back, first's Box now holds End
+    reporter.event(&OwnershipEvent::moved("front.rest", "back"));
+    drop(back);
+    reporter.event(&OwnershipEvent::dropped("back"));
+    tracker.report(reporter);
+    reporter.message("front still has first");
+    drop(front);
+    reporter.event(&OwnershipEvent::dropped("front"));
+    tracker.report(reporter);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boxes_are_one_pointer_and_keep_the_niche() {
+        let layouts = box_layouts();
+        for name in ["Box<i32>", "Box<String>", "Box<List>", "Box<Node>"] {
+            let layout = layouts.iter().find(|layout| layout.name == name).unwrap();
+            assert_eq!(layout.size, size_of::<usize>(), "{name}");
+            assert!(layout.has_niche(), "{name}");
+        }
+        let slice = layouts.iter().find(|layout| layout.name == "Box<[i32]>").unwrap();
+        assert_eq!(slice.size, 2 * size_of::<usize>());
+    }
+
+    #[test]
+    fn shout_owns_the_box_it_is_given() {
+        let output = shout(Box::new(String::from("ferris")));
+        assert_eq!(output.messages, ["FERRIS"]);
+        assert_eq!(output.values, [("boxed".to_string(), "ferris".to_string())]);
+    }
+
+    #[test]
+    fn lists_and_trees_built_from_boxes() {
+        let list = List::from_slice(&[1, 2, 3]);
+        assert_eq!(list.to_string(), "Cons(1, Cons(2, Cons(3, Nil)))");
+        assert_eq!(list.sum(), 6);
+        assert_eq!(List::from_slice(&[]).to_string(), "Nil");
+
+        let mut tree = Node::new(5);
+        for value in [3, 8, 1, 4, 9] {
+            tree.insert(value);
+        }
+        let mut values = Vec::new();
+        tree.in_order(&mut values);
+        assert_eq!(values, [1, 3, 4, 5, 8, 9]);
+        assert_eq!(tree.depth(), 3);
+        assert_eq!(Node::new(1).depth(), 1);
+    }
+
+    #[test]
+    fn box_basics_demo_moves_the_pointer_not_the_string() {
+        let mut output = DemoOutput::new();
+        box_basics_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages[output.messages.len() - 5..],
+            [
+                "number: 5, *number + 1: 6",
+                "name: error, owner: ferris, the String hasn't moved: true",
+                "copy is a new block: true",
+                "FERRIS",
+                "unboxed: ferris, owner: error",
+            ]
+        );
+        assert_eq!(
+            output.events,
+            [
+                OwnershipEvent::moved("name", "owner"),
+                OwnershipEvent::cloned("owner", "copy"),
+                OwnershipEvent::moved("copy", "boxed"),
+                OwnershipEvent::moved("*owner", "unboxed"),
+            ]
+        );
+    }
+
+    #[test]
+    fn recursive_types_demo_shows_the_error_then_the_fix() {
+        let mut output = DemoOutput::new();
+        recursive_types_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0072]")));
+        assert!(output.messages.iter().any(|message| message == "list: Cons(1, Cons(2, Cons(3, Nil))), sum: 6"));
+        assert!(output.messages.iter().any(|message| message == "tree in order: [1, 3, 4, 5, 8, 9], depth: 3"));
+    }
+
+    #[test]
+    fn box_drop_demo_drops_from_the_head_down() {
+        let mut output = DemoOutput::new();
+        box_drop_demo(&mut output).unwrap();
+        let dropped = ["chain", "head", "middle", "tail"].map(OwnershipEvent::dropped);
+        assert_eq!(output.events[..4], dropped);
+        assert_eq!(
+            output.events[4..],
+            [
+                OwnershipEvent::moved("front.rest", "back"),
+                OwnershipEvent::dropped("back"),
+                OwnershipEvent::dropped("second"),
+                OwnershipEvent::dropped("third"),
+                OwnershipEvent::dropped("front"),
+                OwnershipEvent::dropped("first"),
+            ]
+        );
+    }
+}
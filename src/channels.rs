@@ -0,0 +1,149 @@
+use std::sync::mpsc;
+use std::thread;
+
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::OwnershipEvent;
+use crate::report::Reporter;
+use crate::runner;
+
+// A channel moves values from one thread to another instead of sharing them. `send` takes the value by value,
+// like `take_ownership` takes its String, so the sending thread gives it up and the receiving thread becomes the
+// owner. Only the String's pointer, length and capacity travel, the text stays in the same heap block.
+
+pub const TAUGHT: Taught = Taught { day: 12, order: 3 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(channel_send_demo {
+        name: "channel_send",
+        summary: "Sending a String through a channel moves it to the receiving thread",
+        topics: &["concurrency", "ownership"],
+        prerequisites: &["thread_move", "take_ownership"],
+        explanation: "`mpsc::channel()` returns a Sender and a Receiver. `tx.send(name)` moves `name` into the channel, exactly as passing it to `take_ownership` would, so using it afterwards is E0382 even though it's on another thread now. `rx.recv()` hands the value to the receiving thread, which owns it from then on and can drop it or pass it on. Iterating over `rx` receives until every Sender has been dropped.",
+    }),
+    lesson!(multiple_producers_demo {
+        name: "multiple_producers",
+        summary: "Cloned Senders let several threads send to one Receiver",
+        topics: &["concurrency", "ownership"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["channel_send"],
+        explanation: "mpsc means multiple producer, single consumer: `tx.clone()` makes another Sender for the same channel, and each one is moved into its own thread. The Receiver gets every value, in whatever order the threads got to them. `for message in rx` only ends once all the Senders are gone, so the original `tx`, if it isn't moved into a thread, has to be dropped by hand or the loop waits forever.",
+    }),
+];
+
+const USE_AFTER_SEND: (&str, &str) = (
+    "let (tx, rx) = mpsc::channel();\nthread::spawn(move || {\n    let name = String::from(\"ferris\");\n    tx.send(name).unwrap();\n    println!(\"sent {name}\");\n});\nprintln!(\"{}\", rx.recv().unwrap());",
+    "error[E0382]: borrow of moved value: `name`\n --> src/main.rs:5:21\n  |\n3 |     let name = String::from(\"ferris\");\n  |         ---- move occurs because `name` has type `String`, which does not implement the `Copy` trait\n4 |     tx.send(name).unwrap();\n  |             ---- value moved here\n5 |     println!(\"sent {name}\");\n  |                     ^^^^ value borrowed here after move",
+);
+
+// The rejected use after send, then Strings moved from a thread to this one, their heap blocks going with them
+pub fn channel_send_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let (code, error) = USE_AFTER_SEND;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    let (tx, rx) = mpsc::channel();
+    let sender = thread::spawn(move || {
+        for word in ["ferris", "corro", "rusty"] {
+            let name = String::from(word);
+            let address = name.as_ptr() as usize; // Where the text is, to compare on the other side
+            if tx.send((name, address)).is_err() {
+                break; // The receiver is gone, nobody to send to
+            }
+        }
+    }); // The thread drops tx when it's done, which ends the loop over rx below
+    reporter.event(&OwnershipEvent::moved("tx", "thread"));
+
+    for (received, address) in rx {
+        // Each String is owned by this thread now
+        reporter.event(&OwnershipEvent::moved("name", "received"));
+        reporter.message(&format!("received {received}, same heap block as when it was sent: {}", received.as_ptr() as usize == address));
+        drop(received); // This thread owns it, so this thread frees it
+        reporter.event(&OwnershipEvent::dropped("received"));
+    }
+    sender.join().map_err(runner::panicked)?;
+    reporter.message("every Sender is dropped, so the loop over rx has ended");
+    Ok(())
+}
+
+// Three threads with cloned Senders, the original dropped by hand so the Receiver's loop can end
+pub fn multiple_producers_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let (tx, rx) = mpsc::channel();
+    let mut handles = Vec::new();
+    for producer in ["alice", "bob", "carol"] {
+        let tx = tx.clone(); // Another Sender for the same channel, moved into the thread
+        reporter.event(&OwnershipEvent::cloned("tx", producer));
+        handles.push(thread::spawn(move || {
+            for number in 1..=2 {
+                let message = format!("{producer} {number}");
+                if tx.send(message).is_err() {
+                    break;
+                }
+            }
+        }));
+    }
+    drop(tx); // Otherwise this Sender keeps the channel open and the loop below never ends
+    reporter.event(&OwnershipEvent::dropped("tx"));
+
+    let mut received: Vec<String> = rx.iter().collect(); // Ends once the three threads have dropped their Senders
+    reporter.event(&OwnershipEvent::moved("message", "received"));
+    for handle in handles {
+        handle.join().map_err(runner::panicked)?;
+    }
+    reporter.message(&format!("received {} messages, in an order that changes from run to run", received.len()));
+    received.sort(); // Sorted, so the output doesn't
+    reporter.message(&format!("sorted: {received:?}"));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::DemoOutput;
+
+    #[test]
+    fn channel_send_demo_moves_each_string_across() {
+        let mut output = DemoOutput::new();
+        channel_send_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0382]")));
+        assert_eq!(
+            output.messages[output.messages.len() - 4..],
+            [
+                "received ferris, same heap block as when it was sent: true",
+                "received corro, same heap block as when it was sent: true",
+                "received rusty, same heap block as when it was sent: true",
+                "every Sender is dropped, so the loop over rx has ended",
+            ]
+        );
+        assert_eq!(output.events[0], OwnershipEvent::moved("tx", "thread"));
+        assert_eq!(output.events.len(), 7);
+    }
+
+    #[test]
+    fn multiple_producers_demo_gets_every_message() {
+        let mut output = DemoOutput::new();
+        multiple_producers_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            [
+                "received 6 messages, in an order that changes from run to run",
+                "sorted: [\"alice 1\", \"alice 2\", \"bob 1\", \"bob 2\", \"carol 1\", \"carol 2\"]",
+            ]
+        );
+        assert_eq!(
+            output.events,
+            [
+                OwnershipEvent::cloned("tx", "alice"),
+                OwnershipEvent::cloned("tx", "bob"),
+                OwnershipEvent::cloned("tx", "carol"),
+                OwnershipEvent::dropped("tx"),
+                OwnershipEvent::moved("message", "received"),
+            ]
+        );
+    }
+}
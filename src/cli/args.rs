@@ -0,0 +1,82 @@
+use rust_day_6::error::LessonError;
+
+// The command line, with flags taken out one by one as they are recognised
+pub struct ArgList {
+    args: Vec<String>,
+}
+
+impl ArgList {
+    pub fn new(args: impl IntoIterator<Item = String>) -> Self {
+        Self { args: args.into_iter().collect() }
+    }
+
+    // Removes `name` if present, e.g. `--verbose`
+    pub fn flag(&mut self, name: &str) -> bool {
+        match self.args.iter().position(|arg| arg == name) {
+            Some(index) => {
+                self.args.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Removes `name value` or `name=value` and returns the value
+    pub fn value(&mut self, name: &str) -> Result<Option<String>, LessonError> {
+        let prefix = format!("{name}=");
+        for index in 0..self.args.len() {
+            if self.args[index] == name {
+                if index + 1 >= self.args.len() {
+                    return Err(LessonError::BadInput(format!("{name} needs a value")));
+                }
+                let value = self.args.remove(index + 1);
+                self.args.remove(index);
+                return Ok(Some(value));
+            }
+            if let Some(value) = self.args[index].strip_prefix(&prefix) {
+                let value = value.to_string();
+                self.args.remove(index);
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    // Like `value`, parsed with FromStr
+    pub fn parsed<T: std::str::FromStr>(&mut self, name: &str) -> Result<Option<T>, LessonError> {
+        match self.value(name)? {
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| LessonError::BadInput(format!("`{value}` is not a valid value for {name}"))),
+            None => Ok(None),
+        }
+    }
+
+    // What hasn't been taken out yet
+    pub fn rest(&self) -> &[String] {
+        &self.args
+    }
+
+    // Removes and returns the first argument that isn't a flag
+    pub fn positional(&mut self) -> Option<String> {
+        let index = self.args.iter().position(|arg| !arg.starts_with("--"))?;
+        Some(self.args.remove(index))
+    }
+
+    // Everything left over, which must not contain any unrecognised flags
+    pub fn finish(self) -> Result<Vec<String>, LessonError> {
+        match self.args.iter().find(|arg| arg.starts_with("--")) {
+            Some(flag) => Err(LessonError::BadInput(format!("unknown flag {flag}"))),
+            None => Ok(self.args),
+        }
+    }
+
+    // Like `finish`, but nothing may be left over at all
+    pub fn finish_empty(self) -> Result<(), LessonError> {
+        match self.finish()?.first() {
+            Some(extra) => Err(LessonError::BadInput(format!("unexpected argument `{extra}`"))),
+            None => Ok(()),
+        }
+    }
+}
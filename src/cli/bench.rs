@@ -0,0 +1,41 @@
+use rust_day_6::bench;
+use rust_day_6::error::LessonError;
+use rust_day_6::json::{Json, ToJson};
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+const DEFAULT_ITERATIONS: u64 = 200_000;
+
+// `bench [--iterations N]`, times the performance claims from the notes and prints a comparison table
+pub fn bench(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let iterations = args.parsed("--iterations")?.unwrap_or(DEFAULT_ITERATIONS);
+    args.finish_empty()?;
+    if iterations == 0 {
+        return Err(LessonError::BadInput("--iterations must be at least 1".to_string()));
+    }
+
+    let results = bench::run(iterations);
+    if globals.format == Format::Json {
+        println!("{}", Json::array(results.iter().map(ToJson::to_json)));
+        return Ok(());
+    }
+
+    let style = globals.style;
+    let header = format!("{:<24} {:>14} {:>10} {:>14} {:>10} {:>8}", "experiment", "baseline", "per iter", "contender", "per iter", "ratio");
+    println!("{}", style.paint(Role::Heading, &header));
+    for result in &results {
+        let ratio = result.ratio().map_or("-".to_string(), |ratio| format!("{ratio:.1}x"));
+        println!(
+            "{:<24} {:>14} {:>8.2}ns {:>14} {:>8.2}ns {:>8}",
+            result.name, result.baseline.0, result.baseline.1, result.contender.0, result.contender.1, ratio
+        );
+    }
+    println!();
+    for result in &results {
+        println!("{} {}", style.paint(Role::Dim, &format!("{}:", result.name)), result.claim);
+    }
+    println!("{}", style.paint(Role::Dim, &format!("{iterations} iterations each, run with --release for realistic numbers")));
+    Ok(())
+}
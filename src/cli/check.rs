@@ -0,0 +1,68 @@
+use std::path::PathBuf;
+
+use rust_day_6::error::LessonError;
+use rust_day_6::json::{Json, ToJson};
+use rust_day_6::lesson::Lesson;
+use rust_day_6::registry;
+use rust_day_6::sandbox;
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `check <file.rs>`, compiles any snippet and explains the ownership and borrowing errors rustc finds in it
+pub fn check(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let Some(file) = args.positional() else {
+        return Err(LessonError::BadInput("check needs a file, e.g. `check scratch.rs`".to_string()));
+    };
+    args.finish_empty()?;
+    let file = PathBuf::from(file);
+    let diagnostics = sandbox::check(&file)?;
+    let errors = diagnostics.iter().filter(|diagnostic| diagnostic.is_error()).count();
+    globals.transcribe("check", &format!("{}: {}", file.display(), match errors {
+        0 => "compiles".to_string(),
+        1 => "1 error".to_string(),
+        _ => format!("{errors} errors"),
+    }))?;
+    for explanation in diagnostics.iter().filter_map(|diagnostic| diagnostic.explanation()) {
+        globals.transcribe("explain", &format!("{}: {}\n{}\nTry: {}", explanation.code, explanation.title, explanation.why, explanation.pattern))?;
+    }
+
+    match globals.format {
+        Format::Text => {
+            let style = globals.style;
+            for diagnostic in &diagnostics {
+                println!("{}", diagnostic.rendered);
+                println!();
+                let Some(explanation) = diagnostic.explanation() else {
+                    continue;
+                };
+                println!("{}", style.paint(Role::Heading, &format!("What {} means:", explanation.code)));
+                println!("  {}", style.code_spans(explanation.why));
+                println!("{} {}", style.paint(Role::Success, "Try:"), style.code_spans(explanation.pattern));
+                let lesson = registry::find(explanation.lesson).map_or(String::new(), |lesson| format!(", {}", lesson.summary().to_lowercase()));
+                println!(
+                    "{}",
+                    style.paint(Role::Dim, &format!("`rust_day_6 run {}` covers this{lesson}, `rust_day_6 explain {}` has a worked example", explanation.lesson, explanation.code))
+                );
+                println!();
+            }
+            if errors == 0 {
+                println!("{} {} compiles", style.paint(Role::Success, "✓"), file.display());
+            }
+        }
+        Format::Json => println!(
+            "{}",
+            Json::object([
+                ("file", file.display().to_string().into()),
+                ("compiles", (errors == 0).into()),
+                ("diagnostics", Json::array(diagnostics.iter().map(ToJson::to_json))),
+            ])
+        ),
+    }
+    match errors {
+        0 => Ok(()),
+        1 => Err(LessonError::BadInput(format!("{} has 1 error", file.display()))),
+        _ => Err(LessonError::BadInput(format!("{} has {errors} errors", file.display()))),
+    }
+}
@@ -0,0 +1,135 @@
+use rust_day_6::error::LessonError;
+
+use super::args::ArgList;
+use super::{COMMANDS, GLOBAL_FLAGS, LESSON_COMMANDS};
+
+// `completions <shell>`, lesson names are looked up at completion time with `rust_day_6 list --names`
+pub fn completions(mut args: ArgList) -> Result<(), LessonError> {
+    let Some(shell) = args.positional() else {
+        return Err(LessonError::BadInput("completions needs a shell: bash, zsh or fish".to_string()));
+    };
+    args.finish_empty()?;
+
+    let script = match shell.as_str() {
+        "bash" => bash(),
+        "zsh" => zsh(),
+        "fish" => fish(),
+        other => return Err(LessonError::BadInput(format!("no completions for `{other}`, expected bash, zsh or fish"))),
+    };
+    print!("{script}");
+    Ok(())
+}
+
+fn command_names() -> Vec<&'static str> {
+    let mut names: Vec<&str> = COMMANDS.iter().map(|(name, _, _)| *name).collect();
+    names.dedup();
+    names
+}
+
+fn flag_names() -> Vec<&'static str> {
+    GLOBAL_FLAGS.iter().map(|(flag, _)| *flag).collect()
+}
+
+fn bash() -> String {
+    format!(
+        r#"_rust_day_6() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local command=""
+    local word skip=""
+    for word in "${{COMP_WORDS[@]:1:COMP_CWORD-1}}"; do
+        if [[ -n "$skip" ]]; then
+            skip=""
+            continue
+        fi
+        case "$word" in
+            {value_flags}) skip=1 ;;
+            -*) ;;
+            *) command="$word"; break ;;
+        esac
+    done
+
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=($(compgen -W "{flags}" -- "$cur"))
+    elif [[ -z "$command" ]]; then
+        COMPREPLY=($(compgen -W "{commands}" -- "$cur"))
+    else
+        case "$command" in
+            {lesson_commands}) COMPREPLY=($(compgen -W "$(rust_day_6 list --names 2>/dev/null)" -- "$cur")) ;;
+            completions) COMPREPLY=($(compgen -W "bash zsh fish" -- "$cur")) ;;
+        esac
+    fi
+}}
+complete -F _rust_day_6 rust_day_6
+"#,
+        flags = flag_names().join(" "),
+        commands = command_names().join(" "),
+        lesson_commands = LESSON_COMMANDS.join("|"),
+        value_flags = GLOBAL_FLAGS.iter().filter(|(_, value)| !value.is_empty()).map(|(flag, _)| *flag).collect::<Vec<_>>().join("|"),
+    )
+}
+
+fn zsh() -> String {
+    let commands: Vec<String> = command_names()
+        .iter()
+        .map(|name| {
+            let description = COMMANDS.iter().find(|(command, _, _)| command == name).map_or("", |(_, _, description)| *description);
+            format!("        '{name}:{description}'")
+        })
+        .collect();
+    let flags: Vec<String> = GLOBAL_FLAGS
+        .iter()
+        .map(|(flag, value)| if value.is_empty() { format!("'{flag}'") } else { format!("'{flag}:{value}:'") })
+        .collect();
+    format!(
+        r#"#compdef rust_day_6
+
+_rust_day_6() {{
+    local -a commands
+    commands=(
+{commands}
+    )
+
+    _arguments -C \
+        {flags} \
+        '1:command:->command' \
+        '*::arg:->args'
+
+    case $state in
+        command) _describe 'command' commands ;;
+        args)
+            case $words[1] in
+                {lesson_commands}) _values 'lesson' ${{(f)"$(rust_day_6 list --names 2>/dev/null)"}} ;;
+                completions) _values 'shell' bash zsh fish ;;
+            esac
+            ;;
+    esac
+}}
+
+_rust_day_6 "$@"
+"#,
+        commands = commands.join("\n"),
+        flags = flags.join(" \\\n        "),
+        lesson_commands = LESSON_COMMANDS.join("|"),
+    )
+}
+
+fn fish() -> String {
+    let mut script = String::from("complete -c rust_day_6 -f\n");
+    for (flag, value) in GLOBAL_FLAGS {
+        let requires = if value.is_empty() { "" } else { " -r" };
+        script.push_str(&format!("complete -c rust_day_6 -l {}{requires}\n", flag.trim_start_matches("--")));
+    }
+    for name in command_names() {
+        let description = COMMANDS.iter().find(|(command, _, _)| *command == name).map_or("", |(_, _, description)| *description);
+        script.push_str(&format!(
+            "complete -c rust_day_6 -n __fish_use_subcommand -a {name} -d '{description}'\n"
+        ));
+    }
+    for command in LESSON_COMMANDS {
+        script.push_str(&format!(
+            "complete -c rust_day_6 -n '__fish_seen_subcommand_from {command}' -a '(rust_day_6 list --names 2>/dev/null)'\n"
+        ));
+    }
+    script.push_str("complete -c rust_day_6 -n '__fish_seen_subcommand_from completions' -a 'bash zsh fish'\n");
+    script
+}
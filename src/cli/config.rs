@@ -0,0 +1,80 @@
+use std::env;
+
+use rust_day_6::error::LessonError;
+use rust_day_6::json::Json;
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `config`, every setting as it applies to this run and where it came from
+pub fn config(globals: &Globals, args: ArgList) -> Result<(), LessonError> {
+    args.finish_empty()?;
+    let path = globals.config_path();
+    let config = &globals.config;
+    let from_config = "config file";
+
+    let color = match (globals.color, config.color) {
+        (Some(true), _) => "--color",
+        (Some(false), _) => "--no-color",
+        (None, Some(_)) => from_config,
+        (None, None) => "default",
+    };
+    // Colors can still be off with them asked for, when NO_COLOR is set or stdout isn't a terminal
+    let value = match (globals.style.enabled(), globals.color.or(config.color).unwrap_or(true)) {
+        (true, _) => "on",
+        (false, true) => "off (NO_COLOR or not a terminal)",
+        (false, false) => "off",
+    };
+    let color = (value.to_string(), color);
+    let exercises_dir = if globals.exercises_dir.is_some() {
+        "--exercises-dir"
+    } else if env::var_os("RUST_DAY_6_EXERCISES").is_some() {
+        "RUST_DAY_6_EXERCISES"
+    } else if config.exercises_dir.is_some() {
+        from_config
+    } else {
+        "default"
+    };
+    let exercises_dir = (globals.exercises_dir().display().to_string(), exercises_dir);
+    let topics = match config.topics.as_slice() {
+        [] => ("every topic".to_string(), "default"),
+        topics => (topics.join(", "), from_config),
+    };
+    let editor = match (&config.editor, globals.editor()) {
+        (Some(editor), _) => (editor.clone(), from_config),
+        (None, Some(editor)) => (editor, if env::var("VISUAL").is_ok_and(|visual| !visual.is_empty()) { "VISUAL" } else { "EDITOR" }),
+        (None, None) => ("none".to_string(), "default"),
+    };
+    let settings = [("color", color), ("exercises_dir", exercises_dir), ("topics", topics), ("editor", editor)];
+
+    match globals.format {
+        Format::Text => {
+            let style = globals.style;
+            let found = if path.exists() { "" } else { " (not found, using the defaults)" };
+            println!("{} {}{found}", style.paint(Role::Heading, "Config file"), path.display());
+            if let Some(profile) = &globals.profile {
+                println!("{} {profile}", style.paint(Role::Heading, "Profile"));
+            }
+            println!("{} {}", style.paint(Role::Heading, "Progress"), globals.progress_path().display());
+            let width = settings.iter().map(|(_, (value, _))| value.chars().count()).max().unwrap_or(0);
+            for (key, (value, source)) in &settings {
+                println!("  {key:<14} {value:<width$}  {}", style.paint(Role::Dim, source));
+            }
+        }
+        Format::Json => {
+            let settings = settings.map(|(key, (value, source))| (key, Json::object([("value", value.into()), ("source", source.into())])));
+            println!(
+                "{}",
+                Json::object([
+                    ("file", path.display().to_string().into()),
+                    ("found", path.exists().into()),
+                    ("profile", globals.profile.as_deref().into()),
+                    ("progress", globals.progress_path().display().to_string().into()),
+                    ("settings", Json::object(settings)),
+                ])
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use std::env;
+use std::fs::OpenOptions;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::process::Command;
+
+use rust_day_6::error::LessonError;
+use rust_day_6::json::Json;
+use rust_day_6::progress::Progress;
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// Inline format arguments (`println!("{s1}")`) in the exercises need at least this rustc
+const MIN_RUSTC: (u32, u32) = (1, 58);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    // Works, but not as well as it could
+    Warn,
+    Fail,
+}
+
+struct Check {
+    name: &'static str,
+    status: Status,
+    detail: String,
+    // What to do about a warning or failure
+    fix: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self { name, status: Status::Ok, detail: detail.into(), fix: None }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { name, status: Status::Warn, detail: detail.into(), fix: Some(fix.into()) }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { name, status: Status::Fail, detail: detail.into(), fix: Some(fix.into()) }
+    }
+}
+
+// `doctor`, checks everything the exercises and progress tracking rely on and says how to fix what's missing
+pub fn doctor(globals: &Globals, args: ArgList) -> Result<(), LessonError> {
+    args.finish_empty()?;
+    let checks = [toolchain(), exercises_dir(globals), progress_file(globals), color(globals), unicode()];
+
+    match globals.format {
+        Format::Text => {
+            let style = globals.style;
+            for check in &checks {
+                let mark = match check.status {
+                    Status::Ok => style.paint(Role::Success, "✓"),
+                    Status::Warn => style.paint(Role::Event, "!"),
+                    Status::Fail => style.paint(Role::Error, "✗"),
+                };
+                println!("{mark} {:<10} {}", check.name, check.detail);
+                if let Some(fix) = &check.fix {
+                    println!("  {:<10} {}", "", style.paint(Role::Dim, &format!("fix: {fix}")));
+                }
+            }
+        }
+        Format::Json => {
+            let checks = checks.iter().map(|check| {
+                let status = match check.status {
+                    Status::Ok => "ok",
+                    Status::Warn => "warn",
+                    Status::Fail => "fail",
+                };
+                Json::object([
+                    ("check", check.name.into()),
+                    ("status", status.into()),
+                    ("detail", (&check.detail).into()),
+                    ("fix", check.fix.as_ref().into()),
+                ])
+            });
+            println!("{}", Json::array(checks));
+        }
+    }
+
+    match checks.iter().filter(|check| check.status == Status::Fail).count() {
+        0 => Ok(()),
+        failed => Err(LessonError::BadInput(format!("{failed} of {} checks failed", checks.len()))),
+    }
+}
+
+fn toolchain() -> Check {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = match Command::new(&rustc).arg("--version").output() {
+        Ok(output) if output.status.success() => output,
+        _ => {
+            return Check::fail(
+                "rustc",
+                format!("couldn't run `{}`", rustc.to_string_lossy()),
+                "install Rust from https://rustup.rs, or point RUSTC at your rustc",
+            )
+        }
+    };
+
+    // e.g. "rustc 1.85.0 (4d91de4e4 2025-02-17)"
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let number = version.split_whitespace().nth(1).unwrap_or("");
+    let mut parts = number.split('.').map(|part| part.parse::<u32>().unwrap_or(0));
+    let (major, minor) = (parts.next().unwrap_or(0), parts.next().unwrap_or(0));
+    if (major, minor) < MIN_RUSTC {
+        let (min_major, min_minor) = MIN_RUSTC;
+        return Check::fail("rustc", version, format!("the exercises need rustc {min_major}.{min_minor} or newer, run `rustup update`"));
+    }
+    Check::ok("rustc", version)
+}
+
+fn exercises_dir(globals: &Globals) -> Check {
+    let dir = globals.exercises_dir();
+    if dir.is_dir() {
+        // A custom manifest that doesn't parse stops every exercise command, so it counts as a failure
+        match globals.exercises() {
+            Ok(set) => Check::ok("exercises", format!("{} exists, {} exercises", dir.display(), set.len())),
+            Err(err) => Check::fail("exercises", err.to_string(), "fix the exercises.toml in that directory"),
+        }
+    } else {
+        Check::warn(
+            "exercises",
+            format!("{} doesn't exist yet", dir.display()),
+            "run `rust_day_6 watch` to start the exercises, or pass --exercises-dir (or set RUST_DAY_6_EXERCISES) to where yours are",
+        )
+    }
+}
+
+fn progress_file(globals: &Globals) -> Check {
+    let path = globals.progress_path();
+    if let Err(err) = Progress::load(&path) {
+        return Check::fail("progress", format!("can't read {}: {err}", path.display()), "fix or delete the file, or set RUST_DAY_6_PROGRESS to a different one");
+    }
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let unwritable = |err: std::io::Error| {
+        Check::fail(
+            "progress",
+            format!("can't write {}: {err}", path.display()),
+            format!("check the permissions of {}, or set RUST_DAY_6_PROGRESS to a writable file", dir.display()),
+        )
+    };
+    // Progress is saved on the first exercise or lesson, so a directory that isn't there yet is fine as long as
+    // it can be made
+    if let Err(err) = std::fs::create_dir_all(dir) {
+        return unwritable(err);
+    }
+    // Appending nothing checks the file (or its directory) is writable without touching what's in it
+    let existed = path.exists();
+    match OpenOptions::new().append(true).create(true).open(&path) {
+        Ok(_) => {
+            if !existed {
+                let _ = std::fs::remove_file(&path);
+            }
+            let detail = if existed { format!("{} is readable and writable", path.display()) } else { format!("{} can be created", path.display()) };
+            Check::ok("progress", detail)
+        }
+        Err(err) => unwritable(err),
+    }
+}
+
+fn color(globals: &Globals) -> Check {
+    if globals.style.enabled() {
+        return Check::ok("color", globals.style.paint(Role::Success, "colors are on"));
+    }
+    if env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        Check::warn("color", "colors are off because NO_COLOR is set", "unset NO_COLOR to get colored output")
+    } else if !io::stdout().is_terminal() {
+        Check::ok("color", "colors are off because output isn't a terminal")
+    } else if globals.color == Some(false) {
+        Check::warn("color", "colors are off because of --no-color", "drop --no-color to get colored output")
+    } else {
+        Check::warn("color", "colors are off in the config file", format!("remove `color = false` from {}, or pass --color", globals.config_path().display()))
+    }
+}
+
+fn unicode() -> Check {
+    // The first of these that's set decides the character encoding, as in C's setlocale
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"].iter().find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()));
+    match locale {
+        Some(locale) if locale.to_ascii_uppercase().replace('-', "").contains("UTF8") => {
+            Check::ok("unicode", format!("locale {locale}, these should look right: ✓ ✗ █░ │─"))
+        }
+        Some(locale) => Check::warn(
+            "unicode",
+            format!("locale {locale} may not show ✓ ✗ █░ │─ (progress bars, the TUI)"),
+            "use a UTF-8 locale, e.g. `export LANG=en_US.UTF-8`",
+        ),
+        None => Check::warn(
+            "unicode",
+            "no locale set, ✓ ✗ █░ │─ (progress bars, the TUI) may not show",
+            "use a UTF-8 locale, e.g. `export LANG=en_US.UTF-8`",
+        ),
+    }
+}
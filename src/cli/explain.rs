@@ -0,0 +1,56 @@
+use rust_day_6::error::LessonError;
+use rust_day_6::explain;
+use rust_day_6::json::ToJson;
+use rust_day_6::lesson::Lesson;
+use rust_day_6::registry;
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `explain <error-code>`, what a rustc ownership error means, a snippet that triggers it and the fix
+pub fn explain(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let Some(code) = args.positional() else {
+        return Err(LessonError::BadInput("explain needs an error code, e.g. `explain E0382`".to_string()));
+    };
+    args.finish_empty()?;
+
+    let Some(error) = explain::lookup(&code) else {
+        let known: Vec<&str> = explain::ERRORS.iter().map(|error| error.code).collect();
+        return Err(LessonError::BadInput(format!("no explanation for {code}, try one of {}", known.join(", "))));
+    };
+    globals.transcribe("explain", &format!("{}: {}\n{}\nIn general: {}", error.code, error.title, error.why, error.pattern))?;
+    if globals.format == Format::Json {
+        println!("{}", error.to_json());
+        return Ok(());
+    }
+
+    let style = globals.style;
+    println!("{}", style.paint(Role::Heading, &format!("{}: {}", error.code, error.title)));
+    println!();
+    println!("{}", style.code_spans(error.why));
+    println!();
+    println!("{}", style.paint(Role::Error, "This doesn't compile:"));
+    print_code(globals, error.snippet);
+    println!();
+    println!("{}", style.paint(Role::Success, "This does:"));
+    print_code(globals, error.fix);
+    println!("{} {}", style.paint(Role::Success, "In general:"), style.code_spans(error.pattern));
+
+    if let Some(lesson) = registry::find(error.lesson) {
+        println!();
+        println!(
+            "{} (`rust_day_6 run {}`)",
+            style.paint(Role::Heading, &format!("Lesson {}: {}", lesson.name(), lesson.summary())),
+            lesson.name()
+        );
+        println!("{}", style.code_spans(lesson.explanation()));
+    }
+    Ok(())
+}
+
+fn print_code(globals: &Globals, code: &str) {
+    for line in code.lines() {
+        println!("    {}", globals.style.paint(Role::Code, line));
+    }
+}
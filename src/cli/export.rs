@@ -0,0 +1,132 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rust_day_6::anki::{self, Note};
+use rust_day_6::error::LessonError;
+use rust_day_6::flashcards;
+use rust_day_6::json::Json;
+use rust_day_6::quiz;
+use rust_day_6::report::BufferReporter;
+use rust_day_6::shuffle::Rng;
+use rust_day_6::style::Role;
+use rust_day_6::tracer::{self, OwnershipTracer, Traces};
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// Template questions are filled in the same way every time, so exporting again gives the same notes and Anki
+// updates them instead of adding duplicates
+const TEMPLATE_SEED: u64 = 6;
+
+// `export anki <file.tsv>`, `export dot <lesson> [file.dot]` or `export mermaid <lesson> [file.mmd]`
+pub fn export(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    match args.positional().as_deref() {
+        Some("anki") => anki(globals, args),
+        Some("dot") => graph(globals, args, Graph::Dot),
+        Some("mermaid") => graph(globals, args, Graph::Mermaid),
+        Some(other) => Err(LessonError::BadInput(format!("can't export to `{other}`, expected anki, dot or mermaid"))),
+        None => Err(LessonError::BadInput("export needs a format, e.g. `export anki deck.tsv` or `export dot take_ownership`".to_string())),
+    }
+}
+
+// The flashcards and the quiz questions as an Anki deck, `-` for stdout
+fn anki(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let file = args.positional();
+    args.finish_empty()?;
+    let Some(file) = file.map(PathBuf::from) else {
+        return Err(LessonError::BadInput("export anki needs a file, e.g. `export anki deck.tsv`".to_string()));
+    };
+
+    let cards = flashcards::cards();
+    let mut rng = Rng::new(TEMPLATE_SEED);
+    let questions: Vec<_> = quiz::bank().iter().map(|question| question.instantiate(&mut rng)).collect();
+    let notes: Vec<Note> = cards.iter().map(Note::from_card).chain(questions.iter().map(Note::from_question)).collect();
+    if !write(&file, &anki::tsv(&notes))? {
+        return Ok(());
+    }
+    match globals.format {
+        Format::Text => println!(
+            "{} exported {} flashcards and {} quiz questions to {}, import it in Anki with File > Import",
+            globals.style.paint(Role::Success, "✓"),
+            cards.len(),
+            questions.len(),
+            file.display()
+        ),
+        Format::Json => println!(
+            "{}",
+            Json::object([("exported", file.display().to_string().into()), ("cards", cards.len().into()), ("questions", questions.len().into())])
+        ),
+    }
+    Ok(())
+}
+
+// The graphs a lesson can be exported as
+#[derive(Clone, Copy)]
+enum Graph {
+    Dot,
+    Mermaid,
+}
+
+impl Graph {
+    // The name on the command line, also the default file's extension
+    fn name(self) -> &'static str {
+        match self {
+            Graph::Dot => "dot",
+            Graph::Mermaid => "mermaid",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Graph::Dot => "dot",
+            Graph::Mermaid => "mmd",
+        }
+    }
+
+    // What to do with the file next
+    fn hint(self, file: &Path, lesson: &str) -> String {
+        match self {
+            Graph::Dot => format!("render it with `dot -Tsvg {} -o {lesson}.svg`", file.display()),
+            Graph::Mermaid => "paste it into a ```mermaid block in Markdown, or open it in mermaid.live".to_string(),
+        }
+    }
+}
+
+// A lesson's moves, copies, clones and borrows as a Graphviz or Mermaid graph, `<lesson>.dot` or `<lesson>.mmd`
+// unless a file is given, `-` for stdout. The lesson runs without printing anything and without counting as done.
+fn graph(globals: &Globals, mut args: ArgList, graph: Graph) -> Result<(), LessonError> {
+    let Some(lesson) = args.positional() else {
+        return Err(LessonError::BadInput(format!("export {0} needs a lesson, e.g. `export {0} take_ownership`", graph.name())));
+    };
+    let file = args.positional().map_or_else(|| PathBuf::from(format!("{lesson}.{}", graph.extension())), PathBuf::from);
+    args.finish_empty()?;
+
+    let traces = Traces::new();
+    globals.runner().reporter(OwnershipTracer::new(BufferReporter::new(), traces.clone())).build().run(&lesson)?;
+    let events = traces.lessons().into_iter().next().map(|(_, events)| events).unwrap_or_default();
+    let text = match graph {
+        Graph::Dot => tracer::dot(&lesson, &events),
+        Graph::Mermaid => tracer::mermaid(&lesson, &events),
+    };
+    if !write(&file, &text)? {
+        return Ok(());
+    }
+    match globals.format {
+        Format::Text => {
+            println!("{} exported {lesson} to {}, {} events", globals.style.paint(Role::Success, "✓"), file.display(), events.len());
+            println!("{}", globals.style.paint(Role::Dim, &graph.hint(&file, &lesson)));
+        }
+        Format::Json => println!("{}", Json::object([("exported", file.display().to_string().into()), ("lesson", lesson.into()), ("events", events.len().into())])),
+    }
+    Ok(())
+}
+
+// Writes `text` to `file`, or prints it for `-`. Whether it went to a file, which is worth saying.
+fn write(file: &Path, text: &str) -> Result<bool, LessonError> {
+    if file.as_os_str() == "-" {
+        print!("{text}");
+        return Ok(false);
+    }
+    fs::write(file, text).map_err(|err| LessonError::BadInput(format!("can't write {}: {err}", file.display())))?;
+    Ok(true)
+}
@@ -0,0 +1,53 @@
+use std::io::{self, BufWriter, Read, Write};
+
+use rust_day_6::error::LessonError;
+use rust_day_6::json::Json;
+use rust_day_6::slices::first_word;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `firstword [--each-line]`, prints the first word of stdin, or of every line of it
+pub fn firstword(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let each_line = args.flag("--each-line");
+    args.finish_empty()?;
+
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    let words = first_words(&input, each_line);
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    let written = match globals.format {
+        Format::Text => words.iter().try_for_each(|word| writeln!(out, "{word}")),
+        Format::Json if each_line => writeln!(out, "{}", Json::array(words)),
+        Format::Json => writeln!(out, "{}", Json::from(words[0])),
+    };
+    // Stopping early is normal in a pipe, e.g. `| head`
+    match written.and_then(|()| out.flush()) {
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+// The first word of the input, or of each of its lines, leading whitespace skipped
+fn first_words(input: &str, each_line: bool) -> Vec<&str> {
+    if each_line {
+        input.lines().map(|line| first_word(line.trim_start())).collect()
+    } else {
+        vec![first_word(input.trim_start())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_first_word_of_everything_or_of_each_line() {
+        let input = "  hello world\nfoo\tbar\n\n   baz  \n";
+        assert_eq!(first_words(input, false), ["hello"]);
+        assert_eq!(first_words(input, true), ["hello", "foo", "", "baz"]);
+        assert_eq!(first_words("", false), [""]);
+        assert!(first_words("", true).is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+use std::io::{self, BufRead, Write};
+
+use rust_day_6::date::Date;
+use rust_day_6::error::LessonError;
+use rust_day_6::flashcards::{self, Card};
+use rust_day_6::json::Json;
+use rust_day_6::progress::Progress;
+use rust_day_6::style::{Role, Style};
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `flashcards [--topic T] [--all]`, shows the cards due today and the ones not seen yet, front first, then the back
+// once enter is pressed. The learner says whether they knew it, and that decides when the card comes back.
+pub fn flashcards(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let topic = args.value("--topic")?;
+    let all = args.flag("--all");
+    args.finish_empty()?;
+    let today = Date::today();
+    let progress = globals.progress()?;
+    let cards: Vec<Card> = flashcards::cards().into_iter().filter(|card| topic.as_ref().is_none_or(|topic| card.topic == *topic)).collect();
+    if let (Some(topic), true) = (&topic, cards.is_empty()) {
+        return Err(LessonError::BadInput(format!("no flashcards about {topic}")));
+    }
+
+    // Due cards first, most overdue first, then the new ones in the order they're written in, then with --all the rest
+    let due = progress.due_cards(today);
+    let mut picked: Vec<&Card> = due.iter().filter_map(|id| cards.iter().find(|card| card.id == *id)).collect();
+    picked.extend(cards.iter().filter(|card| !progress.card_reviews.contains_key(&card.id)));
+    if all {
+        picked.extend(cards.iter().filter(|card| !picked.contains(card)).collect::<Vec<_>>());
+    }
+
+    if picked.is_empty() && globals.format == Format::Text {
+        let style = globals.style;
+        if let Some(next) = next_card(&progress, today) {
+            let days = next.days_since(today);
+            println!("{} no flashcards due today, next on {next} (in {days} day{}), or go through them all with --all", style.paint(Role::Success, "✓"), if days == 1 { "" } else { "s" });
+        }
+        return Ok(());
+    }
+
+    // Keep stdout for the JSON report, the cards themselves go to stderr
+    let (mut out, style): (Box<dyn Write>, Style) = match globals.format {
+        Format::Text => (Box::new(io::stdout()), globals.style),
+        Format::Json => (Box::new(io::stderr()), Style::plain()),
+    };
+    let mut input = io::stdin().lock();
+    let mut graded: Vec<(&Card, bool)> = Vec::new();
+    for (index, card) in picked.iter().enumerate() {
+        writeln!(out)?;
+        writeln!(out, "{}", style.paint(Role::Heading, &format!("Card {} of {} · {}", index + 1, picked.len(), card.topic)))?;
+        writeln!(out, "{}", style.code_spans(&card.front))?;
+        write!(out, "{}", style.paint(Role::Dim, "enter to turn it over, q to stop "))?;
+        out.flush()?;
+        if matches!(read(&mut input)?.as_deref(), None | Some("q")) {
+            break;
+        }
+        writeln!(out, "{}", style.code_spans(&card.back))?;
+        if let Some(code) = &card.code {
+            writeln!(out)?;
+            for line in code.lines() {
+                writeln!(out, "    {}", style.paint(Role::Code, line))?;
+            }
+        }
+        writeln!(out)?;
+        let knew = loop {
+            write!(out, "Did you know it? [y/n] ")?;
+            out.flush()?;
+            match read(&mut input)?.as_deref() {
+                None | Some("q") => break None,
+                Some("y" | "yes") => break Some(true),
+                Some("n" | "no") => break Some(false),
+                Some(_) => {}
+            }
+        };
+        let Some(knew) = knew else { break };
+        let verdict = if knew { "knew it" } else { "didn't know it" };
+        globals.transcribe("flashcard", &format!("{} ({}): {verdict}\n{}", card.id, card.topic, card.front))?;
+        graded.push((card, knew));
+    }
+
+    let progress = globals.update_progress(|progress| {
+        for (card, knew) in &graded {
+            progress.record_card(card, *knew);
+        }
+        progress.clone()
+    })?;
+    let knew = graded.iter().filter(|(_, knew)| *knew).count();
+    let next = next_card(&progress, today);
+    match globals.format {
+        Format::Text => {
+            println!();
+            println!("{}", style.paint(Role::Heading, &format!("Knew {knew} of {}", graded.len())));
+            if let Some(next) = next {
+                println!("{}", style.paint(Role::Dim, &format!("next flashcards due on {next}")));
+            }
+        }
+        Format::Json => {
+            let cards = graded.iter().map(|(card, knew)| Json::object([("card", (&card.id).into()), ("topic", (&card.topic).into()), ("knew", (*knew).into())]));
+            println!(
+                "{}",
+                Json::object([("cards", Json::array(cards)), ("knew", knew.into()), ("total", graded.len().into()), ("next", next.map(|next| next.to_string()).into())])
+            );
+        }
+    }
+    Ok(())
+}
+
+// The first day after `today` a flashcard is due
+fn next_card(progress: &Progress, today: Date) -> Option<Date> {
+    progress.card_reviews.values().map(|review| review.due).filter(|due| *due > today).min()
+}
+
+// The next line, trimmed and lowercased, None once stdin is closed
+fn read(input: &mut impl BufRead) -> Result<Option<String>, LessonError> {
+    let mut line = String::new();
+    match input.read_line(&mut line)? {
+        0 => Ok(None),
+        _ => Ok(Some(line.trim().to_lowercase())),
+    }
+}
@@ -0,0 +1,73 @@
+use rust_day_6::date::Date;
+use rust_day_6::error::LessonError;
+use rust_day_6::json::Json;
+use rust_day_6::progress::Progress;
+use rust_day_6::style::{Role, Style};
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `goal [N|off]`, shows or sets how many exercises to complete each day
+pub fn goal(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let value = args.positional();
+    args.finish_empty()?;
+    let path = globals.progress_path();
+    let mut state = Progress::load(&path)?;
+    match value.as_deref() {
+        None => {}
+        Some("off") => state.daily_goal = None,
+        Some(count) => match count.parse::<usize>() {
+            Ok(count) if count > 0 => state.daily_goal = Some(count),
+            _ => return Err(LessonError::BadInput(format!("`{count}` isn't a daily goal, expected a number of exercises or `off`"))),
+        },
+    }
+    if value.is_some() {
+        state.save(&path)?;
+    }
+
+    let today = Date::today();
+    match globals.format {
+        Format::Text => {
+            match state.daily_goal {
+                Some(goal) => println!("Daily goal: {goal} exercise{}", if goal == 1 { "" } else { "s" }),
+                None => println!("No daily goal, set one with `rust_day_6 goal 2`"),
+            }
+            if let Some(banner) = banner(&state, today, globals.style) {
+                println!("{banner}");
+            }
+        }
+        Format::Json => println!("{}", summary(&state, today)),
+    }
+    Ok(())
+}
+
+// The streak and today's progress towards the goal, e.g. "3 day streak · 1/2 exercises today", or None for
+// a learner who hasn't completed anything and has no goal
+pub fn banner(state: &Progress, today: Date, style: Style) -> Option<String> {
+    let streak = state.streak(today);
+    let done = state.completed_on(today);
+    let streak = (streak > 0).then(|| style.paint(Role::Event, &format!("{streak} day streak")));
+    let goal = state.daily_goal.map(|goal| {
+        let text = format!("{done}/{goal} exercises today");
+        if done >= goal {
+            style.paint(Role::Success, &format!("{text} ✓"))
+        } else {
+            text
+        }
+    });
+    match (streak, goal) {
+        (Some(streak), Some(goal)) => Some(format!("{streak} · {goal}")),
+        (Some(text), None) | (None, Some(text)) => Some(text),
+        (None, None) => None,
+    }
+}
+
+// The same numbers as `banner`, for JSON output
+pub fn summary(state: &Progress, today: Date) -> Json {
+    Json::object([
+        ("goal", state.daily_goal.into()),
+        ("today", state.completed_on(today).into()),
+        ("streak", state.streak(today).into()),
+        ("longest_streak", state.longest_streak().into()),
+    ])
+}
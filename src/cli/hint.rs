@@ -0,0 +1,61 @@
+use rust_day_6::error::LessonError;
+use rust_day_6::events::EventKind;
+use rust_day_6::exercises::HintTier;
+use rust_day_6::json::Json;
+use rust_day_6::progress::Progress;
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `hint [exercise]`, shows the next hint for an exercise, the current one by default
+pub fn hint(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let name = args.positional();
+    args.finish_empty()?;
+
+    let set = globals.exercises()?;
+    let exercise = match &name {
+        Some(name) => set.get_named(name)?,
+        None => match set.current()? {
+            Some(exercise) => exercise,
+            None => return Err(LessonError::BadInput("every exercise is done, no hints needed".to_string())),
+        },
+    };
+    if exercise.info.hints.is_empty() {
+        return Err(LessonError::BadInput(format!("{} has no hints", exercise.info.name)));
+    }
+
+    // Each call reveals one more, once they're all out the last one is repeated
+    let path = globals.progress_path();
+    let mut state = Progress::load(&path)?;
+    let shown = state.hints.entry(exercise.info.name.to_string()).or_default();
+    *shown = (*shown + 1).min(exercise.info.hints.len());
+    let number = *shown;
+    state.log(EventKind::HintShown { exercise: exercise.info.name.clone(), number });
+    state.save(&path)?;
+
+    let total = exercise.info.hints.len();
+    let hint = &exercise.info.hints[number - 1];
+    let tier = HintTier::of(number, total);
+    match globals.format {
+        Format::Text => {
+            let heading = format!("Hint {number} of {total} for {}", exercise.info.name);
+            println!("{} {}", globals.style.paint(Role::Heading, &heading), globals.style.paint(Role::Dim, &format!("({})", tier.as_str())));
+            println!("{}", globals.style.code_spans(hint));
+            if number == total {
+                println!("{}", globals.style.paint(Role::Dim, "That's the last hint."));
+            }
+        }
+        Format::Json => println!(
+            "{}",
+            Json::object([
+                ("exercise", (&exercise.info.name).into()),
+                ("hint", hint.into()),
+                ("number", number.into()),
+                ("total", total.into()),
+                ("tier", tier.as_str().into()),
+            ])
+        ),
+    }
+    Ok(())
+}
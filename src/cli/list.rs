@@ -0,0 +1,61 @@
+use rust_day_6::days;
+use rust_day_6::error::LessonError;
+use rust_day_6::json::{Json, ToJson};
+use rust_day_6::lesson::{Difficulty, Lesson, LessonDef};
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `list`, every lesson grouped by day, with its topics and a one-line summary
+// `list --names` prints just the names, one per line, for the shell completions
+// `list --difficulty D` only shows the lessons of one difficulty
+pub fn list(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let names_only = args.flag("--names");
+    let difficulty = args.value("--difficulty")?.map(|value| value.parse::<Difficulty>()).transpose()?;
+    args.finish_empty()?;
+    let wanted = |lesson: &LessonDef| difficulty.is_none_or(|difficulty| lesson.difficulty == difficulty);
+
+    if names_only {
+        for lesson in globals.runner().build().lessons() {
+            if difficulty.is_none_or(|difficulty| lesson.difficulty() == difficulty) {
+                println!("{}", lesson.name());
+            }
+        }
+        return Ok(());
+    }
+
+    // Days with nothing left after filtering aren't shown at all
+    let days: Vec<_> = days::DAYS
+        .iter()
+        .filter(|day| globals.day.is_none_or(|wanted| wanted == day.number))
+        .filter(|day| day.lessons().any(wanted))
+        .collect();
+    match globals.format {
+        Format::Text => {
+            // As wide as the longest name and topic list shown, so the summaries line up
+            let shown = || days.iter().flat_map(|day| day.lessons()).filter(|lesson| wanted(lesson));
+            let name_width = shown().map(|lesson| lesson.name.len()).max().unwrap_or(0);
+            let topics_width = shown().map(|lesson| lesson.topics.join(", ").len()).max().unwrap_or(0);
+            for day in &days {
+                let heading = format!("Day {}: {}", day.number, day.title);
+                println!("{}", globals.style.paint(Role::Heading, &heading));
+                for lesson in day.lessons().filter(|lesson| wanted(lesson)) {
+                    let difficulty = globals.style.paint(Role::Dim, &format!("{:<9}", lesson.difficulty));
+                    println!("    {:<name_width$} {difficulty} {:<topics_width$} {}", lesson.name, lesson.topics.join(", "), lesson.summary);
+                }
+            }
+        }
+        Format::Json => {
+            let days = days.into_iter().map(|day| {
+                Json::object([
+                    ("day", u32::from(day.number).into()),
+                    ("title", day.title.into()),
+                    ("lessons", Json::array(day.lessons().filter(|lesson| wanted(lesson)).map(|lesson| (lesson as &dyn Lesson).to_json()))),
+                ])
+            });
+            println!("{}", Json::array(days));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::io::{self, BufRead, Write};
+
+use rust_day_6::date::Date;
+use rust_day_6::error::LessonError;
+use rust_day_6::style::Role;
+
+use super::{announce, goal, save_completed, Globals};
+
+// Numbered list of lessons, runs the one picked and comes back until the user quits
+pub fn menu(globals: &Globals) -> Result<(), LessonError> {
+    let mut runner = globals.runner().build();
+    let lessons: Vec<(String, String)> = runner
+        .lessons()
+        .map(|lesson| (lesson.name().to_string(), lesson.summary().to_string()))
+        .collect();
+
+    // Where the streak stands when a session starts
+    if let Some(banner) = goal::banner(&globals.progress()?, Date::today(), globals.style) {
+        println!("{banner}");
+    }
+
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    loop {
+        println!();
+        for (number, (name, summary)) in lessons.iter().enumerate() {
+            println!("{:>3}. {:<22} {}", number + 1, name, summary);
+        }
+        print!("Pick a lesson (1-{}), or q to quit: ", lessons.len());
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            println!();
+            return Ok(()); // End of input
+        }
+        let choice = line.trim();
+        if choice.eq_ignore_ascii_case("q") || choice.eq_ignore_ascii_case("quit") {
+            return Ok(());
+        }
+
+        // Accept the lesson's name as well as its number
+        let picked = match choice.parse::<usize>() {
+            Ok(number) if (1..=lessons.len()).contains(&number) => Some(&lessons[number - 1].0),
+            _ => lessons.iter().map(|(name, _)| name).find(|name| *name == choice),
+        };
+        match picked {
+            Some(name) => {
+                println!();
+                if let Err(err) = runner.run(name) {
+                    eprintln!("{} {err}", globals.style.for_stderr().paint(Role::Error, "error:"));
+                }
+                announce(globals, &save_completed(globals, &runner)?);
+            }
+            None => println!("`{choice}` isn't one of the lessons"),
+        }
+    }
+}
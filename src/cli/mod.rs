@@ -0,0 +1,396 @@
+mod args;
+mod bench;
+mod check;
+mod completions;
+mod config;
+mod doctor;
+mod explain;
+mod export;
+mod firstword;
+mod flashcards;
+mod goal;
+mod hint;
+mod list;
+mod menu;
+mod new_exercise;
+mod notes;
+mod progress;
+mod quiz;
+mod reset;
+mod review;
+mod run;
+mod search;
+mod show;
+mod solution;
+mod stats;
+mod tui;
+mod verify;
+mod watch;
+
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use rust_day_6::achievements::{self, Achievement};
+use rust_day_6::config::{self as configs, Config};
+use rust_day_6::days;
+use rust_day_6::error::LessonError;
+use rust_day_6::events::EventKind;
+use rust_day_6::exercises::{self, ExerciseSet};
+use rust_day_6::report::{Reporter, StdoutReporter};
+use rust_day_6::shuffle;
+use rust_day_6::runner::{LessonRunner, LessonRunnerBuilder};
+use rust_day_6::progress::Progress;
+use rust_day_6::style::{Role, Style};
+use rust_day_6::transcript::{Transcript, TranscriptReporter};
+
+use args::ArgList;
+
+// (command, arguments, description), the source for both `help` and the shell completions
+pub const COMMANDS: &[(&str, &str, &str)] = &[
+    ("run", "<lesson> [--ignore-prereqs] [--timeline] [--step]", "run a single lesson by name, once the lessons it builds on are done"),
+    ("run", "--all", "run every lesson and print a summary"),
+    ("run", "--difficulty D", "run every intro, core or challenge lesson"),
+    ("list", "[--difficulty D]", "show every lesson with its topics"),
+    ("show", "<lesson>", "print a lesson's demo code with what happened to ownership next to each line"),
+    ("search", "<words>", "find lessons mentioning a keyword"),
+    ("notes", "[topic]", "print the ownership, reference and slice rules"),
+    ("firstword", "[--each-line]", "print the first word of stdin, or of each line"),
+    ("quiz", "[--topic T] [--deck FILE] [--count N] [--uniform] [--timed 60s]", "answer questions about the lessons, more of them on your weak topics, or from a deck"),
+    ("flashcards", "[--topic T] [--all]", "go over the ownership and borrowing rules as cards, graded by you"),
+    ("stats", "", "per-topic accuracy, attempts and trend from your history"),
+    ("review", "[--list]", "go over the quiz questions and exercises due for review today"),
+    ("bench", "[--iterations N]", "time clone vs reference, stack vs heap and friends"),
+    ("watch", "[--shuffle] [--difficulty D]", "check the current exercise on every save, rustlings style"),
+    ("verify", "[--shuffle] [--difficulty D]", "check every exercise in order, stopping at the first unfinished one"),
+    ("hint", "[exercise]", "show the next hint for an exercise, the current one by default"),
+    ("reset", "<exercise>", "restore an exercise file to its original skeleton"),
+    ("solution", "<exercise>", "show the reference solution and a diff against your attempt"),
+    ("new-exercise", "<name> --topic T [--mode M] [--error EXXXX]", "scaffold a new exercise, its solution and manifest entry"),
+    ("progress", "", "show lessons completed, exercises passed and quiz accuracy"),
+    ("progress", "export <file.json>", "save your progress to a file, `-` for stdout"),
+    ("progress", "import <file.json>", "merge progress from another machine into yours"),
+    ("export", "anki <deck.tsv>", "save the flashcards and quiz questions as a deck to import into Anki"),
+    ("export", "dot <lesson> [file.dot]", "draw a lesson's owners, moves and borrows as a Graphviz graph"),
+    ("export", "mermaid <lesson> [file.mmd]", "the same as a Mermaid flowchart, for Markdown docs and wikis"),
+    ("goal", "[N|off]", "show or set how many exercises to complete each day"),
+    ("explain", "<error-code>", "explain a rustc ownership error, e.g. E0382"),
+    ("check", "<file.rs>", "compile a snippet and explain the borrow-check errors in it"),
+    ("config", "", "show the effective configuration and where each setting comes from"),
+    ("doctor", "", "check rustc, the exercises directory, the progress file and the terminal"),
+    ("completions", "<shell>", "print a bash, zsh or fish completion script"),
+    ("help", "", "show this message"),
+];
+
+// Commands whose first argument is a lesson name
+pub const LESSON_COMMANDS: &[&str] = &["run", "show"];
+
+// (flag, value), flags that apply to every command
+pub const GLOBAL_FLAGS: &[(&str, &str)] = &[
+    ("--day", "N"),
+    ("--verbose", ""),
+    ("--trace", ""),
+    ("--format", "text|json"),
+    ("--exercises-dir", "DIR"),
+    ("--profile", "NAME"),
+    ("--transcript", "FILE"),
+    ("--color", ""),
+    ("--no-color", ""),
+    ("--tui", ""),
+    ("--help", ""),
+];
+
+fn usage() -> String {
+    let flags: Vec<String> = GLOBAL_FLAGS
+        .iter()
+        .map(|(flag, value)| if value.is_empty() { format!("[{flag}]") } else { format!("[{flag} {value}]") })
+        .collect();
+    let mut usage = format!("usage: rust_day_6 {} [COMMAND]\n\ncommands:\n", flags.join(" "));
+    let width = COMMANDS.iter().map(|(command, args, _)| command.len() + 1 + args.len()).max().unwrap_or(0);
+    for (command, args, description) in COMMANDS {
+        usage.push_str(&format!("    {:<width$}  {description}\n", format!("{command} {args}")));
+    }
+    usage.push_str(
+        "\nwith --tui a full-screen lesson browser opens instead\n\
+         without a command a menu of lessons is shown, or every lesson is run when stdin isn't a terminal\n\
+         or the format is json",
+    );
+    usage
+}
+
+// How commands print their results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+// Flags that apply to every command
+pub struct Globals {
+    pub day: Option<u8>,
+    pub verbose: bool,
+    // --trace, every demo's calls and returns as ownership events too, implies --verbose
+    pub trace: bool,
+    pub format: Format,
+    pub style: Style,
+    // true for --color, false for --no-color, None when neither was given
+    pub color: Option<bool>,
+    // From --exercises-dir, `exercises_dir()` has the one to use
+    pub exercises_dir: Option<PathBuf>,
+    // From --profile or RUST_DAY_6_PROFILE, whose progress and config to use
+    pub profile: Option<String>,
+    pub config: Config,
+    // From --transcript, where lessons, explanations and quiz answers are written down as well
+    pub transcript: Option<Transcript>,
+}
+
+impl Globals {
+    fn parse(args: &mut ArgList) -> Result<Self, LessonError> {
+        let day = args.parsed("--day")?;
+        if let Some(day) = day {
+            if days::day(day).is_none() {
+                return Err(LessonError::UnknownDay(day));
+            }
+        }
+        let format = match args.value("--format")?.as_deref() {
+            None | Some("text") => Format::Text,
+            Some("json") => Format::Json,
+            Some(other) => return Err(LessonError::BadInput(format!("unknown format `{other}`, expected text or json"))),
+        };
+        let profile = profile(args)?;
+        let config = Config::load(&Config::path(profile.as_deref()))?;
+        let color = match (args.flag("--color"), args.flag("--no-color")) {
+            (true, true) => return Err(LessonError::BadInput("--color and --no-color can't both be given".to_string())),
+            (true, false) => Some(true),
+            (false, true) => Some(false),
+            (false, false) => None,
+        };
+        let style = Style::detect(!config.color(color));
+        let exercises_dir = args.value("--exercises-dir")?.map(|dir| expand_home(&dir));
+        if let Some(dir) = &exercises_dir {
+            // The default directory is created on demand, one that was asked for by name has to exist
+            if !dir.is_dir() {
+                return Err(LessonError::BadInput(format!("--exercises-dir {} isn't a directory", dir.display())));
+            }
+        }
+        let trace = args.flag("--trace");
+        let verbose = args.flag("--verbose") || trace;
+        // Last, so what's left is the command for the session line
+        let transcript = match args.value("--transcript")? {
+            Some(file) => Some(Transcript::open(expand_home(&file), &args.rest().join(" "))?),
+            None => None,
+        };
+        Ok(Self { day, verbose, trace, format, style, color, exercises_dir, profile, config, transcript })
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        Config::path(self.profile.as_deref())
+    }
+
+    pub fn progress_path(&self) -> PathBuf {
+        Progress::path(self.profile.as_deref())
+    }
+
+    // The learner's progress, for the profile if there is one
+    pub fn progress(&self) -> Result<Progress, LessonError> {
+        Progress::load(&self.progress_path())
+    }
+
+    // Loads the progress, changes it and saves it again, returning what `change` did
+    pub fn update_progress<T>(&self, change: impl FnOnce(&mut Progress) -> T) -> Result<T, LessonError> {
+        Progress::update(&self.progress_path(), change)
+    }
+
+    // --exercises-dir, then RUST_DAY_6_EXERCISES, then the config file, then ./exercises
+    pub fn exercises_dir(&self) -> PathBuf {
+        if let Some(dir) = &self.exercises_dir {
+            return dir.clone();
+        }
+        match &self.config.exercises_dir {
+            Some(dir) if std::env::var_os("RUST_DAY_6_EXERCISES").is_none() => expand_home(dir),
+            _ => exercises::dir(),
+        }
+    }
+
+    // The command to open files with, from the config file or else VISUAL or EDITOR
+    pub fn editor(&self) -> Option<String> {
+        self.config.editor.clone().or_else(|| ["VISUAL", "EDITOR"].into_iter().find_map(|var| std::env::var(var).ok().filter(|editor| !editor.is_empty())))
+    }
+
+    // The exercises in the exercises directory, without touching the learner's files
+    pub fn exercises(&self) -> Result<ExerciseSet, LessonError> {
+        ExerciseSet::open_in(self.exercises_dir())
+    }
+
+    // Like `exercises`, writing out the skeletons of any that haven't been started
+    pub fn load_exercises(&self) -> Result<ExerciseSet, LessonError> {
+        ExerciseSet::load_in(self.exercises_dir())
+    }
+
+    // Writes `text` into the transcript if there is one
+    pub fn transcribe(&self, kind: &str, text: &str) -> Result<(), LessonError> {
+        match &self.transcript {
+            Some(transcript) => transcript.record(kind, text),
+            None => Ok(()),
+        }
+    }
+
+    // `reporter`, also writing into the transcript if there is one
+    pub fn reporter(&self, reporter: impl Reporter + 'static) -> Box<dyn Reporter> {
+        match &self.transcript {
+            Some(transcript) => Box::new(TranscriptReporter::new(reporter, transcript.clone()).show_details(self.verbose)),
+            None => Box::new(reporter),
+        }
+    }
+
+    // Where lessons report to unless told otherwise, stdout with the global flags applied
+    pub fn stdout_reporter(&self) -> Box<dyn Reporter> {
+        self.reporter(StdoutReporter::new().show_details(self.verbose).style(self.style))
+    }
+
+    // A runner builder with the global flags already applied, reporting to stdout
+    pub fn runner(&self) -> LessonRunnerBuilder {
+        let mut builder = LessonRunner::builder().verbose(self.verbose).trace(self.trace).reporter(self.stdout_reporter());
+        if let Some(day) = self.day {
+            builder = builder.day(day);
+        }
+        builder
+    }
+}
+
+// `--shuffle` or `--seed N`, the seed to shuffle with if either was given. A fresh seed is picked for a plain
+// `--shuffle` and printed so the same order can be had again.
+pub fn shuffle_seed(globals: &Globals, args: &mut ArgList) -> Result<Option<u64>, LessonError> {
+    let shuffle = args.flag("--shuffle");
+    let seed = match args.parsed("--seed")? {
+        Some(seed) => Some(seed),
+        None if shuffle => Some(shuffle::seed_from_time()),
+        None => None,
+    };
+    if let Some(seed) = seed {
+        let note = format!("shuffled with seed {seed}, use --seed {seed} for the same order again");
+        eprintln!("{}", globals.style.for_stderr().paint(Role::Dim, &note));
+    }
+    Ok(seed)
+}
+
+// --profile, otherwise RUST_DAY_6_PROFILE, so a shared login can set it once
+fn profile(args: &mut ArgList) -> Result<Option<String>, LessonError> {
+    let profile = match args.value("--profile")? {
+        Some(profile) => Some(profile),
+        None => std::env::var("RUST_DAY_6_PROFILE").ok().filter(|profile| !profile.is_empty()),
+    };
+    if let Some(profile) = &profile {
+        configs::check_profile(profile)?;
+    }
+    Ok(profile)
+}
+
+// `~/practice` for a value that didn't go through a shell, e.g. `--exercises-dir=~/practice`
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+// Remembers the lessons `runner` completed, for `progress`, returning the achievements that unlocked
+pub fn save_completed(globals: &Globals, runner: &LessonRunner) -> Result<Vec<&'static Achievement>, LessonError> {
+    if runner.completed().is_empty() {
+        return Ok(Vec::new());
+    }
+    globals.update_progress(|state| {
+        for lesson in runner.completed() {
+            state.lessons.insert(lesson.clone());
+            state.log(EventKind::LessonCompleted { lesson: lesson.clone() });
+        }
+        // A broken exercises.toml is for the exercise commands to complain about
+        globals.exercises().map(|set| achievements::unlock(state, &set)).unwrap_or_default()
+    })
+}
+
+// Unlocks the achievements the latest exercise results earned
+pub fn unlock_achievements(globals: &Globals, set: &ExerciseSet) -> Result<Vec<&'static Achievement>, LessonError> {
+    globals.update_progress(|state| achievements::unlock(state, set))
+}
+
+// Celebrates newly unlocked achievements, on stderr in JSON mode so stdout stays valid JSON
+pub fn announce(globals: &Globals, unlocked: &[&Achievement]) {
+    for achievement in unlocked {
+        let line = format!("★ Achievement unlocked: {}, {}", achievement.title, achievement.description.to_lowercase());
+        match globals.format {
+            Format::Text => println!("{}", globals.style.paint(Role::Event, &line)),
+            Format::Json => eprintln!("{}", globals.style.for_stderr().paint(Role::Event, &line)),
+        }
+    }
+}
+
+pub fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // Globals aren't parsed yet, and parsing them is one of the things that can fail
+    let profile = profile(&mut ArgList::new(args.clone())).ok().flatten();
+    let config = Config::load(&Config::path(profile.as_deref())).unwrap_or_default();
+    let flag = if args.iter().any(|arg| arg == "--color") { Some(true) } else { args.iter().any(|arg| arg == "--no-color").then_some(false) };
+    let color = config.color(flag);
+    let style = Style::detect(!color).for_stderr();
+    match dispatch(ArgList::new(args)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{} {err}", style.paint(Role::Error, "error:"));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn dispatch(mut args: ArgList) -> Result<(), LessonError> {
+    if args.flag("--help") {
+        println!("{}", usage());
+        return Ok(());
+    }
+    let globals = Globals::parse(&mut args)?;
+    if args.flag("--tui") {
+        args.finish_empty()?;
+        return tui::tui(&globals);
+    }
+
+    match args.positional().as_deref() {
+        None => {
+            args.finish_empty()?;
+            if io::stdin().is_terminal() && globals.format == Format::Text {
+                menu::menu(&globals)
+            } else {
+                run::run_all(&globals)
+            }
+        }
+        Some("run") => run::run(&globals, args),
+        Some("list") => list::list(&globals, args),
+        Some("show") => show::show(&globals, args),
+        Some("search") => search::search(&globals, args),
+        Some("notes") => notes::notes(&globals, args),
+        Some("firstword") => firstword::firstword(&globals, args),
+        Some("quiz") => quiz::quiz(&globals, args),
+        Some("review") => review::review(&globals, args),
+        Some("flashcards") => flashcards::flashcards(&globals, args),
+        Some("stats") => stats::stats(&globals, args),
+        Some("bench") => bench::bench(&globals, args),
+        Some("watch") => watch::watch(&globals, args),
+        Some("verify") => verify::verify(&globals, args),
+        Some("hint") => hint::hint(&globals, args),
+        Some("reset") => reset::reset(&globals, args),
+        Some("solution") => solution::solution(&globals, args),
+        Some("new-exercise") => new_exercise::new_exercise(&globals, args),
+        Some("progress") => progress::progress(&globals, args),
+        Some("export") => export::export(&globals, args),
+        Some("goal") => goal::goal(&globals, args),
+        Some("explain") => explain::explain(&globals, args),
+        Some("check") => check::check(&globals, args),
+        Some("config") => config::config(&globals, args),
+        Some("doctor") => doctor::doctor(&globals, args),
+        Some("completions") => completions::completions(args),
+        Some("help") => {
+            println!("{}", usage());
+            Ok(())
+        }
+        Some(other) => Err(LessonError::BadInput(format!("unknown command `{other}`, see `rust_day_6 help`"))),
+    }
+}
@@ -0,0 +1,170 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use rust_day_6::error::LessonError;
+use rust_day_6::exercises::NOT_DONE_MARKER;
+use rust_day_6::json::Json;
+use rust_day_6::registry;
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `NAME`, `TOPIC`, `ERROR` and `MARKER` are filled in. Each template compiles as it is so the author can start from
+// something that builds, the TODOs say what to break or leave out.
+const COMPILE_TEMPLATE: &str = "// NAME.rs
+// TODO: describe what this exercise shows about TOPIC.
+// TODO: say what the learner has to change, e.g. \"make this compile without removing the println!\"
+// Then delete the `I AM NOT DONE` line to move on to the next exercise.
+
+MARKER
+
+fn main() {
+    // TODO: write code that doesn't compile until the learner fixes it
+    let s = String::from(\"hello\");
+    println!(\"{s}\");
+}
+";
+
+const TEST_TEMPLATE: &str = "// NAME.rs
+// TODO: describe what this exercise shows about TOPIC.
+// TODO: say what the learner has to change, e.g. \"make the tests pass by returning a slice\"
+// Then delete the `I AM NOT DONE` line to move on to the next exercise.
+
+MARKER
+
+// TODO: the function the learner implements
+fn answer(s: &str) -> &str {
+    s
+}
+
+fn main() {
+    println!(\"{}\", answer(\"hello\"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TODO: tests that only pass once the exercise is solved
+    #[test]
+    fn works() {
+        assert_eq!(answer(\"hello\"), \"hello\");
+    }
+}
+";
+
+const COMPILE_FAIL_TEMPLATE: &str = "// NAME.rs
+// This one compiles, and it shouldn't.
+// TODO: describe what to break about TOPIC so rustc rejects the program with ERROR.
+// Then delete the `I AM NOT DONE` line to move on to the next exercise.
+
+MARKER
+
+fn main() {
+    let s = String::from(\"hello\");
+    println!(\"{s}\");
+}
+";
+
+// `new-exercise <name> --topic T [--mode M] [--error EXXXX]`, writes a skeleton, a solution stub and a
+// manifest entry for a new exercise in the exercises directory
+pub fn new_exercise(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let topic = args.value("--topic")?;
+    let mode = args.value("--mode")?.unwrap_or_else(|| "compile".to_string());
+    let error = args.value("--error")?;
+    let Some(name) = args.positional() else {
+        return Err(LessonError::BadInput("new-exercise needs a name (e.g. `new-exercise borrow4 --topic references`)".to_string()));
+    };
+    args.finish_empty()?;
+    let Some(topic) = topic else {
+        return Err(LessonError::BadInput(format!("new-exercise needs the lesson topic {name} practices, e.g. --topic references")));
+    };
+
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') {
+        return Err(LessonError::BadInput(format!("`{name}` should be lowercase letters, digits and underscores, it's also the file name")));
+    }
+    if topic.is_empty() || topic.contains(['"', '\\', '\n']) {
+        return Err(LessonError::BadInput(format!("`{topic}` can't be a topic, it goes in the manifest as a plain string")));
+    }
+    let template = match mode.as_str() {
+        "compile" => COMPILE_TEMPLATE,
+        "test" => TEST_TEMPLATE,
+        "compile-fail" => COMPILE_FAIL_TEMPLATE,
+        other => return Err(LessonError::BadInput(format!("unknown mode `{other}`, expected compile, test or compile-fail"))),
+    };
+    let error = match (mode.as_str(), error) {
+        ("compile-fail", Some(error)) => Some(error),
+        ("compile-fail", None) => return Err(LessonError::BadInput("a compile-fail exercise needs the --error code it should fail with, e.g. --error E0382".to_string())),
+        (_, Some(_)) => return Err(LessonError::BadInput("--error only applies to --mode compile-fail".to_string())),
+        (_, None) => None,
+    };
+
+    let dir = globals.exercises_dir();
+    let manifest = dir.join("exercises.toml");
+    let path = format!("{name}.rs");
+    let solution = format!("solutions/{name}.rs");
+    let had_manifest = manifest.exists();
+    // Refuse before writing anything, rather than leave half an exercise behind. Opening the set also stops
+    // at a manifest that's already broken.
+    if had_manifest && globals.exercises()?.find(&name).is_some() {
+        return Err(LessonError::BadInput(format!("{} already has an exercise named {name}", manifest.display())));
+    }
+    for file in [&path, &solution] {
+        if dir.join(file).exists() {
+            return Err(LessonError::BadInput(format!("{} already exists", dir.join(file).display())));
+        }
+    }
+
+    let skeleton = template
+        .replace("NAME", &name)
+        .replace("TOPIC", &topic)
+        .replace("ERROR", error.as_deref().unwrap_or(""))
+        .replace("MARKER", NOT_DONE_MARKER);
+    let solved = skeleton.replace(&format!("{NOT_DONE_MARKER}\n\n"), "");
+    fs::create_dir_all(dir.join("solutions"))?;
+    fs::write(dir.join(&path), skeleton)?;
+    fs::write(dir.join(&solution), format!("// TODO: the solved {name}.rs, shown by `rust_day_6 solution {name}`\n{solved}"))?;
+
+    let mut entry = String::new();
+    if had_manifest {
+        entry.push('\n');
+    } else {
+        entry.push_str("# Exercises for rust_day_6, see `rust_day_6 new-exercise`\n\n");
+    }
+    entry.push_str(&format!("[[exercises]]\nname = \"{name}\"\npath = \"{path}\"\ntopic = \"{topic}\"\nmode = \"{mode}\"\n"));
+    if let Some(error) = &error {
+        entry.push_str(&format!("error = \"{error}\"\n"));
+    }
+    entry.push_str(&format!("solution = \"{solution}\"\n"));
+    entry.push_str("# From a nudge to something close to the solution\nhints = []\n");
+    OpenOptions::new().create(true).append(true).open(&manifest)?.write_all(entry.as_bytes())?;
+
+    match globals.format {
+        Format::Text => {
+            let style = globals.style;
+            println!("{} created {name}", style.paint(Role::Success, "✓"));
+            for file in [&path, &solution] {
+                println!("  {}", dir.join(file).display());
+            }
+            println!("  {} {}", manifest.display(), if had_manifest { "(entry added)" } else { "(new)" });
+            if !had_manifest {
+                println!("{}", style.paint(Role::Event, &format!("{} now uses its own exercises instead of the built-in ones", dir.display())));
+            }
+            if !registry::lessons().any(|lesson| lesson.topics.contains(&topic.as_str())) {
+                println!("{}", style.paint(Role::Dim, &format!("no lesson has the topic `{topic}`, `rust_day_6 list` shows the ones there are")));
+            }
+            println!("{}", style.paint(Role::Dim, "fill in the TODOs and the hints, then try it with `rust_day_6 verify`"));
+        }
+        Format::Json => println!(
+            "{}",
+            Json::object([
+                ("exercise", name.into()),
+                ("path", dir.join(&path).display().to_string().into()),
+                ("solution", dir.join(&solution).display().to_string().into()),
+                ("manifest", manifest.display().to_string().into()),
+            ])
+        ),
+    }
+    Ok(())
+}
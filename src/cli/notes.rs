@@ -0,0 +1,41 @@
+use rust_day_6::error::LessonError;
+use rust_day_6::json::{Json, ToJson};
+use rust_day_6::notes;
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `notes [topic]`, the cheatsheet of rules for ownership, references and slices
+pub fn notes(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let topic = args.positional();
+    args.finish_empty()?;
+
+    if let Some(topic) = &topic {
+        if !notes::topics().contains(&topic.as_str()) {
+            return Err(LessonError::BadInput(format!(
+                "no notes about `{topic}`, try one of {}",
+                notes::topics().join(", ")
+            )));
+        }
+    }
+
+    let notes: Vec<_> = notes::notes(topic.as_deref()).collect();
+    if globals.format == Format::Json {
+        println!("{}", Json::array(notes.iter().map(|note| note.to_json())));
+        return Ok(());
+    }
+
+    let style = globals.style;
+    for (index, note) in notes.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        println!("{}", style.paint(Role::Heading, note.title));
+        for (number, item) in note.items.iter().enumerate() {
+            let marker = if note.numbered { format!("{}.", number + 1) } else { "-".to_string() };
+            println!("    {marker} {}", style.code_spans(item));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,258 @@
+use std::fs;
+use std::path::PathBuf;
+
+use rust_day_6::achievements::ACHIEVEMENTS;
+use rust_day_6::date::Date;
+use rust_day_6::error::LessonError;
+use rust_day_6::exercises::HintTier;
+use rust_day_6::json::{Json, ToJson};
+use rust_day_6::progress::{self, Progress};
+use rust_day_6::registry;
+use rust_day_6::style::{Role, Style};
+
+use super::args::ArgList;
+use super::{goal, Format, Globals};
+
+const BAR_WIDTH: usize = 20;
+
+// `progress export <file>`, the whole progress file as it is, `-` for stdout
+fn export(globals: &Globals, file: PathBuf) -> Result<(), LessonError> {
+    let state = globals.progress()?;
+    let text = format!("{}\n", state.to_json().pretty());
+    if file.as_os_str() == "-" {
+        print!("{text}");
+        return Ok(());
+    }
+    fs::write(&file, text).map_err(|err| LessonError::BadInput(format!("can't write {}: {err}", file.display())))?;
+    match globals.format {
+        Format::Text => println!("{} exported progress to {}", globals.style.paint(Role::Success, "✓"), file.display()),
+        Format::Json => println!("{}", Json::object([("exported", file.display().to_string().into())])),
+    }
+    Ok(())
+}
+
+// `progress import <file>`, merges an exported file into the current progress, keeping the best of both
+fn import(globals: &Globals, file: PathBuf) -> Result<(), LessonError> {
+    let imported = Progress::read(&file)?;
+    let (before, after) = globals.update_progress(|state| {
+        let before = state.clone();
+        state.merge(&imported);
+        (before, state.clone())
+    })?;
+    // (plural, singular, how many the import added)
+    let added = [
+        ("lessons", "lesson", after.lessons.len() - before.lessons.len()),
+        ("exercises", "exercise", after.exercises.len() - before.exercises.len()),
+        ("quizzes", "quiz", after.quiz_history.len() - before.quiz_history.len()),
+        ("achievements", "achievement", after.achievements.len() - before.achievements.len()),
+    ];
+    match globals.format {
+        Format::Text => {
+            let style = globals.style;
+            if before == after {
+                println!("{} nothing new in {}", style.paint(Role::Success, "✓"), file.display());
+                return Ok(());
+            }
+            println!("{} imported {}", style.paint(Role::Success, "✓"), file.display());
+            for (plural, singular, count) in added.iter().filter(|(_, _, count)| *count > 0) {
+                println!("  {count} new {}", if *count == 1 { singular } else { plural });
+            }
+        }
+        Format::Json => {
+            let added = added.map(|(plural, _, count)| (plural, count.into()));
+            println!("{}", Json::object([("imported", file.display().to_string().into()), ("added", Json::object(added))]));
+        }
+    }
+    Ok(())
+}
+
+// `progress`, how far through the lessons, exercises and quizzes the learner is, or `progress export|import`
+pub fn progress(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let action = args.positional();
+    let file = args.positional();
+    args.finish_empty()?;
+    match (action.as_deref(), file) {
+        (None, _) => {}
+        (Some("export"), Some(file)) => return export(globals, PathBuf::from(file)),
+        (Some("import"), Some(file)) => return import(globals, PathBuf::from(file)),
+        (Some(action @ ("export" | "import")), None) => {
+            return Err(LessonError::BadInput(format!("progress {action} needs a file, e.g. `progress {action} progress-backup.json`")))
+        }
+        (Some(other), _) => return Err(LessonError::BadInput(format!("unknown progress action `{other}`, expected export or import"))),
+    }
+    let state = globals.progress()?;
+
+    // Only count what still exists, a renamed lesson shouldn't push the total past 100%
+    let lessons = registry::lessons().filter(|lesson| state.lessons.contains(lesson.name)).count();
+    let lesson_total = registry::lessons().count();
+    let set = globals.exercises()?;
+    let passed = set.iter().filter(|exercise| state.exercises.contains(&exercise.info.name)).count();
+    let exercise_total = set.len();
+    let now = progress::now();
+
+    if globals.format == Format::Json {
+        let scores = set.iter().filter_map(|exercise| {
+            let (score, total) = state.scores.get(&exercise.info.name)?;
+            Some(Json::object([("exercise", (&exercise.info.name).into()), ("score", (*score).into()), ("total", (*total).into())]))
+        });
+        let hints = set.iter().filter_map(|exercise| {
+            let used = state.hints.get(&exercise.info.name)?;
+            Some(Json::object([("exercise", (&exercise.info.name).into()), ("used", (*used).into()), ("total", exercise.info.hints.len().into())]))
+        });
+        let time = set.iter().filter_map(|exercise| {
+            let timing = state.timing.get(&exercise.info.name)?;
+            Some(Json::object([
+                ("exercise", (&exercise.info.name).into()),
+                ("seconds", timing.seconds(now).into()),
+                ("finished", timing.finished.is_some().into()),
+            ]))
+        });
+        let achievements = ACHIEVEMENTS.iter().map(|achievement| {
+            Json::object([
+                ("id", achievement.id.into()),
+                ("title", achievement.title.into()),
+                ("description", achievement.description.into()),
+                ("unlocked", state.achievements.contains(achievement.id).into()),
+            ])
+        });
+        let quiz = state.quiz.iter().map(|(topic, (correct, answered))| {
+            Json::object([("topic", topic.into()), ("correct", (*correct).into()), ("answered", (*answered).into())])
+        });
+        println!(
+            "{}",
+            Json::object([
+                ("profile", globals.profile.as_deref().into()),
+                ("lessons", Json::object([("completed", lessons.into()), ("total", lesson_total.into())])),
+                ("exercises", Json::object([("passed", passed.into()), ("total", exercise_total.into()), ("scores", Json::array(scores))])),
+                ("hints", Json::array(hints)),
+                ("time", Json::array(time)),
+                ("quiz", Json::array(quiz)),
+                ("achievements", Json::array(achievements)),
+                ("streak", goal::summary(&state, Date::today())),
+                ("review", {
+                    let (questions, exercises) = state.due_reviews(Date::today());
+                    Json::object([
+                        ("due", Json::object([("questions", questions.len().into()), ("exercises", exercises.len().into()), ("cards", state.due_cards(Date::today()).len().into())])),
+                        ("next", state.next_review(Date::today()).map(|next| next.to_string()).into()),
+                    ])
+                }),
+            ])
+        );
+        return Ok(());
+    }
+
+    let style = globals.style;
+    if let Some(profile) = &globals.profile {
+        println!("{}", style.paint(Role::Heading, &format!("Profile {profile}")));
+        println!();
+    }
+    println!("{:<10} {} {lessons}/{lesson_total}", "Lessons", bar(style, lessons, lesson_total));
+    println!("{:<10} {} {passed}/{exercise_total}", "Exercises", bar(style, passed, exercise_total));
+
+    // Partial credit for the exercises that have been tried
+    let scores: Vec<_> = set.iter().filter_map(|exercise| Some((&exercise.info.name, state.scores.get(&exercise.info.name)?))).collect();
+    if !scores.is_empty() {
+        println!();
+        println!("{}", style.paint(Role::Heading, "Exercise scores"));
+        for (name, (score, total)) in scores {
+            println!("  {name:<16} {score:>2}/{total:<2} {}", bar(style, *score, *total));
+        }
+    }
+
+    // Fewer hints means more of the exercise was worked out alone
+    let hints: Vec<_> = set.iter().filter_map(|exercise| Some((exercise, state.hints.get(&exercise.info.name)?))).collect();
+    if !hints.is_empty() {
+        println!();
+        println!("{}", style.paint(Role::Heading, "Hints used"));
+        for (exercise, used) in hints {
+            let tier = HintTier::of(*used, exercise.info.hints.len()).as_str();
+            println!("  {:<16} {used:>2}/{:<2} {}", exercise.info.name, exercise.info.hints.len(), style.paint(Role::Dim, &format!("up to {tier}")));
+        }
+    }
+
+    // From first opening an exercise to passing it, the slowest one is probably worth another look at its lesson
+    let times: Vec<_> = set.iter().filter_map(|exercise| Some((&exercise.info.name, state.timing.get(&exercise.info.name)?))).collect();
+    if !times.is_empty() {
+        let slowest = times.iter().filter(|(_, timing)| timing.finished.is_some()).max_by_key(|(_, timing)| timing.seconds(now)).map(|(name, _)| *name);
+        println!();
+        println!("{}", style.paint(Role::Heading, "Time spent"));
+        for (name, timing) in &times {
+            let took = duration(timing.seconds(now));
+            let note = if timing.finished.is_none() {
+                style.paint(Role::Dim, "so far")
+            } else if Some(*name) == slowest && times.len() > 1 {
+                style.paint(Role::Event, "slowest")
+            } else {
+                String::new()
+            };
+            let line = format!("  {name:<16} {took:>8} {note}");
+            println!("{}", line.trim_end());
+        }
+    }
+
+    println!();
+    println!("{}", style.paint(Role::Heading, "Quiz accuracy"));
+    if state.quiz.is_empty() {
+        println!("{}", style.paint(Role::Dim, "  no quizzes taken yet, try `rust_day_6 quiz`"));
+    }
+    for (topic, (correct, answered)) in &state.quiz {
+        let percent = (correct * 100).checked_div(*answered).unwrap_or(0);
+        println!("  {topic:<12} {correct:>3}/{answered:<3} {percent:>3}%");
+    }
+
+    println!();
+    let today = Date::today();
+    println!("{}", style.paint(Role::Heading, "Streak"));
+    println!("  {:<16} {} days", "current", state.streak(today));
+    println!("  {:<16} {} days", "longest", state.longest_streak());
+    match state.daily_goal {
+        Some(goal) => println!("  {:<16} {}/{goal} exercises", "today", state.completed_on(today)),
+        None => println!("  {:<16} {}", "today", style.paint(Role::Dim, "no daily goal, try `rust_day_6 goal 2`")),
+    }
+
+    let (questions, exercises) = state.due_reviews(today);
+    if !state.question_reviews.is_empty() || !state.exercise_reviews.is_empty() || !state.card_reviews.is_empty() {
+        println!();
+        println!("{}", style.paint(Role::Heading, "Review"));
+        println!("  {:<16} {} questions, {} exercises, {} flashcards", "due today", questions.len(), exercises.len(), state.due_cards(today).len());
+        if let Some(next) = state.next_review(today) {
+            println!("  {:<16} {next}", "next after that");
+        }
+    }
+
+    println!();
+    let unlocked = ACHIEVEMENTS.iter().filter(|achievement| state.achievements.contains(achievement.id)).count();
+    println!("{}", style.paint(Role::Heading, &format!("Achievements {unlocked}/{}", ACHIEVEMENTS.len())));
+    for achievement in ACHIEVEMENTS {
+        if state.achievements.contains(achievement.id) {
+            println!("  {} {:<20} {}", style.paint(Role::Event, "★"), achievement.title, achievement.description);
+        } else {
+            println!("  {}", style.paint(Role::Dim, &format!("☆ {:<20} {}", achievement.title, achievement.description)));
+        }
+    }
+    Ok(())
+}
+
+// e.g. 45s, 12m 5s, 3h 20m, 2d 4h
+fn duration(seconds: u64) -> String {
+    let (minutes, hours, days) = (seconds / 60, seconds / 3600, seconds / 86400);
+    if days > 0 {
+        format!("{days}d {}h", hours % 24)
+    } else if hours > 0 {
+        format!("{hours}h {}m", minutes % 60)
+    } else if minutes > 0 {
+        format!("{minutes}m {}s", seconds % 60)
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+// e.g. [██████░░░░░░░░░░░░░░]
+fn bar(style: Style, done: usize, total: usize) -> String {
+    let filled = (done * BAR_WIDTH).checked_div(total).unwrap_or(0);
+    format!(
+        "[{}{}]",
+        style.paint(Role::Success, &"█".repeat(filled)),
+        style.paint(Role::Dim, &"░".repeat(BAR_WIDTH - filled))
+    )
+}
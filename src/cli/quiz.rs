@@ -0,0 +1,166 @@
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use rust_day_6::deck::Deck;
+use rust_day_6::error::LessonError;
+use rust_day_6::json::ToJson;
+use rust_day_6::quiz::{self, Answer, AnswerSource, Question, QuizReport, ThreadedInput, TimeLimit};
+use rust_day_6::shuffle::{self, Rng};
+use rust_day_6::style::{Role, Style};
+
+use super::args::ArgList;
+use super::{shuffle_seed, Format, Globals};
+
+// How many questions a quiz asks unless --count says otherwise
+const QUIZ_LENGTH: usize = 10;
+
+// `quiz [--topic T] [--deck FILE] [--count N] [--uniform] [--timed 60s] [--timed-quiz 10m]`, asks questions from
+// the question bank, or a deck of questions from a file, one question at a time with immediate feedback
+pub fn quiz(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let topic = args.value("--topic")?;
+    let deck = args.value("--deck")?;
+    let limit = TimeLimit {
+        per_question: args.value("--timed")?.map(|value| duration("--timed", &value)).transpose()?,
+        per_quiz: args.value("--timed-quiz")?.map(|value| duration("--timed-quiz", &value)).transpose()?,
+    };
+    let count = args.parsed("--count")?.unwrap_or(QUIZ_LENGTH);
+    let uniform = args.flag("--uniform");
+    let seed = shuffle_seed(globals, &mut args)?;
+    args.finish_empty()?;
+    if count == 0 {
+        return Err(LessonError::BadInput("--count needs to be at least 1".to_string()));
+    }
+
+    let deck = deck.map(|path| Deck::load(Path::new(&path))).transpose()?;
+    // --topic, otherwise the preferred topics from the config file, otherwise everything. A deck was picked
+    // on purpose, the config's topics don't narrow it down.
+    let topics = match topic {
+        Some(topic) => vec![topic],
+        None if deck.is_some() => Vec::new(),
+        None => globals.config.topics.clone(),
+    };
+    let (title, bank) = match deck {
+        Some(deck) => (deck.title, deck.questions),
+        None => (None, quiz::bank()),
+    };
+    let questions: Vec<_> = bank
+        .into_iter()
+        .filter(|question| topics.is_empty() || topics.contains(&question.topic))
+        .collect();
+    if questions.is_empty() {
+        return Err(LessonError::BadInput(format!("no questions about {}", topics.join(" or "))));
+    }
+    // Which questions come up, and the names and values in template questions, are different every time and
+    // the same again with --seed. Topics that have gone badly before come up more often, unless --uniform.
+    let mut rng = Rng::new(seed.unwrap_or_else(shuffle::seed_from_time));
+    let history = globals.progress()?.quiz;
+    let questions = match uniform {
+        true => quiz::select(questions, count, |_| 1.0, &mut rng),
+        false => quiz::select(questions, count, |question| quiz::topic_weight(history.get(&question.topic).copied()), &mut rng),
+    };
+    let questions: Vec<Question> = questions.iter().map(|question| question.instantiate(&mut rng)).collect();
+
+    if let Some(title) = &title {
+        match globals.format {
+            Format::Text => println!("{}", globals.style.paint(Role::Heading, title)),
+            Format::Json => eprintln!("{title}"),
+        }
+    }
+    let report = ask(globals, &questions, limit)?;
+    globals.update_progress(|progress| progress.record_quiz(&report))?;
+
+    match globals.format {
+        Format::Text => {
+            let score = format!("Score: {} / {}", report.correct(), report.total());
+            println!();
+            println!("{}", globals.style.paint(Role::Heading, &score));
+            if limit != TimeLimit::default() {
+                println!();
+                println!("{}", globals.style.paint(Role::Heading, "Average answer time"));
+                for (topic, average, timeouts) in report.latency_by_topic() {
+                    let timeouts = match timeouts {
+                        0 => String::new(),
+                        count => globals.style.paint(Role::Error, &format!("{count} timed out")),
+                    };
+                    let line = format!("  {topic:<12} {:>6.1}s {timeouts}", average.as_secs_f64());
+                    println!("{}", line.trim_end());
+                }
+            }
+        }
+        Format::Json => println!("{}", report.to_json()),
+    }
+    Ok(())
+}
+
+// Asks `questions` on the terminal, writing each answer into the transcript as it comes in
+pub fn ask(globals: &Globals, questions: &[Question], limit: TimeLimit) -> Result<QuizReport, LessonError> {
+    // Waiting on stdin can only time out from another thread
+    let mut input: Box<dyn AnswerSource> = match limit {
+        TimeLimit { per_question: None, per_quiz: None } => Box::new(io::stdin().lock()),
+        _ => Box::new(ThreadedInput::stdin()),
+    };
+    let mut transcribe = |question: &Question, answer: &Answer| {
+        let verdict = match (answer.timed_out, answer.correct) {
+            (true, _) => "timed out",
+            (false, true) => "correct",
+            (false, false) => "wrong",
+        };
+        let prompt = question.prompt.lines().next().unwrap_or_default();
+        globals.transcribe("quiz", &format!("{} ({}): {}, {verdict}\n{prompt}", question.id, question.topic, answer.given))
+    };
+    match globals.format {
+        Format::Text => quiz::run_quiz_with(questions, input.as_mut(), &mut io::stdout(), globals.style, limit, &mut transcribe),
+        // Keep stdout for the JSON report, the questions themselves go to stderr
+        Format::Json => quiz::run_quiz_with(questions, input.as_mut(), &mut io::stderr(), Style::plain(), limit, &mut transcribe),
+    }
+}
+
+// e.g. 90, 45s, 2m or 1m30s
+fn duration(flag: &str, value: &str) -> Result<Duration, LessonError> {
+    let bad = || LessonError::BadInput(format!("`{value}` isn't a time for {flag}, e.g. 45s, 2m or 1m30s"));
+    let mut seconds: u64 = 0;
+    let mut number = String::new();
+    for c in value.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'm' | 's' if !number.is_empty() => {
+                let count: u64 = number.parse().map_err(|_| bad())?;
+                let count = if c == 'm' { count.checked_mul(60).ok_or_else(bad)? } else { count };
+                seconds = seconds.checked_add(count).ok_or_else(bad)?;
+                number.clear();
+            }
+            _ => return Err(bad()),
+        }
+    }
+    if !number.is_empty() {
+        seconds = seconds.checked_add(number.parse().map_err(|_| bad())?).ok_or_else(bad)?;
+    }
+    match seconds {
+        0 => Err(bad()),
+        seconds => Ok(Duration::from_secs(seconds)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        assert_eq!(duration("--time", "90").unwrap(), Duration::from_secs(90));
+        assert_eq!(duration("--time", "45s").unwrap(), Duration::from_secs(45));
+        assert_eq!(duration("--time", "1m30s").unwrap(), Duration::from_secs(90));
+        assert_eq!(duration("--time", "2m").unwrap(), Duration::from_secs(120));
+    }
+
+    #[test]
+    fn bad_durations() {
+        for value in ["", "0", "0s", "m", "1h", "1.5m", "99999999999999999999m", "307445734561825861m", "18446744073709551615s1s", "18446744073709551615m"] {
+            match duration("--time", value) {
+                Err(LessonError::BadInput(message)) => assert_eq!(message, format!("`{value}` isn't a time for --time, e.g. 45s, 2m or 1m30s")),
+                other => panic!("expected BadInput for {value:?}, got {other:?}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use rust_day_6::error::LessonError;
+use rust_day_6::json::Json;
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `reset <exercise>`, restores an exercise file to the skeleton it shipped with
+pub fn reset(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let Some(name) = args.positional() else {
+        return Err(LessonError::BadInput("reset needs an exercise name (e.g. `reset move1`)".to_string()));
+    };
+    args.finish_empty()?;
+    let set = globals.exercises()?;
+    let exercise = set.get_named(&name)?;
+
+    set.reset(exercise)?;
+    let file = exercise.file(set.dir());
+    match globals.format {
+        Format::Text => println!("{} {} is back to its original skeleton", globals.style.paint(Role::Success, "✓"), file.display()),
+        Format::Json => println!("{}", Json::object([("exercise", (&exercise.info.name).into()), ("path", file.display().to_string().into())])),
+    }
+    Ok(())
+}
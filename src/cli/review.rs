@@ -0,0 +1,97 @@
+use rust_day_6::date::Date;
+use rust_day_6::error::LessonError;
+use rust_day_6::json::{Json, ToJson};
+use rust_day_6::quiz::{self, Question, TimeLimit};
+use rust_day_6::shuffle::{self, Rng};
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `review [--list]`, asks the quiz questions that are due again and lists the exercises due to be redone
+pub fn review(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let list = args.flag("--list");
+    args.finish_empty()?;
+    let today = Date::today();
+    let progress = globals.progress()?;
+    let (question_ids, exercises) = progress.due_reviews(today);
+    // A question that's left the bank, or an exercise that's left the manifest, has nothing left to review
+    let bank = quiz::bank();
+    let mut rng = Rng::new(shuffle::seed_from_time());
+    let questions: Vec<Question> =
+        question_ids.iter().filter_map(|id| bank.iter().find(|question| question.id == *id)).map(|question| question.instantiate(&mut rng)).collect();
+    let set = globals.exercises()?;
+    let exercises: Vec<&str> = exercises.into_iter().filter(|name| set.find(name).is_some()).collect();
+    let cards = progress.due_cards(today);
+
+    let style = globals.style;
+    if globals.format == Format::Text {
+        if questions.is_empty() && exercises.is_empty() {
+            if !cards.is_empty() {
+                println!("No questions or exercises to review today, but {} flashcard{} due, `rust_day_6 flashcards` goes over them", cards.len(), plural(cards.len()));
+                return Ok(());
+            }
+            match progress.next_review(today) {
+                Some(next) => {
+                    let days = next.days_since(today);
+                    println!("{} nothing to review today, next review on {next} (in {days} day{})", style.paint(Role::Success, "✓"), if days == 1 { "" } else { "s" });
+                }
+                None => println!("Nothing to review yet, take a `quiz` or pass an exercise and it comes back when it's due"),
+            }
+            return Ok(());
+        }
+        println!("{}", style.paint(Role::Heading, &format!("Due today: {} question{}, {} exercise{}", questions.len(), plural(questions.len()), exercises.len(), plural(exercises.len()))));
+    }
+
+    let report = if list || questions.is_empty() {
+        None
+    } else {
+        let report = super::quiz::ask(globals, &questions, TimeLimit::default())?;
+        globals.update_progress(|progress| progress.record_quiz(&report))?;
+        Some(report)
+    };
+
+    match globals.format {
+        Format::Text => {
+            if list {
+                for question in &questions {
+                    println!("  {} {}", style.paint(Role::Dim, &question.topic), style.code_spans(&question.prompt));
+                }
+            }
+            if let Some(report) = &report {
+                println!();
+                println!("{}", style.paint(Role::Heading, &format!("Score: {} / {}", report.correct(), report.total())));
+            }
+            if !exercises.is_empty() {
+                println!();
+                println!("{}", style.paint(Role::Heading, "Redo from memory"));
+                for name in &exercises {
+                    println!("  {name:<18} {}", style.paint(Role::Dim, &format!("`rust_day_6 reset {name}`, then `rust_day_6 watch`")));
+                }
+            }
+            if !cards.is_empty() {
+                println!();
+                println!("{}", style.paint(Role::Dim, &format!("{} flashcard{} due too, `rust_day_6 flashcards` goes over them", cards.len(), plural(cards.len()))));
+            }
+        }
+        Format::Json => println!(
+            "{}",
+            Json::object([
+                ("questions", Json::array(questions.iter().map(|question| question.id.as_str()))),
+                ("exercises", Json::array(exercises.iter().copied())),
+                ("cards", Json::array(cards.iter().copied())),
+                ("report", report.as_ref().map_or(Json::Null, ToJson::to_json)),
+                ("next", progress.next_review(today).map(|next| next.to_string()).into()),
+            ])
+        ),
+    }
+    Ok(())
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
@@ -0,0 +1,277 @@
+use std::io::{self, BufRead, Write};
+
+use rust_day_6::error::LessonError;
+use rust_day_6::json::{Json, ToJson};
+use rust_day_6::lesson::{Difficulty, Lesson};
+use rust_day_6::output::OwnershipEvent;
+use rust_day_6::report::{BufferReporter, Reporter};
+use rust_day_6::runner::{LessonOutcome, LessonRunnerBuilder, Prerequisites};
+use rust_day_6::style::{Role, Style};
+use rust_day_6::tracer::{Cell, OwnershipTracer, Timeline, Traces};
+
+use super::args::ArgList;
+use super::{announce, save_completed, Format, Globals};
+
+// `run <lesson>`, or `run --all` for every lesson followed by a summary. `run --difficulty D` is `run --all`
+// for the lessons of one difficulty. A single lesson won't run before its prerequisites are done, several
+// only warn about it, and `--ignore-prereqs` turns the check off. `--timeline` draws a single lesson's moves,
+// borrows and drops afterwards, a lane per variable, and `--step` stops at each of them and waits for enter.
+pub fn run(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let difficulty = args.value("--difficulty")?.map(|value| value.parse::<Difficulty>()).transpose()?;
+    let ignore_prereqs = args.flag("--ignore-prereqs");
+    let timeline = args.flag("--timeline");
+    let step = args.flag("--step");
+    let several = if ignore_prereqs { Prerequisites::Ignore } else { Prerequisites::Warn };
+    for (flag, set) in [("--timeline", timeline), ("--step", step)] {
+        if set && (args.flag("--all") || difficulty.is_some()) {
+            return Err(LessonError::BadInput(format!("{flag} works with a single lesson, e.g. `run take_ownership {flag}`")));
+        }
+    }
+    if step && globals.format == Format::Json {
+        return Err(LessonError::BadInput("--step waits for enter after each step, it doesn't go with --format json".to_string()));
+    }
+    if args.flag("--all") {
+        args.finish_empty()?;
+        return run_all_with_summary(globals, difficulty, several);
+    }
+
+    let Some(name) = args.positional() else {
+        if difficulty.is_some() {
+            args.finish_empty()?;
+            return run_all_with_summary(globals, difficulty, several);
+        }
+        return Err(LessonError::BadInput("run needs a lesson name (e.g. `run slicing`) or --all".to_string()));
+    };
+    args.finish_empty()?;
+    if let Some(difficulty) = difficulty {
+        let actual = globals.runner().build().find(&name).map(|lesson| lesson.difficulty());
+        if let Some(actual) = actual.filter(|actual| *actual != difficulty) {
+            return Err(LessonError::BadInput(format!("{name} is a {actual} lesson, not {difficulty}")));
+        }
+    }
+
+    let single = if ignore_prereqs { Prerequisites::Ignore } else { Prerequisites::Block };
+    // With --timeline the events are kept on the way through, and drawn once the lesson is done
+    let traces = Traces::new();
+    let traced = |reporter: Box<dyn Reporter>| -> Box<dyn Reporter> {
+        let reporter: Box<dyn Reporter> = match step {
+            true => Box::new(Stepper::new(reporter, globals.style)),
+            false => reporter,
+        };
+        match timeline {
+            true => Box::new(OwnershipTracer::new(reporter, traces.clone())),
+            false => reporter,
+        }
+    };
+    match globals.format {
+        Format::Text => {
+            let mut runner = prerequisites(globals, globals.runner(), single)?.reporter(traced(globals.stdout_reporter())).build();
+            let result = runner.run(&name).map_err(prerequisites_hint);
+            announce(globals, &save_completed(globals, &runner)?);
+            result?;
+            if let Some((_, events)) = traces.lessons().first() {
+                println!();
+                println!("{}", globals.style.paint(Role::Heading, "Ownership timeline"));
+                match events.is_empty() {
+                    true => println!("{}", globals.style.paint(Role::Dim, "nothing moved, copied, borrowed or dropped")),
+                    false => print!("{}", Timeline::new(events).render(globals.style)),
+                }
+            }
+            Ok(())
+        }
+        Format::Json => {
+            let buffer = BufferReporter::new();
+            let mut runner = prerequisites(globals, globals.runner(), single)?.reporter(traced(globals.reporter(buffer.clone()))).build();
+            let result = runner.run(&name).map_err(prerequisites_hint);
+            announce(globals, &save_completed(globals, &runner)?);
+            let lessons = buffer.lessons();
+            result?;
+            let mut fields = vec![("lesson", name.into()), ("output", lessons[0].1.to_json())];
+            if let Some((_, events)) = traces.lessons().first() {
+                fields.push(("timeline", Timeline::new(events).to_json()));
+            }
+            println!("{}", Json::object(fields));
+            Ok(())
+        }
+    }
+}
+
+// `run --step`: after each ownership event, says what happened and where every variable stands now, then
+// waits for enter. q, or stdin running out, lets the rest of the lesson run without stopping.
+struct Stepper {
+    inner: OwnershipTracer<Box<dyn Reporter>>,
+    traces: Traces,
+    style: Style,
+    steps: usize,
+    stopping: bool,
+}
+
+impl Stepper {
+    fn new(inner: Box<dyn Reporter>, style: Style) -> Self {
+        let traces = Traces::new();
+        Self { inner: OwnershipTracer::new(inner, traces.clone()), traces, style, steps: 0, stopping: true }
+    }
+
+    fn pause(&mut self) {
+        let Some((_, events)) = self.traces.lessons().pop() else {
+            return;
+        };
+        let Some(event) = events.last() else {
+            return;
+        };
+        self.steps += 1;
+        let style = self.style;
+        println!("{}", style.paint(Role::Event, &format!("  ▸ step {}: {event}", self.steps)));
+        let timeline = Timeline::new(&events);
+        let groups = [("owners", &[Cell::Owns, Cell::Alive, Cell::CopiedFrom, Cell::ClonedFrom, Cell::Borrowed, Cell::BorrowedMut][..]), ("moved out", &[Cell::MovedOut][..]), ("dropped", &[Cell::Dropped][..])];
+        let states = timeline.states();
+        let parts: Vec<String> = groups
+            .iter()
+            .filter_map(|(label, cells)| {
+                let names: Vec<&str> = states.iter().filter(|(_, cell)| cells.contains(cell)).map(|(name, _)| *name).collect();
+                (!names.is_empty()).then(|| format!("{label} {}", names.join(", ")))
+            })
+            .collect();
+        if !parts.is_empty() {
+            println!("{}", style.paint(Role::Dim, &format!("    {}", parts.join(" · "))));
+        }
+        print!("{}", style.paint(Role::Dim, "    enter for the next step, q to run to the end "));
+        let _ = io::stdout().flush();
+        let mut line = String::new();
+        match io::stdin().lock().read_line(&mut line) {
+            Ok(0) | Err(_) => self.stopping = false,
+            Ok(_) => self.stopping = line.trim() != "q",
+        }
+    }
+}
+
+impl Reporter for Stepper {
+    fn begin_lesson(&mut self, lesson: &dyn Lesson) {
+        self.inner.begin_lesson(lesson);
+    }
+
+    fn heading(&mut self, lesson: &dyn Lesson) {
+        self.inner.heading(lesson);
+    }
+
+    fn message(&mut self, message: &str) {
+        self.inner.message(message);
+    }
+
+    fn value(&mut self, name: &str, value: &str) {
+        self.inner.value(name, value);
+    }
+
+    fn warning(&mut self, message: &str) {
+        self.inner.warning(message);
+    }
+
+    fn event(&mut self, event: &OwnershipEvent) {
+        self.inner.event(event);
+        if self.stopping {
+            self.pause();
+        }
+    }
+
+    fn end_lesson(&mut self, lesson: &dyn Lesson) {
+        self.inner.end_lesson(lesson);
+    }
+
+    fn finish(&mut self) -> Result<(), LessonError> {
+        self.inner.finish()
+    }
+}
+
+// Every lesson with no summary, what happens without a command when stdin isn't a terminal
+pub fn run_all(globals: &Globals) -> Result<(), LessonError> {
+    match globals.format {
+        Format::Text => {
+            let mut runner = prerequisites(globals, globals.runner(), Prerequisites::Warn)?.build();
+            let result = runner.run_all();
+            announce(globals, &save_completed(globals, &runner)?);
+            result
+        }
+        Format::Json => run_all_with_summary(globals, None, Prerequisites::Warn),
+    }
+}
+
+// Checks prerequisites against the lessons done in earlier sessions
+fn prerequisites(globals: &Globals, builder: LessonRunnerBuilder, policy: Prerequisites) -> Result<LessonRunnerBuilder, LessonError> {
+    if policy == Prerequisites::Ignore {
+        return Ok(builder);
+    }
+    let done = globals.progress()?.lessons;
+    Ok(builder.prerequisites(policy, done))
+}
+
+// Says how to get past a lesson that was blocked by its prerequisites
+fn prerequisites_hint(err: LessonError) -> LessonError {
+    match err {
+        LessonError::MissingPrerequisites { ref missing, .. } => {
+            LessonError::BadInput(format!("{err}. Run `rust_day_6 run {}` first, or pass --ignore-prereqs", missing[0]))
+        }
+        err => err,
+    }
+}
+
+fn run_all_with_summary(globals: &Globals, difficulty: Option<Difficulty>, policy: Prerequisites) -> Result<(), LessonError> {
+    let runner = || {
+        let builder = match difficulty {
+            Some(difficulty) => globals.runner().difficulty(difficulty),
+            None => globals.runner(),
+        };
+        prerequisites(globals, builder, policy)
+    };
+    let outcomes = match globals.format {
+        Format::Text => {
+            let mut runner = runner()?.build();
+            let outcomes = runner.run_each()?;
+            announce(globals, &save_completed(globals, &runner)?);
+            print_summary(&outcomes, globals.style);
+            outcomes
+        }
+        Format::Json => {
+            let buffer = BufferReporter::new();
+            let mut runner = runner()?.reporter(globals.reporter(buffer.clone())).build();
+            let outcomes = runner.run_each()?;
+            announce(globals, &save_completed(globals, &runner)?);
+            print_summary_json(&outcomes, &buffer);
+            outcomes
+        }
+    };
+
+    match outcomes.iter().filter(|outcome| outcome.result.is_err()).count() {
+        0 => Ok(()),
+        failed => Err(LessonError::BadInput(format!("{failed} of {} lessons failed", outcomes.len()))),
+    }
+}
+
+fn print_summary(outcomes: &[LessonOutcome], style: Style) {
+    println!();
+    println!("{}", style.paint(Role::Heading, &format!("{:<22} {:>10}  result", "lesson", "time")));
+    for outcome in outcomes {
+        let result = match &outcome.result {
+            Ok(()) => style.paint(Role::Success, "ok"),
+            Err(err) => style.paint(Role::Error, &format!("FAILED: {err}")),
+        };
+        println!("{:<22} {:>10.2?}  {}", outcome.name, outcome.duration, result);
+    }
+
+    let failed = outcomes.iter().filter(|outcome| outcome.result.is_err()).count();
+    let total: std::time::Duration = outcomes.iter().map(|outcome| outcome.duration).sum();
+    println!("{} lessons run, {} failed, {:.2?} total", outcomes.len(), failed, total);
+}
+
+fn print_summary_json(outcomes: &[LessonOutcome], buffer: &BufferReporter) {
+    let captured = buffer.lessons();
+    let lessons = outcomes.iter().zip(&captured).map(|(outcome, (_, output))| {
+        let Json::Object(mut fields) = outcome.to_json() else { unreachable!() };
+        fields.push(("output".to_string(), output.to_json()));
+        Json::Object(fields)
+    });
+    let failed = outcomes.iter().filter(|outcome| outcome.result.is_err()).count();
+    println!(
+        "{}",
+        Json::object([("lessons", Json::array(lessons)), ("run", outcomes.len().into()), ("failed", failed.into())])
+    );
+}
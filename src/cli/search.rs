@@ -0,0 +1,37 @@
+use rust_day_6::error::LessonError;
+use rust_day_6::json::{Json, ToJson};
+use rust_day_6::search;
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `search <keyword>...`, every lesson whose name, summary, topics or explanation mention the keyword
+pub fn search(globals: &Globals, args: ArgList) -> Result<(), LessonError> {
+    let words = args.finish()?;
+    if words.is_empty() {
+        return Err(LessonError::BadInput("search needs a keyword, e.g. `search mutable reference`".to_string()));
+    }
+    let query = words.join(" ");
+
+    let runner = globals.runner().build();
+    let hits = search::search(runner.lessons(), &query);
+    if globals.format == Format::Json {
+        println!("{}", Json::array(hits.iter().map(ToJson::to_json)));
+        return Ok(());
+    }
+    if hits.is_empty() {
+        println!("No lessons mention `{query}`");
+        return Ok(());
+    }
+
+    let mut last_lesson = "";
+    for hit in &hits {
+        if hit.lesson != last_lesson {
+            println!("{}", globals.style.paint(Role::Heading, &hit.lesson));
+            last_lesson = &hit.lesson;
+        }
+        println!("    {:<12} {}", format!("{}:", hit.field), hit.context);
+    }
+    Ok(())
+}
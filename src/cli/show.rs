@@ -0,0 +1,99 @@
+use std::cell::RefCell;
+use std::panic::Location;
+use std::rc::Rc;
+
+use rust_day_6::error::LessonError;
+use rust_day_6::json::{Json, ToJson};
+use rust_day_6::output::OwnershipEvent;
+use rust_day_6::registry;
+use rust_day_6::report::Reporter;
+use rust_day_6::source;
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// Annotations start at least this far in, so short lines don't put them right against the code
+const MARGIN: usize = 60;
+
+// (file, line, event), in the order they were reported
+type Located = Rc<RefCell<Vec<(&'static str, usize, OwnershipEvent)>>>;
+
+// Keeps each event with the line of the demo that reported it, and prints nothing
+struct Locator(Located);
+
+impl Reporter for Locator {
+    fn message(&mut self, _message: &str) {}
+
+    fn event(&mut self, event: &OwnershipEvent) {
+        let caller = Location::caller();
+        self.0.borrow_mut().push((caller.file(), caller.line() as usize, event.clone()));
+    }
+}
+
+// `show <lesson>`, the demo's code with what happened to ownership in the margin, next to the line that
+// reported it. The lesson runs to find out, without printing anything and without counting as done.
+pub fn show(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let Some(name) = args.positional() else {
+        return Err(LessonError::BadInput("show needs a lesson, e.g. `show take_ownership`".to_string()));
+    };
+    args.finish_empty()?;
+    let lesson = registry::find(&name).ok_or_else(|| LessonError::UnknownLesson(name.clone()))?;
+    let Some(source) = source::function(lesson.function) else {
+        return Err(LessonError::BadInput(format!("can't find the code of {}", lesson.function)));
+    };
+
+    let located = Located::default();
+    globals.runner().reporter(Locator(located.clone())).build().run(&name)?;
+    let mut annotations: Vec<Vec<OwnershipEvent>> = vec![Vec::new(); source.lines.len()];
+    // Reported from somewhere else, e.g. by the runner for --trace
+    let mut elsewhere = Vec::new();
+    for (file, line, event) in located.take() {
+        match source.contains(file, line) {
+            true => annotations[line - source.first_line].push(event),
+            false => elsewhere.push(event),
+        }
+    }
+
+    if globals.format == Format::Json {
+        let lines = source.lines.iter().zip(&annotations).enumerate().map(|(index, (code, events))| {
+            Json::object([("line", (source.first_line + index).into()), ("code", (*code).into()), ("events", Json::array(events.iter().map(ToJson::to_json)))])
+        });
+        println!(
+            "{}",
+            Json::object([
+                ("lesson", lesson.name.into()),
+                ("function", lesson.function.into()),
+                ("file", source.file.into()),
+                ("lines", Json::array(lines)),
+                ("elsewhere", Json::array(elsewhere.iter().map(ToJson::to_json))),
+            ])
+        );
+        return Ok(());
+    }
+
+    let style = globals.style;
+    println!("{}", style.paint(Role::Heading, &format!("{}: {}", lesson.name, lesson.summary)));
+    println!("{}", style.paint(Role::Dim, &format!("{} in {}:{}", lesson.function, source.file, source.first_line)));
+    println!();
+    let number_width = (source.first_line + source.lines.len()).to_string().len();
+    let code_width = source.lines.iter().map(|line| line.chars().count()).filter(|width| *width <= MARGIN + 20).max().unwrap_or(0).max(MARGIN);
+    for (index, (code, events)) in source.lines.iter().zip(&annotations).enumerate() {
+        let number = style.paint(Role::Dim, &format!("{:>number_width$} │", source.first_line + index));
+        if events.is_empty() {
+            println!("{number} {code}");
+            continue;
+        }
+        let events: Vec<String> = events.iter().map(ToString::to_string).collect();
+        let padding = " ".repeat(code_width.saturating_sub(code.chars().count()));
+        println!("{number} {code}{padding}  {}", style.paint(Role::Event, &format!("← {}", events.join(", "))));
+    }
+    if !elsewhere.is_empty() {
+        println!();
+        println!("{}", style.paint(Role::Dim, "reported outside the demo:"));
+        for event in &elsewhere {
+            println!("  {}", style.paint(Role::Event, &event.to_string()));
+        }
+    }
+    Ok(())
+}
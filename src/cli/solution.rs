@@ -0,0 +1,94 @@
+use std::fs;
+
+use rust_day_6::diff::{self, Line};
+use rust_day_6::error::LessonError;
+use rust_day_6::json::Json;
+use rust_day_6::style::{Role, Style};
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// Unchanged lines shown around each change
+const CONTEXT: usize = 3;
+
+// `solution <exercise>`, shows the reference solution and how the learner's attempt differs from it
+pub fn solution(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let Some(name) = args.positional() else {
+        return Err(LessonError::BadInput("solution needs an exercise name (e.g. `solution move1`)".to_string()));
+    };
+    args.finish_empty()?;
+    let set = globals.exercises()?;
+    let exercise = set.get_named(&name)?;
+    let solution = exercise.solution(set.dir())?;
+    let file = exercise.file(set.dir());
+    // No attempt yet is fine, there's just nothing to compare
+    let attempt = fs::read_to_string(&file).ok();
+    let hunks = attempt.as_deref().map(|attempt| diff::hunks(&diff::lines(attempt, &solution), CONTEXT));
+
+    if globals.format == Format::Json {
+        let diff = hunks.as_ref().map(|hunks| {
+            let mut text = String::new();
+            for hunk in hunks {
+                text.push_str(&hunk.header());
+                text.push('\n');
+                for line in &hunk.lines {
+                    text.push(line.prefix());
+                    text.push_str(line.text());
+                    text.push('\n');
+                }
+            }
+            text
+        });
+        let changes = hunks.iter().flatten().flat_map(|hunk| &hunk.lines).filter_map(|line| {
+            let note = diff::ownership_note(line.text()).filter(|_| !matches!(line, Line::Same(_)))?;
+            Some(Json::object([("line", format!("{}{}", line.prefix(), line.text()).into()), ("change", note.into())]))
+        });
+        println!(
+            "{}",
+            Json::object([
+                ("exercise", (&exercise.info.name).into()),
+                ("solution", (&solution).into()),
+                ("diff", diff.into()),
+                ("ownership_changes", Json::array(changes)),
+            ])
+        );
+        return Ok(());
+    }
+
+    let style = globals.style;
+    println!("{}", style.paint(Role::Heading, &format!("Solution for {}", exercise.info.name)));
+    for line in solution.lines() {
+        println!("{}", style.paint(Role::Code, line));
+    }
+
+    println!();
+    match hunks {
+        None => println!("{}", style.paint(Role::Dim, &format!("no attempt at {} yet, nothing to compare", file.display()))),
+        Some(hunks) if hunks.is_empty() => println!("{}", style.paint(Role::Success, "your attempt is the same as the solution")),
+        Some(hunks) => {
+            println!("{}", style.paint(Role::Heading, &format!("Your attempt → the solution ({})", file.display())));
+            println!("{}", style.paint(Role::Dim, &format!("--- {}\n+++ solution", file.display())));
+            for hunk in &hunks {
+                println!("{}", style.paint(Role::Dim, &hunk.header()));
+                for line in &hunk.lines {
+                    println!("{}", diff_line(style, line));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// A changed line in red or green, with what it does to ownership in yellow when it does something
+fn diff_line(style: Style, line: &Line) -> String {
+    let text = format!("{}{}", line.prefix(), line.text());
+    let role = match line {
+        Line::Same(_) => return text,
+        Line::Removed(_) => Role::Error,
+        Line::Added(_) => Role::Success,
+    };
+    match diff::ownership_note(line.text()) {
+        Some(note) => format!("{}  {}", style.paint(role, &text), style.paint(Role::Event, &format!("← {note}"))),
+        None => style.paint(role, &text),
+    }
+}
@@ -0,0 +1,46 @@
+use rust_day_6::error::LessonError;
+use rust_day_6::json::{Json, ToJson};
+use rust_day_6::stats;
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{Format, Globals};
+
+// `stats`, per-topic quiz accuracy and answer times, checks it took to solve the exercises and whether answers are getting
+// better, all worked out from the event log
+pub fn stats(globals: &Globals, args: ArgList) -> Result<(), LessonError> {
+    args.finish_empty()?;
+    let progress = globals.progress()?;
+    let topics = stats::by_topic(&progress);
+    if globals.format == Format::Json {
+        println!("{}", Json::array(topics.iter().map(ToJson::to_json)));
+        return Ok(());
+    }
+
+    let style = globals.style;
+    if topics.is_empty() {
+        println!("{}", style.paint(Role::Dim, "nothing to report yet, answer a `rust_day_6 quiz` or try an exercise with `rust_day_6 watch`"));
+        return Ok(());
+    }
+    let width = topics.iter().map(|topic| topic.topic.chars().count()).max().unwrap_or(0).max("topic".len());
+    let header = format!("{:<width$}  {:>7}  {:>8}  {:>8}  {:>9}  {:>10}  {:>5}", "topic", "quiz", "accuracy", "avg time", "exercises", "avg checks", "trend");
+    println!("{}", style.paint(Role::Heading, &header));
+    for topic in &topics {
+        let dash = || "–".to_string();
+        let quiz = format!("{}/{}", topic.correct, topic.answered);
+        let accuracy = topic.accuracy().map_or_else(dash, |percent| format!("{percent}%"));
+        let time = topic.average_ms.map_or_else(dash, |ms| format!("{:.1}s", ms as f64 / 1000.0));
+        let exercises = format!("{}/{}", topic.solved, topic.exercises);
+        let checks = topic.average_checks.map_or_else(dash, |checks| format!("{checks:.1}"));
+        let trend = match topic.trend {
+            None => style.paint(Role::Dim, &format!("{:>5}", "–")),
+            Some(points) if points > 0 => style.paint(Role::Success, &format!("{:>5}", format!("↑{points}"))),
+            Some(points) if points < 0 => style.paint(Role::Error, &format!("{:>5}", format!("↓{}", -points))),
+            Some(_) => format!("{:>5}", "→"),
+        };
+        println!("{:<width$}  {quiz:>7}  {accuracy:>8}  {time:>8}  {exercises:>9}  {checks:>10}  {trend}", topic.topic);
+    }
+    println!();
+    println!("{}", style.paint(Role::Dim, "exercises are solved/tried, trend is the later half of a topic's answers against the earlier half"));
+    Ok(())
+}
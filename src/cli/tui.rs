@@ -0,0 +1,205 @@
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+
+use rust_day_6::error::LessonError;
+use rust_day_6::report::BufferReporter;
+
+use super::{save_completed, Globals};
+
+const LIST_WIDTH: usize = 26;
+
+// Puts the terminal in raw mode on the alternate screen, and puts it back however the TUI exits
+struct RawTerminal {
+    saved: String,
+}
+
+impl RawTerminal {
+    fn enter() -> Result<Self, LessonError> {
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "min", "1"])?;
+        print!("\x1b[?1049h\x1b[?25l"); // Alternate screen, hide cursor
+        io::stdout().flush()?;
+        Ok(Self { saved: saved.trim().to_string() })
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l"); // Show cursor, back to the normal screen
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+// Runs `stty` against the terminal on stdin, there's no portable way to do this from std alone
+fn stty(args: &[&str]) -> Result<String, LessonError> {
+    let output = Command::new("stty").args(args).stdin(Stdio::inherit()).output()?;
+    if !output.status.success() {
+        return Err(LessonError::BadInput("--tui needs a terminal that `stty` can configure".to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// (rows, columns), falling back to 24x80 when stty can't tell
+fn terminal_size() -> (usize, usize) {
+    stty(&["size"])
+        .ok()
+        .and_then(|size| {
+            let mut parts = size.split_whitespace().map(|part| part.parse().ok());
+            Some((parts.next()??, parts.next()??))
+        })
+        .unwrap_or((24, 80))
+}
+
+enum Key {
+    Up,
+    Down,
+    Enter,
+    Quit,
+    Other,
+}
+
+fn read_key(input: &mut impl Read) -> Result<Key, LessonError> {
+    let mut byte = [0u8; 1];
+    if input.read(&mut byte)? == 0 {
+        return Ok(Key::Quit);
+    }
+    Ok(match byte[0] {
+        b'q' | b'Q' => Key::Quit,
+        b'k' => Key::Up,
+        b'j' => Key::Down,
+        b'\n' | b'\r' => Key::Enter,
+        0x1b => {
+            // Arrow keys arrive as ESC [ A / ESC [ B
+            let mut sequence = [0u8; 2];
+            input.read_exact(&mut sequence)?;
+            match sequence {
+                [b'[', b'A'] => Key::Up,
+                [b'[', b'B'] => Key::Down,
+                _ => Key::Other,
+            }
+        }
+        _ => Key::Other,
+    })
+}
+
+// Splits text into lines of at most `width` characters, breaking at spaces
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+// Pads or cuts a line to exactly `width` characters so panes don't bleed into each other
+fn fit(text: &str, width: usize) -> String {
+    let mut fitted: String = text.chars().take(width).collect();
+    let len = fitted.chars().count();
+    fitted.extend(std::iter::repeat_n(' ', width - len));
+    fitted
+}
+
+struct Screen<'a> {
+    lessons: &'a [(String, String, String)],
+    selected: usize,
+    output: Vec<String>,
+}
+
+impl Screen<'_> {
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        let (rows, columns) = terminal_size();
+        let content_width = columns.saturating_sub(LIST_WIDTH + 3).max(10);
+        let (name, summary, explanation) = &self.lessons[self.selected];
+
+        let mut content = vec![format!("{name}: {summary}"), String::new()];
+        content.extend(wrap(explanation, content_width));
+        let content_rows = rows.saturating_sub(3) / 2;
+        content.truncate(content_rows);
+        content.resize(content_rows, String::new());
+        content.push("─".repeat(content_width));
+        content.push("Output (Enter to run)".to_string());
+        content.extend(self.output.iter().flat_map(|line| wrap(line, content_width)));
+
+        // Scroll the list so the selected lesson stays on screen
+        let list_rows = rows.saturating_sub(1).max(1);
+        let first = (self.selected + 1).saturating_sub(list_rows);
+
+        write!(out, "\x1b[H\x1b[2J")?;
+        for row in 0..list_rows {
+            let index = first + row;
+            let item = match self.lessons.get(index) {
+                Some((name, _, _)) if index == self.selected => format!("\x1b[7m{}\x1b[0m", fit(&format!(" {name}"), LIST_WIDTH)),
+                Some((name, _, _)) => fit(&format!(" {name}"), LIST_WIDTH),
+                None => fit("", LIST_WIDTH),
+            };
+            let text = content.get(row).map(String::as_str).unwrap_or("");
+            write!(out, "{item} │ {}\r\n", fit(text, content_width))?;
+        }
+        write!(out, "{}", fit(" ↑/↓ or j/k to move, Enter to run, q to quit", columns))?;
+        out.flush()
+    }
+}
+
+// `--tui`, lesson list on the left, explanation and demo output on the right
+pub fn tui(globals: &Globals) -> Result<(), LessonError> {
+    let lessons: Vec<(String, String, String)> = globals
+        .runner()
+        .build()
+        .lessons()
+        .map(|lesson| (lesson.name().to_string(), lesson.summary().to_string(), lesson.explanation().to_string()))
+        .collect();
+    if lessons.is_empty() {
+        return Err(LessonError::BadInput("no lessons to show".to_string()));
+    }
+
+    let _terminal = RawTerminal::enter()?;
+    let mut screen = Screen { lessons: &lessons, selected: 0, output: Vec::new() };
+    let mut stdin = io::stdin().lock();
+    let mut stdout = io::stdout().lock();
+    loop {
+        screen.draw(&mut stdout)?;
+        match read_key(&mut stdin)? {
+            Key::Up => {
+                screen.selected = screen.selected.saturating_sub(1);
+                screen.output.clear();
+            }
+            Key::Down => {
+                screen.selected = (screen.selected + 1).min(lessons.len() - 1);
+                screen.output.clear();
+            }
+            Key::Enter => screen.output = run_captured(globals, &lessons[screen.selected].0),
+            Key::Quit => return Ok(()),
+            Key::Other => {}
+        }
+    }
+}
+
+// Runs one lesson into a buffer and returns what it printed, plus its ownership events
+fn run_captured(globals: &Globals, name: &str) -> Vec<String> {
+    let buffer = BufferReporter::new();
+    let mut runner = globals.runner().reporter(globals.reporter(buffer.clone())).build();
+    let result = runner.run(name);
+    let unlocked = match result.and_then(|()| save_completed(globals, &runner)) {
+        Ok(unlocked) => unlocked,
+        Err(err) => return vec![format!("error: {err}")],
+    };
+
+    let mut lines = Vec::new();
+    for (_, output) in buffer.lessons() {
+        lines.extend(output.messages);
+        lines.extend(output.events.iter().map(|event| format!("  ownership: {event}")));
+    }
+    lines.extend(unlocked.iter().map(|achievement| format!("★ Achievement unlocked: {}", achievement.title)));
+    lines
+}
@@ -0,0 +1,76 @@
+use rust_day_6::error::LessonError;
+use rust_day_6::exercises::NOT_DONE_MARKER;
+use rust_day_6::lesson::Difficulty;
+use rust_day_6::json::{Json, ToJson};
+use rust_day_6::style::Role;
+
+use super::args::ArgList;
+use super::{announce, shuffle_seed, unlock_achievements, Format, Globals};
+
+// `verify`, checks every exercise in order and stops at the first one that isn't done
+pub fn verify(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let seed = shuffle_seed(globals, &mut args)?;
+    let difficulty = args.value("--difficulty")?.map(|value| value.parse::<Difficulty>()).transpose()?;
+    args.finish_empty()?;
+    let mut set = globals.load_exercises()?;
+    if let Some(difficulty) = difficulty {
+        set.retain_difficulty(difficulty);
+    }
+    if let Some(seed) = seed {
+        set.shuffle(seed);
+    }
+
+    let style = globals.style;
+    let mut checked = Vec::new();
+    let mut stuck = None;
+    for exercise in set.iter() {
+        let result = set.check(exercise)?;
+        let done = result.done();
+        globals.update_progress(|state| state.record_check(&exercise.info, &result))?;
+        if globals.format == Format::Text {
+            let (score, total) = result.score();
+            let score = style.paint(Role::Dim, &format!("{score}/{total}"));
+            if done {
+                println!("{} {} {score}", style.paint(Role::Success, "✓"), exercise.info.name);
+            } else if result.passed {
+                println!(
+                    "{} {} {score}, passes but still has the `{NOT_DONE_MARKER}` line",
+                    style.paint(Role::Error, "✗"),
+                    exercise.info.name
+                );
+            } else {
+                println!("{} {} {score}", style.paint(Role::Error, "✗"), exercise.info.name);
+                println!("{}", result.output.trim_end());
+            }
+        }
+        checked.push((&exercise.info.name, result));
+        if !done {
+            stuck = Some(&exercise.info.name);
+            break;
+        }
+    }
+
+    if globals.format == Format::Json {
+        let results = checked.iter().map(|(name, result)| {
+            let Json::Object(mut fields) = result.to_json() else { unreachable!() };
+            fields.insert(0, ("exercise".to_string(), (*name).into()));
+            Json::Object(fields)
+        });
+        println!("{}", Json::object([("exercises", Json::array(results)), ("total", set.len().into())]));
+    }
+
+    announce(globals, &unlock_achievements(globals, &set)?);
+    match stuck {
+        None => {
+            if globals.format == Format::Text {
+                println!("{}", style.paint(Role::Success, &format!("All {} exercises done!", checked.len())));
+            }
+            Ok(())
+        }
+        Some(name) => Err(LessonError::BadInput(format!(
+            "{name} isn't done yet, {} of {} exercises done",
+            checked.len() - 1,
+            set.len()
+        ))),
+    }
+}
@@ -0,0 +1,112 @@
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use rust_day_6::achievements::Achievement;
+use rust_day_6::date::Date;
+use rust_day_6::error::LessonError;
+use rust_day_6::exercises::{CheckResult, Exercise, NOT_DONE_MARKER};
+use rust_day_6::lesson::Difficulty;
+use rust_day_6::style::{Role, Style};
+
+use super::args::ArgList;
+use super::{announce, goal, shuffle_seed, unlock_achievements, Globals};
+
+// How often the exercises directory is checked for changes, there's no file watching in std
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// `watch`, checks the current exercise every time a file in the exercises directory is saved and moves on
+// to the next one once it passes
+pub fn watch(globals: &Globals, mut args: ArgList) -> Result<(), LessonError> {
+    let seed = shuffle_seed(globals, &mut args)?;
+    let difficulty = args.value("--difficulty")?.map(|value| value.parse::<Difficulty>()).transpose()?;
+    args.finish_empty()?;
+    let mut set = globals.load_exercises()?;
+    if let Some(difficulty) = difficulty {
+        set.retain_difficulty(difficulty);
+    }
+    if let Some(seed) = seed {
+        set.shuffle(seed);
+    }
+    let style = globals.style;
+    let mut current = 0;
+    // Unlocked since the screen was last cleared
+    let mut unlocked: Vec<&Achievement> = Vec::new();
+    while let Some(exercise) = set.get(current) {
+        let result = set.check(exercise)?;
+        globals.update_progress(|state| state.record_check(&exercise.info, &result))?;
+        if result.done() {
+            println!("{} {}", style.paint(Role::Success, "✓"), exercise.info.name);
+            unlocked.extend(unlock_achievements(globals, &set)?);
+            current += 1;
+            continue;
+        }
+
+        show(style, exercise, set.dir(), &result, seed, globals.editor().as_deref());
+        if let Some(banner) = goal::banner(&globals.progress()?, Date::today(), style) {
+            println!("{banner}");
+        }
+        announce(globals, &unlocked);
+        unlocked.clear();
+        let seen = modified_times(set.dir());
+        while modified_times(set.dir()) == seen {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    println!();
+    announce(globals, &unlocked);
+    println!("{}", style.paint(Role::Success, "All exercises done!"));
+    Ok(())
+}
+
+fn show(style: Style, exercise: &Exercise, dir: &Path, result: &CheckResult, seed: Option<u64>, editor: Option<&str>) {
+    print!("\x1b[H\x1b[2J"); // Start each check on a clear screen
+    let file = exercise.file(dir);
+    if result.passed {
+        println!("{} {} passes", style.paint(Role::Success, "✓"), exercise.info.name);
+        println!("{}", result.output.trim_end());
+        println!();
+        println!("Delete the `{NOT_DONE_MARKER}` line in {} to move on.", file.display());
+    } else {
+        println!("{} {} doesn't pass yet", style.paint(Role::Error, "✗"), exercise.info.name);
+        println!("{}", result.output.trim_end());
+        println!();
+        for checkpoint in &result.checkpoints {
+            let mark = if checkpoint.passed { style.paint(Role::Success, "✓") } else { style.paint(Role::Error, "✗") };
+            println!("  {mark} {}", checkpoint.name);
+        }
+        let (score, total) = result.score();
+        println!("  {score} of {total} checkpoints");
+        println!();
+        println!("Edit {} and save, it's checked again automatically.", file.display());
+    }
+    if let Some(editor) = editor {
+        println!("{}", style.paint(Role::Dim, &format!("`{editor} {}` opens it", file.display())));
+    }
+    println!("{}", style.paint(Role::Dim, "Watching for changes, Ctrl-C to stop"));
+    // The screen is cleared on every check, so the seed printed at the start would scroll away
+    if let Some(seed) = seed {
+        println!("{}", style.paint(Role::Dim, &format!("Shuffled, use --seed {seed} for the same order again")));
+    }
+}
+
+// Modification time of every file under `dir`, compared between polls to spot a save
+fn modified_times(dir: &Path) -> Vec<(String, Option<SystemTime>)> {
+    let mut times = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return times;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            times.extend(modified_times(&path));
+        } else {
+            let modified = entry.metadata().and_then(|metadata| metadata.modified()).ok();
+            times.push((path.display().to_string(), modified));
+        }
+    }
+    times.sort();
+    times
+}
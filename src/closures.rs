@@ -0,0 +1,158 @@
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+use crate::tracked::{self, Tracked, Tracker};
+
+// A closure captures the variables it uses, as little as it can get away with: by reference if it only reads them,
+// by mutable reference if it changes them, by value if it moves them out or is marked `move`. Capturing runs no
+// code, so the demos report it themselves, but the captured values are Tracked and log their own drops, which is
+// when the closure holding them is dropped or when a call uses them up.
+
+pub const TAUGHT: Taught = Taught { day: 10, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(closure_captures_demo {
+        name: "closure_captures",
+        summary: "Closures capture by reference, by mutable reference or, with move, by value",
+        topics: &["closures", "ownership"],
+        prerequisites: &["tracked", "mutate_reference"],
+        explanation: "A closure that only reads a variable borrows it, so the variable can still be read next to it. One that changes a variable borrows it mutably, has to be declared `mut` to be called, and nothing else can use the variable until the closure's last call. `move` makes the closure take ownership of what it uses, even if it only reads it: the variable can't be used afterwards, and the value is dropped when the closure is, not at the end of the original owner's scope.",
+    }),
+    lesson!(fn_traits_demo {
+        name: "fn_traits",
+        summary: "Fn, FnMut and FnOnce, and which one a closure is",
+        topics: &["closures", "ownership"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["closure_captures"],
+        explanation: "What a closure does with its captures decides which traits it has. `Fn` closures only read them and can be called any number of times, even at once. `FnMut` closures change them, so each call needs the closure mutably. `FnOnce` closures move something out of their captures, so they can only be called once, the call uses them up. Every Fn is also FnMut and FnOnce, so a function asking for `impl FnOnce` takes anything, and one asking for `impl Fn` rejects a closure that moves out (E0525).",
+    }),
+];
+
+// Reads, changes and then takes a Tracked value in three closures, watching when each capture ends
+pub fn closure_captures_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let tracker = Tracker::new();
+    let name = tracker.track("name", String::from("ferris"));
+    let greet = || format!("hello, {}", name.borrow_as("greet")); // Only reads name, captures &name
+    reporter.event(&OwnershipEvent::borrowed("name", "greet"));
+    reporter.message(&greet());
+    reporter.message(&greet());
+    reporter.message(&format!("name is still usable next to greet: {name}"));
+    tracker.report(reporter);
+
+    let mut count = tracker.track("count", 0);
+    let mut bump = || *count.borrow_mut_as("bump") += 1; // Changes count, captures &mut count
+    reporter.event(&OwnershipEvent::borrowed_mut("count", "bump"));
+    bump();
+    bump(); // Reading count before this line would be E0502, bump still holds it mutably
+    reporter.message(&format!("count: {}", *count));
+    tracker.report(reporter);
+
+    let shout = move || name.to_uppercase(); // Only reads name, but `move` takes it into the closure
+    reporter.event(&OwnershipEvent::moved("name", "shout"));
+    reporter.message(&shout());
+    reporter.message("name: error");
+    tracker.report(reporter);
+    drop(shout); // name's String goes with the closure that owns it
+    tracker.report(reporter);
+    Ok(())
+}
+
+// Calls `f` twice, it may only read what it captured
+pub fn call_twice(f: impl Fn() -> String) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(format!("Fn, first call: {}", f()));
+    output.message(format!("Fn, second call: {}", f()));
+    output
+}
+
+// Calls `f` twice, it may change what it captured
+pub fn call_twice_mut(mut f: impl FnMut() -> String) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(format!("FnMut, first call: {}", f()));
+    output.message(format!("FnMut, second call: {}", f()));
+    output
+}
+
+// Calls `f` once, it may use up what it captured
+pub fn call_once(f: impl FnOnce() -> DemoOutput) -> DemoOutput {
+    let mut output = f();
+    output.messages.insert(0, "FnOnce, the only call:".to_string());
+    output
+}
+
+const FN_ONCE_AS_FN: (&str, &str) = (
+    "let name = String::from(\"ferris\");\nlet consume = move || {\n    let taken = name;\n    taken.len()\n};\ncall_twice(consume);",
+    "error[E0525]: expected a closure that implements the `Fn` trait, but this closure only implements `FnOnce`\n --> src/main.rs:2:15\n  |\n2 | let consume = move || {\n  |               ^^^^^^^ this closure implements `FnOnce`, not `Fn`\n3 |     let taken = name;\n  |                 ---- closure is `FnOnce` because it moves the variable `name` out of its environment\n...\n6 | call_twice(consume);\n  | ---------- ------- the requirement to implement `Fn` derives from here\n  | |\n  | required by a bound introduced by this call",
+);
+
+// One closure for each trait, passed to a function asking for it, then the one rustc rejects
+pub fn fn_traits_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let tracker = Tracker::new();
+    let name = tracker.track("name", String::from("ferris"));
+    reporter.event(&OwnershipEvent::borrowed("name", "f"));
+    reporter.output(&call_twice(|| name.to_string())); // Reads only, Fn
+
+    let mut calls: Tracked<Vec<String>> = tracker.track("calls", Vec::new());
+    reporter.event(&OwnershipEvent::borrowed_mut("calls", "f"));
+    reporter.output(&call_twice_mut(|| {
+        // Pushes onto calls, FnMut
+        let call = format!("call {}", calls.len() + 1);
+        calls.push(call);
+        calls.join(", ")
+    }));
+
+    reporter.event(&OwnershipEvent::moved("name", "f"));
+    reporter.output(&call_once(move || tracked::take_ownership(name))); // Moves name out of the closure, FnOnce
+    tracker.report(reporter); // name was dropped inside take_ownership, during the one call
+    reporter.message("");
+
+    let (code, error) = FN_ONCE_AS_FN;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_fn_trait_is_called_as_often_as_it_allows() {
+        let name = String::from("ferris");
+        assert_eq!(call_twice(|| name.clone()).messages, ["Fn, first call: ferris", "Fn, second call: ferris"]);
+        let mut calls = 0;
+        let output = call_twice_mut(|| {
+            calls += 1;
+            format!("call {calls}")
+        });
+        assert_eq!(output.messages, ["FnMut, first call: call 1", "FnMut, second call: call 2"]);
+        let output = call_once(move || {
+            let mut output = DemoOutput::new();
+            output.message(name);
+            output
+        });
+        assert_eq!(output.messages, ["FnOnce, the only call:", "ferris"]);
+    }
+
+    #[test]
+    fn closure_captures_demo_borrows_then_moves_name() {
+        let mut output = DemoOutput::new();
+        closure_captures_demo(&mut output).unwrap();
+        assert_eq!(output.messages, ["hello, ferris", "hello, ferris", "name is still usable next to greet: ferris", "count: 2", "FERRIS", "name: error"]);
+        assert_eq!(output.events[output.events.len() - 2..], [OwnershipEvent::moved("name", "shout"), OwnershipEvent::dropped("name")]);
+    }
+
+    #[test]
+    fn fn_traits_demo_rejects_an_fn_once_where_fn_is_needed() {
+        let mut output = DemoOutput::new();
+        fn_traits_demo(&mut output).unwrap();
+        assert_eq!(output.messages[..6], ["Fn, first call: ferris", "Fn, second call: ferris", "FnMut, first call: call 1", "FnMut, second call: call 1, call 2", "FnOnce, the only call:", "ferris"]);
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0525]")));
+        assert_eq!(output.events.last(), Some(&OwnershipEvent::dropped("complex")));
+    }
+}
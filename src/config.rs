@@ -0,0 +1,206 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::LessonError;
+use crate::toml;
+
+// Every key config.toml may set
+pub const KEYS: &[&str] = &["color", "exercises_dir", "topics", "editor"];
+
+// Defaults from config.toml, the command line overrides every one of them, e.g.
+//
+//     color = false
+//     exercises_dir = "~/rust/exercises"
+//     topics = ["references", "slices"]
+//     editor = "code --wait"
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    // false turns colors off like --no-color, they're still only used on a terminal otherwise
+    pub color: Option<bool>,
+    // As written, a leading `~/` is expanded where it's used
+    pub exercises_dir: Option<String>,
+    // What `quiz` asks about when it isn't given a --topic
+    pub topics: Vec<String>,
+    // The command to open an exercise with, VISUAL and EDITOR are used when it isn't set
+    pub editor: Option<String>,
+}
+
+impl Config {
+    // RUST_DAY_6_CONFIG if it's set, otherwise config.toml in $XDG_CONFIG_HOME/rust_day_6, which defaults to
+    // ~/.config/rust_day_6. A profile with its own config.toml in profiles/<name> uses that, the others share
+    // the main one so a classroom can set its defaults once.
+    pub fn path(profile: Option<&str>) -> PathBuf {
+        if let Some(path) = std::env::var_os("RUST_DAY_6_CONFIG") {
+            return PathBuf::from(path);
+        }
+        let dir = xdg_dir("XDG_CONFIG_HOME", ".config");
+        match profile.map(|profile| profile_dir(&dir, profile).join("config.toml")) {
+            Some(own) if own.exists() => own,
+            _ => dir.join("config.toml"),
+        }
+    }
+
+    // A missing file means every default
+    pub fn load(path: &Path) -> Result<Self, LessonError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.into()),
+        };
+        let invalid = |err: LessonError| LessonError::BadInput(format!("{} is invalid: {err}", path.display()));
+        let table = toml::parse(&text).map_err(invalid)?;
+        let bad = |message: String| invalid(LessonError::BadInput(message));
+        if let Some((key, _)) = table.0.iter().find(|(key, _)| !KEYS.contains(&key.as_str())) {
+            return Err(bad(format!("unknown key `{key}`, expected one of {}", KEYS.join(", "))));
+        }
+
+        let string = |key: &str| match table.get(key) {
+            None => Ok(None),
+            Some(_) => table.str(key).map(|value| Some(value.to_string())).ok_or_else(|| bad(format!("`{key}` should be a string"))),
+        };
+        let color = match table.get("color") {
+            None => None,
+            Some(_) => Some(table.bool("color").ok_or_else(|| bad("`color` should be true or false".to_string()))?),
+        };
+        let topics = match table.get("topics") {
+            None => Vec::new(),
+            Some(_) => {
+                let topics = table.strings("topics").ok_or_else(|| bad("`topics` should be a list of strings".to_string()))?;
+                topics.into_iter().map(str::to_string).collect()
+            }
+        };
+        Ok(Self { color, exercises_dir: string("exercises_dir")?, topics, editor: string("editor")? })
+    }
+
+    // Whether colors are wanted, --color or --no-color when one was given (`flag`), then the file, then yes
+    pub fn color(&self, flag: Option<bool>) -> bool {
+        flag.or(self.color).unwrap_or(true)
+    }
+}
+
+// `$<var>/rust_day_6`, or `~/<fallback>/rust_day_6` when the variable isn't set, the XDG base directory rules
+pub fn xdg_dir(var: &str, fallback: &str) -> PathBuf {
+    let base = match (std::env::var_os(var).filter(|dir| !dir.is_empty()), std::env::var_os("HOME")) {
+        (Some(dir), _) => PathBuf::from(dir),
+        (None, Some(home)) => PathBuf::from(home).join(fallback),
+        (None, None) => PathBuf::from("."),
+    };
+    base.join("rust_day_6")
+}
+
+// A directory of the user's own for what rustc builds, under $XDG_CACHE_HOME/rust_day_6, which defaults to
+// ~/.cache/rust_day_6. Only the user can get into it, so nobody else can put a binary there for us to run.
+pub fn build_dir(name: &str) -> Result<PathBuf, LessonError> {
+    let dir = xdg_dir("XDG_CACHE_HOME", ".cache").join("build").join(name);
+    fs::create_dir_all(&dir)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))?;
+    }
+    Ok(dir)
+}
+
+// Where a profile's files go under one of the directories from `xdg_dir`
+pub fn profile_dir(dir: &Path, profile: &str) -> PathBuf {
+    dir.join("profiles").join(profile)
+}
+
+// Profile names become directory names, so they're kept to something every file system takes
+pub fn check_profile(name: &str) -> Result<(), LessonError> {
+    let valid = name.chars().next().is_some_and(|c| c.is_ascii_alphanumeric())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(LessonError::BadInput(format!("`{name}` can't be a profile name, use letters, digits, `-` and `_`")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A config.toml with `text` in a directory of its own, removed again on drop
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new(name: &str, text: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("rust_day_6-test-{}-config-{name}", std::process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("config.toml"), text).unwrap();
+            Self(dir)
+        }
+
+        fn load(&self) -> Result<Config, LessonError> {
+            Config::load(&self.0.join("config.toml"))
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn error(result: Result<Config, LessonError>) -> String {
+        match result {
+            Err(LessonError::BadInput(message)) => message,
+            other => panic!("expected BadInput, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn every_key_is_read() {
+        let scratch = Scratch::new("every", "color = false\nexercises_dir = \"~/rust/exercises\"\ntopics = [\"references\", \"slices\"]\neditor = \"code --wait\"\n");
+        let config = scratch.load().unwrap();
+        assert_eq!(config, Config {
+            color: Some(false),
+            exercises_dir: Some("~/rust/exercises".to_string()),
+            topics: vec!["references".to_string(), "slices".to_string()],
+            editor: Some("code --wait".to_string()),
+        });
+    }
+
+    #[test]
+    fn missing_file_or_keys_are_defaults() {
+        let nowhere = std::env::temp_dir().join(format!("rust_day_6-test-{}-config-nowhere", std::process::id())).join("config.toml");
+        assert_eq!(Config::load(&nowhere).unwrap(), Config::default());
+        assert_eq!(Scratch::new("empty", "").load().unwrap(), Config::default());
+        let config = Scratch::new("some", "editor = \"vi\"\n").load().unwrap();
+        assert_eq!(config.color, None);
+        assert!(config.topics.is_empty());
+        assert_eq!(config.editor.as_deref(), Some("vi"));
+    }
+
+    #[test]
+    fn bad_values_name_the_file_and_key() {
+        let scratch = Scratch::new("typo", "colour = false\n");
+        let message = error(scratch.load());
+        assert!(message.starts_with(&format!("{} is invalid: unknown key `colour`", scratch.0.join("config.toml").display())), "{message}");
+        assert!(error(Scratch::new("color", "color = \"no\"\n").load()).ends_with("`color` should be true or false"));
+        assert!(error(Scratch::new("topics", "topics = \"slices\"\n").load()).ends_with("`topics` should be a list of strings"));
+        assert!(error(Scratch::new("editor", "editor = 1\n").load()).ends_with("`editor` should be a string"));
+    }
+
+    #[test]
+    fn flags_win_over_the_file() {
+        let off = Config { color: Some(false), ..Config::default() };
+        assert!(!off.color(None));
+        assert!(off.color(Some(true)));
+        let on = Config { color: Some(true), ..Config::default() };
+        assert!(!on.color(Some(false)));
+        assert!(Config::default().color(None));
+    }
+
+    #[test]
+    fn profile_names() {
+        for name in ["alice", "room-2", "a_b", "7th"] {
+            assert!(check_profile(name).is_ok(), "{name}");
+        }
+        for name in ["", "-x", "_x", "../up", "a/b", "a b", "é"] {
+            assert!(matches!(check_profile(name), Err(LessonError::BadInput(_))), "{name}");
+        }
+    }
+}
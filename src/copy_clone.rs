@@ -0,0 +1,237 @@
+use std::cell::Cell;
+
+use crate::error::LessonError;
+use crate::generics;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::OwnershipEvent;
+use crate::report::Reporter;
+use crate::tracked::{Tracked, Tracker};
+
+// Whether `let b = a;` copies or moves isn't about how small a type is, it's whether the type implements Copy.
+// i32 does, String can't, and a struct of your own does only if you derive it, even when every field is Copy.
+// Clone is the explicit version, `a.clone()` runs code and can allocate, and every Copy type has to be Clone as
+// well. The demos push three structs, one of each kind, through what `makes_copy` and `take_ownership` did with
+// i32 and String, using the generic functions from day 13.
+
+pub const TAUGHT: Taught = Taught { day: 14, order: 2 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(copy_vs_clone_demo {
+        name: "copy_vs_clone",
+        summary: "The same calls with a Copy struct, a Clone-only struct and one with neither",
+        topics: &["ownership", "copy", "traits"],
+        prerequisites: &["partial_moves", "generic_functions"],
+        explanation: "`#[derive(Clone, Copy)] struct Point { x: i32, y: i32 }` behaves like an i32: assigning it or passing it to `consume` copies it, and the original stays usable. `#[derive(Clone)] struct Label { text: String }` behaves like a String: it moves, and `.clone()` makes a second one. `struct Ticket { id: u32 }` has only Copy fields, but without the derive it moves anyway, it just can't be cloned either, and using it after `consume(ticket)` is E0382. A function asking for `T: Copy` or `T: Clone` only takes the types that derived it.",
+    }),
+    lesson!(copy_rules_demo {
+        name: "copy_rules",
+        summary: "Copy needs Clone, can't have a Drop, and never calls clone",
+        topics: &["ownership", "copy", "traits"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["copy_vs_clone", "drop_trait"],
+        explanation: "Copy is a promise that copying the bytes makes a proper second value, so it's only allowed when that's true. It requires Clone (E0277 without it), and a type with a Drop can't be Copy (E0184): two bitwise copies would run the drop twice. A copy is always just the bytes, even a Copy type's own Clone impl isn't called by `let b = a;`. `#[derive(Clone)]` clones field by field, so a struct's clone costs whatever its fields' clones do, a String field's heap copy included.",
+    }),
+];
+
+// Copied like an i32, every field is Copy and it derives Copy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+// Moved like a String, it can't be Copy with a String in it, but it can be cloned
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub text: String,
+}
+
+// Neither, nothing was derived, so it moves though its one field is Copy
+#[derive(Debug, PartialEq)]
+pub struct Ticket {
+    pub id: u32,
+}
+
+// takes_and_gives_back for any T
+pub fn gives_back<T>(value: T) -> T {
+    value
+}
+
+// Two of it without a clone, so only for Copy types
+pub fn duplicate<T: Copy>(value: T) -> (T, T) {
+    (value, value)
+}
+
+const TICKET_AFTER_MOVE: (&str, &str) = (
+    "let ticket = Ticket { id: 7 };\nconsume(ticket);\nprintln!(\"{}\", ticket.id);",
+    "error[E0382]: borrow of moved value: `ticket`\n --> src/main.rs:3:16\n  |\n1 | let ticket = Ticket { id: 7 };\n  |     ------ move occurs because `ticket` has type `Ticket`, which does not implement the `Copy` trait\n2 | consume(ticket);\n  |         ------ value moved here\n3 | println!(\"{}\", ticket.id);\n  |                ^^^^^^^^^ value borrowed here after move",
+);
+
+// Each type assigned, passed to consume and given back, cloned and duplicated where it's allowed
+pub fn copy_vs_clone_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let p1 = Point { x: 1, y: 2 };
+    let p2 = p1; // Copied, like `let y = x;`
+    reporter.event(&OwnershipEvent::copied("p1", "p2"));
+    reporter.event(&OwnershipEvent::copied("p1", "value"));
+    reporter.output(&generics::consume(p1)); // consume::<Point> gets a copy
+    let (p3, p4) = duplicate(p1);
+    reporter.message(&format!("p1: {p1:?}, p2: {p2:?}, duplicate(p1): {p3:?} and {p4:?}"));
+    reporter.message("");
+
+    let l1 = Label { text: String::from("hello") };
+    let l2 = l1; // Moved, like `let s2 = s1;`
+    reporter.event(&OwnershipEvent::moved("l1", "l2"));
+    let l3 = l2.clone(); // A second Label with its own String
+    reporter.event(&OwnershipEvent::cloned("l2", "l3"));
+    reporter.event(&OwnershipEvent::moved("l3", "value"));
+    reporter.output(&generics::consume(l3)); // consume::<Label> takes ownership and drops it
+    let l2 = gives_back(l2); // Moved in and back out, like takes_and_gives_back
+    reporter.event(&OwnershipEvent::moved("l2", "value"));
+    reporter.event(&OwnershipEvent::moved("value", "l2"));
+    reporter.message(&format!("l1: error, l3: error, l2: {l2:?}, duplicate(l2): error, Label isn't Copy"));
+    reporter.message("");
+
+    let t1 = Ticket { id: 7 };
+    let t2 = t1; // Moved: all its fields are Copy, but Ticket isn't
+    reporter.event(&OwnershipEvent::moved("t1", "t2"));
+    reporter.event(&OwnershipEvent::moved("t2", "value"));
+    reporter.output(&generics::consume(t2));
+    reporter.message("t1: error, t2: error, t2.clone(): error, Ticket isn't Clone either");
+    let (code, error) = TICKET_AFTER_MOVE;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    reporter.message("type    let b = a  consume(a), then a  a.clone()  duplicate(a)");
+    reporter.message("i32     copy       usable              yes        yes");
+    reporter.message("Point   copy       usable              yes        yes");
+    reporter.message("String  move       error               yes        error");
+    reporter.message("Label   move       error               yes        error");
+    reporter.message("Ticket  move       error               error      error");
+    Ok(())
+}
+
+const COPY_WITHOUT_CLONE: (&str, &str) = (
+    "#[derive(Copy)]\nstruct Point { x: i32, y: i32 }",
+    "error[E0277]: the trait bound `Point: Clone` is not satisfied\n --> src/main.rs:2:8\n  |\n1 | #[derive(Copy)]\n  |          ---- in this derive macro expansion\n2 | struct Point { x: i32, y: i32 }\n  |        ^^^^^ the trait `Clone` is not implemented for `Point`",
+);
+
+const COPY_WITH_DROP: (&str, &str) = (
+    "#[derive(Clone, Copy)]\nstruct Point { x: i32, y: i32 }\nimpl Drop for Point {\n    fn drop(&mut self) {}\n}",
+    "error[E0184]: the trait `Copy` cannot be implemented for this type; the type has a destructor\n --> src/main.rs:2:8\n  |\n1 | #[derive(Clone, Copy)]\n  |                 ---- in this derive macro expansion\n2 | struct Point { x: i32, y: i32 }\n  |        ^^^^^ `Copy` not allowed on types with destructors",
+);
+
+// A Copy type with a Clone impl that counts its calls, to show which copies go through it
+#[derive(Debug)]
+pub struct Counted<'a> {
+    pub value: i32,
+    pub clones: &'a Cell<u32>,
+}
+
+impl Copy for Counted<'_> {}
+
+#[allow(clippy::non_canonical_clone_impl)] // Counting is the point, a real Copy type's clone is just `*self`
+impl Clone for Counted<'_> {
+    fn clone(&self) -> Self {
+        self.clones.set(self.clones.get() + 1);
+        *self
+    }
+}
+
+// A struct with a tracked String field, so the derived clone shows what it clones
+#[derive(Clone)]
+pub struct Badge {
+    pub name: Tracked<String>,
+    pub level: u8,
+}
+
+// What rustc refuses, then copies that never call clone and a derived clone going field by field
+pub fn copy_rules_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    for (code, error) in [COPY_WITHOUT_CLONE, COPY_WITH_DROP] {
+        for line in code.lines() {
+            reporter.message(&format!("    {line}"));
+        }
+        for line in error.lines() {
+            reporter.message(line);
+        }
+        reporter.message("");
+    }
+
+    let clones = Cell::new(0);
+    let a = Counted { value: 1, clones: &clones };
+    let b = a; // The bytes, Counted::clone isn't called
+    let (c, d) = duplicate(a); // Copies again, inside duplicate too
+    reporter.event(&OwnershipEvent::copied("a", "b"));
+    reporter.message(&format!("after let b = a and duplicate(a): {} clone calls, values {} {} {}", clones.get(), b.value, c.value, d.value));
+    #[allow(clippy::clone_on_copy)] // The call clippy would take out is the one being counted
+    let e = a.clone(); // Only an explicit clone() runs the impl
+    reporter.event(&OwnershipEvent::cloned("a", "e"));
+    reporter.message(&format!("after a.clone(): {} clone call, e.value {}", clones.get(), e.value));
+    reporter.message("");
+
+    let tracker = Tracker::new();
+    {
+        let badge = Badge { name: tracker.track("badge.name", String::from("ferris")), level: 3 };
+        let copy = badge.clone(); // Derived: clones name, which logs it, and copies level
+        tracker.report(reporter);
+        reporter.message(&format!("copy: {} at level {}, badge is still {} at level {}", *copy.name, copy.level, *badge.name, badge.level));
+    } // Both Strings are dropped, the clone's and the original's
+    tracker.report(reporter);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::DemoOutput;
+
+    #[test]
+    fn copies_never_call_clone() {
+        let clones = Cell::new(0);
+        let a = Counted { value: 4, clones: &clones };
+        let (b, c) = duplicate(a);
+        let d = gives_back(a);
+        assert_eq!((b.value, c.value, d.value, clones.get()), (4, 4, 4, 0));
+        #[allow(clippy::clone_on_copy)]
+        let e = a.clone();
+        assert_eq!((e.value, clones.get()), (4, 1));
+        assert_eq!(duplicate(Point { x: 1, y: 2 }).1, Point { x: 1, y: 2 });
+        assert_eq!(gives_back(Ticket { id: 7 }), Ticket { id: 7 });
+    }
+
+    #[test]
+    fn copy_vs_clone_demo_copies_points_and_moves_the_rest() {
+        let mut output = DemoOutput::new();
+        copy_vs_clone_demo(&mut output).unwrap();
+        assert!(output.messages.contains(&"l1: error, l3: error, l2: Label { text: \"hello\" }, duplicate(l2): error, Label isn't Copy".to_string()));
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0382]")));
+        assert_eq!(output.messages.last().unwrap(), "Ticket  move       error               error      error");
+        // Only the Label owns anything to drop
+        let drops: Vec<&OwnershipEvent> = output.events.iter().filter(|event| matches!(event, OwnershipEvent::Drop { .. })).collect();
+        assert_eq!(drops, [&OwnershipEvent::dropped("value")]);
+        assert_eq!(output.events[..2], [OwnershipEvent::copied("p1", "p2"), OwnershipEvent::copied("p1", "value")]);
+        assert_eq!(output.events[8..], [OwnershipEvent::moved("t1", "t2"), OwnershipEvent::moved("t2", "value")]);
+    }
+
+    #[test]
+    fn copy_rules_demo_counts_clone_calls_and_clones_fields() {
+        let mut output = DemoOutput::new();
+        copy_rules_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0277]")));
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0184]")));
+        assert!(output.messages.contains(&"after let b = a and duplicate(a): 0 clone calls, values 1 1 1".to_string()));
+        assert!(output.messages.contains(&"after a.clone(): 1 clone call, e.value 1".to_string()));
+        assert_eq!(
+            output.events[2..],
+            [
+                OwnershipEvent::cloned("badge.name", "badge.name (clone)"),
+                OwnershipEvent::dropped("badge.name (clone)"),
+                OwnershipEvent::dropped("badge.name"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::borrow::Cow;
+use std::mem;
+
+use crate::allocations;
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::OwnershipEvent;
+use crate::report::Reporter;
+
+// A function that usually hands back its input unchanged, and sometimes has to change it, can't return `&str`
+// (the changed text is a local String) and returning `String` allocates every time. `Cow<str>`, clone on write,
+// is either: `Cow::Borrowed(&str)` when the input will do, `Cow::Owned(String)` when it didn't. It derefs to `&str`
+// either way, so the caller reads it the same and only pays for the allocation when there was one.
+
+pub const TAUGHT: Taught = Taught { day: 13, order: 4 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(cow_basics_demo {
+        name: "cow_basics",
+        summary: "A Cow<str> is either a borrowed &str or an owned String",
+        topics: &["smart-pointers", "borrowing"],
+        prerequisites: &["deref_coercion", "take_ownership"],
+        explanation: "`Cow::Borrowed(text)` holds a `&str` and allocates nothing, `Cow::Owned(string)` holds a String and owns it. Both deref to `&str`, so `len()`, printing and passing it on as a `&str` work without looking at which one it is. `to_mut()` gives a `&mut String`, cloning the borrowed text into a String the first time and reusing it after, and `into_owned()` hands you the String, cloning only if it was borrowed.",
+    }),
+    lesson!(copy_on_write_demo {
+        name: "copy_on_write",
+        summary: "normalize_spaces borrows its input when it's fine and allocates only when it changes it",
+        topics: &["smart-pointers", "performance"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["cow_basics", "dangling_references"],
+        explanation: "`normalize_spaces` collapses tabs, newlines and runs of spaces into single spaces and trims the ends. Most text is already like that, and then the result is the input: it returns `Cow::Borrowed(text)`, no allocation. Only text that needs changing gets a new String, returned as `Cow::Owned`. Returning `&str` can't do the second case (E0515, the String would be dropped at the end of the function) and returning `String` allocates in the first. With the count-allocations feature the demo counts the allocations for each input.",
+    }),
+];
+
+// Which of the two the Cow is
+#[allow(clippy::ptr_arg)] // A &str would deref away the very thing being looked at
+pub fn kind(text: &Cow<str>) -> &'static str {
+    match text {
+        Cow::Borrowed(_) => "Borrowed",
+        Cow::Owned(_) => "Owned",
+    }
+}
+
+// Borrowed, then cloned into a String by the first write, then taken out whole
+pub fn cow_basics_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let s1 = String::from("hello");
+    let mut text: Cow<str> = Cow::Borrowed(&s1); // Just the &str, nothing allocated
+    reporter.event(&OwnershipEvent::borrowed("s1", "text"));
+    reporter.message(&format!("text: {text}, {}, len {} through deref, same bytes as s1: {}", kind(&text), text.len(), text.as_ptr() == s1.as_ptr()));
+
+    text.to_mut().push_str(" world"); // The first write clones the text into a String of its own
+    reporter.event(&OwnershipEvent::cloned("s1", "text"));
+    reporter.message(&format!("after to_mut().push_str: {text}, {}, same bytes as s1: {}", kind(&text), text.as_ptr() == s1.as_ptr()));
+    text.to_mut().push('!'); // Already Owned, so no clone this time
+    reporter.message(&format!("after a second to_mut(): {text}, still {}, s1 is untouched: {s1}", kind(&text)));
+
+    let owned: String = text.into_owned(); // Owned already, the String is moved out as it is
+    reporter.event(&OwnershipEvent::moved("text", "owned"));
+    reporter.message(&format!("owned: {owned}, text: error"));
+    reporter.message(&format!("size_of Cow<str>: {} bytes, the same as String, Borrowed fits in String's niche", mem::size_of::<Cow<str>>()));
+    Ok(())
+}
+
+// Whether normalize_spaces would leave the text as it is: only single spaces, none at either end
+fn is_normalized(text: &str) -> bool {
+    let mut after_space = true; // As if there was a space before the start, so a leading one counts as doubled
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if c != ' ' || after_space {
+                return false;
+            }
+            after_space = true;
+        } else {
+            after_space = false;
+        }
+    }
+    text.is_empty() || !after_space
+}
+
+// Runs of whitespace become one space and the ends are trimmed, borrowing `text` if it's like that already
+pub fn normalize_spaces(text: &str) -> Cow<'_, str> {
+    if is_normalized(text) {
+        return Cow::Borrowed(text);
+    }
+    let mut normalized = String::with_capacity(text.len()); // Never longer than the input, one allocation
+    for word in text.split_whitespace() {
+        if !normalized.is_empty() {
+            normalized.push(' ');
+        }
+        normalized.push_str(word);
+    }
+    Cow::Owned(normalized)
+}
+
+// Runs `f` and counts the allocations it made, None when the crate doesn't count allocations
+fn allocations_in<R>(f: impl FnOnce() -> R) -> (R, Option<usize>) {
+    let before = allocations::counts();
+    let result = f();
+    let after = allocations::counts();
+    let allocated = before.zip(after).map(|(before, after)| after.since(&before).allocations);
+    (result, allocated)
+}
+
+const RETURNS_LOCAL: (&str, &str) = (
+    "fn normalize_spaces(text: &str) -> &str {\n    let normalized = text.split_whitespace().collect::<Vec<_>>().join(\" \");\n    &normalized\n}",
+    "error[E0515]: cannot return reference to local variable `normalized`\n --> src/main.rs:3:5\n  |\n3 |     &normalized\n  |     ^^^^^^^^^^^ returns a reference to data owned by the current function",
+);
+
+// Why not &str, then normalize_spaces on tidy and untidy text, with what each call allocated
+pub fn copy_on_write_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let (code, error) = RETURNS_LOCAL;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    for input in ["already tidy", "  padded  ", "tabs\tand\nnewlines", "double  spaces", ""] {
+        let (normalized, allocated) = allocations_in(|| normalize_spaces(input));
+        let allocated = match allocated {
+            Some(count) => format!(", allocations: {count}"),
+            None => String::new(),
+        };
+        reporter.message(&format!("{input:?} -> {normalized:?}, {}{allocated}", kind(&normalized)));
+    }
+    if allocations::counts().is_none() {
+        reporter.message("run with `cargo run --features count-allocations` to count each call's allocations");
+    }
+    reporter.message("");
+
+    let tidy = normalize_spaces("already tidy");
+    let (owned, allocated) = allocations_in(|| tidy.into_owned()); // Borrowed, so into_owned has to clone now
+    reporter.event(&OwnershipEvent::cloned("tidy", "owned"));
+    let allocated = allocated.map(|count| format!(", allocations: {count}")).unwrap_or_default();
+    reporter.message(&format!("into_owned() on a Borrowed makes the String then: {owned:?}{allocated}"));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::DemoOutput;
+
+    #[test]
+    fn normalize_spaces_borrows_tidy_text() {
+        for tidy in ["already tidy", "one", ""] {
+            assert!(matches!(normalize_spaces(tidy), Cow::Borrowed(text) if text == tidy), "{tidy:?}");
+        }
+        for (untidy, expected) in [("  padded  ", "padded"), ("tabs\tand\nnewlines", "tabs and newlines"), ("double  spaces", "double spaces"), (" ", "")] {
+            let normalized = normalize_spaces(untidy);
+            assert_eq!(kind(&normalized), "Owned", "{untidy:?}");
+            assert_eq!(normalized, expected);
+        }
+    }
+
+    #[test]
+    fn cow_basics_demo_clones_on_the_first_write() {
+        let mut output = DemoOutput::new();
+        cow_basics_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages[..4],
+            [
+                "text: hello, Borrowed, len 5 through deref, same bytes as s1: true",
+                "after to_mut().push_str: hello world, Owned, same bytes as s1: false",
+                "after a second to_mut(): hello world!, still Owned, s1 is untouched: hello",
+                "owned: hello world!, text: error",
+            ]
+        );
+        assert_eq!(output.events, [OwnershipEvent::borrowed("s1", "text"), OwnershipEvent::cloned("s1", "text"), OwnershipEvent::moved("text", "owned")]);
+    }
+
+    #[test]
+    fn copy_on_write_demo_owns_only_what_it_changed() {
+        let mut output = DemoOutput::new();
+        copy_on_write_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0515]")));
+        let kinds: Vec<&str> = output.messages.iter().filter(|message| message.starts_with('"') && message.contains(" -> ")).map(|message| if message.contains(", Borrowed") { "Borrowed" } else { "Owned" }).collect();
+        assert_eq!(kinds, ["Borrowed", "Owned", "Owned", "Owned", "Borrowed"]);
+        assert!(output.messages.last().unwrap().starts_with("into_owned() on a Borrowed makes the String then: \"already tidy\""));
+        assert_eq!(output.events, [OwnershipEvent::cloned("tidy", "owned")]);
+    }
+}
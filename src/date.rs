@@ -0,0 +1,145 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::LessonError;
+
+// A calendar day, e.g. 2024-03-09. Days are counted in UTC, std can't tell the local time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    // Days since 1970-01-01
+    days: i64,
+}
+
+impl Date {
+    // The day a Unix timestamp (in seconds) falls on
+    pub fn from_timestamp(seconds: u64) -> Self {
+        Self { days: (seconds / 86_400) as i64 }
+    }
+
+    pub fn today() -> Self {
+        Self::from_timestamp(crate::progress::now())
+    }
+
+    pub fn from_ymd(year: i64, month: u32, day: u32) -> Self {
+        // Howard Hinnant's days_from_civil, with years starting in March so leap days come last
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month_from_march = (month as i64 + 9) % 12;
+        let day_of_year = (153 * month_from_march + 2) / 5 + day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        Self { days: era * 146_097 + day_of_era - 719_468 }
+    }
+
+    // (year, month, day)
+    pub fn ymd(self) -> (i64, u32, u32) {
+        let days = self.days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+        let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        (year, month, day)
+    }
+
+    // The day before
+    pub fn previous(self) -> Self {
+        Self { days: self.days - 1 }
+    }
+
+    pub fn add_days(self, days: i64) -> Self {
+        Self { days: self.days + days }
+    }
+
+    // Whole days from `earlier` to this date
+    pub fn days_since(self, earlier: Date) -> i64 {
+        self.days - earlier.days
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{year:04}-{month:02}-{day:02}")
+    }
+}
+
+impl FromStr for Date {
+    type Err = LessonError;
+
+    // YYYY-MM-DD
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let bad = || LessonError::BadInput(format!("`{text}` isn't a date like 2024-03-09"));
+        let mut parts = text.split('-');
+        let (Some(year), Some(month), Some(day), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+            return Err(bad());
+        };
+        let year: i64 = year.parse().map_err(|_| bad())?;
+        let month: u32 = month.parse().map_err(|_| bad())?;
+        let day: u32 = day.parse().map_err(|_| bad())?;
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(bad());
+        }
+        let date = Self::from_ymd(year, month, day);
+        // Catches days past the end of the month, e.g. 2023-02-30
+        if date.ymd() != (year, month, day) {
+            return Err(bad());
+        }
+        Ok(date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_epoch_is_day_zero() {
+        assert_eq!(Date::from_ymd(1970, 1, 1), Date::from_timestamp(0));
+        assert_eq!(Date::from_timestamp(86_399).to_string(), "1970-01-01");
+        assert_eq!(Date::from_timestamp(86_400).to_string(), "1970-01-02");
+        assert_eq!(Date::from_timestamp(1_709_942_400).to_string(), "2024-03-09");
+    }
+
+    #[test]
+    fn every_day_round_trips() {
+        // 1899 to 2101, past the century rules in both directions
+        let mut date = Date::from_ymd(1899, 1, 1);
+        while date < Date::from_ymd(2102, 1, 1) {
+            let (year, month, day) = date.ymd();
+            assert_eq!(Date::from_ymd(year, month, day), date);
+            assert_eq!(date.to_string().parse::<Date>().unwrap(), date);
+            assert_eq!(date.add_days(1).previous(), date);
+            date = date.add_days(1);
+        }
+    }
+
+    #[test]
+    fn leap_days() {
+        assert!("2024-02-29".parse::<Date>().is_ok());
+        assert!("2000-02-29".parse::<Date>().is_ok());
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert!("1900-02-29".parse::<Date>().is_err());
+        assert_eq!(Date::from_ymd(2024, 3, 1).days_since(Date::from_ymd(2024, 2, 28)), 2);
+        assert_eq!(Date::from_ymd(2023, 3, 1).days_since(Date::from_ymd(2023, 2, 28)), 1);
+    }
+
+    #[test]
+    fn before_the_epoch() {
+        assert_eq!(Date::from_timestamp(0).previous().to_string(), "1969-12-31");
+        assert_eq!(Date::from_ymd(1969, 12, 31).days_since(Date::from_ymd(1970, 1, 1)), -1);
+    }
+
+    #[test]
+    fn rejects_what_isnt_a_date() {
+        for text in ["", "2024-03", "2024-03-09-01", "2024-13-01", "2024-00-10", "2024-04-31", "2024-03-00", "2024-3-x", "today"] {
+            match text.parse::<Date>() {
+                Err(LessonError::BadInput(message)) => assert_eq!(message, format!("`{text}` isn't a date like 2024-03-09")),
+                other => panic!("expected BadInput for {text:?}, got {other:?}"),
+            }
+        }
+    }
+}
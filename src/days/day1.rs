@@ -0,0 +1,51 @@
+use super::Day;
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::report::Reporter;
+
+/*
+Getting started
+    - `cargo new` creates a project, `cargo run` builds and runs it
+    - Every program starts at `fn main()`
+    - `println!` is a macro (the `!`), it formats and prints a line
+*/
+
+pub static DAY: Day = Day {
+    number: 1,
+    title: "Hello, Cargo",
+};
+
+pub const TAUGHT: Taught = Taught { day: 1, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(hello_world_demo {
+        name: "hello_world",
+        summary: "Printing a line with println!",
+        topics: &["basics"],
+        difficulty: Difficulty::Intro,
+        explanation: "Every Rust program starts at `fn main()`. `println!` is a macro, which is what the `!` means: it takes a format string and prints it followed by a newline.",
+    }),
+    lesson!(formatting_demo {
+        name: "formatting",
+        summary: "Placeholders in format strings",
+        topics: &["basics"],
+        difficulty: Difficulty::Intro,
+        explanation: "`{}` formats a value with Display, meant for users. `{:?}` uses Debug, meant for programmers, and shows strings with their quotes. Variables can also be named right inside the braces, as in `{name}`.",
+    }),
+];
+
+// The first program everyone writes
+pub fn hello_world_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    reporter.message("Hello, world!");
+    Ok(())
+}
+
+// {} uses Display, {:?} uses Debug, and variables can be named right inside the braces
+pub fn formatting_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let name = "Rust";
+    let day = 1;
+    reporter.message(&format!("Hello {}, this is day {}", name, day));
+    reporter.message(&format!("Hello {name}, this is day {day}")); // Same thing, captured by name
+    reporter.message(&format!("{:?}", (name, day))); // Debug formatting shows the quotes
+    Ok(())
+}
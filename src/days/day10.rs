@@ -0,0 +1,7 @@
+use super::Day;
+
+// Day 10: closures and the iterators they're passed to
+pub static DAY: Day = Day {
+    number: 10,
+    title: "Closures and iterators",
+};
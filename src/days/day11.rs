@@ -0,0 +1,7 @@
+use super::Day;
+
+// Day 11: smart pointers, Box, Rc, RefCell and Weak
+pub static DAY: Day = Day {
+    number: 11,
+    title: "Smart pointers",
+};
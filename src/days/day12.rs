@@ -0,0 +1,7 @@
+use super::Day;
+
+// Day 12: threads, locks and channels
+pub static DAY: Day = Day {
+    number: 12,
+    title: "Concurrency",
+};
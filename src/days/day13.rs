@@ -0,0 +1,7 @@
+use super::Day;
+
+// Day 13: generics, trait objects, Deref and Cow
+pub static DAY: Day = Day {
+    number: 13,
+    title: "Generics and traits",
+};
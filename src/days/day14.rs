@@ -0,0 +1,7 @@
+use super::Day;
+
+// Day 14: Drop and RAII guards, then Copy and Clone on your own types
+pub static DAY: Day = Day {
+    number: 14,
+    title: "Drop, Copy and Clone",
+};
@@ -0,0 +1,67 @@
+use super::Day;
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::report::Reporter;
+
+/*
+Variables
+    - Variables are immutable by default, `mut` makes them mutable
+    - Constants are always immutable, need a type, and are named in SCREAMING_CASE
+    - Shadowing (`let x = x + 1;`) makes a new variable with the same name
+*/
+
+pub static DAY: Day = Day {
+    number: 2,
+    title: "Variables and mutability",
+};
+
+pub const TAUGHT: Taught = Taught { day: 2, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(mutability_demo {
+        name: "mutability",
+        summary: "Variables are immutable unless declared mut",
+        topics: &["basics", "variables"],
+        difficulty: Difficulty::Intro,
+        explanation: "Variables are immutable by default, so `x = 6;` is a compile error unless `x` was declared with `let mut`. Making mutation opt-in means you can tell at a glance which values change.",
+    }),
+    lesson!(constants_demo {
+        name: "constants",
+        summary: "Constants are fixed at compile time",
+        topics: &["basics", "variables"],
+        difficulty: Difficulty::Intro,
+        explanation: "Constants are declared with `const`, always need a type, and can only be set to something the compiler can work out ahead of time. They are named in SCREAMING_SNAKE_CASE by convention.",
+    }),
+    lesson!(shadowing_basics_demo {
+        name: "shadowing_basics",
+        summary: "Shadowing declares a new variable with the same name",
+        topics: &["basics", "variables"],
+        difficulty: Difficulty::Intro,
+        explanation: "Declaring a new variable with the same name shadows the old one. Unlike `mut`, shadowing creates a brand new variable, so it can even change the type, such as turning a string of spaces into its length.",
+    }),
+];
+
+const SECONDS_IN_A_DAY: u32 = 60 * 60 * 24;
+
+// Without mut, `x = 6;` would not compile
+pub fn mutability_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let mut x = 5;
+    reporter.message(&format!("x: {x}"));
+    x = 6;
+    reporter.message(&format!("x: {x}"));
+    Ok(())
+}
+
+// Constants can be computed from other constants, the compiler works them out ahead of time
+pub fn constants_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    reporter.message(&format!("SECONDS_IN_A_DAY: {SECONDS_IN_A_DAY}"));
+    Ok(())
+}
+
+// Shadowing can even change the type, which mut can't
+pub fn shadowing_basics_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let spaces = "   ";
+    let spaces = spaces.len();
+    reporter.message(&format!("spaces: {spaces}"));
+    Ok(())
+}
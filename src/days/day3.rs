@@ -0,0 +1,70 @@
+use super::Day;
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::report::Reporter;
+
+/*
+Data types
+    - Scalars: integers (i32, u8, ...), floats (f64), bool, char
+    - Compound: tuples (mixed types, fixed length) and arrays (one type, fixed length)
+    - All of these have a known size, so they live on the stack
+*/
+
+pub static DAY: Day = Day {
+    number: 3,
+    title: "Data types",
+};
+
+pub const TAUGHT: Taught = Taught { day: 3, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(scalars_demo {
+        name: "scalars",
+        summary: "Integers, floats, booleans and chars",
+        topics: &["basics", "types"],
+        difficulty: Difficulty::Intro,
+        explanation: "Scalar types hold a single value: integers (i32 by default), floats (f64 by default), bool and char. A char is four bytes, so it can hold any unicode scalar value, not just ASCII.",
+    }),
+    lesson!(tuples_demo {
+        name: "tuples",
+        summary: "Grouping values of different types",
+        topics: &["basics", "types"],
+        difficulty: Difficulty::Intro,
+        explanation: "A tuple groups a fixed number of values that can have different types. It can be taken apart with a pattern (`let (x, y, z) = tup;`) or indexed with `.0`, `.1` and so on.",
+    }),
+    lesson!(arrays_demo {
+        name: "arrays",
+        summary: "Fixed length lists of one type",
+        topics: &["basics", "types"],
+        difficulty: Difficulty::Intro,
+        explanation: "An array holds a fixed number of values of one type and lives on the stack. Indexing past the end panics at runtime instead of reading whatever memory comes next.",
+    }),
+];
+
+// A char is four bytes, so it can hold any unicode scalar value
+pub fn scalars_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let integer: i32 = -42;
+    let float = 2.5; // f64 by default
+    let boolean = true;
+    let character = 'ℤ';
+    reporter.message(&format!("{integer} {float} {boolean} {character}"));
+    Ok(())
+}
+
+// Tuples can be destructured or indexed with .0, .1, ...
+pub fn tuples_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let tup: (i32, f64, u8) = (500, 6.4, 1);
+    let (x, y, z) = tup;
+    reporter.message(&format!("x: {x}, y: {y}, z: {z}"));
+    reporter.message(&format!("tup.0: {}", tup.0));
+    Ok(())
+}
+
+// Arrays know their length, indexing past the end panics instead of reading random memory
+pub fn arrays_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let a = [1, 2, 3, 4, 5];
+    let zeros = [0; 3]; // [0, 0, 0]
+    reporter.message(&format!("a[0]: {}, len: {}", a[0], a.len()));
+    reporter.message(&format!("zeros: {:?}", zeros));
+    Ok(())
+}
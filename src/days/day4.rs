@@ -0,0 +1,59 @@
+use super::Day;
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::report::Reporter;
+
+/*
+Functions
+    - Parameters always need a type
+    - Statements do something, expressions evaluate to a value
+    - The last expression of a function (no semicolon) is its return value
+*/
+
+pub static DAY: Day = Day {
+    number: 4,
+    title: "Functions",
+};
+
+pub const TAUGHT: Taught = Taught { day: 4, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(parameters_demo {
+        name: "parameters",
+        summary: "Passing values into a function",
+        topics: &["basics", "functions"],
+        difficulty: Difficulty::Intro,
+        explanation: "Function parameters always need a type annotation. That way the compiler never has to guess what a function takes, and error messages point at the right place.",
+    }),
+    lesson!(return_values_demo {
+        name: "return_values",
+        summary: "The last expression is the return value",
+        topics: &["basics", "functions"],
+        difficulty: Difficulty::Intro,
+        explanation: "Statements do something, expressions evaluate to a value. The last expression of a block, without a semicolon, is the value of the block, and for a function body that makes it the return value.",
+    }),
+];
+
+fn print_labeled_measurement(value: i32, unit_label: char) -> String {
+    format!("The measurement is: {value}{unit_label}")
+}
+
+fn plus_one(x: i32) -> i32 {
+    x + 1 // Adding a semicolon here would turn it into a statement, and the function would return ()
+}
+
+// Each parameter has a name and a type
+pub fn parameters_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    reporter.message(&print_labeled_measurement(5, 'h'));
+    Ok(())
+}
+
+// Blocks are expressions too, so they can be assigned
+pub fn return_values_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let y = {
+        let x = 3;
+        x + 1
+    };
+    reporter.message(&format!("y: {y}, plus_one(y): {}", plus_one(y)));
+    Ok(())
+}
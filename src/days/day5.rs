@@ -0,0 +1,66 @@
+use super::Day;
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::report::Reporter;
+
+/*
+Control flow
+    - `if` conditions must be a bool, and `if` is an expression
+    - `loop` repeats forever until `break`, which can return a value
+    - `while` loops on a condition, `for` loops over an iterator
+*/
+
+pub static DAY: Day = Day {
+    number: 5,
+    title: "Control flow",
+};
+
+pub const TAUGHT: Taught = Taught { day: 5, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(if_expressions_demo {
+        name: "if_expressions",
+        summary: "if is an expression that picks a value",
+        topics: &["basics", "control-flow"],
+        difficulty: Difficulty::Intro,
+        explanation: "`if` is an expression, so it can be used on the right hand side of `let`. The condition must be a bool (there's no truthiness) and both branches must produce the same type.",
+    }),
+    lesson!(loops_demo {
+        name: "loops",
+        summary: "loop, while and for",
+        topics: &["basics", "control-flow"],
+        difficulty: Difficulty::Intro,
+        explanation: "`loop` repeats until `break`, which can hand a value out of the loop. `while` repeats as long as a condition holds, and `for` walks an iterator, which is the usual choice because there's no index to get wrong.",
+    }),
+];
+
+// Both branches must have the same type
+pub fn if_expressions_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let condition = true;
+    let number = if condition { 5 } else { 6 };
+    reporter.message(&format!("number: {number}"));
+    Ok(())
+}
+
+// for is the usual choice, there is no index to get wrong
+pub fn loops_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let mut counter = 0;
+    let result = loop {
+        counter += 1;
+        if counter == 10 {
+            break counter * 2; // break can hand a value out of the loop
+        }
+    };
+    reporter.message(&format!("loop result: {result}"));
+
+    let mut number = 3;
+    while number != 0 {
+        reporter.message(&format!("{number}!"));
+        number -= 1;
+    }
+
+    for element in [10, 20, 30] {
+        reporter.message(&format!("element: {element}"));
+    }
+    Ok(())
+}
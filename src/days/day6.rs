@@ -0,0 +1,7 @@
+use super::Day;
+
+// Day 6: ownership, borrowing, scopes, slices, strings and Option
+pub static DAY: Day = Day {
+    number: 6,
+    title: "Ownership, references and slices",
+};
@@ -0,0 +1,7 @@
+use super::Day;
+
+// Day 7: lifetimes and 'static
+pub static DAY: Day = Day {
+    number: 7,
+    title: "Lifetimes",
+};
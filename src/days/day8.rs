@@ -0,0 +1,7 @@
+use super::Day;
+
+// Day 8: error handling with Result and `?`
+pub static DAY: Day = Day {
+    number: 8,
+    title: "Error handling",
+};
@@ -0,0 +1,7 @@
+use super::Day;
+
+// Day 9: who owns what in a Vec and a HashMap
+pub static DAY: Day = Day {
+    number: 9,
+    title: "Collections",
+};
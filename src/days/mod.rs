@@ -0,0 +1,80 @@
+use crate::lesson::LessonDef;
+use crate::registry;
+
+#[cfg(feature = "lessons-basics")]
+pub mod day1;
+#[cfg(feature = "lessons-basics")]
+pub mod day2;
+#[cfg(feature = "lessons-basics")]
+pub mod day3;
+#[cfg(feature = "lessons-basics")]
+pub mod day4;
+#[cfg(feature = "lessons-basics")]
+pub mod day5;
+pub mod day6;
+#[cfg(feature = "lessons-ownership")]
+pub mod day7;
+#[cfg(feature = "lessons-ownership")]
+pub mod day8;
+#[cfg(feature = "lessons-collections")]
+pub mod day9;
+#[cfg(feature = "lessons-ownership")]
+pub mod day10;
+#[cfg(feature = "lessons-ownership")]
+pub mod day11;
+#[cfg(feature = "lessons-concurrency")]
+pub mod day12;
+#[cfg(feature = "lessons-ownership")]
+pub mod day13;
+#[cfg(feature = "lessons-ownership")]
+pub mod day14;
+
+// One day of the series, its lessons are the ones whose module is `TAUGHT` on it
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+}
+
+impl Day {
+    // The day's lessons, in the order they are taught
+    pub fn lessons(&self) -> impl Iterator<Item = &'static LessonDef> {
+        let number = self.number;
+        registry::MODULES.iter().filter(move |(taught, _)| taught.day == number).flat_map(|(_, lessons)| lessons.iter())
+    }
+}
+
+// Every day whose lessons are compiled in, in order
+pub static DAYS: &[&Day] = &[
+    #[cfg(feature = "lessons-basics")]
+    &day1::DAY,
+    #[cfg(feature = "lessons-basics")]
+    &day2::DAY,
+    #[cfg(feature = "lessons-basics")]
+    &day3::DAY,
+    #[cfg(feature = "lessons-basics")]
+    &day4::DAY,
+    #[cfg(feature = "lessons-basics")]
+    &day5::DAY,
+    &day6::DAY,
+    #[cfg(feature = "lessons-ownership")]
+    &day7::DAY,
+    #[cfg(feature = "lessons-ownership")]
+    &day8::DAY,
+    #[cfg(feature = "lessons-collections")]
+    &day9::DAY,
+    #[cfg(feature = "lessons-ownership")]
+    &day10::DAY,
+    #[cfg(feature = "lessons-ownership")]
+    &day11::DAY,
+    #[cfg(feature = "lessons-concurrency")]
+    &day12::DAY,
+    #[cfg(feature = "lessons-ownership")]
+    &day13::DAY,
+    #[cfg(feature = "lessons-ownership")]
+    &day14::DAY,
+];
+
+// Looks up a day by its number
+pub fn day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().copied().find(|day| day.number == number)
+}
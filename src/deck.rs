@@ -0,0 +1,310 @@
+use std::fs;
+use std::path::Path;
+
+use crate::error::LessonError;
+use crate::json::Json;
+use crate::quiz::{CompileError, Question, BLANK};
+
+// Quiz decks, question sets that aren't built in, for `quiz --deck`. A deck is a JSON file
+//
+//     {
+//         "title": "Week 2: borrowing",
+//         "questions": [
+//             {"id": "w2-move", "topic": "moves", "type": "true_false", "prompt": "`let b = a;` moves a String.",
+//              "answer": true, "explanation": "Strings aren't Copy, assigning one moves it."}
+//         ]
+//     }
+//
+// or a CSV file with a header row naming the same fields, one question per row. Lists in CSV are separated by
+// `|`, quoted cells may span lines, which is handy for code.
+//
+// Every question has an `id`, a `topic`, a `type` and an `explanation`, shown after answering. `title` is optional,
+// and so is a question's `lesson`, the lesson that covers it, whose demo runs after a wrong answer. By type:
+//   multiple_choice  `prompt`, `choices` and `answer`, the index of the right choice counting from 0 or its text
+//   true_false       `prompt`, a statement, and `answer`, true or false
+//   predict_output   `code` and `output`, what it prints, one line per printed line
+//   fill_in_blank    `prompt`, `code` with a ___ in it and `answers`, everything accepted in the blank
+//   will_it_compile  `code`, `lesson`, which isn't optional here, and when it doesn't compile `error`, the
+//                    code rustc gives (e.g. E0382), and `message`, its error message
+//
+// Like the built-in questions, any text may use `$a`, `$b` and `$c` for variable names, `$n` and `$m` for numbers
+// and `$text` for a word, filled in differently every time the deck is asked.
+pub const TYPES: &[&str] = &["multiple_choice", "true_false", "predict_output", "fill_in_blank", "will_it_compile"];
+const FIELDS: &[&str] = &["id", "topic", "type", "prompt", "choices", "answer", "answers", "code", "output", "lesson", "error", "message", "explanation"];
+// Fields that are lists, split on `|` in CSV
+const LISTS: &[&str] = &["choices", "answers"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deck {
+    pub title: Option<String>,
+    pub questions: Vec<Question>,
+}
+
+impl Deck {
+    // A .csv file is read as CSV, anything else as JSON
+    pub fn load(path: &Path) -> Result<Self, LessonError> {
+        let text = fs::read_to_string(path).map_err(|err| LessonError::BadInput(format!("can't read the deck {}: {err}", path.display())))?;
+        let invalid = |err: LessonError| LessonError::BadInput(format!("{} is invalid: {err}", path.display()));
+        let csv = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+        let deck = if csv { Self::from_csv(&text) } else { Self::from_json(&text) }.map_err(invalid)?;
+        if deck.questions.is_empty() {
+            return Err(invalid(LessonError::BadInput("there are no questions in it".to_string())));
+        }
+        Ok(deck)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, LessonError> {
+        let json = Json::parse(text)?;
+        let bad = |message: &str| LessonError::BadInput(message.to_string());
+        json.as_object().ok_or_else(|| bad("a deck is an object with a `questions` array"))?;
+        let title = match json.get("title") {
+            None | Some(Json::Null) => None,
+            Some(title) => Some(title.as_str().ok_or_else(|| bad("`title` should be a string"))?.to_string()),
+        };
+        let records = json.get("questions").and_then(Json::as_array).ok_or_else(|| bad("a deck needs a `questions` array"))?;
+        let questions = records.iter().enumerate().map(|(index, record)| question(record).map_err(|problem| at_record(record, &format!("question {}", index + 1), problem)));
+        Ok(Self { title, questions: unique(questions.collect::<Result<_, _>>()?)? })
+    }
+
+    // The header row names the fields, see the top of this file
+    pub fn from_csv(text: &str) -> Result<Self, LessonError> {
+        let mut rows = csv_rows(text)?.into_iter();
+        let (_, header) = rows.next().ok_or_else(|| LessonError::BadInput("the file is empty, it needs a header row".to_string()))?;
+        if let Some(column) = header.iter().find(|column| !FIELDS.contains(&column.as_str())) {
+            return Err(LessonError::BadInput(format!("line 1: unknown column `{column}`, expected some of {}", FIELDS.join(", "))));
+        }
+        let mut questions = Vec::new();
+        for (line, cells) in rows {
+            if cells.iter().all(String::is_empty) {
+                continue;
+            }
+            if cells.len() > header.len() {
+                return Err(LessonError::BadInput(format!("line {line}: {} cells but only {} columns", cells.len(), header.len())));
+            }
+            // Empty cells are left out, as if the field wasn't there
+            let fields = header.iter().zip(cells).filter(|(_, cell)| !cell.is_empty()).map(|(column, cell)| {
+                let value = if LISTS.contains(&column.as_str()) {
+                    Json::Array(cell.split('|').map(|item| item.trim().into()).collect())
+                } else if column == "answer" {
+                    match (cell.trim(), cell.trim().parse::<u64>()) {
+                        ("true", _) => true.into(),
+                        ("false", _) => false.into(),
+                        (_, Ok(index)) => index.into(),
+                        _ => cell.into(),
+                    }
+                } else {
+                    cell.into()
+                };
+                (column.clone(), value)
+            });
+            let record = Json::Object(fields.collect());
+            questions.push(question(&record).map_err(|problem| at_record(&record, &format!("line {line}"), problem))?);
+        }
+        Ok(Self { title: None, questions: unique(questions)? })
+    }
+}
+
+// e.g. "question 3 (`w2-move`): needs a `choices` list"
+fn at_record(record: &Json, place: &str, problem: String) -> LessonError {
+    match record.get("id").and_then(Json::as_str) {
+        Some(id) => LessonError::BadInput(format!("{place} (`{id}`): {problem}")),
+        None => LessonError::BadInput(format!("{place}: {problem}")),
+    }
+}
+
+fn unique(questions: Vec<Question>) -> Result<Vec<Question>, LessonError> {
+    for (index, question) in questions.iter().enumerate() {
+        if questions[..index].iter().any(|earlier| earlier.id == question.id) {
+            return Err(LessonError::BadInput(format!("question {} (`{}`): there's already a question with that id", index + 1, question.id)));
+        }
+    }
+    Ok(questions)
+}
+
+fn question(record: &Json) -> Result<Question, String> {
+    let fields = record.as_object().ok_or("should be an object")?;
+    if let Some((key, _)) = fields.iter().find(|(key, _)| !FIELDS.contains(&key.as_str())) {
+        return Err(format!("unknown field `{key}`, expected some of {}", FIELDS.join(", ")));
+    }
+    let text = |key: &str| match record.get(key) {
+        None => Err(format!("needs a `{key}` string")),
+        Some(value) => value.as_str().filter(|text| !text.trim().is_empty()).ok_or(format!("`{key}` should be a string with something in it")),
+    };
+    let list = |key: &str| {
+        let items = record.get(key).and_then(Json::as_array).ok_or(format!("needs a `{key}` list"))?;
+        let items: Option<Vec<&str>> = items.iter().map(Json::as_str).collect();
+        match items {
+            Some(items) if !items.is_empty() => Ok(items),
+            _ => Err(format!("`{key}` should be a list of strings, at least one")),
+        }
+    };
+    let (id, topic, explanation) = (text("id")?, text("topic")?, text("explanation")?);
+    let kind = text("type")?;
+    let question = match kind {
+        "multiple_choice" => {
+            let choices = list("choices")?;
+            let answer = match record.get("answer") {
+                Some(Json::String(answer)) => choices.iter().position(|choice| choice == answer).ok_or(format!("`answer` \"{answer}\" isn't one of the choices"))?,
+                Some(answer) => match answer.as_u64() {
+                    Some(index) if (index as usize) < choices.len() => index as usize,
+                    _ => return Err(format!("`answer` should be the right choice's index, 0 to {}, or its text", choices.len() - 1)),
+                },
+                None => return Err("needs an `answer`, the right choice's index counting from 0 or its text".to_string()),
+            };
+            Ok(Question::multiple_choice(id, topic, text("prompt")?, &choices, answer, explanation))
+        }
+        "true_false" => {
+            let answer = record.get("answer").and_then(Json::as_bool).ok_or("needs an `answer` of true or false")?;
+            Ok(Question::true_false(id, topic, text("prompt")?, answer, explanation))
+        }
+        "predict_output" => Ok(Question::predict_output(id, topic, text("code")?, text("output")?, explanation)),
+        "fill_in_blank" => {
+            let code = text("code")?;
+            if !code.contains(BLANK) {
+                return Err(format!("`code` needs a {} where the answer goes", BLANK));
+            }
+            Ok(Question::fill_in_blank(id, topic, text("prompt")?, code, &list("answers")?, explanation))
+        }
+        "will_it_compile" => {
+            let error = match (record.get("error"), record.get("message")) {
+                (None, None) => None,
+                (Some(_), Some(_)) => Some(CompileError { code: text("error")?.to_string(), message: text("message")?.to_string() }),
+                (Some(_), None) => return Err("an `error` needs the `message` rustc gives with it".to_string()),
+                (None, Some(_)) => return Err("a `message` needs the `error` code it goes with".to_string()),
+            };
+            Ok(Question::will_it_compile(id, topic, text("code")?, error, text("lesson")?, explanation))
+        }
+        other => Err(format!("unknown type `{other}`, expected one of {}", TYPES.join(", "))),
+    }?;
+    match record.get("lesson") {
+        Some(_) => Ok(question.with_lesson(text("lesson")?)),
+        None => Ok(question),
+    }
+}
+
+// (line the row starts on, cells). Cells in double quotes may hold commas and newlines, `""` is a quote.
+fn csv_rows(text: &str) -> Result<Vec<(usize, Vec<String>)>, LessonError> {
+    let mut rows = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    while chars.peek().is_some() {
+        let start = line;
+        let mut cells = Vec::new();
+        let mut cell = String::new();
+        loop {
+            match chars.next() {
+                None => {
+                    cells.push(cell);
+                    break;
+                }
+                Some('\n') => {
+                    line += 1;
+                    cells.push(cell.trim_end_matches('\r').to_string());
+                    break;
+                }
+                Some(',') => cells.push(std::mem::take(&mut cell)),
+                Some('"') if cell.is_empty() => loop {
+                    match chars.next() {
+                        None => return Err(LessonError::BadInput(format!("line {start}: a quoted cell is missing its closing `\"`"))),
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            cell.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            cell.push(c);
+                        }
+                    }
+                },
+                Some(c) => cell.push(c),
+            }
+        }
+        rows.push((start, cells));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quiz::QuestionKind;
+
+    fn error(result: Result<Deck, LessonError>) -> String {
+        match result {
+            Err(LessonError::BadInput(message)) => message,
+            other => panic!("expected BadInput, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn json_deck_of_every_type() {
+        let deck = Deck::from_json(r#"{"title": "Week 2", "questions": [
+            {"id": "mc", "topic": "moves", "type": "multiple_choice", "prompt": "Which moves?", "choices": ["a", "b"], "answer": "b", "explanation": "e"},
+            {"id": "tf", "topic": "moves", "type": "true_false", "prompt": "It moves", "answer": true, "explanation": "e", "lesson": "take_ownership"},
+            {"id": "po", "topic": "moves", "type": "predict_output", "code": "println!(\"hi\");", "output": "hi", "explanation": "e"},
+            {"id": "fb", "topic": "borrowing", "type": "fill_in_blank", "prompt": "Fill it", "code": "fn f(s: ___)", "answers": ["&str"], "explanation": "e"},
+            {"id": "wc", "topic": "moves", "type": "will_it_compile", "code": "let b = a;", "lesson": "take_ownership", "error": "E0382", "message": "borrow of moved value", "explanation": "e"}
+        ]}"#).unwrap();
+        assert_eq!(deck.title.as_deref(), Some("Week 2"));
+        assert_eq!(deck.questions.iter().map(|question| question.id.as_str()).collect::<Vec<_>>(), ["mc", "tf", "po", "fb", "wc"]);
+        assert_eq!(deck.questions[0].kind, QuestionKind::MultipleChoice { choices: vec!["a".to_string(), "b".to_string()], answer: 1 });
+        assert_eq!(deck.questions[1].lesson.as_deref(), Some("take_ownership"));
+        let QuestionKind::WillItCompile { error: Some(error), .. } = &deck.questions[4].kind else { panic!("{:?}", deck.questions[4].kind) };
+        assert_eq!(error.code, "E0382");
+    }
+
+    #[test]
+    fn csv_deck_with_quoted_cells() {
+        let deck = Deck::from_csv("id,topic,type,prompt,choices,answer,code,output,explanation\r\n\
+            mc,moves,multiple_choice,\"Which one, then?\",a | b,0,,,\"Say \"\"a\"\"\"\n\
+            \n\
+            po,moves,predict_output,,,,\"let s = 1;\nprintln!(\"\"{s}\"\");\",1,e\n").unwrap();
+        assert_eq!(deck.title, None);
+        assert_eq!(deck.questions[0].prompt, "Which one, then?");
+        assert_eq!(deck.questions[0].explanation, "Say \"a\"");
+        assert_eq!(deck.questions[0].kind, QuestionKind::MultipleChoice { choices: vec!["a".to_string(), "b".to_string()], answer: 0 });
+        assert_eq!(deck.questions[1].kind, QuestionKind::PredictOutput { code: "let s = 1;\nprintln!(\"{s}\");".to_string(), output: "1".to_string() });
+    }
+
+    #[test]
+    fn json_errors_name_the_question() {
+        let message = error(Deck::from_json(r#"{"questions": [
+            {"id": "ok", "topic": "t", "type": "true_false", "prompt": "p", "answer": true, "explanation": "e"},
+            {"id": "w2-move", "topic": "t", "type": "multiple_choice", "prompt": "p", "answer": 0, "explanation": "e"}
+        ]}"#));
+        assert_eq!(message, "question 2 (`w2-move`): needs a `choices` list");
+        assert_eq!(error(Deck::from_json(r#"{"questions": [{"topic": "t"}]}"#)), "question 1: needs a `id` string");
+        assert_eq!(error(Deck::from_json("[]")), "a deck is an object with a `questions` array");
+        let message = error(Deck::from_json(r#"{"questions": [{"id": "x", "topic": "t", "type": "essay", "explanation": "e"}]}"#));
+        assert!(message.starts_with("question 1 (`x`): unknown type `essay`"), "{message}");
+    }
+
+    #[test]
+    fn csv_errors_name_the_line() {
+        let header = "id,topic,type,prompt,answer,explanation\n";
+        // The second question starts on line 4, the first one's prompt spans two lines
+        let text = format!("{header}a,t,true_false,\"two\nlines\",true,e\nb,t,true_false,p,maybe,e\n");
+        assert_eq!(error(Deck::from_csv(&text)), "line 4 (`b`): needs an `answer` of true or false");
+        assert_eq!(error(Deck::from_csv(&format!("{header}a,t,true_false,p,true,e,extra\n"))), "line 2: 7 cells but only 6 columns");
+        assert_eq!(error(Deck::from_csv(&format!("{header}a,t,true_false,\"open,true,e\n"))), "line 2: a quoted cell is missing its closing `\"`");
+        assert!(error(Deck::from_csv("id,colour\n")).starts_with("line 1: unknown column `colour`"));
+        assert_eq!(error(Deck::from_csv(&format!("{header}a,t,true_false,p,true,e\na,t,true_false,p,false,e\n"))), "question 2 (`a`): there's already a question with that id");
+    }
+
+    #[test]
+    fn load_picks_the_format_by_extension() {
+        let dir = std::env::temp_dir().join(format!("rust_day_6-test-{}-deck", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let csv = dir.join("deck.CSV");
+        fs::write(&csv, "id,topic,type,prompt,answer,explanation\na,t,true_false,p,true,e\n").unwrap();
+        assert_eq!(Deck::load(&csv).unwrap().questions.len(), 1);
+        let empty = dir.join("empty.json");
+        fs::write(&empty, r#"{"questions": []}"#).unwrap();
+        let message = error(Deck::load(&empty));
+        assert!(message.ends_with("is invalid: there are no questions in it"), "{message}");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
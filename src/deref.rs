@@ -0,0 +1,166 @@
+use std::ops::Deref;
+
+use crate::borrowing;
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::OwnershipEvent;
+use crate::report::Reporter;
+
+// A String derefs to a str: `impl Deref for String { type Target = str; }`. When a function wants a `&str` and
+// gets a `&String`, rustc inserts the `*` itself, as many times as it takes, which is deref coercion. That's why
+// `reference` takes `&str`: every `&String` still works, and so does a literal, a slice of a String or a
+// `&Box<String>`, where a `&String` parameter only takes the one.
+
+pub const TAUGHT: Taught = Taught { day: 13, order: 3 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(deref_coercion_demo {
+        name: "deref_coercion",
+        summary: "A &String passed where a &str is wanted is turned into one on the way in",
+        topics: &["borrowing", "traits"],
+        prerequisites: &["reference", "slicing"],
+        explanation: "`reference(&s5)` used to need a `&String`, so `reference(\"hello\")` was rejected (E0308): a literal is a `&str`. Taking `&str` instead takes both, because String implements `Deref<Target = str>` and rustc coerces `&String` to `&str` by calling `deref` for you. It chains, so a `&Box<String>` becomes a `&String` then a `&str`. Nothing is copied, the `&str` points at the String's own heap bytes. It's also what lets `s5.len()` find str's methods through the String.",
+    }),
+    lesson!(custom_deref_demo {
+        name: "custom_deref",
+        summary: "Implementing Deref on a newtype lets it be used like what it wraps",
+        topics: &["traits", "smart-pointers"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["deref_coercion", "box_basics"],
+        explanation: "A newtype `struct Username(String)` hides its String, so on its own none of String's methods work on it. `impl Deref for Username { type Target = String; }` hands out `&self.0`, and then `name.len()`, `reference(&name)` and `&name[..3]` all go through it: `&Username` coerces to `&String` and on to `&str`. Deref only gives out shared references, changing the String through it needs DerefMut as well (E0596), and leaving that out keeps the newtype read-only, its own methods decide how it may change.",
+    }),
+];
+
+const LITERAL_FOR_STRING: (&str, &str) = (
+    "fn reference(my_string: &String) {}\nreference(\"hello\");",
+    "error[E0308]: mismatched types\n --> src/main.rs:2:11\n  |\n2 | reference(\"hello\");\n  | --------- ^^^^^^^ expected `&String`, found `&str`\n  | |\n  | arguments to this function are incorrect",
+);
+
+// What `reference(&String)` rejected, then the &str version taking everything string-like
+pub fn deref_coercion_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let (code, error) = LITERAL_FOR_STRING;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    let s5 = String::from("world");
+    reporter.event(&OwnershipEvent::borrowed("s5", "my_string"));
+    reporter.output(&borrowing::reference(&s5)); // &String coerced to &str
+    reporter.output(&borrowing::reference("hello")); // Already a &str
+    reporter.event(&OwnershipEvent::borrowed("s5", "my_string"));
+    reporter.output(&borrowing::reference(&s5[1..4])); // A slice, a &str too
+
+    let boxed = Box::new(String::from("boxed"));
+    reporter.event(&OwnershipEvent::borrowed("boxed", "my_string"));
+    reporter.output(&borrowing::reference(&boxed)); // &Box<String> to &String to &str, two derefs
+    reporter.message("");
+
+    let coerced: &str = &s5; // What rustc does for the call, written out
+    let explicit: &str = s5.deref(); // The same, calling deref by hand
+    reporter.event(&OwnershipEvent::borrowed("s5", "coerced"));
+    reporter.event(&OwnershipEvent::borrowed("s5", "explicit"));
+    reporter.message(&format!("coerced and explicit point at s5's heap bytes: {}", coerced.as_ptr() == s5.as_ptr() && explicit.as_ptr() == s5.as_ptr()));
+    reporter.message(&format!("s5.len() is str's len, found through deref: {}", s5.len()));
+    Ok(())
+}
+
+// A name that derefs to the String inside, with no DerefMut, so it can't be changed through the deref
+pub struct Username(String);
+
+impl Username {
+    // Lowercased on the way in, which is why nothing else should be able to change it
+    pub fn new(name: &str) -> Self {
+        Username(name.to_lowercase())
+    }
+}
+
+impl Deref for Username {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        &self.0
+    }
+}
+
+const NO_DEREF_MUT: (&str, &str) = (
+    "let mut name = Username::new(\"ferris\");\nname.push_str(\"_the_crab\");",
+    "error[E0596]: cannot borrow data in dereference of `Username` as mutable\n --> src/main.rs:2:1\n  |\n2 | name.push_str(\"_the_crab\");\n  | ^^^^ cannot borrow as mutable",
+);
+
+// String's methods and reference() on a Username, the derefs spelled out, then the change Deref doesn't allow
+pub fn custom_deref_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let name = Username::new("Ferris");
+    reporter.message(&format!("name.len(): {}, name.to_uppercase(): {}", name.len(), name.to_uppercase())); // String's and str's methods, through deref
+    reporter.event(&OwnershipEvent::borrowed("name", "my_string"));
+    reporter.output(&borrowing::reference(&name)); // &Username to &String to &str
+    reporter.message(&format!("&name[..3]: {}", &name[..3]));
+
+    let string: &String = &name; // One deref, Username's
+    let text: &str = &name; // Two, Username's then String's
+    reporter.event(&OwnershipEvent::borrowed("name", "string"));
+    reporter.event(&OwnershipEvent::borrowed("name", "text"));
+    reporter.message(&format!("*name is a `String`: {:?}, &**name is a `&str`: {:?}", *name, &**name));
+    reporter.message(&format!("string and text are the same bytes: {}", string.as_ptr() == text.as_ptr()));
+    reporter.message("");
+
+    let (code, error) = NO_DEREF_MUT;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::DemoOutput;
+
+    #[test]
+    fn username_is_lowercased_and_derefs_to_its_string() {
+        let name = Username::new("FeRRis");
+        let string: &String = &name;
+        assert_eq!(string, "ferris");
+        assert_eq!(name.len(), 6);
+        assert_eq!(&name[1..], "erris");
+    }
+
+    #[test]
+    fn deref_coercion_demo_coerces_to_str() {
+        let mut output = DemoOutput::new();
+        deref_coercion_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0308]")));
+        assert_eq!(
+            output.messages[output.messages.len() - 7..],
+            ["world", "hello", "orl", "boxed", "", "coerced and explicit point at s5's heap bytes: true", "s5.len() is str's len, found through deref: 5"]
+        );
+        assert_eq!(output.events[2], OwnershipEvent::borrowed("boxed", "my_string"));
+    }
+
+    #[test]
+    fn custom_deref_demo_reaches_string_and_str_methods() {
+        let mut output = DemoOutput::new();
+        custom_deref_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages[..5],
+            [
+                "name.len(): 6, name.to_uppercase(): FERRIS",
+                "ferris",
+                "&name[..3]: fer",
+                "*name is a `String`: \"ferris\", &**name is a `&str`: \"ferris\"",
+                "string and text are the same bytes: true",
+            ]
+        );
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0596]")));
+        assert_eq!(
+            output.events,
+            [OwnershipEvent::borrowed("name", "my_string"), OwnershipEvent::borrowed("name", "string"), OwnershipEvent::borrowed("name", "text")]
+        );
+    }
+}
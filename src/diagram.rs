@@ -0,0 +1,121 @@
+use std::fmt;
+
+// The classic picture of a String or Vec: a box on the stack with its ptr, len and capacity, and an arrow to the
+// bytes (or elements) on the heap. The numbers are read from the real values, so a move shows as a second box
+// pointing at the same heap block and a clone as a new block.
+//
+//     s2
+//       ┌──────────┬─────┐
+//       │ ptr      │ ●───┼──▶ ┌───┬───┬───┬───┬───┐
+//       │ len      │   5 │    │ h │ e │ l │ l │ o │  heap block A
+//       │ capacity │   5 │    └───┴───┴───┴───┴───┘
+//       └──────────┴─────┘
+#[derive(Debug, Clone, Default)]
+pub struct Diagram {
+    slots: Vec<Slot>,
+}
+
+#[derive(Debug, Clone)]
+struct Slot {
+    name: String,
+    ptr: usize,
+    len: usize,
+    capacity: usize,
+    // What's in the first `len` places on the heap
+    cells: Vec<String>,
+    // Moved out of, the box is still there but can't be used
+    moved: bool,
+}
+
+// More heap cells than this are cut short with a …
+const MAX_CELLS: usize = 12;
+
+impl Diagram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(clippy::ptr_arg)] // Takes the String itself, a &str has no capacity to show
+    pub fn string(mut self, name: &str, value: &String) -> Self {
+        self.add(name, value.as_ptr() as usize, value.len(), value.capacity(), value.chars().flat_map(|c| cell(c, c.len_utf8())).collect());
+        self
+    }
+
+    #[allow(clippy::ptr_arg)] // Same as `string`, a slice has no capacity
+    pub fn vec<T: fmt::Display>(mut self, name: &str, value: &Vec<T>) -> Self {
+        self.add(name, value.as_ptr() as usize, value.len(), value.capacity(), value.iter().map(|item| item.to_string()).collect());
+        self
+    }
+
+    // `let to = from;` happened, `from`'s box is drawn as it was left, pointing at `to`'s heap block but unusable
+    pub fn moved(mut self, from: &str, to: &str) -> Self {
+        if let Some(slot) = self.slots.iter().find(|slot| slot.name == to) {
+            let moved = Slot { name: from.to_string(), moved: true, ..slot.clone() };
+            let index = self.slots.iter().position(|slot| slot.name == to).unwrap_or(0);
+            self.slots.insert(index, moved);
+        }
+        self
+    }
+
+    fn add(&mut self, name: &str, ptr: usize, len: usize, capacity: usize, cells: Vec<String>) {
+        self.slots.push(Slot { name: name.to_string(), ptr, len, capacity, cells, moved: false });
+    }
+
+    // The lines of the picture, each stack box next to the heap block it points at
+    pub fn render(&self) -> Vec<String> {
+        // Blocks are lettered in the order they come up, addresses change from run to run
+        let mut blocks: Vec<usize> = Vec::new();
+        let mut lines = Vec::new();
+        for slot in &self.slots {
+            let letter = match blocks.iter().position(|ptr| *ptr == slot.ptr) {
+                Some(index) => index,
+                None => {
+                    blocks.push(slot.ptr);
+                    blocks.len() - 1
+                }
+            };
+            let block = format!("heap block {}", (b'A' + (letter % 26) as u8) as char);
+            lines.push(if slot.moved { format!("{} (moved out of, can't be used)", slot.name) } else { slot.name.clone() });
+
+            let width = slot.len.max(slot.capacity).to_string().len().max(3);
+            let (len, capacity) = (format!("{:>width$}", slot.len), format!("{:>width$}", slot.capacity));
+            let arrow = format!("{:>width$}", "●──");
+            let (top, middle, bottom) = heap(slot);
+            lines.push(format!("  ┌──────────┬─{}─┐", "─".repeat(width)));
+            if slot.capacity == 0 {
+                // Nothing was allocated yet, the pointer is dangling and points at nothing
+                lines.push(format!("  │ ptr      │ {:>width$} │   nothing on the heap yet", "─"));
+                lines.push(format!("  │ len      │ {len} │"));
+                lines.push(format!("  │ capacity │ {capacity} │"));
+            } else {
+                lines.push(format!("  │ ptr      │ {arrow}─┼──▶ {top}"));
+                lines.push(format!("  │ len      │ {len} │    {middle}  {block}"));
+                lines.push(format!("  │ capacity │ {capacity} │    {bottom}"));
+            }
+            lines.push(format!("  └──────────┴─{}─┘", "─".repeat(width)));
+        }
+        lines
+    }
+}
+
+// The heap block's three lines, a cell per byte or element and empty cells for the unused capacity
+fn heap(slot: &Slot) -> (String, String, String) {
+    let mut cells: Vec<String> = slot.cells.clone();
+    cells.extend(std::iter::repeat_n(String::new(), slot.capacity.saturating_sub(slot.len)));
+    let more = cells.len() > MAX_CELLS;
+    cells.truncate(MAX_CELLS);
+    let widths: Vec<usize> = cells.iter().map(|cell| cell.chars().count().max(1)).collect();
+    let rule = |left: &str, join: &str, right: &str| {
+        let parts: Vec<String> = widths.iter().map(|width| "─".repeat(width + 2)).collect();
+        format!("{left}{}{right}", parts.join(join))
+    };
+    let middle: Vec<String> = cells.iter().zip(&widths).map(|(cell, width)| format!(" {cell:<width$} ")).collect();
+    let etc = if more { " …" } else { "" };
+    (rule("┌", "┬", "┐"), format!("│{}│{etc}", middle.join("│")), rule("└", "┴", "┘"))
+}
+
+// A character's bytes on the heap, itself in the first and a · in the rest when it takes more than one
+fn cell(c: char, bytes: usize) -> Vec<String> {
+    let first = if c == ' ' { "␣".to_string() } else { c.to_string() };
+    std::iter::once(first).chain(std::iter::repeat_n("·".to_string(), bytes - 1)).collect()
+}
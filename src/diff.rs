@@ -0,0 +1,197 @@
+// Line diffs for showing how an attempt differs from the solution, small files only since it's a plain
+// longest-common-subsequence table
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+impl<'a> Line<'a> {
+    pub fn text(&self) -> &'a str {
+        match self {
+            Line::Same(text) | Line::Removed(text) | Line::Added(text) => text,
+        }
+    }
+
+    // The character unified diffs put in front of the line
+    pub fn prefix(&self) -> char {
+        match self {
+            Line::Same(_) => ' ',
+            Line::Removed(_) => '-',
+            Line::Added(_) => '+',
+        }
+    }
+}
+
+// A run of changes with the unchanged lines around them, starts are 1-based like in `diff -u`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk<'a> {
+    pub old_start: usize,
+    pub old_len: usize,
+    pub new_start: usize,
+    pub new_len: usize,
+    pub lines: Vec<Line<'a>>,
+}
+
+impl Hunk<'_> {
+    // e.g. "@@ -3,7 +3,8 @@"
+    pub fn header(&self) -> String {
+        format!("@@ -{},{} +{},{} @@", self.old_start, self.old_len, self.new_start, self.new_len)
+    }
+}
+
+// Every line of `old` and `new`, with the fewest removals and additions that turn one into the other
+pub fn lines<'a>(old: &'a str, new: &'a str) -> Vec<Line<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // common[i][j] is how many lines old[i..] and new[j..] have in common
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] { common[i + 1][j + 1] + 1 } else { common[i + 1][j].max(common[i][j + 1]) };
+        }
+    }
+
+    let mut diff = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push(Line::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            diff.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            diff.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+    diff
+}
+
+// Groups a diff into hunks with `context` unchanged lines on either side, empty when nothing changed
+pub fn hunks<'a>(diff: &[Line<'a>], context: usize) -> Vec<Hunk<'a>> {
+    let changed: Vec<usize> = diff.iter().enumerate().filter(|(_, line)| !matches!(line, Line::Same(_))).map(|(index, _)| index).collect();
+    let mut hunks = Vec::new();
+    let mut next = 0;
+    while next < changed.len() {
+        // Changes with at most twice the context between them share a hunk, as in `diff -u`, so hunks never touch
+        let mut last = next;
+        while last + 1 < changed.len() && changed[last + 1] - changed[last] <= 2 * context + 1 {
+            last += 1;
+        }
+        let start = changed[next].saturating_sub(context);
+        let end = (changed[last] + context + 1).min(diff.len());
+
+        // Line numbers of the hunk's first line in each file
+        let before = &diff[..start];
+        let old_start = before.iter().filter(|line| !matches!(line, Line::Added(_))).count() + 1;
+        let new_start = before.iter().filter(|line| !matches!(line, Line::Removed(_))).count() + 1;
+        let lines = diff[start..end].to_vec();
+        hunks.push(Hunk {
+            old_start,
+            old_len: lines.iter().filter(|line| !matches!(line, Line::Added(_))).count(),
+            new_start,
+            new_len: lines.iter().filter(|line| !matches!(line, Line::Removed(_))).count(),
+            lines,
+        });
+        next = last + 1;
+    }
+    hunks
+}
+
+// What a changed line does to ownership, if anything: the changes worth a second look in an exercise diff
+pub fn ownership_note(line: &str) -> Option<&'static str> {
+    let code = line.split("//").next().unwrap_or("");
+    if code.contains("&mut ") {
+        Some("mutable borrow")
+    } else if code.contains(".clone()") {
+        Some("clone")
+    } else if code.contains("&'") {
+        Some("lifetime")
+    } else if code.contains("move |") || code.contains("move||") {
+        Some("move closure")
+    } else if borrows(code) {
+        Some("borrow")
+    } else {
+        None
+    }
+}
+
+// A `&` that takes a reference, not `&&`
+fn borrows(code: &str) -> bool {
+    let bytes = code.as_bytes();
+    bytes.iter().enumerate().any(|(i, &byte)| {
+        let next = bytes.get(i + 1).copied().unwrap_or(b' ');
+        let prev = if i == 0 { b' ' } else { bytes[i - 1] };
+        byte == b'&' && prev != b'&' && next != b'&' && (next.is_ascii_alphabetic() || next == b'[' || next == b'_')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_text_has_no_hunks() {
+        let diff = lines("a\nb\n", "a\nb\n");
+        assert_eq!(diff, [Line::Same("a"), Line::Same("b")]);
+        assert!(hunks(&diff, 3).is_empty());
+    }
+
+    #[test]
+    fn fewest_changes() {
+        let diff = lines("a\nb\nc\nd", "a\nx\nc\nd\ne");
+        assert_eq!(diff, [Line::Same("a"), Line::Removed("b"), Line::Added("x"), Line::Same("c"), Line::Same("d"), Line::Added("e")]);
+        let text: String = diff.iter().map(|line| format!("{}{}\n", line.prefix(), line.text())).collect();
+        assert_eq!(text, " a\n-b\n+x\n c\n d\n+e\n");
+    }
+
+    #[test]
+    fn empty_sides() {
+        assert_eq!(lines("", "a\nb"), [Line::Added("a"), Line::Added("b")]);
+        assert_eq!(lines("a", ""), [Line::Removed("a")]);
+    }
+
+    #[test]
+    fn hunks_keep_context_and_line_numbers() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n10\n11";
+        let diff = lines(old, new);
+        let hunks = hunks(&diff, 1);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].header(), "@@ -4,3 +4,3 @@");
+        assert_eq!(hunks[0].lines, [Line::Same("4"), Line::Removed("5"), Line::Added("five"), Line::Same("6")]);
+        assert_eq!(hunks[1].header(), "@@ -10,1 +10,2 @@");
+    }
+
+    #[test]
+    fn nearby_changes_share_a_hunk() {
+        let diff = lines("a\nb\nc\nd\ne", "A\nb\nc\nD\ne");
+        let hunks = hunks(&diff, 1);
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].header(), "@@ -1,5 +1,5 @@");
+    }
+
+    #[test]
+    fn changes_further_apart_get_their_own_hunks() {
+        let diff = lines("a\nb\nc\nd\ne\nf", "A\nb\nc\nd\ne\nF");
+        assert_eq!(hunks(&diff, 1).len(), 2);
+        assert_eq!(hunks(&diff, 2).len(), 1);
+    }
+
+    #[test]
+    fn ownership_notes() {
+        assert_eq!(ownership_note("    add(&mut v);"), Some("mutable borrow"));
+        assert_eq!(ownership_note("let t = s.clone();"), Some("clone"));
+        assert_eq!(ownership_note("fn longest<'a>(x: &'a str) -> &'a str {"), Some("lifetime"));
+        assert_eq!(ownership_note("thread::spawn(move || v.len());"), Some("move closure"));
+        assert_eq!(ownership_note("let r = &s;"), Some("borrow"));
+        assert_eq!(ownership_note("if a && b {"), None);
+        assert_eq!(ownership_note("let n = 1; // not &mut here"), None);
+    }
+}
@@ -0,0 +1,65 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+// Everything that can go wrong in the crate
+#[derive(Debug)]
+pub enum LessonError {
+    // Reading or writing a file or the terminal failed
+    Io(io::Error),
+    // Input from the user that doesn't make sense, e.g. an unknown flag
+    BadInput(String),
+    // A slice index that isn't on a char boundary or is past the end of the string
+    SliceBoundary { index: usize, len: usize },
+    // A quiz file that couldn't be parsed, `line` is 1-based
+    QuizParse { line: usize, message: String },
+    // A TOML file (the exercise manifest, the config) that couldn't be parsed, `line` is 1-based
+    TomlParse { line: usize, message: String },
+    // JSON that couldn't be parsed, e.g. a rustc diagnostic, `line` is 1-based
+    JsonParse { line: usize, message: String },
+    // No lesson is registered under this name
+    UnknownLesson(String),
+    // There's no day with this number
+    UnknownDay(u8),
+    // A lesson was run before the lessons it builds on
+    MissingPrerequisites { lesson: String, missing: Vec<String> },
+    // A lesson's demo panicked, with the panic message
+    Panicked(String),
+}
+
+impl fmt::Display for LessonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::BadInput(message) => write!(f, "{message}"),
+            Self::SliceBoundary { index, len } => {
+                write!(f, "byte index {index} is not a char boundary of a string of length {len}")
+            }
+            Self::QuizParse { line, message } => write!(f, "quiz line {line}: {message}"),
+            Self::TomlParse { line, message } => write!(f, "toml line {line}: {message}"),
+            Self::JsonParse { line, message } => write!(f, "json line {line}: {message}"),
+            Self::UnknownLesson(name) => write!(f, "no lesson named `{name}`"),
+            Self::UnknownDay(day) => write!(f, "no day {day}"),
+            Self::MissingPrerequisites { lesson, missing } => {
+                let verb = if missing.len() == 1 { "isn't" } else { "aren't" };
+                write!(f, "{lesson} builds on {}, which {verb} done yet", missing.join(" and "))
+            }
+            Self::Panicked(message) => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+impl Error for LessonError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LessonError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
@@ -0,0 +1,138 @@
+use crate::error::LessonError;
+use crate::json::{Json, ToJson};
+
+// Something the learner did, logged in order in the progress file so reports like `stats` can be worked out
+// from what actually happened rather than from running totals
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Event {
+    // Seconds since the Unix epoch
+    pub at: u64,
+    pub kind: EventKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    // A quiz question was answered, `took_ms` is how long that took when it was measured
+    Answered { question: String, topic: String, correct: bool, took_ms: Option<u64> },
+    // An exercise was checked, by `watch` on every save or by `verify`
+    Checked { exercise: String, topic: String, passed: usize, total: usize, done: bool },
+    // `hint` showed hint `number` of an exercise
+    HintShown { exercise: String, number: usize },
+    // A lesson's demo ran to the end
+    LessonCompleted { lesson: String },
+    // `flashcards` showed a card and the learner said whether they knew it
+    Flashcard { card: String, topic: String, knew: bool },
+}
+
+impl EventKind {
+    // How the event is tagged in the progress file
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::Answered { .. } => "answered",
+            EventKind::Checked { .. } => "checked",
+            EventKind::HintShown { .. } => "hint",
+            EventKind::LessonCompleted { .. } => "lesson",
+            EventKind::Flashcard { .. } => "flashcard",
+        }
+    }
+}
+
+impl Event {
+    pub fn from_json(json: &Json) -> Result<Self, LessonError> {
+        let name = json.get("event").and_then(Json::as_str).unwrap_or_default();
+        let bad = |what: &str| LessonError::BadInput(format!("a `{name}` event needs {what}"));
+        let text = |key: &str| json.get(key).and_then(Json::as_str).map(str::to_string).ok_or_else(|| bad(key));
+        let count = |key: &str| json.get(key).and_then(Json::as_u64).map(|n| n as usize).ok_or_else(|| bad(key));
+        let flag = |key: &str| json.get(key).and_then(Json::as_bool).ok_or_else(|| bad(key));
+        let kind = match name {
+            "answered" => EventKind::Answered {
+                question: text("question")?,
+                topic: text("topic")?,
+                correct: flag("correct")?,
+                took_ms: json.get("took_ms").and_then(Json::as_u64),
+            },
+            "checked" => EventKind::Checked {
+                exercise: text("exercise")?,
+                topic: text("topic")?,
+                passed: count("passed")?,
+                total: count("total")?,
+                done: flag("done")?,
+            },
+            "hint" => EventKind::HintShown { exercise: text("exercise")?, number: count("number")? },
+            "lesson" => EventKind::LessonCompleted { lesson: text("lesson")? },
+            "flashcard" => EventKind::Flashcard { card: text("card")?, topic: text("topic")?, knew: flag("knew")? },
+            other => return Err(LessonError::BadInput(format!("unknown event `{other}`"))),
+        };
+        Ok(Self { at: json.get("at").and_then(Json::as_u64).ok_or_else(|| bad("at"))?, kind })
+    }
+}
+
+// e.g. {"at": 1700000000, "event": "answered", "question": "moves-read-after-move", ...}
+impl ToJson for Event {
+    fn to_json(&self) -> Json {
+        let mut fields = vec![("at", self.at.into()), ("event", self.kind.name().into())];
+        match &self.kind {
+            EventKind::Answered { question, topic, correct, took_ms } => {
+                fields.extend([("question", question.into()), ("topic", topic.into()), ("correct", (*correct).into())]);
+                if let Some(took_ms) = took_ms {
+                    fields.push(("took_ms", (*took_ms).into()));
+                }
+            }
+            EventKind::Checked { exercise, topic, passed, total, done } => fields.extend([
+                ("exercise", exercise.into()),
+                ("topic", topic.into()),
+                ("passed", (*passed).into()),
+                ("total", (*total).into()),
+                ("done", (*done).into()),
+            ]),
+            EventKind::HintShown { exercise, number } => fields.extend([("exercise", exercise.into()), ("number", (*number).into())]),
+            EventKind::LessonCompleted { lesson } => fields.push(("lesson", lesson.into())),
+            EventKind::Flashcard { card, topic, knew } => fields.extend([("card", card.into()), ("topic", topic.into()), ("knew", (*knew).into())]),
+        }
+        Json::object(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn every_kind() -> Vec<Event> {
+        let kinds = [
+            EventKind::Answered { question: "moves-read-after-move".to_string(), topic: "moves".to_string(), correct: true, took_ms: Some(1500) },
+            EventKind::Answered { question: "moves-clone".to_string(), topic: "moves".to_string(), correct: false, took_ms: None },
+            EventKind::Checked { exercise: "move1".to_string(), topic: "moves".to_string(), passed: 2, total: 3, done: false },
+            EventKind::HintShown { exercise: "move1".to_string(), number: 1 },
+            EventKind::LessonCompleted { lesson: "slicing".to_string() },
+            EventKind::Flashcard { card: "box-size".to_string(), topic: "smart-pointers".to_string(), knew: true },
+        ];
+        kinds.into_iter().enumerate().map(|(index, kind)| Event { at: 1_700_000_000 + index as u64, kind }).collect()
+    }
+
+    #[test]
+    fn every_kind_round_trips_through_json() {
+        for event in every_kind() {
+            let text = event.to_json().to_string();
+            assert_eq!(Event::from_json(&Json::parse(&text).unwrap()).unwrap(), event, "{text}");
+        }
+    }
+
+    #[test]
+    fn an_untimed_answer_has_no_took_ms() {
+        let json = every_kind()[1].to_json();
+        assert!(json.get("took_ms").is_none());
+        assert_eq!(json.get("event").and_then(Json::as_str), Some("answered"));
+    }
+
+    #[test]
+    fn missing_fields_and_unknown_events_are_named() {
+        let error = |text: &str| match Event::from_json(&Json::parse(text).unwrap()) {
+            Err(LessonError::BadInput(message)) => message,
+            other => panic!("expected BadInput for {text}, got {other:?}"),
+        };
+        assert_eq!(error(r#"{"at": 1, "event": "hint", "exercise": "move1"}"#), "a `hint` event needs number");
+        assert_eq!(error(r#"{"event": "lesson", "lesson": "slicing"}"#), "a `lesson` event needs at");
+        assert_eq!(error(r#"{"at": 1, "event": "checked", "exercise": "move1", "topic": "moves", "passed": "2", "total": 3, "done": false}"#), "a `checked` event needs passed");
+        assert_eq!(error(r#"{"at": 1, "event": "sneezed"}"#), "unknown event `sneezed`");
+    }
+}
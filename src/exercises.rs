@@ -0,0 +1,659 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config;
+use crate::error::LessonError;
+use crate::json::{Json, ToJson};
+use crate::lesson::Difficulty;
+use crate::shuffle;
+use crate::toml;
+
+// Left in every exercise until the learner deletes it, so passing by accident doesn't count as done
+pub const NOT_DONE_MARKER: &str = "// I AM NOT DONE";
+
+// How an exercise is checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    // Compiles and runs `main`, which must exit successfully
+    Compile,
+    // Compiles with `--test` and runs the tests
+    Test,
+    // Must not compile, and rustc has to report the exercise's expected error code
+    CompileFail,
+}
+
+// How long an exercise's binary may run before it's killed, so an endless loop doesn't hang `check` and `watch`
+pub const RUN_TIME_LIMIT: Duration = Duration::from_secs(10);
+
+// The manifest of the exercises that come with the crate
+pub const BUILTIN_MANIFEST: &str = include_str!("exercises/exercises.toml");
+
+// The skeleton of every built-in exercise by path, built into the binary so `reset` can put it back
+static SKELETONS: &[(&str, &str)] = &[
+    ("move1.rs", include_str!("exercises/move1.rs")),
+    ("move2.rs", include_str!("exercises/move2.rs")),
+    ("borrow1.rs", include_str!("exercises/borrow1.rs")),
+    ("borrow2.rs", include_str!("exercises/borrow2.rs")),
+    ("borrow3.rs", include_str!("exercises/borrow3.rs")),
+    ("slices1.rs", include_str!("exercises/slices1.rs")),
+    ("slices2.rs", include_str!("exercises/slices2.rs")),
+    ("fail_move.rs", include_str!("exercises/fail_move.rs")),
+    ("fail_mut_borrow.rs", include_str!("exercises/fail_mut_borrow.rs")),
+    ("fail_dangle.rs", include_str!("exercises/fail_dangle.rs")),
+];
+
+// The reference solution of every built-in exercise by path, for `solution`
+static SOLUTIONS: &[(&str, &str)] = &[
+    ("move1.rs", include_str!("exercises/solutions/move1.rs")),
+    ("move2.rs", include_str!("exercises/solutions/move2.rs")),
+    ("borrow1.rs", include_str!("exercises/solutions/borrow1.rs")),
+    ("borrow2.rs", include_str!("exercises/solutions/borrow2.rs")),
+    ("borrow3.rs", include_str!("exercises/solutions/borrow3.rs")),
+    ("slices1.rs", include_str!("exercises/solutions/slices1.rs")),
+    ("slices2.rs", include_str!("exercises/solutions/slices2.rs")),
+    ("fail_move.rs", include_str!("exercises/solutions/fail_move.rs")),
+    ("fail_mut_borrow.rs", include_str!("exercises/solutions/fail_mut_borrow.rs")),
+    ("fail_dangle.rs", include_str!("exercises/solutions/fail_dangle.rs")),
+];
+
+// One `[[exercises]]` entry of an exercises.toml
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExerciseInfo {
+    pub name: String,
+    // Relative to the exercises directory
+    pub path: String,
+    pub topic: String,
+    pub mode: Mode,
+    pub difficulty: Difficulty,
+    // For CompileFail, the error code rustc has to report, e.g. "E0382"
+    pub error: Option<String>,
+    // Shown one at a time by `hint`, from a nudge to something close to the solution
+    pub hints: Vec<String>,
+    // A file with the reference solution, relative to the exercises directory
+    pub solution: Option<String>,
+}
+
+// How much a hint gives away
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintTier {
+    // Points at where to look
+    Nudge,
+    // Explains the rule involved
+    Concept,
+    // Says more or less what to change
+    NearSolution,
+}
+
+impl HintTier {
+    // The tier of hint `number` (counting from 1) out of `total`: the first is a nudge, the last is close to
+    // the solution and anything in between explains the concept
+    pub fn of(number: usize, total: usize) -> Self {
+        if number <= 1 {
+            Self::Nudge
+        } else if number >= total {
+            Self::NearSolution
+        } else {
+            Self::Concept
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Nudge => "nudge",
+            Self::Concept => "concept",
+            Self::NearSolution => "near-solution",
+        }
+    }
+}
+
+// The keys an `[[exercises]]` entry may have, anything else is probably a typo
+const MANIFEST_KEYS: &[&str] = &["name", "path", "topic", "mode", "difficulty", "error", "hints", "solution"];
+
+impl ExerciseInfo {
+    // Every exercise in a manifest, in order. Every problem with every entry is reported at once, one per
+    // line, so fixing a hand-written manifest doesn't take a round trip per typo.
+    pub fn parse_manifest(text: &str) -> Result<Vec<Self>, LessonError> {
+        let manifest = toml::parse(text)?;
+        let entries = manifest.tables("exercises");
+        if entries.is_empty() {
+            return Err(LessonError::BadInput("no exercises, each one needs an `[[exercises]]` entry".to_string()));
+        }
+
+        let mut exercises: Vec<Self> = Vec::new();
+        let mut problems = Vec::new();
+        for (index, table) in entries.into_iter().enumerate() {
+            let label = match table.str("name") {
+                Some(name) => format!("exercise {} (`{name}`)", index + 1),
+                None => format!("exercise {}", index + 1),
+            };
+            match Self::from_table(table) {
+                Ok(info) if exercises.iter().any(|exercise| exercise.name == info.name) => {
+                    problems.push(format!("{label}: there's already an exercise named {}", info.name));
+                }
+                Ok(info) => exercises.push(info),
+                Err(entry_problems) => problems.extend(entry_problems.into_iter().map(|problem| format!("{label}: {problem}"))),
+            }
+        }
+        if problems.is_empty() {
+            Ok(exercises)
+        } else {
+            Err(problem_list(problems))
+        }
+    }
+
+    // One entry, or everything that's wrong with it
+    fn from_table(table: &toml::Table) -> Result<Self, Vec<String>> {
+        let mut problems = Vec::new();
+        for (key, _) in &table.0 {
+            if !MANIFEST_KEYS.contains(&key.as_str()) {
+                problems.push(format!("unknown key `{key}`, expected one of {}", MANIFEST_KEYS.join(", ")));
+            }
+        }
+        let mut field = |key: &str, required: bool| match table.get(key) {
+            None if !required => None,
+            None => {
+                problems.push(format!("needs a `{key}` string"));
+                None
+            }
+            Some(_) => {
+                let value = table.str(key).map(str::to_string);
+                if value.is_none() {
+                    problems.push(format!("`{key}` should be a string in quotes"));
+                }
+                value
+            }
+        };
+        let name = field("name", true);
+        let path = field("path", true);
+        let topic = field("topic", true);
+        let mode = field("mode", true);
+        let difficulty = field("difficulty", false);
+        let error = field("error", false);
+        let solution = field("solution", false);
+
+        // The name becomes the binary's file name under the build dir, so it has to stay a single file name in there
+        let name = name.filter(|name| {
+            let plain = !name.contains(['/', '\\']) && !matches!(name.as_str(), "" | "." | "..");
+            if !plain {
+                problems.push(format!("`name` \"{name}\" should be a plain name, without `/`, `\\` or `..`"));
+            }
+            plain
+        });
+        let mode = mode.and_then(|mode| match mode.as_str() {
+            "compile" => Some(Mode::Compile),
+            "test" => Some(Mode::Test),
+            "compile-fail" => Some(Mode::CompileFail),
+            other => {
+                problems.push(format!("mode `{other}` should be compile, test or compile-fail"));
+                None
+            }
+        });
+        if mode == Some(Mode::CompileFail) && error.is_none() {
+            problems.push("mode compile-fail needs the `error` code to fail with, e.g. error = \"E0382\"".to_string());
+        }
+        let difficulty = match difficulty.map(|difficulty| difficulty.parse::<Difficulty>()).transpose() {
+            Ok(difficulty) => difficulty.unwrap_or_default(),
+            Err(err) => {
+                problems.push(err.to_string());
+                Difficulty::default()
+            }
+        };
+        let hints = match table.get("hints") {
+            None => Vec::new(),
+            Some(_) => match table.strings("hints") {
+                Some(hints) => hints.into_iter().map(str::to_string).collect(),
+                None => {
+                    problems.push("`hints` should be a list of strings".to_string());
+                    Vec::new()
+                }
+            },
+        };
+
+        match (name, path, topic, mode) {
+            (Some(name), Some(path), Some(topic), Some(mode)) if problems.is_empty() => {
+                Ok(Self { name, path, topic, mode, difficulty, error, hints, solution })
+            }
+            _ => Err(problems),
+        }
+    }
+}
+
+// One problem as it is, several as an indented list under a count
+fn problem_list(mut problems: Vec<String>) -> LessonError {
+    match problems.len() {
+        1 => LessonError::BadInput(problems.remove(0)),
+        count => LessonError::BadInput(format!("{count} problems\n  {}", problems.join("\n  "))),
+    }
+}
+
+// A small Rust file under the exercises directory that the learner fixes, following its `// TODO` comments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exercise {
+    pub info: ExerciseInfo,
+    // The file as it ships, only known for the built-in exercises
+    pub skeleton: Option<&'static str>,
+    // The reference solution of a built-in exercise
+    pub solution: Option<&'static str>,
+}
+
+// One step towards passing, e.g. "compiles" or "test two_words"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub name: String,
+    pub passed: bool,
+}
+
+impl Checkpoint {
+    fn new(name: impl Into<String>, passed: bool) -> Self {
+        Self { name: name.into(), passed }
+    }
+}
+
+// What checking an exercise found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    // Whether it compiled and then ran or passed its tests
+    pub passed: bool,
+    // Still has the NOT_DONE_MARKER in it
+    pub marked: bool,
+    // What rustc and the program printed
+    pub output: String,
+    // Partial credit: compiling, then running or each test passing
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+impl CheckResult {
+    // Passing and unmarked, time to move on
+    pub fn done(&self) -> bool {
+        self.passed && !self.marked
+    }
+
+    // (checkpoints passed, checkpoints)
+    pub fn score(&self) -> (usize, usize) {
+        (self.checkpoints.iter().filter(|checkpoint| checkpoint.passed).count(), self.checkpoints.len())
+    }
+}
+
+// Where the learner's copies of the exercises live, `exercises/` in the current directory unless
+// RUST_DAY_6_EXERCISES says otherwise. Each file is written from its skeleton the first time it's checked.
+pub fn dir() -> PathBuf {
+    std::env::var_os("RUST_DAY_6_EXERCISES").map_or_else(|| PathBuf::from("exercises"), PathBuf::from)
+}
+
+// The exercises in order, together with the directory holding the learner's copies
+#[derive(Debug, Clone)]
+pub struct ExerciseSet {
+    dir: PathBuf,
+    exercises: Vec<Exercise>,
+}
+
+impl ExerciseSet {
+    pub fn new(dir: impl Into<PathBuf>, exercises: Vec<Exercise>) -> Self {
+        Self { dir: dir.into(), exercises }
+    }
+
+    // The exercises in `dir()`, see `open_in`
+    pub fn open() -> Result<Self, LessonError> {
+        Self::open_in(dir())
+    }
+
+    // The exercises in `dir`, as described by its exercises.toml, or the ones that come with the crate when
+    // there isn't one
+    pub fn open_in(dir: impl Into<PathBuf>) -> Result<Self, LessonError> {
+        let dir = dir.into();
+        let manifest = dir.join("exercises.toml");
+        if !manifest.exists() {
+            let exercises = ExerciseInfo::parse_manifest(BUILTIN_MANIFEST)?
+                .into_iter()
+                .map(|info| {
+                    let builtin = |table: &[(&str, &'static str)]| table.iter().find(|(path, _)| *path == info.path).map(|(_, text)| *text);
+                    Exercise { skeleton: builtin(SKELETONS), solution: builtin(SOLUTIONS), info }
+                })
+                .collect();
+            return Ok(Self::new(dir, exercises));
+        }
+
+        let invalid = |err: LessonError| LessonError::BadInput(format!("{} is invalid: {err}", manifest.display()));
+        let text = fs::read_to_string(&manifest)?;
+        let infos = ExerciseInfo::parse_manifest(&text).map_err(invalid)?;
+        // Custom exercises have no skeleton to start from, so their files have to be there already
+        let missing: Vec<String> = infos
+            .iter()
+            .filter(|info| !dir.join(&info.path).is_file())
+            .map(|info| format!("{}: {} doesn't exist", info.name, dir.join(&info.path).display()))
+            .collect();
+        if !missing.is_empty() {
+            return Err(invalid(problem_list(missing)));
+        }
+        let exercises = infos.into_iter().map(|info| Exercise { info, skeleton: None, solution: None }).collect();
+        Ok(Self::new(dir, exercises))
+    }
+
+    // Like `open`, also writing out the skeleton of any exercise the learner doesn't have a copy of yet
+    pub fn load() -> Result<Self, LessonError> {
+        Self::load_in(dir())
+    }
+
+    // Like `open_in`, also writing out missing skeletons
+    pub fn load_in(dir: impl Into<PathBuf>) -> Result<Self, LessonError> {
+        let set = Self::open_in(dir)?;
+        for exercise in &set.exercises {
+            exercise.start(&set.dir)?;
+        }
+        Ok(set)
+    }
+
+    // Puts the exercises in a random order, the same for the same seed
+    pub fn shuffle(&mut self, seed: u64) {
+        shuffle::shuffle(&mut self.exercises, seed);
+    }
+
+    // Keeps only the exercises tagged `difficulty`
+    pub fn retain_difficulty(&mut self, difficulty: Difficulty) {
+        self.exercises.retain(|exercise| exercise.info.difficulty == difficulty);
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Exercise> {
+        self.exercises.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.exercises.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.exercises.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Exercise> {
+        self.exercises.get(index)
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Exercise> {
+        self.exercises.iter().find(|exercise| exercise.info.name == name)
+    }
+
+    // Like `find`, but an unknown name is an error
+    pub fn get_named(&self, name: &str) -> Result<&Exercise, LessonError> {
+        self.find(name).ok_or_else(|| LessonError::BadInput(format!("no exercise named `{name}`")))
+    }
+
+    pub fn check(&self, exercise: &Exercise) -> Result<CheckResult, LessonError> {
+        exercise.check(&self.dir)
+    }
+
+    pub fn reset(&self, exercise: &Exercise) -> Result<(), LessonError> {
+        exercise.reset(&self.dir)
+    }
+
+    // The first exercise that isn't done yet, checking each in order
+    pub fn current(&self) -> Result<Option<&Exercise>, LessonError> {
+        for exercise in &self.exercises {
+            if !self.check(exercise)?.done() {
+                return Ok(Some(exercise));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Exercise {
+    pub fn file(&self, dir: &Path) -> PathBuf {
+        dir.join(&self.info.path)
+    }
+
+    // Puts the original skeleton back, throwing away the learner's changes
+    pub fn reset(&self, dir: &Path) -> Result<(), LessonError> {
+        let Some(skeleton) = self.skeleton else {
+            return Err(LessonError::BadInput(format!("{} isn't built in, there's no original copy to restore", self.info.name)));
+        };
+        let file = self.file(dir);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(file, skeleton)?;
+        Ok(())
+    }
+
+    // The reference solution, built in or from the file the manifest's `solution` names
+    pub fn solution(&self, dir: &Path) -> Result<String, LessonError> {
+        if let Some(solution) = self.solution {
+            return Ok(solution.to_string());
+        }
+        let Some(path) = &self.info.solution else {
+            return Err(LessonError::BadInput(format!("{} has no solution", self.info.name)));
+        };
+        let file = dir.join(path);
+        fs::read_to_string(&file).map_err(|err| LessonError::BadInput(format!("can't read {}: {err}", file.display())))
+    }
+
+    // Writes the skeleton for the learner to work on, unless they already have a copy
+    pub fn start(&self, dir: &Path) -> Result<(), LessonError> {
+        if self.file(dir).exists() {
+            return Ok(());
+        }
+        match self.skeleton {
+            Some(_) => self.reset(dir),
+            None => Err(LessonError::BadInput(format!("{} is missing", self.file(dir).display()))),
+        }
+    }
+
+    // Compiles the exercise with rustc and runs the result
+    pub fn check(&self, dir: &Path) -> Result<CheckResult, LessonError> {
+        self.start(dir)?;
+        let file = self.file(dir);
+        let source = fs::read_to_string(&file)
+            .map_err(|err| LessonError::BadInput(format!("can't read {}: {err}", file.display())))?;
+        let marked = source.lines().any(|line| line.trim() == NOT_DONE_MARKER);
+
+        let binary = config::build_dir("exercises")?.join(&self.info.name);
+
+        let mut rustc = Command::new(std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()));
+        rustc.args(["--edition", "2021", "--color", "never", "-o"]).arg(&binary).arg(&file);
+        if self.info.mode == Mode::Test {
+            rustc.arg("--test");
+        }
+        let compiled = rustc.output().map_err(|err| LessonError::BadInput(format!("couldn't run rustc: {err}")))?;
+        if self.info.mode == Mode::CompileFail {
+            let error = self.info.error.as_deref().unwrap_or_default();
+            let stderr = String::from_utf8_lossy(&compiled.stderr).into_owned();
+            let (passed, output) = if compiled.status.success() {
+                (false, format!("It compiled, but it should fail with {error}."))
+            } else if stderr.contains(&format!("error[{error}]")) {
+                (true, stderr)
+            } else {
+                (false, format!("{stderr}\nIt fails, but not with {error}."))
+            };
+            let checkpoints = vec![Checkpoint::new(format!("fails with {error}"), passed)];
+            return Ok(CheckResult { passed, marked, output, checkpoints });
+        }
+
+        // Tests are found in the source so the number of checkpoints is the same whether it compiles or not
+        let tests = test_names(&source);
+        let compiles = compiled.status.success();
+        let mut checkpoints = vec![Checkpoint::new("compiles", compiles)];
+        if !compiles {
+            match self.info.mode {
+                Mode::Test => checkpoints.extend(tests.iter().map(|test| Checkpoint::new(format!("test {test}"), false))),
+                _ => checkpoints.push(Checkpoint::new("runs", false)),
+            }
+            let output = String::from_utf8_lossy(&compiled.stderr).into_owned();
+            return Ok(CheckResult { passed: false, marked, output, checkpoints });
+        }
+
+        let Some(ran) = output_within(&mut Command::new(&binary), RUN_TIME_LIMIT)? else {
+            let output = format!("Still running after {} seconds, so it was stopped. Is there a loop that never ends?", RUN_TIME_LIMIT.as_secs());
+            match self.info.mode {
+                Mode::Test => checkpoints.extend(tests.iter().map(|test| Checkpoint::new(format!("test {test}"), false))),
+                _ => checkpoints.push(Checkpoint::new("runs", false)),
+            }
+            return Ok(CheckResult { passed: false, marked, output, checkpoints });
+        };
+        let mut output = String::from_utf8_lossy(&ran.stdout).into_owned();
+        output.push_str(&String::from_utf8_lossy(&ran.stderr));
+        match self.info.mode {
+            Mode::Test => checkpoints.extend(tests.iter().map(|test| Checkpoint::new(format!("test {test}"), test_passed(&output, test)))),
+            _ => checkpoints.push(Checkpoint::new("runs", ran.status.success())),
+        }
+        Ok(CheckResult { passed: ran.status.success(), marked, output, checkpoints })
+    }
+}
+
+// Runs the command like `output()` does, but kills it and returns None if it's still going after `limit`
+fn output_within(command: &mut Command, limit: Duration) -> io::Result<Option<Output>> {
+    let mut child = command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    // Read on their own threads, a child that fills a pipe nobody reads would never finish
+    let read = |pipe: Option<Box<dyn Read + Send>>| {
+        thread::spawn(move || {
+            let mut bytes = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut bytes);
+            }
+            bytes
+        })
+    };
+    let stdout = read(child.stdout.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>));
+    let stderr = read(child.stderr.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>));
+
+    let deadline = Instant::now() + limit;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    Ok(Some(Output { status, stdout, stderr }))
+}
+
+// Whether libtest's output has the test passing, it prints `test tests::two_words ... ok` for each one that does
+fn test_passed(output: &str, test: &str) -> bool {
+    output.lines().any(|line| {
+        line.starts_with("test ")
+            && line.ends_with(" ... ok")
+            && (line.contains(&format!("::{test} ...")) || line.starts_with(&format!("test {test} ...")))
+    })
+}
+
+// The name of every function marked `#[test]`
+fn test_names(source: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut after_attribute = false;
+    for line in source.lines().map(str::trim) {
+        if line == "#[test]" {
+            after_attribute = true;
+        } else if after_attribute {
+            if let Some(rest) = line.strip_prefix("fn ") {
+                names.push(rest.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect());
+                after_attribute = false;
+            }
+        }
+    }
+    names
+}
+
+impl ToJson for CheckResult {
+    fn to_json(&self) -> Json {
+        let (score, total) = self.score();
+        let checkpoints = self
+            .checkpoints
+            .iter()
+            .map(|checkpoint| Json::object([("name", (&checkpoint.name).into()), ("passed", checkpoint.passed.into())]));
+        Json::object([
+            ("passed", self.passed.into()),
+            ("marked", self.marked.into()),
+            ("done", self.done().into()),
+            ("score", score.into()),
+            ("total", total.into()),
+            ("checkpoints", Json::array(checkpoints)),
+            ("output", (&self.output).into()),
+        ])
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_within_returns_what_the_command_printed() {
+        let ran = output_within(Command::new("sh").args(["-c", "echo out; echo err >&2"]), Duration::from_secs(10)).unwrap().unwrap();
+        assert!(ran.status.success());
+        assert_eq!(ran.stdout, b"out\n");
+        assert_eq!(ran.stderr, b"err\n");
+    }
+
+    #[test]
+    fn output_within_kills_a_command_that_runs_too_long() {
+        let started = Instant::now();
+        let ran = output_within(Command::new("sleep").arg("30"), Duration::from_millis(100)).unwrap();
+        assert!(ran.is_none());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn names_have_to_stay_inside_the_build_dir() {
+        let entry = |name: &str| format!("[[exercises]]\nname = \"{name}\"\npath = \"a.rs\"\ntopic = \"t\"\nmode = \"compile\"\n");
+        assert_eq!(ExerciseInfo::parse_manifest(&entry("move_semantics1")).unwrap()[0].name, "move_semantics1");
+        for name in ["../escape", "/tmp/abs", "a/b", "a\\\\b", "..", ""] {
+            match ExerciseInfo::parse_manifest(&entry(name)) {
+                Err(LessonError::BadInput(message)) => assert!(message.contains("should be a plain name"), "{name}: {message}"),
+                other => panic!("{name}: expected BadInput, got {other:?}"),
+            }
+        }
+    }
+
+    // The skeleton's instructions don't belong in the finished file
+    #[test]
+    fn solutions_have_no_todos() {
+        for (path, solution) in SOLUTIONS {
+            assert!(!solution.contains("TODO") && !solution.contains("I AM NOT DONE"), "solutions/{path} still has the skeleton's instructions");
+        }
+    }
+
+    #[test]
+    fn test_names_are_the_functions_after_each_test_attribute() {
+        let source = "fn helper() {}\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn two_words() {}\n\n    #[test]\n    #[should_panic]\n    fn empty_input() {}\n    fn not_a_test() {}\n}\n";
+        assert_eq!(test_names(source), ["two_words", "empty_input"]);
+        assert!(test_names("fn main() {}").is_empty());
+    }
+
+    #[test]
+    fn tests_pass_only_on_their_own_ok_line() {
+        let output = "running 3 tests\ntest tests::two_words ... ok\ntest tests::empty_input ... FAILED\ntest one_word ... ok\n";
+        assert!(test_passed(output, "two_words"));
+        assert!(test_passed(output, "one_word"));
+        assert!(!test_passed(output, "empty_input"));
+        assert!(!test_passed(output, "words"));
+        assert!(!test_passed(output, "missing"));
+    }
+
+    #[test]
+    fn score_counts_the_checkpoints_passed() {
+        let result = CheckResult {
+            passed: false,
+            marked: true,
+            output: String::new(),
+            checkpoints: vec![Checkpoint::new("compiles", true), Checkpoint::new("test two_words", true), Checkpoint::new("test empty_input", false)],
+        };
+        assert_eq!(result.score(), (2, 3));
+        let json = result.to_json();
+        assert_eq!(json.get("score").and_then(Json::as_u64), Some(2));
+        assert_eq!(json.get("total").and_then(Json::as_u64), Some(3));
+        assert_eq!(json.get("done").and_then(Json::as_bool), Some(false));
+    }
+}
@@ -0,0 +1,17 @@
+// borrow1.rs
+// `calculate_length` takes ownership of the String, so `s` can't be used after the call.
+// TODO: change `calculate_length` to borrow the String instead, and fix the call to match.
+// Then delete the `I AM NOT DONE` line to move on to the next exercise.
+
+// I AM NOT DONE
+
+fn calculate_length(s: String) -> usize {
+    s.len()
+}
+
+fn main() {
+    let s = String::from("hello");
+    let len = calculate_length(s);
+
+    println!("The length of '{s}' is {len}.");
+}
@@ -0,0 +1,17 @@
+// borrow2.rs
+// Only one mutable reference to a value can be in use at a time.
+// TODO: make this compile by fixing the borrow, the output should still be "hello, world!".
+// Then delete the `I AM NOT DONE` line to move on to the next exercise.
+
+// I AM NOT DONE
+
+fn main() {
+    let mut s = String::from("hello");
+
+    let first = &mut s;
+    let second = &mut s;
+    first.push_str(", world");
+    second.push('!');
+
+    println!("{s}");
+}
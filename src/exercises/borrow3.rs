@@ -0,0 +1,15 @@
+// borrow3.rs
+// While an immutable reference is in use, the value can't be changed through a mutable one.
+// TODO: make this compile by moving one line, so it prints the length before and after.
+// Then delete the `I AM NOT DONE` line to move on to the next exercise.
+
+// I AM NOT DONE
+
+fn main() {
+    let mut s = String::from("hello");
+
+    let before = &s;
+    s.push_str(" world");
+    println!("before: {}", before.len());
+    println!("after: {}", s.len());
+}
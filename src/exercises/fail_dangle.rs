@@ -0,0 +1,17 @@
+// fail_dangle.rs
+// The last one that compiles but shouldn't.
+// TODO: change `no_dangle` to return a reference to its local `s` (`-> &String` and `&s`), so rustc
+// rejects the program with E0106.
+// Then delete the `I AM NOT DONE` line to move on to the next exercise.
+
+// I AM NOT DONE
+
+fn no_dangle() -> String {
+    let s = String::from("hello");
+    s
+}
+
+fn main() {
+    let s = no_dangle();
+    println!("{s}");
+}
@@ -0,0 +1,13 @@
+// fail_move.rs
+// This one is the other way round: it compiles, and it shouldn't.
+// TODO: print `s1` after it has been moved into `s2`, so rustc rejects the program with E0382.
+// Then delete the `I AM NOT DONE` line to move on to the next exercise.
+
+// I AM NOT DONE
+
+fn main() {
+    let s1 = String::from("hello");
+    let s2 = s1;
+
+    println!("s2 = {s2}");
+}
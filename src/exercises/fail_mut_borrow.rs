@@ -0,0 +1,16 @@
+// fail_mut_borrow.rs
+// Another one that compiles but shouldn't.
+// TODO: take a second mutable reference `r2` to `s` while `r1` is still in use, so rustc rejects the
+// program with E0499.
+// Then delete the `I AM NOT DONE` line to move on to the next exercise.
+
+// I AM NOT DONE
+
+fn main() {
+    let mut s = String::from("hello");
+
+    let r1 = &mut s;
+    r1.push_str(", world");
+
+    println!("{r1}");
+}
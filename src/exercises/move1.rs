@@ -0,0 +1,14 @@
+// move1.rs
+// `s1` is moved into `s2`, so it can't be printed afterwards.
+// TODO: make this compile without removing either println!
+// Then delete the `I AM NOT DONE` line to move on to the next exercise.
+
+// I AM NOT DONE
+
+fn main() {
+    let s1 = String::from("hello");
+    let s2 = s1;
+
+    println!("s1 = {s1}");
+    println!("s2 = {s2}");
+}
@@ -0,0 +1,17 @@
+// move2.rs
+// Passing a String to a function moves it, just like assigning it to another variable.
+// TODO: make this compile so both words get printed, without changing `shout`.
+// Then delete the `I AM NOT DONE` line to move on to the next exercise.
+
+// I AM NOT DONE
+
+fn shout(word: String) -> String {
+    word.to_uppercase()
+}
+
+fn main() {
+    let word = String::from("ownership");
+    let loud = shout(word);
+
+    println!("{word} becomes {loud}");
+}
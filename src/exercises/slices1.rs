@@ -0,0 +1,34 @@
+// slices1.rs
+// `first_word` should return the first word of a string, without copying it.
+// TODO: make the tests pass by returning a slice of `s`.
+// Then delete the `I AM NOT DONE` line to move on to the next exercise.
+
+// I AM NOT DONE
+
+fn first_word(s: &str) -> &str {
+    todo!("return the text before the first space, or all of `s` if there is none")
+}
+
+fn main() {
+    println!("{}", first_word("hello world"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_words() {
+        assert_eq!(first_word("hello world"), "hello");
+    }
+
+    #[test]
+    fn one_word() {
+        assert_eq!(first_word("hello"), "hello");
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(first_word(""), "");
+    }
+}
@@ -0,0 +1,13 @@
+// borrow1.rs
+// `calculate_length` takes ownership of the String, so `s` can't be used after the call.
+
+fn calculate_length(s: &String) -> usize {
+    s.len()
+}
+
+fn main() {
+    let s = String::from("hello");
+    let len = calculate_length(&s);
+
+    println!("The length of '{s}' is {len}.");
+}
@@ -0,0 +1,13 @@
+// borrow2.rs
+// Only one mutable reference to a value can be in use at a time.
+
+fn main() {
+    let mut s = String::from("hello");
+
+    let first = &mut s;
+    first.push_str(", world");
+    let second = &mut s;
+    second.push('!');
+
+    println!("{s}");
+}
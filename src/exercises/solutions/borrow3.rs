@@ -0,0 +1,11 @@
+// borrow3.rs
+// While an immutable reference is in use, the value can't be changed through a mutable one.
+
+fn main() {
+    let mut s = String::from("hello");
+
+    let before = &s;
+    println!("before: {}", before.len());
+    s.push_str(" world");
+    println!("after: {}", s.len());
+}
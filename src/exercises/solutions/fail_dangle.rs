@@ -0,0 +1,12 @@
+// fail_dangle.rs
+// The last one that compiles but shouldn't.
+
+fn no_dangle() -> &String {
+    let s = String::from("hello");
+    &s
+}
+
+fn main() {
+    let s = no_dangle();
+    println!("{s}");
+}
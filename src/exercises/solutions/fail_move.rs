@@ -0,0 +1,10 @@
+// fail_move.rs
+// This one is the other way round: it compiles, and it shouldn't.
+
+fn main() {
+    let s1 = String::from("hello");
+    let s2 = s1;
+    println!("s1 = {s1}");
+
+    println!("s2 = {s2}");
+}
@@ -0,0 +1,13 @@
+// fail_mut_borrow.rs
+// Another one that compiles but shouldn't.
+
+fn main() {
+    let mut s = String::from("hello");
+
+    let r1 = &mut s;
+    let r2 = &mut s;
+    r1.push_str(", world");
+
+    println!("{r1}");
+    r2.push('!');
+}
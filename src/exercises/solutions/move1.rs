@@ -0,0 +1,10 @@
+// move1.rs
+// `s1` is moved into `s2`, so it can't be printed afterwards.
+
+fn main() {
+    let s1 = String::from("hello");
+    let s2 = s1.clone();
+
+    println!("s1 = {s1}");
+    println!("s2 = {s2}");
+}
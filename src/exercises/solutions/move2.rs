@@ -0,0 +1,13 @@
+// move2.rs
+// Passing a String to a function moves it, just like assigning it to another variable.
+
+fn shout(word: String) -> String {
+    word.to_uppercase()
+}
+
+fn main() {
+    let word = String::from("ownership");
+    let loud = shout(word.clone());
+
+    println!("{word} becomes {loud}");
+}
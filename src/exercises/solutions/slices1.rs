@@ -0,0 +1,33 @@
+// slices1.rs
+// `first_word` should return the first word of a string, without copying it.
+
+fn first_word(s: &str) -> &str {
+    match s.find(' ') {
+        Some(index) => &s[..index],
+        None => s,
+    }
+}
+
+fn main() {
+    println!("{}", first_word("hello world"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_words() {
+        assert_eq!(first_word("hello world"), "hello");
+    }
+
+    #[test]
+    fn one_word() {
+        assert_eq!(first_word("hello"), "hello");
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(first_word(""), "");
+    }
+}
@@ -0,0 +1,35 @@
+// slices2.rs
+// Slices work on arrays and vectors too, not just strings.
+
+// Everything after the first element, or nothing when there isn't one
+fn tail(numbers: &[i32]) -> &[i32] {
+    if numbers.is_empty() {
+        return &[];
+    }
+    &numbers[1..]
+}
+
+fn main() {
+    println!("{:?}", tail(&[1, 2, 3]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn three_numbers() {
+        assert_eq!(tail(&[1, 2, 3]), &[2, 3]);
+    }
+
+    #[test]
+    fn vec() {
+        let numbers = vec![10, 20];
+        assert_eq!(tail(&numbers), &[20]);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(tail(&[]), &[] as &[i32]);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::json::{Json, ToJson};
+
+// What a rustc ownership error means and which lesson covers it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorExplanation {
+    pub code: &'static str,
+    pub title: &'static str,
+    // Name of the lesson that teaches the rule behind the error
+    pub lesson: &'static str,
+    // The smallest program that triggers the error
+    pub snippet: &'static str,
+    // The same program, fixed
+    pub fix: &'static str,
+    pub why: &'static str,
+    // The usual fix in one line, for `check` to suggest next to rustc's own message
+    pub pattern: &'static str,
+}
+
+pub static ERRORS: &[ErrorExplanation] = &[
+    ErrorExplanation {
+        code: "E0382",
+        title: "use of moved value",
+        lesson: "take_ownership",
+        snippet: "let s1 = String::from(\"hello\");\nlet s2 = s1;\nprintln!(\"{s1}\"); // s1 was moved to s2",
+        fix: "let s1 = String::from(\"hello\");\nlet s2 = s1.clone(); // or borrow it: let s2 = &s1;\nprintln!(\"{s1}\");",
+        why: "A value has only one owner. After `let s2 = s1;` the String belongs to s2, so s1 can't be used anymore. Clone it if you need two owners, or borrow it if you only need to read it.",
+        pattern: "Borrow with `&value` where you only read it, or `.clone()` where you really need a second owner.",
+    },
+    ErrorExplanation {
+        code: "E0499",
+        title: "cannot borrow as mutable more than once at a time",
+        lesson: "mutate_reference",
+        snippet: "let mut s = String::from(\"hello\");\nlet r1 = &mut s;\nlet r2 = &mut s;\nprintln!(\"{r1}, {r2}\");",
+        fix: "let mut s = String::from(\"hello\");\n{\n    let r1 = &mut s;\n    r1.push('!');\n} // r1 ends here\nlet r2 = &mut s;\nprintln!(\"{r2}\");",
+        why: "Only one mutable reference to a value may exist at a time, which rules out data races at compile time. End the first borrow before starting the second.",
+        pattern: "Let the first `&mut` borrow end (its last use, or a `{ }` block) before taking the next one.",
+    },
+    ErrorExplanation {
+        code: "E0502",
+        title: "cannot borrow as mutable because it is also borrowed as immutable",
+        lesson: "mutate_reference",
+        snippet: "let mut s = String::from(\"hello\");\nlet r1 = &s;\nlet r2 = &mut s;\nprintln!(\"{r1}, {r2}\");",
+        fix: "let mut s = String::from(\"hello\");\nlet r1 = &s;\nprintln!(\"{r1}\"); // last use of r1\nlet r2 = &mut s;\nprintln!(\"{r2}\");",
+        why: "In any scope you can have either one mutable reference or any number of immutable ones, never both. Finish using the immutable borrows before taking the mutable one.",
+        pattern: "Move the last use of the shared `&` borrows above the line that takes `&mut`.",
+    },
+    ErrorExplanation {
+        code: "E0596",
+        title: "cannot borrow as mutable, as it is not declared as mutable",
+        lesson: "mutate_reference",
+        snippet: "let s = String::from(\"hello\");\nlet r = &mut s;\nr.push_str(\", world\");",
+        fix: "let mut s = String::from(\"hello\");\nlet r = &mut s;\nr.push_str(\", world\");",
+        why: "A mutable borrow lets you change the value, so the value itself has to be mutable. Variables are immutable unless they're declared with `mut`.",
+        pattern: "Declare the variable with `let mut`, or a parameter as `&mut T` instead of `&T`.",
+    },
+    ErrorExplanation {
+        code: "E0505",
+        title: "cannot move out of a value because it is borrowed",
+        lesson: "reference",
+        snippet: "let s = String::from(\"hello\");\nlet r = &s;\nlet moved = s;\nprintln!(\"{r}\");",
+        fix: "let s = String::from(\"hello\");\nlet r = &s;\nprintln!(\"{r}\"); // last use of r\nlet moved = s;",
+        why: "A reference must always point at a valid value. Moving the value while it's borrowed would leave the reference pointing at nothing.",
+        pattern: "Use the reference one last time before the move, or move a `.clone()` instead.",
+    },
+    ErrorExplanation {
+        code: "E0597",
+        title: "borrowed value does not live long enough",
+        lesson: "reference",
+        snippet: "let r;\n{\n    let s = String::from(\"hello\");\n    r = &s;\n} // s is dropped here\nprintln!(\"{r}\");",
+        fix: "let s = String::from(\"hello\");\nlet r = &s;\nprintln!(\"{r}\"); // s outlives r",
+        why: "References must always be valid. s is dropped at the end of its scope, so a reference to it can't be used after that.",
+        pattern: "Declare the value in an outer scope so it outlives every reference to it.",
+    },
+    ErrorExplanation {
+        code: "E0106",
+        title: "missing lifetime specifier",
+        lesson: "reference",
+        snippet: "fn dangle() -> &String {\n    let s = String::from(\"hello\");\n    &s\n}",
+        fix: "fn no_dangle() -> String {\n    let s = String::from(\"hello\");\n    s // give ownership to the caller instead\n}",
+        why: "A function can't return a reference to its own local variable, the variable is dropped when the function returns and the reference would dangle. Return the owned value instead.",
+        pattern: "Return the owned value (e.g. `String` instead of `&String`), or take a reference parameter for the result to borrow from.",
+    },
+];
+
+// Looks up an error code, with or without the leading E, e.g. "E0382" or "0382"
+pub fn lookup(code: &str) -> Option<&'static ErrorExplanation> {
+    let code = code.trim().to_uppercase();
+    let code = if code.starts_with('E') { code } else { format!("E{code}") };
+    ERRORS.iter().find(|error| error.code == code)
+}
+
+impl ToJson for ErrorExplanation {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("code", self.code.into()),
+            ("title", self.title.into()),
+            ("lesson", self.lesson.into()),
+            ("snippet", self.snippet.into()),
+            ("fix", self.fix.into()),
+            ("why", self.why.into()),
+            ("pattern", self.pattern.into()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulator::Program;
+
+    #[test]
+    fn lookup_takes_the_code_with_or_without_its_e() {
+        for code in ["E0382", "e0382", "0382", " E0382 "] {
+            assert_eq!(lookup(code).map(|error| error.code), Some("E0382"), "{code:?}");
+        }
+        assert!(lookup("E0000").is_none());
+        assert!(lookup("").is_none());
+    }
+
+    #[test]
+    fn codes_are_unique() {
+        let mut codes: Vec<&str> = ERRORS.iter().map(|error| error.code).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), ERRORS.len());
+    }
+
+    #[cfg(feature = "lessons-ownership")]
+    #[test]
+    fn every_error_points_at_a_lesson() {
+        for error in ERRORS {
+            assert!(crate::registry::find(error.lesson).is_some(), "{} points at {}", error.code, error.lesson);
+        }
+    }
+
+    #[test]
+    fn the_simulator_agrees_with_each_snippet_and_fix() {
+        let mut checked = Vec::new();
+        for error in ERRORS {
+            let (Ok(snippet), Ok(fix)) = (Program::parse(error.snippet), Program::parse(error.fix)) else {
+                continue; // Not every snippet is something the simulator models
+            };
+            assert_eq!(snippet.check().err().and_then(|rejection| rejection.code), Some(error.code));
+            assert!(fix.check().is_ok(), "{}'s fix", error.code);
+            checked.push(error.code);
+        }
+        assert_eq!(checked, ["E0382", "E0502", "E0505", "E0597"]);
+    }
+
+    #[test]
+    fn explanations_as_json() {
+        let json = lookup("E0505").unwrap().to_json().to_string();
+        assert!(json.starts_with(r#"{"code":"E0505","title":"cannot move out of a value because it is borrowed","lesson":"reference""#), "{json}");
+    }
+}
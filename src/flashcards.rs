@@ -0,0 +1,55 @@
+use crate::error::LessonError;
+use crate::json::{Json, ToJson};
+use crate::toml;
+
+// A concept to remember, graded by the learner rather than checked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    // Stable identifier, e.g. "ownership-rule-2"
+    pub id: String,
+    pub topic: String,
+    pub front: String,
+    pub back: String,
+    pub code: Option<String>,
+}
+
+const CARDS: &str = include_str!("flashcards/cards.toml");
+const CARD_KEYS: &[&str] = &["id", "topic", "front", "back", "code"];
+
+// The cards that come with the crate
+pub fn cards() -> Vec<Card> {
+    parse(CARDS).expect("the built-in cards.toml is valid")
+}
+
+// The cards in a file like cards.toml
+pub fn parse(text: &str) -> Result<Vec<Card>, LessonError> {
+    let data = toml::parse(text)?;
+    let mut cards = Vec::new();
+    for (index, table) in data.tables("cards").into_iter().enumerate() {
+        let bad = |problem: String| LessonError::BadInput(format!("card {}: {problem}", index + 1));
+        if let Some((key, _)) = table.0.iter().find(|(key, _)| !CARD_KEYS.contains(&key.as_str())) {
+            return Err(bad(format!("unknown key `{key}`, expected one of {}", CARD_KEYS.join(", "))));
+        }
+        let field = |key: &str| table.str(key).map(str::to_string).ok_or_else(|| bad(format!("needs a `{key}` string")));
+        cards.push(Card {
+            id: field("id")?,
+            topic: field("topic")?,
+            front: field("front")?,
+            back: field("back")?,
+            code: table.str("code").map(|code| code.trim_end().to_string()),
+        });
+    }
+    Ok(cards)
+}
+
+impl ToJson for Card {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("id", (&self.id).into()),
+            ("topic", (&self.topic).into()),
+            ("front", (&self.front).into()),
+            ("back", (&self.back).into()),
+            ("code", self.code.as_deref().into()),
+        ])
+    }
+}
@@ -0,0 +1,240 @@
+use std::any;
+use std::fmt::{self, Debug, Display};
+use std::mem;
+
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+
+// `makes_copy` takes an i32 and `take_ownership` a String, two functions with the same body. A generic function
+// writes it once with a type parameter, and the caller's type picks what happens: an i32 is still copied in, a
+// String is still moved in, the generic changes nothing about ownership. The compiler makes a separate copy of
+// the function for each type it's called with, monomorphization, so there's no cost at runtime for it.
+
+pub const TAUGHT: Taught = Taught { day: 13, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(generic_functions_demo {
+        name: "generic_functions",
+        summary: "One generic function takes an i32 by copy and a String by move",
+        topics: &["generics", "ownership"],
+        prerequisites: &["makes_copy", "take_ownership"],
+        explanation: "`fn consume<T: Debug>(value: T)` takes its parameter by value, so it's `makes_copy` and `take_ownership` in one: called with an i32 it gets a copy and the caller's variable is still usable, called with a String it takes ownership and drops it at the end. `fn show<T: Display>(value: &T)` borrows instead, whatever T is. The body can only do what works for every T, so using `value` twice is rejected (E0382): T might not be Copy.",
+    }),
+    lesson!(trait_bounds_demo {
+        name: "trait_bounds",
+        summary: "A trait bound says what a generic function may do with its T",
+        topics: &["generics", "traits"],
+        prerequisites: &["generic_functions"],
+        explanation: "Without a bound a generic function knows nothing about T, so printing it with `{value}` is rejected (E0277): not every type implements Display. `T: Display` allows it, and every caller then has to pass a type that implements Display, or their call is rejected instead. Several bounds are joined with `+`, and a `where` clause after the signature is the same thing with more room. Your own types meet a bound with `#[derive(Debug)]` or an `impl Display`.",
+    }),
+    lesson!(monomorphization_demo {
+        name: "monomorphization",
+        summary: "The compiler makes one copy of a generic function for each type it's used with",
+        topics: &["generics", "performance"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["trait_bounds"],
+        explanation: "`consume(5)` and `consume(String::from(\"hello\"))` don't call the same machine code: the compiler fills T in and compiles `consume::<i32>` and `consume::<String>` as two functions, as if you had written both by hand. Everything about T is known in each copy, its size, whether it needs dropping, which `fmt` to call, so a generic call is as fast as a concrete one. The cost is compile time and a bigger binary, one copy per type.",
+    }),
+];
+
+// Takes ownership of any value it can print, makes_copy and take_ownership in one
+pub fn consume<T: Debug>(value: T) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(format!("{value:?}"));
+    if mem::needs_drop::<T>() {
+        output.event(OwnershipEvent::dropped("value")); // A String is freed here, an i32 has nothing to free
+    }
+    output
+}
+
+// Borrows any value it can print, the caller keeps it
+pub fn show<T: Display>(value: &T) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(format!("{value}"));
+    output
+}
+
+const USED_TWICE: (&str, &str) = (
+    "fn twice<T>(value: T) -> (T, T) {\n    (value, value)\n}",
+    "error[E0382]: use of moved value: `value`\n --> src/main.rs:2:13\n  |\n1 | fn twice<T>(value: T) -> (T, T) {\n  |             ----- move occurs because `value` has type `T`, which does not implement the `Copy` trait\n2 |     (value, value)\n  |      -----  ^^^^^ value used here after move\n  |      |\n  |      value moved here",
+);
+
+// Two of anything that can be cloned, the bound that makes the rejected `twice` work
+pub fn twice<T: Clone>(value: T) -> (T, T) {
+    (value.clone(), value)
+}
+
+// consume with an i32 and a String, show borrowing, then what a body can't assume about T
+pub fn generic_functions_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let x = 5;
+    reporter.event(&OwnershipEvent::copied("x", "value"));
+    reporter.output(&consume(x)); // T is i32, which is Copy, so like makes_copy
+    reporter.message(&format!("x: {x}"));
+
+    let s = String::from("hello");
+    reporter.event(&OwnershipEvent::borrowed("s", "value"));
+    reporter.output(&show(&s)); // Borrowed, for any T
+    reporter.event(&OwnershipEvent::moved("s", "value"));
+    reporter.output(&consume(s)); // T is String, so like take_ownership
+    reporter.message("s: error");
+    reporter.message("");
+
+    let (code, error) = USED_TWICE;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    let (first, second) = twice(String::from("ferris")); // T: Clone, so the body may clone it
+    reporter.message(&format!("twice with T: Clone: ({first}, {second})"));
+    Ok(())
+}
+
+// A type of our own, printable once it implements the traits the bounds ask for
+#[derive(Debug)]
+pub struct Crab {
+    pub name: String,
+}
+
+impl fmt::Display for Crab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} the crab", self.name)
+    }
+}
+
+// Both bounds at once, written in a where clause
+pub fn describe<T>(value: &T) -> String
+where
+    T: Display + Debug,
+{
+    format!("shown as {value}, debugged as {value:?}")
+}
+
+const NO_BOUND: (&str, &str) = (
+    "fn show<T>(value: &T) -> String {\n    format!(\"{value}\")\n}",
+    "error[E0277]: `T` doesn't implement `std::fmt::Display`\n --> src/main.rs:2:14\n  |\n2 |     format!(\"{value}\")\n  |              ^^^^^^^ `T` cannot be formatted with the default formatter",
+);
+
+const MISSING_DEBUG: (&str, &str) = (
+    "struct Crab;\nconsume(Crab);",
+    "error[E0277]: `Crab` doesn't implement `Debug`\n --> src/main.rs:2:9\n  |\n2 | consume(Crab);\n  | ------- ^^^^ the trait `Debug` is not implemented for `Crab`\n  | |\n  | required by a bound introduced by this call",
+);
+
+// The body rejected without a bound, a caller rejected for not meeting one, then a type that meets both
+pub fn trait_bounds_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    for (code, error) in [NO_BOUND, MISSING_DEBUG] {
+        for line in code.lines() {
+            reporter.message(&format!("    {line}"));
+        }
+        for line in error.lines() {
+            reporter.message(line);
+        }
+        reporter.message("");
+    }
+
+    let crab = Crab { name: String::from("ferris") };
+    reporter.event(&OwnershipEvent::borrowed("crab", "value"));
+    reporter.output(&show(&crab)); // Crab implements Display
+    reporter.event(&OwnershipEvent::borrowed("crab", "value"));
+    reporter.message(&describe(&crab));
+    reporter.message(&describe(&42));
+    reporter.event(&OwnershipEvent::moved("crab", "value"));
+    reporter.output(&consume(crab)); // And Debug, derived
+    Ok(())
+}
+
+// What the copy of a generic function made for T knows about T, with the crate paths taken off
+fn instance<T>() -> String {
+    let name = any::type_name::<T>().replace("alloc::string::", "");
+    format!("{name}: size {} bytes, needs drop: {}", mem::size_of::<T>(), mem::needs_drop::<T>())
+}
+
+// The function names rustc gives each copy of consume, and what each copy knows about its T
+pub fn monomorphization_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    reporter.message(&format!("consume(5) calls {}", any::type_name_of_val(&consume::<i32>)));
+    reporter.message(&format!("consume(String::from(\"hello\")) calls {}", any::type_name_of_val(&consume::<String>).replace("alloc::string::", "")));
+    reporter.message(&format!("consume(crab) calls {}", any::type_name_of_val(&consume::<Crab>)));
+    reporter.message("");
+    reporter.message("each copy has its T filled in at compile time:");
+    for line in [instance::<i32>(), instance::<String>(), instance::<&str>(), instance::<Crab>()] {
+        reporter.message(&format!("  {line}"));
+    }
+    reporter.message("");
+    reporter.message("consume::<i32> drops nothing, consume::<String> frees its String, decided per copy before the program runs");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consume_drops_only_what_needs_dropping() {
+        let number = consume(5);
+        assert_eq!(number.messages, ["5"]);
+        assert!(number.events.is_empty());
+        let text = consume(String::from("hello"));
+        assert_eq!(text.messages, ["\"hello\""]);
+        assert_eq!(text.events, [OwnershipEvent::dropped("value")]);
+    }
+
+    #[test]
+    fn show_twice_and_describe() {
+        assert_eq!(show(&"hello").messages, ["hello"]);
+        assert_eq!(twice(String::from("ferris")), (String::from("ferris"), String::from("ferris")));
+        let crab = Crab { name: String::from("ferris") };
+        assert_eq!(describe(&crab), "shown as ferris the crab, debugged as Crab { name: \"ferris\" }");
+        assert_eq!(describe(&42), "shown as 42, debugged as 42");
+    }
+
+    #[test]
+    fn instance_knows_size_and_drop() {
+        assert_eq!(instance::<i32>(), "i32: size 4 bytes, needs drop: false");
+        assert_eq!(instance::<String>(), format!("String: size {} bytes, needs drop: true", size_of::<String>()));
+    }
+
+    #[test]
+    fn generic_functions_demo_copies_borrows_then_moves() {
+        let mut output = DemoOutput::new();
+        generic_functions_demo(&mut output).unwrap();
+        assert_eq!(output.messages[..5], ["5", "x: 5", "hello", "\"hello\"", "s: error"]);
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0382]")));
+        assert_eq!(output.messages.last().unwrap(), "twice with T: Clone: (ferris, ferris)");
+        assert_eq!(
+            output.events,
+            [OwnershipEvent::copied("x", "value"), OwnershipEvent::borrowed("s", "value"), OwnershipEvent::moved("s", "value"), OwnershipEvent::dropped("value")]
+        );
+    }
+
+    #[test]
+    fn trait_bounds_demo_shows_both_rejections_then_the_bounded_calls() {
+        let mut output = DemoOutput::new();
+        trait_bounds_demo(&mut output).unwrap();
+        let errors: Vec<&String> = output.messages.iter().filter(|message| message.starts_with("error[E0277]")).collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            output.messages[output.messages.len() - 4..],
+            ["ferris the crab", "shown as ferris the crab, debugged as Crab { name: \"ferris\" }", "shown as 42, debugged as 42", "Crab { name: \"ferris\" }"]
+        );
+    }
+
+    #[test]
+    fn monomorphization_demo_names_a_copy_per_type() {
+        let mut output = DemoOutput::new();
+        monomorphization_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages[..3],
+            [
+                "consume(5) calls rust_day_6::generics::consume<i32>",
+                "consume(String::from(\"hello\")) calls rust_day_6::generics::consume<String>",
+                "consume(crab) calls rust_day_6::generics::consume<rust_day_6::generics::Crab>",
+            ]
+        );
+        assert!(output.messages.contains(&"  i32: size 4 bytes, needs drop: false".to_string()));
+    }
+}
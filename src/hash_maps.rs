@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+
+// A HashMap owns its keys and its values. Putting a String in moves it, looking one up hands out a reference,
+// and the entry API finds or makes a value in one lookup. A HashMap's order changes from run to run, so the
+// demos sort what they print.
+
+pub const TAUGHT: Taught = Taught { day: 9, order: 2 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(map_insert_demo {
+        name: "map_insert",
+        summary: "Inserting Strings moves them into the map, keys and values both",
+        topics: &["collections", "ownership"],
+        prerequisites: &["vec_push"],
+        explanation: "`scores.insert(name, score)` moves the key and the value into the map, so a String used as either can't be used afterwards, while Copy values like an i32 are copied in. Inserting a key that's already there replaces the value and hands the old one back, `insert` returns it as an Option. A map with String keys can still be searched with a `&str`, no String has to be made to look one up.",
+    }),
+    lesson!(map_get_demo {
+        name: "map_get",
+        summary: "get returns a reference into the map, which keeps the map borrowed",
+        topics: &["collections", "references"],
+        prerequisites: &["map_insert", "reference"],
+        explanation: "`get` returns an `Option<&V>`, a reference to the value inside the map, never the value itself. While that reference is in use the map is borrowed, so inserting into it is rejected (E0502), the insert could move every value to a bigger table. `copied()` or `cloned()` turns it into an owned value, `get_mut` gives a `&mut V` to change it in place, and `remove` moves the value out to you.",
+    }),
+    lesson!(map_entry_demo {
+        name: "map_entry",
+        summary: "entry().or_insert_with() finds or creates a value in one lookup",
+        topics: &["collections", "ownership"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["map_get"],
+        explanation: "`map.entry(key)` looks the key up once and returns an Entry, occupied or vacant. `or_insert(value)` and `or_insert_with(f)` put a value in when it's vacant and either way return a `&mut V` to the value, so counting words is `*counts.entry(word).or_insert(0) += 1`. The key is moved into `entry` even when it turns out to be there already, where it's dropped, and `or_insert_with` only runs its closure, say `Vec::new`, when a value is needed.",
+    }),
+];
+
+// The map's entries sorted by key, so the output is the same every run
+pub fn sorted<K: Ord, V>(map: &HashMap<K, V>) -> Vec<(&K, &V)> {
+    let mut entries: Vec<(&K, &V)> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+// Prints a map's entries, borrowing it
+pub fn entries<K: Ord + Debug, V: Debug>(name: &str, map: &HashMap<K, V>) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    let entries: Vec<String> = sorted(map).iter().map(|(key, value)| format!("{key:?}: {value:?}")).collect();
+    output.message(format!("{name}: {{{}}}", entries.join(", "))).value("len", map.len());
+    output
+}
+
+// Moves a String key and value in, replaces a value and gets the old one back, then looks a key up by &str
+pub fn map_insert_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let mut colours = HashMap::new();
+    let pet = String::from("ferris");
+    let colour = String::from("orange");
+    colours.insert(pet, colour); // Both Strings move into the map
+    reporter.event(&OwnershipEvent::moved("pet", "colours"));
+    reporter.event(&OwnershipEvent::moved("colour", "colours"));
+    reporter.message("pet: error, colour: error");
+
+    let old = colours.insert(String::from("ferris"), String::from("red")); // The old value moves back out
+    reporter.event(&OwnershipEvent::moved("colours[\"ferris\"]", "old"));
+    reporter.message(&format!("old: {old:?}"));
+    colours.insert(String::from("corro"), String::from("yellow"));
+    reporter.event(&OwnershipEvent::borrowed("colours", "map"));
+    reporter.output(&entries("colours", &colours));
+
+    let mut scores = HashMap::new();
+    let score = 3;
+    scores.insert("ferris", score); // An i32 is Copy, score is still usable
+    reporter.event(&OwnershipEvent::copied("score", "scores"));
+    reporter.message(&format!("score: {score}"));
+
+    let found = colours.contains_key("corro"); // A &str finds a String key, nothing is allocated to look it up
+    reporter.message(&format!("contains_key(\"corro\"): {found}"));
+    Ok(())
+}
+
+const INSERT_WHILE_BORROWED: (&str, &str) = (
+    "let mut scores = HashMap::new();\nscores.insert(String::from(\"ferris\"), 3);\nlet ferris = scores.get(\"ferris\");\nscores.insert(String::from(\"corro\"), 7);\nprintln!(\"{ferris:?}\");",
+    "error[E0502]: cannot borrow `scores` as mutable because it is also borrowed as immutable\n --> src/main.rs:4:1\n  |\n3 | let ferris = scores.get(\"ferris\");\n  |              ------ immutable borrow occurs here\n4 | scores.insert(String::from(\"corro\"), 7);\n  | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ mutable borrow occurs here\n5 | println!(\"{ferris:?}\");\n  |            ------ immutable borrow later used here",
+);
+
+// Borrows a value with get, copies one out, changes one with get_mut and takes one with remove
+pub fn map_get_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let (code, error) = INSERT_WHILE_BORROWED;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    let mut scores = HashMap::from([(String::from("ferris"), 3), (String::from("corro"), 7)]);
+    let ferris = scores.get("ferris"); // An Option<&i32> pointing into the map
+    reporter.event(&OwnershipEvent::borrowed("scores", "ferris"));
+    reporter.message(&format!("get(\"ferris\"): {ferris:?}, get(\"rusty\"): {:?}", scores.get("rusty")));
+
+    let corro = scores.get("corro").copied().unwrap_or(0); // An owned i32, scores isn't borrowed anymore
+    scores.insert(String::from("rusty"), 1);
+    reporter.message(&format!("corro: {corro}, inserted rusty while corro is in use"));
+
+    if let Some(score) = scores.get_mut("ferris") {
+        // A &mut i32, the change is made in the map
+        reporter.event(&OwnershipEvent::borrowed_mut("scores", "score"));
+        *score += 10;
+    }
+    reporter.event(&OwnershipEvent::borrowed("scores", "map"));
+    reporter.output(&entries("scores", &scores));
+
+    let mut names = HashMap::from([(1, String::from("ferris"))]);
+    let name = names.remove(&1); // The String moves out of the map
+    reporter.event(&OwnershipEvent::moved("names[1]", "name"));
+    reporter.message(&format!("remove(&1): {name:?}, names: {names:?}"));
+    Ok(())
+}
+
+// Counts words with or_insert and groups them by first letter with or_insert_with
+pub fn map_entry_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let text = "the crab and the cat and a duck";
+    let mut counts: HashMap<&str, i32> = HashMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(word).or_insert(0) += 1; // or_insert returns a &mut i32 to the count, new or not
+    }
+    reporter.event(&OwnershipEvent::borrowed("counts", "map"));
+    reporter.output(&entries("counts", &counts));
+
+    let mut by_letter: HashMap<char, Vec<String>> = HashMap::new();
+    #[allow(clippy::unwrap_or_default)] // or_default() does the same, spelled out to show or_insert_with
+    for word in text.split_whitespace() {
+        let letter = word.chars().next().unwrap_or(' ');
+        let words = by_letter.entry(letter).or_insert_with(Vec::new); // Vec::new only runs for a new letter
+        if !words.iter().any(|known| known == word) {
+            words.push(word.to_string());
+        }
+    }
+    reporter.event(&OwnershipEvent::borrowed("by_letter", "map"));
+    reporter.output(&entries("by_letter", &by_letter));
+
+    let mut owners: HashMap<String, String> = HashMap::new();
+    for (pet, owner) in [("ferris", "alice"), ("corro", "bob"), ("ferris", "carol")] {
+        let key = pet.to_string();
+        let first = owners.entry(key).or_insert_with(|| owner.to_string()); // key moves in, dropped if ferris is there
+        reporter.event(&OwnershipEvent::moved("key", "entry"));
+        reporter.message(&format!("{pet}: {first}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_are_sorted_by_key() {
+        let map = HashMap::from([("b", 2), ("c", 3), ("a", 1)]);
+        assert_eq!(sorted(&map), [(&"a", &1), (&"b", &2), (&"c", &3)]);
+        let output = entries("map", &map);
+        assert_eq!(output.messages, ["map: {\"a\": 1, \"b\": 2, \"c\": 3}"]);
+        assert_eq!(output.values, [("len".to_string(), "3".to_string())]);
+        assert_eq!(entries("empty", &HashMap::<i32, i32>::new()).messages, ["empty: {}"]);
+    }
+
+    #[test]
+    fn map_insert_demo_moves_keys_and_values_in() {
+        let mut output = DemoOutput::new();
+        map_insert_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            ["pet: error, colour: error", "old: Some(\"orange\")", "colours: {\"corro\": \"yellow\", \"ferris\": \"red\"}", "score: 3", "contains_key(\"corro\"): true"]
+        );
+        assert_eq!(output.events[..3], [OwnershipEvent::moved("pet", "colours"), OwnershipEvent::moved("colour", "colours"), OwnershipEvent::moved("colours[\"ferris\"]", "old")]);
+    }
+
+    #[test]
+    fn map_get_demo_borrows_copies_changes_and_removes() {
+        let mut output = DemoOutput::new();
+        map_get_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0502]")));
+        assert_eq!(
+            output.messages[output.messages.len() - 4..],
+            [
+                "get(\"ferris\"): Some(3), get(\"rusty\"): None",
+                "corro: 7, inserted rusty while corro is in use",
+                "scores: {\"corro\": 7, \"ferris\": 13, \"rusty\": 1}",
+                "remove(&1): Some(\"ferris\"), names: {}",
+            ]
+        );
+    }
+
+    #[test]
+    fn map_entry_demo_keeps_the_first_owner() {
+        let mut output = DemoOutput::new();
+        map_entry_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            [
+                "counts: {\"a\": 1, \"and\": 2, \"cat\": 1, \"crab\": 1, \"duck\": 1, \"the\": 2}",
+                "by_letter: {'a': [\"and\", \"a\"], 'c': [\"crab\", \"cat\"], 'd': [\"duck\"], 't': [\"the\"]}",
+                "ferris: alice",
+                "corro: bob",
+                "ferris: alice",
+            ]
+        );
+    }
+}
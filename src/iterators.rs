@@ -0,0 +1,214 @@
+use std::any;
+use std::cell::Cell;
+
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+use crate::slices;
+
+// An iterator hands out a collection's elements one at a time, and which method made it decides what the elements
+// are: references, mutable references or the values themselves. Adapters like map and filter only describe work,
+// nothing runs until something pulls items through, which is also why the collection stays borrowed until then.
+
+pub const TAUGHT: Taught = Taught { day: 10, order: 2 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(iterator_kinds_demo {
+        name: "iterator_kinds",
+        summary: "iter() borrows, iter_mut() borrows mutably, into_iter() takes ownership",
+        topics: &["iterators", "ownership"],
+        prerequisites: &["vec_iteration"],
+        explanation: "`names.iter()` yields a `&String` for each element and leaves the Vec as it was. `names.iter_mut()` yields `&mut String`s, so the elements can be changed where they are. `names.into_iter()` yields the Strings themselves and uses the Vec up. A `for` loop calls `into_iter()` on whatever it's given, which is why `for name in &names` borrows: `(&names).into_iter()` is the same as `names.iter()`.",
+    }),
+    lesson!(iterator_borrows_demo {
+        name: "iterator_borrows",
+        summary: "Why you can't push onto a Vec while iterating over it",
+        topics: &["iterators", "borrowing"],
+        prerequisites: &["iterator_kinds", "map_get"],
+        explanation: "An iterator over `&numbers` holds a shared borrow of the Vec for the whole loop, and `numbers.push` needs a mutable one, so rustc rejects the push (E0502). That's not fussiness: a push can reallocate the Vec and leave the iterator pointing at freed memory. Collect what you want to add first and `extend` afterwards, loop over indexes so no borrow outlives a single step, or use a method like `retain` that changes the Vec itself.",
+    }),
+    lesson!(lazy_adapters_demo {
+        name: "lazy_adapters",
+        summary: "Adapters are lazy, nothing runs until collect or a for loop pulls items",
+        topics: &["iterators", "closures"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["iterator_kinds", "fn_traits", "slicing"],
+        explanation: "`map`, `filter` and `take` wrap an iterator in another one and return straight away, their closures haven't been called yet. The work happens when something consumes the iterator, `collect`, `sum`, `position` or a `for` loop, and only as far as it needs: `take(2)` stops after two items and `position` after the first match. The byte loop in `slicing` is `bytes().position(|byte| byte == b'r')`, and its early `return` is the short-circuit `position` does for you.",
+    }),
+];
+
+// The type of one item as rustc sees it, with the crate path taken off String
+fn item_type<T>(_item: &T) -> String {
+    any::type_name::<T>().replace("alloc::string::", "")
+}
+
+// Pulls one item out of each kind of iterator and shows its type, then uses each kind for what it's for
+pub fn iterator_kinds_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let mut names = vec![String::from("ferris"), String::from("corro")];
+
+    #[allow(clippy::iter_next_slice)] // names.first() is the same, but this is the iterator being shown
+    if let Some(name) = names.iter().next() {
+        reporter.event(&OwnershipEvent::borrowed("names", "iter"));
+        reporter.message(&format!("iter() yields `{}`", item_type(&name)));
+    }
+    if let Some(name) = names.iter_mut().next() {
+        reporter.event(&OwnershipEvent::borrowed_mut("names", "iter_mut"));
+        reporter.message(&format!("iter_mut() yields `{}`", item_type(&name)));
+    }
+    if let Some(name) = names.clone().into_iter().next() {
+        // A clone's into_iter, so names is still here for the rest of the demo
+        reporter.event(&OwnershipEvent::cloned("names", "into_iter"));
+        reporter.message(&format!("into_iter() yields `{}`", item_type(&name)));
+    }
+
+    let total: usize = names.iter().map(|name| name.len()).sum(); // Reads every element, borrows names
+    reporter.message(&format!("total letters: {total}, names still usable: {names:?}"));
+
+    for name in names.iter_mut() {
+        // Each &mut String changes the element in the Vec
+        name.insert(0, '@');
+    }
+    reporter.message(&format!("after iter_mut: {names:?}"));
+
+    let owned: Vec<String> = names.into_iter().rev().collect(); // Every String moves out of names into owned
+    reporter.event(&OwnershipEvent::moved("names", "into_iter"));
+    reporter.message(&format!("owned: {owned:?}, names: error"));
+    Ok(())
+}
+
+const PUSH_WHILE_ITERATING: (&str, &str) = (
+    "let mut numbers = vec![1, 2, 3];\nfor n in &numbers {\n    numbers.push(*n * 10);\n}",
+    "error[E0502]: cannot borrow `numbers` as mutable because it is also borrowed as immutable\n --> src/main.rs:3:5\n  |\n2 | for n in &numbers {\n  |          --------\n  |          |\n  |          immutable borrow occurs here\n  |          immutable borrow later used here\n3 |     numbers.push(*n * 10);\n  |     ^^^^^^^^^^^^^^^^^^^^^ mutable borrow occurs here",
+);
+
+// The rejected push, then three ways of growing or shrinking a Vec based on its own elements
+pub fn iterator_borrows_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let (code, error) = PUSH_WHILE_ITERATING;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    let mut numbers = vec![1, 2, 3];
+    let extra: Vec<i32> = numbers.iter().map(|n| n * 10).collect(); // The borrow ends when collect returns
+    reporter.event(&OwnershipEvent::borrowed("numbers", "iter"));
+    numbers.extend(extra); // extra moves in, numbers is only borrowed mutably now
+    reporter.event(&OwnershipEvent::moved("extra", "extend"));
+    reporter.message(&format!("collect then extend: {numbers:?}"));
+
+    let mut doubled = vec![1, 2, 3];
+    for i in 0..doubled.len() {
+        // The range is worked out once, each step borrows doubled only for its own line
+        let n = doubled[i];
+        doubled.push(n * 2);
+    }
+    reporter.message(&format!("by index: {doubled:?}"));
+
+    doubled.retain(|n| n % 2 == 0); // retain has &mut doubled and decides what stays
+    reporter.event(&OwnershipEvent::borrowed_mut("doubled", "retain"));
+    reporter.message(&format!("retain evens: {doubled:?}"));
+    Ok(())
+}
+
+// `slices::slicing` with the loop replaced by adapters: position finds the first 'r', map_or slices up to it or
+// keeps the whole string
+pub fn slicing_with_adapters(my_string: &str) -> &str {
+    my_string.bytes().position(|byte| byte == b'r').map_or(my_string, |i| &my_string[..i])
+}
+
+// Counts how many items a chain pulls through, one map call per item
+pub fn count_pulled(texts: &[&str], take: usize) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    let calls = Cell::new(0); // A Cell so the map closure can count while the iterator borrows it
+    let lengths = texts.iter().map(|text| {
+        calls.set(calls.get() + 1);
+        text.len()
+    });
+    output.message(format!("built the chain, map has run {} times", calls.get()));
+    let first: Vec<usize> = lengths.take(take).collect();
+    output.message(format!("take({take}).collect(): {first:?}, map has run {} times of {}", calls.get(), texts.len()));
+    output.value("calls", calls.get());
+    output
+}
+
+// Shows nothing runs before the chain is consumed, then checks the adapter version of slicing against the loop
+pub fn lazy_adapters_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let words = ["hello", "crab", "world", "rust"];
+    reporter.event(&OwnershipEvent::borrowed("words", "texts"));
+    reporter.output(&count_pulled(&words, 2));
+
+    let long: Vec<&&str> = words.iter().filter(|word| word.len() > 4).collect(); // References to the elements, words isn't touched
+    reporter.message(&format!("filter(len > 4): {long:?}"));
+
+    for text in ["hello world", "crab", "rust"] {
+        let with_loop = slices::slicing(text);
+        let with_adapters = slicing_with_adapters(text);
+        reporter.message(&format!("slicing({text:?}): loop {with_loop:?}, adapters {with_adapters:?}, same: {}", with_loop == with_adapters));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn item_types_leave_the_crate_path_off() {
+        let names = vec![String::from("ferris")];
+        assert_eq!(item_type(&names.first().unwrap()), "&String");
+        assert_eq!(item_type(&names.into_iter().next().unwrap()), "String");
+    }
+
+    #[test]
+    fn slicing_with_adapters_matches_the_loop() {
+        for text in ["hello world", "crab", "rust", "", "no such letter"] {
+            assert_eq!(slicing_with_adapters(text), slices::slicing(text), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn count_pulled_only_runs_map_for_what_is_taken() {
+        let output = count_pulled(&["hello", "crab", "world"], 2);
+        assert_eq!(output.messages, ["built the chain, map has run 0 times", "take(2).collect(): [5, 4], map has run 2 times of 3"]);
+        assert_eq!(output.values, [("calls".to_string(), "2".to_string())]);
+        assert_eq!(count_pulled(&["a"], 5).values, [("calls".to_string(), "1".to_string())]);
+    }
+
+    #[test]
+    fn iterator_kinds_demo_borrows_then_consumes_names() {
+        let mut output = DemoOutput::new();
+        iterator_kinds_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            [
+                "iter() yields `&String`",
+                "iter_mut() yields `&mut String`",
+                "into_iter() yields `String`",
+                "total letters: 11, names still usable: [\"ferris\", \"corro\"]",
+                "after iter_mut: [\"@ferris\", \"@corro\"]",
+                "owned: [\"@corro\", \"@ferris\"], names: error",
+            ]
+        );
+        assert_eq!(output.events.last(), Some(&OwnershipEvent::moved("names", "into_iter")));
+    }
+
+    #[test]
+    fn iterator_borrows_demo_changes_the_vec_without_pushing_while_iterating() {
+        let mut output = DemoOutput::new();
+        iterator_borrows_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0502]")));
+        assert_eq!(output.messages[output.messages.len() - 3..], ["collect then extend: [1, 2, 3, 10, 20, 30]", "by index: [1, 2, 3, 2, 4, 6]", "retain evens: [2, 2, 4, 6]"]);
+    }
+
+    #[test]
+    fn lazy_adapters_demo_agrees_with_slicing() {
+        let mut output = DemoOutput::new();
+        lazy_adapters_demo(&mut output).unwrap();
+        assert_eq!(output.messages[0], "built the chain, map has run 0 times");
+        assert!(output.messages.iter().filter(|message| message.starts_with("slicing(")).all(|message| message.ends_with("same: true")));
+    }
+}
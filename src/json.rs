@@ -0,0 +1,537 @@
+use std::fmt;
+
+use crate::error::LessonError;
+
+// A JSON value, just enough to print and read machine-readable output without pulling in a dependency
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    // Keys keep their insertion order so output is stable
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    // Builds an object from (key, value) pairs
+    pub fn object<K: Into<String>>(fields: impl IntoIterator<Item = (K, Json)>) -> Self {
+        Json::Object(fields.into_iter().map(|(key, value)| (key.into(), value)).collect())
+    }
+
+    // Builds an array from anything convertible to JSON
+    pub fn array<T: Into<Json>>(items: impl IntoIterator<Item = T>) -> Self {
+        Json::Array(items.into_iter().map(Into::into).collect())
+    }
+
+    // Parses a single JSON value, surrounding whitespace is fine but nothing else may follow it
+    pub fn parse(text: &str) -> Result<Self, LessonError> {
+        let mut parser = Parser { chars: text.chars().collect(), pos: 0, line: 1, depth: 0 };
+        parser.skip_whitespace();
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(value),
+            Some(c) => Err(parser.error(&format!("unexpected `{c}` after the value"))),
+        }
+    }
+
+    // The value of `key` in an object
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Json> {
+        match self {
+            Json::Object(fields) => fields.iter_mut().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    // Sets `key` in an object, replacing it if it's there already and adding it at the end if not
+    pub fn set(&mut self, key: &str, value: Json) {
+        if let Some(existing) = self.get_mut(key) {
+            *existing = value;
+        } else if let Json::Object(fields) = self {
+            fields.push((key.to_string(), value));
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    // A number that's a whole, non-negative value, e.g. a count or a timestamp
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Json::Number(value) if *value >= 0.0 && value.fract() == 0.0 => Some(*value as u64),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&[(String, Json)]> {
+        match self {
+            Json::Object(fields) => Some(fields),
+            _ => None,
+        }
+    }
+
+    // Indented with two spaces per level, for files people might open, e.g. progress.json
+    pub fn pretty(&self) -> String {
+        let mut text = String::new();
+        self.write_pretty(&mut text, 0);
+        text
+    }
+
+    fn write_pretty(&self, text: &mut String, depth: usize) {
+        let indent = |text: &mut String, depth: usize| text.push_str(&"  ".repeat(depth));
+        match self {
+            // A list of plain values stays on one line, e.g. [2, 3]
+            Json::Array(items) if items.iter().all(|item| !matches!(item, Json::Array(_) | Json::Object(_))) => {
+                let items: Vec<String> = items.iter().map(Json::to_string).collect();
+                text.push_str(&format!("[{}]", items.join(", ")));
+            }
+            Json::Array(items) => {
+                text.push_str("[\n");
+                for (index, item) in items.iter().enumerate() {
+                    indent(text, depth + 1);
+                    item.write_pretty(text, depth + 1);
+                    text.push_str(if index + 1 < items.len() { ",\n" } else { "\n" });
+                }
+                indent(text, depth);
+                text.push(']');
+            }
+            Json::Object(fields) if !fields.is_empty() => {
+                text.push_str("{\n");
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    indent(text, depth + 1);
+                    text.push_str(&format!("{}: ", Json::String(key.clone())));
+                    value.write_pretty(text, depth + 1);
+                    text.push_str(if index + 1 < fields.len() { ",\n" } else { "\n" });
+                }
+                indent(text, depth);
+                text.push('}');
+            }
+            // Empty containers and plain values look the same either way
+            value => text.push_str(&value.to_string()),
+        }
+    }
+}
+
+// How deep arrays and objects may nest. Parsing recurses, so a file of nothing but `[` would otherwise
+// overflow the stack instead of being an error.
+const MAX_DEPTH: usize = 128;
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    // Arrays and objects that are open around the current value
+    depth: usize,
+}
+
+impl Parser {
+    fn error(&self, message: &str) -> LessonError {
+        LessonError::JsonParse { line: self.line, message: message.to_string() }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c @ (' ' | '\t' | '\n' | '\r')) = self.peek() {
+            if c == '\n' {
+                self.line += 1;
+            }
+            self.pos += 1;
+        }
+    }
+
+    // `word` spelled out exactly, for true, false and null
+    fn keyword(&mut self, word: &str, value: Json) -> Result<Json, LessonError> {
+        let end = self.pos + word.chars().count();
+        if end <= self.chars.len() && self.chars[self.pos..end].iter().copied().eq(word.chars()) {
+            self.pos = end;
+            Ok(value)
+        } else {
+            Err(self.error("expected a value"))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, LessonError> {
+        match self.peek() {
+            Some('{' | '[') if self.depth == MAX_DEPTH => Err(self.error(&format!("nested more than {MAX_DEPTH} deep"))),
+            Some('{') => self.nested(Self::object),
+            Some('[') => self.nested(Self::array),
+            Some('"') => Ok(Json::String(self.string()?)),
+            Some('t') => self.keyword("true", Json::Bool(true)),
+            Some('f') => self.keyword("false", Json::Bool(false)),
+            Some('n') => self.keyword("null", Json::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            Some(c) => Err(self.error(&format!("unexpected `{c}`, expected a value"))),
+            None => Err(self.error("unexpected end of input, expected a value")),
+        }
+    }
+
+    // An array or object, one level further in
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Json, LessonError>) -> Result<Json, LessonError> {
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> Result<Json, LessonError> {
+        self.pos += 1;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("expected a key in quotes"));
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            if self.peek() != Some(':') {
+                return Err(self.error(&format!("expected `:` after \"{key}\"")));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return Err(self.error("expected `,` or `}` in the object")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, LessonError> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            self.skip_whitespace();
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return Err(self.error("expected `,` or `]` in the array")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, LessonError> {
+        self.pos += 1; // Opening quote
+        let mut text = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.error("string is missing its closing `\"`"));
+            };
+            self.pos += 1;
+            match c {
+                '"' => return Ok(text),
+                '\\' => {
+                    let escaped = match self.peek() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            self.pos += 1;
+                            text.push(self.unicode_escape()?);
+                            continue;
+                        }
+                        _ => return Err(self.error("unknown escape in string")),
+                    };
+                    self.pos += 1;
+                    text.push(escaped);
+                }
+                c => text.push(c),
+            }
+        }
+    }
+
+    // The XXXX of `\uXXXX`, joining surrogate pairs into one character
+    fn unicode_escape(&mut self) -> Result<char, LessonError> {
+        let first = self.hex4()?;
+        if (0xD800..0xDC00).contains(&first) && self.chars.get(self.pos..self.pos + 2) == Some(&['\\', 'u']) {
+            self.pos += 2;
+            let second = self.hex4()?;
+            let combined = 0x10000 + ((first - 0xD800) << 10) + (second.wrapping_sub(0xDC00) & 0x3FF);
+            return Ok(char::from_u32(combined).unwrap_or('\u{FFFD}'));
+        }
+        Ok(char::from_u32(first).unwrap_or('\u{FFFD}'))
+    }
+
+    fn hex4(&mut self) -> Result<u32, LessonError> {
+        let digits: String = self.chars.get(self.pos..self.pos + 4).unwrap_or(&[]).iter().collect();
+        let value = u32::from_str_radix(&digits, 16).map_err(|_| self.error("`\\u` needs four hex digits"))?;
+        self.pos += 4;
+        Ok(value)
+    }
+
+    fn number(&mut self) -> Result<Json, LessonError> {
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')) {
+            self.pos += 1;
+        }
+        let number: String = self.chars[start..self.pos].iter().collect();
+        number.parse().map(Json::Number).map_err(|_| self.error(&format!("`{number}` isn't a number")))
+    }
+}
+
+// Types that have a JSON representation
+pub trait ToJson {
+    fn to_json(&self) -> Json;
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Self {
+        Json::Number(value)
+    }
+}
+
+impl From<u32> for Json {
+    fn from(value: u32) -> Self {
+        Json::Number(value.into())
+    }
+}
+
+impl From<u64> for Json {
+    fn from(value: u64) -> Self {
+        Json::Number(value as f64)
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        Json::Number(value as f64)
+    }
+}
+
+impl From<i64> for Json {
+    fn from(value: i64) -> Self {
+        Json::Number(value as f64)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::String(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::String(value)
+    }
+}
+
+impl From<&String> for Json {
+    fn from(value: &String) -> Self {
+        Json::String(value.clone())
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+impl From<Vec<Json>> for Json {
+    fn from(value: Vec<Json>) -> Self {
+        Json::Array(value)
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    f.write_str("\"")
+}
+
+// Compact JSON, e.g. {"name":"slicing","ok":true}
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(value) => write!(f, "{value}"),
+            Json::Number(value) if !value.is_finite() => f.write_str("null"),
+            Json::Number(value) if value.fract() == 0.0 && value.abs() < 1e15 => write!(f, "{}", *value as i64),
+            Json::Number(value) => write!(f, "{value}"),
+            Json::String(text) => write_string(f, text),
+            Json::Array(items) => {
+                f.write_str("[")?;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_str("]")
+            }
+            Json::Object(fields) => {
+                f.write_str("{")?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The message of a JsonParse error, and the line it was on
+    fn parse_error(text: &str) -> (usize, String) {
+        match Json::parse(text) {
+            Err(LessonError::JsonParse { line, message }) => (line, message),
+            other => panic!("expected a JsonParse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_every_kind_of_value() {
+        let value = Json::parse(r#" {"name": "slicing", "ok": true, "skipped": null, "day": 6, "ratio": -0.5e1, "tags": ["a", []], "none": {}} "#).unwrap();
+        assert_eq!(value.get("name").and_then(Json::as_str), Some("slicing"));
+        assert_eq!(value.get("ok").and_then(Json::as_bool), Some(true));
+        assert_eq!(value.get("skipped"), Some(&Json::Null));
+        assert_eq!(value.get("day").and_then(Json::as_u64), Some(6));
+        assert_eq!(value.get("ratio"), Some(&Json::Number(-5.0)));
+        assert_eq!(value.get("tags"), Some(&Json::Array(vec![Json::from("a"), Json::Array(Vec::new())])));
+        assert_eq!(value.get("none"), Some(&Json::Object(Vec::new())));
+    }
+
+    #[test]
+    fn keys_keep_their_order() {
+        let value = Json::parse(r#"{"b": 1, "a": 2}"#).unwrap();
+        let keys: Vec<&str> = value.as_object().unwrap().iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, ["b", "a"]);
+    }
+
+    #[test]
+    fn string_escapes() {
+        let value = Json::parse(r#""a\"b\\c\/d\n\t\u00e9\ud83e\udd80""#).unwrap();
+        assert_eq!(value.as_str(), Some("a\"b\\c/d\n\té🦀"));
+    }
+
+    #[test]
+    fn display_and_pretty_round_trip() {
+        let value = Json::object([
+            ("name", Json::from("quote \" and\nnewline\u{1}")),
+            ("days", Json::array([2u32, 3])),
+            ("lessons", Json::Array(vec![Json::object([("ok", Json::from(true))])])),
+            ("empty", Json::Object(Vec::new())),
+        ]);
+        assert_eq!(Json::parse(&value.to_string()).unwrap(), value);
+        assert_eq!(Json::parse(&value.pretty()).unwrap(), value);
+        assert!(value.pretty().contains("\"days\": [2, 3]"));
+    }
+
+    #[test]
+    fn whole_numbers_print_without_a_fraction() {
+        assert_eq!(Json::from(6u32).to_string(), "6");
+        assert_eq!(Json::Number(0.25).to_string(), "0.25");
+        assert_eq!(Json::Number(f64::NAN).to_string(), "null");
+    }
+
+    #[test]
+    fn set_replaces_or_appends() {
+        let mut value = Json::object([("a", Json::from(1u32))]);
+        value.set("a", Json::from(2u32));
+        value.set("b", Json::Null);
+        assert_eq!(value.to_string(), r#"{"a":2,"b":null}"#);
+    }
+
+    #[test]
+    fn errors_say_which_line() {
+        assert_eq!(parse_error("{\n  \"a\": 1,\n  \"b\" 2\n}"), (3, "expected `:` after \"b\"".to_string()));
+        assert_eq!(parse_error("[1, 2"), (1, "expected `,` or `]` in the array".to_string()));
+        assert_eq!(parse_error("{\"a\": 1"), (1, "expected `,` or `}` in the object".to_string()));
+        assert_eq!(parse_error("{1: 2}"), (1, "expected a key in quotes".to_string()));
+        assert_eq!(parse_error("\"open"), (1, "string is missing its closing `\"`".to_string()));
+        assert_eq!(parse_error("\"\\x\""), (1, "unknown escape in string".to_string()));
+        assert_eq!(parse_error("\"\\u12\""), (1, "`\\u` needs four hex digits".to_string()));
+        assert_eq!(parse_error("tru"), (1, "expected a value".to_string()));
+        assert_eq!(parse_error("1-2"), (1, "`1-2` isn't a number".to_string()));
+        assert_eq!(parse_error(""), (1, "unexpected end of input, expected a value".to_string()));
+        assert_eq!(parse_error("1\n2"), (2, "unexpected `2` after the value".to_string()));
+    }
+
+    #[test]
+    fn nesting_is_limited() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(Json::parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(parse_error(&nested(MAX_DEPTH + 1)), (1, format!("nested more than {MAX_DEPTH} deep")));
+        assert_eq!(parse_error(&format!("{}1", "{\"a\": ".repeat(MAX_DEPTH + 1))).1, format!("nested more than {MAX_DEPTH} deep"));
+        // Far deeper than the stack would take
+        assert!(parse_error(&"[".repeat(1_000_000)).1.starts_with("nested more than"));
+    }
+}
@@ -0,0 +1,94 @@
+use std::mem;
+
+// What `size_of` and `align_of` say about a type, measured on the machine running it. A word is a usize, 8 bytes
+// on 64-bit targets: a &str is two of them (pointer, length) and a String three (pointer, length, capacity).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    pub name: &'static str,
+    pub size: usize,
+    pub align: usize,
+    // The size of Option<T>. It's no bigger than T when some bit pattern is never a valid T (a null pointer, a
+    // bool of 2), None is stored as that niche. Otherwise Option needs room for a separate tag.
+    pub option_size: usize,
+    pub note: &'static str,
+}
+
+impl Layout {
+    pub fn of<T>(name: &'static str, note: &'static str) -> Self {
+        Self {
+            name,
+            size: mem::size_of::<T>(),
+            align: mem::align_of::<T>(),
+            option_size: mem::size_of::<Option<T>>(),
+            note,
+        }
+    }
+
+    pub fn has_niche(&self) -> bool {
+        self.option_size == self.size
+    }
+
+    // The size in words, if it's a whole number of them
+    pub fn words(&self) -> Option<usize> {
+        let word = mem::size_of::<usize>();
+        (self.size > 0 && self.size.is_multiple_of(word)).then_some(self.size / word)
+    }
+}
+
+// Two i32s side by side, nothing to pad
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+// A u8, a u32 and a u8. Rust is free to reorder fields, so the u32 goes first and the two u8s share the rest
+// of its alignment.
+pub struct Reordered {
+    pub first: u8,
+    pub middle: u32,
+    pub last: u8,
+}
+
+// The same fields in the order they're written, as C would lay them out, each u8 padded up to the u32's alignment
+#[repr(C)]
+pub struct InOrder {
+    pub first: u8,
+    pub middle: u32,
+    pub last: u8,
+}
+
+// A slice of a string and where it starts, what a function like `first_word` could hand back
+pub struct Word<'a> {
+    pub text: &'a str,
+    pub start: usize,
+}
+
+// The types the layout lesson measures, from plain integers through the string types to the demo structs
+pub fn layouts() -> Vec<Layout> {
+    vec![
+        Layout::of::<i32>("i32", "any bit pattern is a valid i32, Option needs a separate tag"),
+        Layout::of::<bool>("bool", "one byte, only 0 and 1 are valid"),
+        Layout::of::<char>("char", "a Unicode scalar value, not a byte"),
+        Layout::of::<&str>("&str", "pointer and length, the bytes live elsewhere"),
+        Layout::of::<&String>("&String", "just a pointer, to the String's stack part"),
+        Layout::of::<String>("String", "pointer, length and capacity, the bytes are on the heap"),
+        Layout::of::<Vec<i32>>("Vec<i32>", "the same three words as String"),
+        Layout::of::<Box<i32>>("Box<i32>", "a pointer to the heap, never null, so None is the null pointer"),
+        Layout::of::<Point>("Point", "two i32s"),
+        Layout::of::<Reordered>("Reordered", "u8, u32, u8, reordered to fit in 8"),
+        Layout::of::<InOrder>("InOrder", "the same with #[repr(C)], padded to 12"),
+        Layout::of::<Word>("Word", "a &str and a usize, three words"),
+    ]
+}
+
+// The layouts as lines of a table, one row per type, the Option<T> column says when None fits in a niche
+pub fn table(layouts: &[Layout]) -> Vec<String> {
+    let width = layouts.iter().map(|layout| layout.name.len()).max().unwrap_or(0).max("type".len());
+    let mut lines = vec![format!("{:<width$}  {:>4}  {:>5}  {:>5}  {:<9}  note", "type", "size", "align", "words", "Option<T>")];
+    for layout in layouts {
+        let words = layout.words().map(|words| words.to_string()).unwrap_or_default();
+        let option = if layout.has_niche() { format!("{}, niche", layout.option_size) } else { layout.option_size.to_string() };
+        lines.push(format!("{:<width$}  {:>4}  {:>5}  {words:>5}  {option:<9}  {}", layout.name, layout.size, layout.align, layout.note));
+    }
+    lines
+}
@@ -0,0 +1,229 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::LessonError;
+use crate::json::{Json, ToJson};
+use crate::report::Reporter;
+
+// How much a lesson or exercise expects the learner to know already
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Difficulty {
+    // The first steps, e.g. copying an integer
+    Intro,
+    // The everyday rules, e.g. moves and borrowing
+    #[default]
+    Core,
+    // Where the rules interact in surprising ways, e.g. borrow conflicts and char boundaries
+    Challenge,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Intro, Difficulty::Core, Difficulty::Challenge];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Difficulty::Intro => "intro",
+            Difficulty::Core => "core",
+            Difficulty::Challenge => "challenge",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = LessonError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.as_str().eq_ignore_ascii_case(text))
+            .ok_or_else(|| LessonError::BadInput(format!("unknown difficulty `{text}`, expected intro, core or challenge")))
+    }
+}
+
+// A single topic that can be demonstrated, e.g. moving a String into a function
+pub trait Lesson {
+    // Short identifier used to pick the lesson, e.g. "take_ownership"
+    fn name(&self) -> &str;
+
+    // One-line description of what the lesson shows
+    fn summary(&self) -> &str;
+
+    // Tags such as "ownership" or "slices" used to group lessons
+    fn topics(&self) -> &[&str] {
+        &[]
+    }
+
+    // The longer explanation behind the demo, a few short paragraphs of plain text
+    fn explanation(&self) -> &str {
+        ""
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        Difficulty::Core
+    }
+
+    // Names of the lessons this one builds on, which should be done first
+    fn prerequisites(&self) -> &[&str] {
+        &[]
+    }
+
+    // The function that runs the demo, for `--trace`
+    fn function(&self) -> &str {
+        self.name()
+    }
+
+    // Runs the demo for this lesson, reporting what it does as it goes
+    fn run(&self, reporter: &mut dyn Reporter) -> Result<(), LessonError>;
+}
+
+// Lets borrowed lessons (such as statics) be registered like owned ones
+impl<L: Lesson + ?Sized> Lesson for &L {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn summary(&self) -> &str {
+        (**self).summary()
+    }
+
+    fn topics(&self) -> &[&str] {
+        (**self).topics()
+    }
+
+    fn explanation(&self) -> &str {
+        (**self).explanation()
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        (**self).difficulty()
+    }
+
+    fn prerequisites(&self) -> &[&str] {
+        (**self).prerequisites()
+    }
+
+    fn function(&self) -> &str {
+        (**self).function()
+    }
+
+    fn run(&self, reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+        (**self).run(reporter)
+    }
+}
+
+// The lesson's description, without running it
+impl ToJson for dyn Lesson + '_ {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("name", self.name().into()),
+            ("summary", self.summary().into()),
+            ("topics", Json::array(self.topics().iter().copied())),
+            ("difficulty", self.difficulty().as_str().into()),
+            ("prerequisites", Json::array(self.prerequisites().iter().copied())),
+        ])
+    }
+}
+
+// Where a module's lessons come in the curriculum, its `TAUGHT`. The modules of a day are taught from the
+// lowest `order` up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Taught {
+    pub day: u8,
+    pub order: u8,
+}
+
+// A lesson described by data, built with the `lesson!` macro
+pub struct LessonDef {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub topics: &'static [&'static str],
+    pub explanation: &'static str,
+    pub difficulty: Difficulty,
+    pub prerequisites: &'static [&'static str],
+    pub run: fn(&mut dyn Reporter) -> Result<(), LessonError>,
+    // The name of `run`, the macro fills it in
+    pub function: &'static str,
+}
+
+impl LessonDef {
+    // Defaults for every field the `lesson!` macro doesn't set
+    pub const EMPTY: LessonDef = LessonDef {
+        name: "",
+        summary: "",
+        topics: &[],
+        explanation: "",
+        difficulty: Difficulty::Core,
+        prerequisites: &[],
+        run: |_| Ok(()),
+        function: "",
+    };
+}
+
+impl Lesson for LessonDef {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn summary(&self) -> &str {
+        self.summary
+    }
+
+    fn topics(&self) -> &[&str] {
+        self.topics
+    }
+
+    fn explanation(&self) -> &str {
+        self.explanation
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    fn prerequisites(&self) -> &[&str] {
+        self.prerequisites
+    }
+
+    fn function(&self) -> &str {
+        self.function
+    }
+
+    fn run(&self, reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+        (self.run)(reporter)
+    }
+}
+
+// Wraps a demo function into a `LessonDef`, any field left out falls back to `LessonDef::EMPTY`
+//
+//     pub const TAUGHT: Taught = Taught { day: 6, order: 4 };
+//     pub static LESSONS: &[LessonDef] = &[
+//         lesson!(slicing_demo {
+//             name: "slicing",
+//             summary: "String slices borrow part of a string",
+//             topics: &["slices"],
+//         }),
+//     ];
+//
+// An entry in a module's `LESSONS` is registered, build.rs finds every `LESSONS` with a `TAUGHT` above it and
+// puts it in `registry::MODULES`. A new module only needs its `pub mod`.
+#[macro_export]
+macro_rules! lesson {
+    ($run:path { $($field:ident : $value:expr),* $(,)? }) => {
+        {
+            #[allow(clippy::needless_update)] // Lessons setting every field still go through EMPTY
+            const DEF: $crate::lesson::LessonDef = $crate::lesson::LessonDef {
+                run: $run,
+                function: stringify!($run),
+                $($field: $value,)*
+                ..$crate::lesson::LessonDef::EMPTY
+            };
+            DEF
+        }
+    };
+}
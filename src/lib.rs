@@ -0,0 +1,91 @@
+// The notes behind the lessons (what ownership buys you, the ownership and reference rules) live in `notes`
+
+pub mod achievements;
+pub mod allocations;
+pub mod anki;
+pub mod bench;
+#[cfg(feature = "lessons-ownership")]
+pub mod borrowing;
+#[cfg(feature = "lessons-ownership")]
+pub mod boxes;
+#[cfg(feature = "lessons-concurrency")]
+pub mod channels;
+#[cfg(feature = "lessons-ownership")]
+pub mod closures;
+pub mod config;
+#[cfg(feature = "lessons-ownership")]
+pub mod copy_clone;
+#[cfg(feature = "lessons-ownership")]
+pub mod cow;
+pub mod date;
+pub mod days;
+pub mod deck;
+#[cfg(feature = "lessons-ownership")]
+pub mod deref;
+pub mod diagram;
+pub mod diff;
+pub mod error;
+pub mod events;
+pub mod exercises;
+pub mod explain;
+pub mod flashcards;
+#[cfg(feature = "lessons-ownership")]
+pub mod generics;
+#[cfg(feature = "lessons-collections")]
+pub mod hash_maps;
+#[cfg(feature = "lessons-collections")]
+pub mod iterators;
+pub mod json;
+pub mod layout;
+pub mod lesson;
+#[cfg(feature = "lessons-ownership")]
+pub mod lifetimes;
+#[cfg(feature = "lessons-concurrency")]
+pub mod locks;
+pub mod notes;
+#[cfg(feature = "lessons-ownership")]
+pub mod options;
+pub mod output;
+#[cfg(feature = "lessons-ownership")]
+pub mod ownership;
+pub mod prelude;
+pub mod progress;
+pub mod quiz;
+#[cfg(feature = "lessons-ownership")]
+pub mod raii;
+#[cfg(feature = "lessons-ownership")]
+pub mod rc;
+#[cfg(feature = "lessons-ownership")]
+pub mod refcell;
+pub mod registry;
+pub mod report;
+#[cfg(feature = "lessons-ownership")]
+pub mod results;
+pub mod review;
+pub mod runner;
+pub mod sandbox;
+#[cfg(feature = "lessons-ownership")]
+pub mod scopes;
+pub mod search;
+pub mod shuffle;
+pub mod simulator;
+pub mod slices;
+pub mod source;
+#[cfg(feature = "lessons-ownership")]
+pub mod statics;
+pub mod stats;
+#[cfg(feature = "lessons-ownership")]
+pub mod strings;
+pub mod style;
+#[cfg(feature = "lessons-concurrency")]
+pub mod threads;
+pub mod toml;
+#[cfg(feature = "lessons-ownership")]
+pub mod trait_objects;
+pub mod tracer;
+pub mod tracked;
+pub mod transcript;
+#[cfg(feature = "lessons-collections")]
+pub mod vectors;
+#[cfg(feature = "lessons-ownership")]
+pub mod weak;
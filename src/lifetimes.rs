@@ -0,0 +1,264 @@
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::OwnershipEvent;
+use crate::report::Reporter;
+use crate::simulator::Program;
+use crate::slices;
+
+// Lifetimes are the second reference rule (references must always be valid) written into the types. The elision
+// rules are in `notes` (`rust_day_6 notes lifetimes`)
+
+pub const TAUGHT: Taught = Taught { day: 7, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(longest_demo {
+        name: "longest",
+        summary: "A lifetime ties a returned reference to the references passed in",
+        topics: &["lifetimes", "references"],
+        prerequisites: &["slicing"],
+        explanation: "`fn longest<'a>(x: &'a str, y: &'a str) -> &'a str` says the result lives as long as both arguments do, the shorter of their lifetimes. Without the `'a` rustc can't tell whether the result borrows from x or from y and asks for one (E0106). A lifetime doesn't make anything live longer, it describes how long the references already live so the borrow checker can check the caller.",
+    }),
+    lesson!(struct_lifetimes_demo {
+        name: "struct_lifetimes",
+        summary: "A struct holding a &str can't outlive the text it borrows",
+        topics: &["lifetimes", "references"],
+        prerequisites: &["longest"],
+        explanation: "A struct field can be a reference, but then the struct needs a lifetime parameter, `struct Excerpt<'a> { part: &'a str }`, which says an Excerpt is only valid while the string it points into is. Methods on it usually need no annotations: with `&self` among the inputs, a returned reference is assumed to borrow from self.",
+    }),
+    lesson!(lifetime_elision_demo {
+        name: "lifetime_elision",
+        summary: "The three rules that let most signatures leave lifetimes out",
+        topics: &["lifetimes", "references"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["struct_lifetimes"],
+        explanation: "rustc fills in lifetimes it can work out. Each reference parameter gets its own lifetime (rule 1), with exactly one input lifetime the outputs get it (rule 2), and with `&self` or `&mut self` the outputs get self's (rule 3). When the rules don't settle the output, as with two `&str` parameters, you have to write it.",
+    }),
+    lesson!(dangling_references_demo {
+        name: "dangling_references",
+        summary: "Why a function can't return a reference to its own local variable",
+        topics: &["lifetimes", "references"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["lifetime_elision"],
+        explanation: "A local is dropped when its function returns, so a reference to it would point at freed memory. With no parameters there's nothing for the result to borrow from (E0106), and adding `'a` only moves the error to the `&s` (E0515): no lifetime the caller picks can outlast the function. Return the owned value instead and ownership moves out to the caller.",
+    }),
+];
+
+// Returns whichever string slice is longer, the result is only valid while both arguments are
+pub fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+// Only x can come back, so y gets a lifetime of its own and may end sooner
+pub fn first<'a>(x: &'a str, _y: &str) -> &'a str {
+    x
+}
+
+// The result of `longest` has to be used while both strings are alive, `first`'s only while x is
+pub fn longest_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let string1 = String::from("long string is long");
+    {
+        let string2 = String::from("xyz");
+        reporter.event(&OwnershipEvent::borrowed("string1", "x"));
+        reporter.event(&OwnershipEvent::borrowed("string2", "y"));
+        let result = longest(string1.as_str(), string2.as_str());
+        reporter.message(&format!("the longest string is {result}")); // Fine, string2 is still alive here
+    }
+    reporter.event(&OwnershipEvent::dropped("string2"));
+
+    let kept;
+    {
+        let string3 = String::from("short");
+        reporter.event(&OwnershipEvent::borrowed("string1", "x"));
+        kept = first(string1.as_str(), string3.as_str()); // Had this been `longest`, using kept below would be E0597
+    }
+    reporter.event(&OwnershipEvent::dropped("string3"));
+    reporter.message(&format!("first kept: {kept}")); // string3 is gone, but kept only borrows from string1
+    Ok(())
+}
+
+// An excerpt of some text, only valid while the text is
+pub struct Excerpt<'a> {
+    pub part: &'a str,
+}
+
+impl Excerpt<'_> {
+    // The part without a leading `prefix`. Rule 3 of elision: the returned &str borrows from self, not from prefix
+    pub fn without_prefix(&self, prefix: &str) -> &str {
+        self.part.strip_prefix(prefix).unwrap_or(self.part)
+    }
+}
+
+// The excerpt borrows the first sentence of the novel, the novel has to outlive it
+pub fn struct_lifetimes_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let novel = String::from("Call me Ishmael. Some years ago...");
+    let first_sentence = novel.split('.').next().unwrap_or_default();
+    let excerpt = Excerpt { part: first_sentence };
+    reporter.event(&OwnershipEvent::borrowed("novel", "excerpt.part"));
+    reporter.message(&format!("excerpt: {}", excerpt.part));
+
+    let prefix = String::from("Call me ");
+    reporter.event(&OwnershipEvent::borrowed("excerpt", "self"));
+    reporter.event(&OwnershipEvent::borrowed("prefix", "prefix"));
+    let name = excerpt.without_prefix(&prefix);
+    drop(prefix); // name borrows from excerpt (so from novel), the prefix can go
+    reporter.event(&OwnershipEvent::dropped("prefix"));
+    reporter.message(&format!("without_prefix: {name}"));
+    Ok(())
+}
+
+// Signatures as written, what rustc reads them as after elision, and the rules that got it there
+const ELIDED: &[(&str, &str, &str)] = &[
+    ("fn print(s: &str, n: &usize)", "fn print<'a, 'b>(s: &'a str, n: &'b usize)", "rule 1, every reference parameter gets its own lifetime"),
+    ("fn first_word(text: &str) -> &str", "fn first_word<'a>(text: &'a str) -> &'a str", "rules 1 and 2, the one input lifetime goes to the output"),
+    ("fn without_prefix(&self, prefix: &str) -> &str", "fn without_prefix<'a, 'b>(&'a self, prefix: &'b str) -> &'a str", "rules 1 and 3, the output gets self's"),
+    ("fn longest(x: &str, y: &str) -> &str", "nothing, rustc asks for a lifetime (E0106)", "rule 1 gives x and y one each, rules 2 and 3 don't apply"),
+];
+
+// Goes through the elision rules, then calls the elided functions to show they work as rustc read them
+pub fn lifetime_elision_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    for (index, (written, elided, rules)) in ELIDED.iter().enumerate() {
+        if index > 0 {
+            reporter.message("");
+        }
+        reporter.message(&format!("    {written}"));
+        reporter.message(&format!("  = {elided}"));
+        reporter.message(&format!("    ({rules})"));
+    }
+    reporter.message("");
+
+    let sentence = String::from("hello world");
+    reporter.event(&OwnershipEvent::borrowed("sentence", "text"));
+    let word = slices::first_word(&sentence); // Rule 2: word borrows from sentence
+    reporter.message(&format!("first_word: {word}"));
+
+    let excerpt = Excerpt { part: &sentence };
+    reporter.event(&OwnershipEvent::borrowed("sentence", "excerpt.part"));
+    reporter.message(&format!("without_prefix: {}", excerpt.without_prefix("hello "))); // Rule 3: borrows from excerpt
+    Ok(())
+}
+
+// Returning a reference to a local and what rustc says about it, cut short after the first help line
+const DANGLING: &[(&str, &str)] = &[
+    (
+        "fn dangle() -> &String {\n    let s = String::from(\"hello\");\n    &s\n}",
+        "error[E0106]: missing lifetime specifier\n --> src/main.rs:1:16\n  |\n1 | fn dangle() -> &String {\n  |                ^ expected named lifetime parameter\n  |\n  = help: this function's return type contains a borrowed value, but there is no value for it to be borrowed from",
+    ),
+    (
+        "fn dangle<'a>() -> &'a String {\n    let s = String::from(\"hello\");\n    &s\n}",
+        "error[E0515]: cannot return reference to local variable `s`\n --> src/main.rs:3:5\n  |\n3 |     &s\n  |     ^^ returns a reference to data owned by the current function",
+    ),
+];
+
+// The same thing without a function, a reference that outlives the scope of what it borrows
+const OUTLIVES: &str = "let r;\n{\n    let s = String::from(\"hello\");\n    r = &s;\n}\nprintln!(\"{}\", r);";
+
+// Returns the String itself, ownership moves out to the caller and nothing dangles
+#[allow(clippy::let_and_return)] // Kept as the same local `dangle` borrowed from, only the return changes
+pub fn no_dangle() -> String {
+    let s = String::from("hello");
+    s
+}
+
+// The rejected versions and why, then the one that compiles
+pub fn dangling_references_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    for (code, error) in DANGLING {
+        for line in code.lines() {
+            reporter.message(&format!("    {line}"));
+        }
+        for line in error.lines() {
+            reporter.message(line);
+        }
+        reporter.message("");
+    }
+
+    // s is dropped at the end of its scope like it would be at the end of dangle, the simulator says why
+    let program = Program::parse(OUTLIVES)?;
+    for line in &program.lines {
+        reporter.message(&format!("    {line}"));
+    }
+    if let Err(rejection) = program.check() {
+        for line in program.render(&rejection).lines() {
+            reporter.message(line);
+        }
+    }
+    reporter.message("");
+
+    let string = no_dangle();
+    reporter.event(&OwnershipEvent::moved("s", "string"));
+    reporter.message(&format!("no_dangle: {string}"));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::DemoOutput;
+
+    #[test]
+    fn longest_returns_the_longer_one() {
+        assert_eq!(longest("long string is long", "xyz"), "long string is long");
+        assert_eq!(longest("ab", "xyz"), "xyz");
+        // A tie goes to y
+        assert_eq!(longest("abc", "xyz"), "xyz");
+        assert_eq!(first("abc", "a much longer one"), "abc");
+    }
+
+    #[test]
+    fn without_prefix_borrows_from_the_excerpt() {
+        let excerpt = Excerpt { part: "Call me Ishmael" };
+        assert_eq!(excerpt.without_prefix("Call me "), "Ishmael");
+        assert_eq!(excerpt.without_prefix("Hello "), "Call me Ishmael");
+    }
+
+    #[test]
+    fn longest_demo_is_done_with_string2_before_it_is_dropped() {
+        let mut output = DemoOutput::new();
+        longest_demo(&mut output).unwrap();
+        assert_eq!(output.messages, ["the longest string is long string is long", "first kept: long string is long"]);
+        assert_eq!(
+            output.events,
+            [
+                OwnershipEvent::borrowed("string1", "x"),
+                OwnershipEvent::borrowed("string2", "y"),
+                OwnershipEvent::dropped("string2"),
+                OwnershipEvent::borrowed("string1", "x"),
+                OwnershipEvent::dropped("string3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn struct_lifetimes_demo_drops_the_prefix_before_using_the_result() {
+        let mut output = DemoOutput::new();
+        struct_lifetimes_demo(&mut output).unwrap();
+        assert_eq!(output.messages, ["excerpt: Call me Ishmael", "without_prefix: Ishmael"]);
+        assert_eq!(output.events.last(), Some(&OwnershipEvent::dropped("prefix")));
+    }
+
+    #[test]
+    fn lifetime_elision_demo_shows_every_rule_then_uses_them() {
+        let mut output = DemoOutput::new();
+        lifetime_elision_demo(&mut output).unwrap();
+        for (written, elided, _) in ELIDED {
+            assert!(output.messages.contains(&format!("    {written}")), "{written}");
+            assert!(output.messages.contains(&format!("  = {elided}")), "{elided}");
+        }
+        assert_eq!(output.messages[output.messages.len() - 2..], ["first_word: hello", "without_prefix: world"]);
+    }
+
+    #[test]
+    fn dangling_references_demo_is_rejected_until_it_returns_the_string() {
+        let mut output = DemoOutput::new();
+        dangling_references_demo(&mut output).unwrap();
+        for code in ["error[E0106]", "error[E0515]", "error[E0597]: `s` does not live long enough"] {
+            assert!(output.messages.iter().any(|message| message.starts_with(code)), "{code}");
+        }
+        assert_eq!(output.messages.last().map(String::as_str), Some("no_dangle: hello"));
+        assert_eq!(output.events, [OwnershipEvent::moved("s", "string")]);
+        assert_eq!(no_dangle(), "hello");
+    }
+}
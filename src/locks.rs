@@ -0,0 +1,184 @@
+use std::sync::{Arc, Mutex, PoisonError, RwLock, TryLockError};
+use std::thread;
+
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::OwnershipEvent;
+use crate::report::Reporter;
+use crate::runner;
+
+// A lock checks the borrowing rules at runtime, like a RefCell, but across threads, and instead of panicking it
+// makes the second thread wait. Mutex::lock() is a `&mut` handed to one thread at a time, RwLock::read() a `&`
+// that any number of threads can hold while nobody writes. The guard is the borrow, dropping it unlocks, so the
+// demos log taking a lock as a mutable (or shared) borrow by the guard and unlocking as the guard's drop, which
+// is how `run --timeline` draws them.
+
+pub const TAUGHT: Taught = Taught { day: 12, order: 2 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(mutex_counter_demo {
+        name: "mutex_counter",
+        summary: "Arc<Mutex<T>> lets several threads change one value, one thread at a time",
+        topics: &["concurrency", "borrowing"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["race_conditions", "refcell_basics"],
+        explanation: "`counter.lock()` waits until no other thread holds the lock, then returns a MutexGuard that derefs to `&mut T`: while it's alive, this thread has the only mutable borrow of the value, and every other `lock()` waits. Dropping the guard unlocks. With the Mutex in an Arc, each thread gets its own pointer and they all take turns on the one counter, so no increment is lost. `lock()` returns a Result because a thread that panics while holding the guard poisons the lock.",
+    }),
+    lesson!(rwlock_demo {
+        name: "rwlock",
+        summary: "RwLock allows many readers or one writer, like & and &mut",
+        topics: &["concurrency", "borrowing"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["mutex_counter"],
+        explanation: "`read()` returns a guard that derefs to `&T`, and any number of threads can hold one at once. `write()` returns one that derefs to `&mut T`, and waits until there are no readers and no other writer. That's the reference rule, any number of `&` or one `&mut`, checked while the program runs. It suits values that are read much more often than they change, where a Mutex would make the readers queue for no reason.",
+    }),
+];
+
+// A thread panicked while holding the lock, the value might be half-changed
+fn poisoned<T>(err: PoisonError<T>) -> LessonError {
+    LessonError::Panicked(err.to_string())
+}
+
+// One thread's share of the counting, locking once per increment. The first lock's borrow and drop are the
+// events it hands back, the rest look the same.
+fn count_up(counter: &Mutex<u32>, index: usize, times: u32) -> Result<Vec<OwnershipEvent>, LessonError> {
+    let guard_name = format!("guard {index}");
+    let mut events = Vec::new();
+    for time in 0..times {
+        let mut guard = counter.lock().map_err(poisoned)?; // Waits here while another thread holds it
+        *guard += 1;
+        drop(guard); // Unlocks, the next thread waiting can go
+        if time == 0 {
+            events.push(OwnershipEvent::borrowed_mut("counter", &guard_name));
+            events.push(OwnershipEvent::dropped(&guard_name));
+        }
+    }
+    Ok(events)
+}
+
+// Four threads counting to a thousand between them, then the same lock held on this thread
+pub fn mutex_counter_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let counter = Arc::new(Mutex::new(0));
+    let handles: Vec<_> = (0..4)
+        .map(|index| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || count_up(&counter, index, 250))
+        })
+        .collect();
+    for (index, handle) in handles.into_iter().enumerate() {
+        // Each thread's events are reported after it's joined, in order, so the output is the same every run
+        for event in handle.join().map_err(runner::panicked)?? {
+            reporter.event(&event);
+        }
+        reporter.message(&format!("thread {index} locked the counter 250 times"));
+    }
+    reporter.message(&format!("counter: {}, no increment was lost", *counter.lock().map_err(poisoned)?));
+
+    let guard = counter.lock().map_err(poisoned)?;
+    reporter.event(&OwnershipEvent::borrowed_mut("counter", "guard"));
+    let blocked = matches!(counter.try_lock(), Err(TryLockError::WouldBlock));
+    reporter.message(&format!("try_lock while guard is alive would block: {blocked}"));
+    drop(guard);
+    reporter.event(&OwnershipEvent::dropped("guard"));
+    reporter.message(&format!("try_lock after the guard is dropped: {}", counter.try_lock().is_ok()));
+
+    if let Ok(mutex) = Arc::try_unwrap(counter) {
+        // The threads dropped their Arcs, so this is the only owner and can take the value out
+        let total = mutex.into_inner().map_err(poisoned)?;
+        reporter.event(&OwnershipEvent::moved("counter", "total"));
+        reporter.message(&format!("into_inner: {total}"));
+    }
+    Ok(())
+}
+
+// Two readers at once, a writer once they're gone, then three threads reading together
+pub fn rwlock_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let config = Arc::new(RwLock::new(vec![String::from("verbose")]));
+    let first = config.read().map_err(poisoned)?;
+    let second = config.read().map_err(poisoned)?; // A second reader doesn't wait for the first
+    reporter.event(&OwnershipEvent::borrowed("config", "first"));
+    reporter.event(&OwnershipEvent::borrowed("config", "second"));
+    reporter.message(&format!("first: {:?}, second: {:?}", *first, *second));
+    let blocked = matches!(config.try_write(), Err(TryLockError::WouldBlock));
+    reporter.message(&format!("try_write while they read would block: {blocked}"));
+    drop(first);
+    drop(second);
+    reporter.event(&OwnershipEvent::dropped("first"));
+    reporter.event(&OwnershipEvent::dropped("second"));
+
+    let mut writer = config.write().map_err(poisoned)?; // No readers left, so the write lock is free
+    reporter.event(&OwnershipEvent::borrowed_mut("config", "writer"));
+    writer.push(String::from("colour"));
+    drop(writer);
+    reporter.event(&OwnershipEvent::dropped("writer"));
+
+    let handles: Vec<_> = (0..3)
+        .map(|index| {
+            let config = Arc::clone(&config);
+            thread::spawn(move || -> Result<String, LessonError> {
+                let settings = config.read().map_err(poisoned)?; // All three may hold a read guard at the same time
+                Ok(format!("reader {index} sees {:?}", *settings))
+            })
+        })
+        .collect();
+    for handle in handles {
+        reporter.message(&handle.join().map_err(runner::panicked)??);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::DemoOutput;
+
+    #[test]
+    fn count_up_reports_only_its_first_lock() {
+        let counter = Mutex::new(0);
+        let events = count_up(&counter, 2, 5).unwrap();
+        assert_eq!(events, [OwnershipEvent::borrowed_mut("counter", "guard 2"), OwnershipEvent::dropped("guard 2")]);
+        assert_eq!(*counter.lock().unwrap(), 5);
+    }
+
+    #[test]
+    fn a_poisoned_lock_is_a_panicked_error() {
+        let counter = Mutex::new(0);
+        let _ = thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let _guard = counter.lock().unwrap();
+                    panic!("dropped the guard while panicking");
+                })
+                .join()
+        });
+        assert!(matches!(count_up(&counter, 0, 1), Err(LessonError::Panicked(_))));
+    }
+
+    #[test]
+    fn mutex_counter_demo_loses_no_increments() {
+        let mut output = DemoOutput::new();
+        mutex_counter_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages[4..],
+            ["counter: 1000, no increment was lost", "try_lock while guard is alive would block: true", "try_lock after the guard is dropped: true", "into_inner: 1000"]
+        );
+        assert_eq!(output.events.last(), Some(&OwnershipEvent::moved("counter", "total")));
+    }
+
+    #[test]
+    fn rwlock_demo_readers_share_and_see_the_write() {
+        let mut output = DemoOutput::new();
+        rwlock_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            [
+                "first: [\"verbose\"], second: [\"verbose\"]",
+                "try_write while they read would block: true",
+                "reader 0 sees [\"verbose\", \"colour\"]",
+                "reader 1 sees [\"verbose\", \"colour\"]",
+                "reader 2 sees [\"verbose\", \"colour\"]",
+            ]
+        );
+        assert_eq!(output.events[4], OwnershipEvent::borrowed_mut("config", "writer"));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::json::{Json, ToJson};
+
+// A short list of rules or facts about one topic, the cheatsheet behind the lessons
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Note {
+    pub topic: &'static str,
+    pub title: &'static str,
+    pub items: &'static [&'static str],
+    // Rules are numbered so lessons can refer to them ("rule 2"), other notes are bullet points
+    pub numbered: bool,
+}
+
+pub static NOTES: &[Note] = &[
+    Note {
+        topic: "ownership",
+        title: "Ownership in Rust",
+        items: &[
+            "Gives control over memory",
+            "Error free",
+            "Faster runtime than Garbage Collection",
+            "Small program size",
+            "Slower write time and learning curve",
+        ],
+        numbered: false,
+    },
+    Note {
+        topic: "ownership",
+        title: "Stack and heap",
+        items: &[
+            "Dynamic size variables get stored on the heap.",
+            "Static size variables get stored on the stack.",
+        ],
+        numbered: false,
+    },
+    Note {
+        topic: "ownership",
+        title: "Ownership rules",
+        items: &[
+            "Each value in Rust has a variable called its owner",
+            "There can't be more than one owner at a time",
+            "When the owner goes out of scope, the value will be dropped",
+        ],
+        numbered: true,
+    },
+    Note {
+        topic: "references",
+        title: "Reference rules",
+        items: &[
+            "In any scope, you can have either one mutable, or any number of immutable references",
+            "References must always be valid (They can't be from a function that is dropped)",
+        ],
+        numbered: true,
+    },
+    Note {
+        topic: "lifetimes",
+        title: "Lifetime elision rules",
+        items: &[
+            "Each reference parameter gets its own lifetime",
+            "If there's exactly one input lifetime, it's given to every output reference",
+            "If one of the parameters is &self or &mut self, its lifetime is given to every output reference",
+        ],
+        numbered: true,
+    },
+    Note {
+        topic: "slices",
+        title: "Slices",
+        items: &[
+            "Slices can be used on collections such as strings, vectors, arrays, and hash maps",
+            "A slice borrows part of a collection, it doesn't own or copy the data",
+            "`&str` is a string slice, string literals are `&str` too",
+            "String slice indexes are byte offsets and must fall on character boundaries",
+        ],
+        numbered: false,
+    },
+];
+
+// Every topic that has notes, in the order they appear
+pub fn topics() -> Vec<&'static str> {
+    let mut topics: Vec<&str> = NOTES.iter().map(|note| note.topic).collect();
+    topics.dedup();
+    topics
+}
+
+// The notes for one topic, or all of them
+pub fn notes(topic: Option<&str>) -> impl Iterator<Item = &'static Note> + '_ {
+    NOTES.iter().filter(move |note| topic.is_none_or(|topic| note.topic == topic))
+}
+
+impl ToJson for Note {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("topic", self.topic.into()),
+            ("title", self.title.into()),
+            ("items", Json::array(self.items.iter().copied())),
+            ("numbered", self.numbered.into()),
+        ])
+    }
+}
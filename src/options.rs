@@ -0,0 +1,315 @@
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+
+// An Option<String> owns its String the way a variable does. Getting at it means moving it out, borrowing it or
+// swapping something else in, and moving out from behind a reference is where "cannot move out of" (E0507) first
+// comes up.
+
+pub const TAUGHT: Taught = Taught { day: 6, order: 6 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(option_take_demo {
+        name: "option_take",
+        summary: "take() moves a value out of an Option behind a &mut, leaving None",
+        topics: &["ownership", "options"],
+        prerequisites: &["mutate_reference"],
+        explanation: "A `&mut` lets you change a value but not take it away, the owner still expects something to be there. `self.item` on its own would move the Option out from behind `&mut self`, which is E0507. `Option::take` swaps in None and hands back what was there, `Option::replace` swaps in a new value instead, so the struct always has a valid Option.",
+    }),
+    lesson!(option_as_ref_demo {
+        name: "option_as_ref",
+        summary: "as_ref() looks inside an Option without moving what's in it",
+        topics: &["borrowing", "options"],
+        prerequisites: &["reference"],
+        explanation: "Methods like `map` and `unwrap_or` take the Option by value, so on an `Option<String>` they move the String out and the Option is gone. `as_ref()` turns an `&Option<String>` into an `Option<&String>`, which those methods can use up while the original keeps its String. `as_deref()` goes one step further, to an `Option<&str>`.",
+    }),
+    lesson!(option_match_demo {
+        name: "option_match",
+        summary: "Matching an Option by value moves out of it, matching a reference borrows",
+        topics: &["ownership", "options"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["option_take", "option_as_ref"],
+        explanation: "`match guest { Some(name) => ... }` binds `name` by value, moving the String out of guest. Matching on `&guest` instead makes every binding a reference, `name` is a `&String` and guest is untouched. Writing `match *guest` on a reference asks for the by-value match again, and rustc refuses to move the String out from behind the reference (E0507).",
+    }),
+    lesson!(binding_modes_demo {
+        name: "binding_modes",
+        summary: "ref, ref mut and default binding modes decide whether a pattern moves or borrows",
+        topics: &["ownership", "patterns"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["option_match"],
+        explanation: "A binding in a pattern takes what it matches by value, and for a String that's a move. `ref name` binds a `&String` instead and `ref mut name` a `&mut String`, so the value stays where it is. Matching on a reference, `let (name, score) = &mut entry;` or `for (name, score) in &entries`, changes the default binding mode: every binding becomes a `ref` or `ref mut` without writing it. Copy values like a u32 are copied when bound by value, which never moves anything.",
+    }),
+];
+
+// Code that rustc rejects and what it says, cut short before the suggestions
+#[track_caller] // So `show` puts the rejection next to the demo line that reported it
+fn report_rejected(reporter: &mut dyn Reporter, code: &str, error: &str) {
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+}
+
+// A slot that may hold an item, emptied and filled through &mut self
+pub struct Slot {
+    pub item: Option<String>,
+}
+
+impl Slot {
+    // Hands over the item and leaves None behind
+    pub fn take_item(&mut self) -> Option<String> {
+        self.item.take()
+    }
+
+    // Puts a new item in and hands back the old one, if there was one
+    pub fn replace_item(&mut self, item: String) -> Option<String> {
+        self.item.replace(item)
+    }
+}
+
+const MOVE_OUT_OF_SELF: (&str, &str) = (
+    "fn take_item(&mut self) -> Option<String> {\n    self.item\n}",
+    "error[E0507]: cannot move out of `self.item` which is behind a mutable reference\n --> src/main.rs:2:5\n  |\n2 |     self.item\n  |     ^^^^^^^^^ move occurs because `self.item` has type `Option<String>`, which does not implement the `Copy` trait",
+);
+
+// Empties the slot with take, then fills it with replace, the slot is never left without an Option
+pub fn option_take_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    report_rejected(reporter, MOVE_OUT_OF_SELF.0, MOVE_OUT_OF_SELF.1);
+
+    let mut slot = Slot { item: Some(String::from("sword")) };
+    reporter.event(&OwnershipEvent::borrowed_mut("slot", "self"));
+    let taken = slot.take_item(); // The String moves out, None moves in
+    reporter.event(&OwnershipEvent::moved("slot.item", "taken"));
+    reporter.message(&format!("taken: {taken:?}, slot.item: {:?}", slot.item));
+
+    reporter.event(&OwnershipEvent::borrowed_mut("slot", "self"));
+    let old = slot.replace_item(String::from("shield")); // Nothing was there, so nothing comes back
+    reporter.message(&format!("old: {old:?}, slot.item: {:?}", slot.item));
+
+    reporter.event(&OwnershipEvent::borrowed_mut("slot", "self"));
+    let old = slot.replace_item(String::from("bow"));
+    reporter.event(&OwnershipEvent::moved("slot.item", "old"));
+    reporter.message(&format!("old: {old:?}, slot.item: {:?}", slot.item));
+    Ok(())
+}
+
+// Measures a maybe-missing name through a reference, as_ref keeps the String where it is
+pub fn name_len(name: &Option<String>) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    let len = name.as_ref().map(|name| name.len()); // An Option<&String>, map uses up the reference, not the String
+    output.value("len", format!("{len:?}"));
+    output.message(format!("{}: {} bytes", name.as_deref().unwrap_or("nothing"), len.unwrap_or(0)));
+    output
+}
+
+// Borrows through as_ref and as_deref, then lets map take the Option by value
+pub fn option_as_ref_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let item = Some(String::from("lantern"));
+    reporter.event(&OwnershipEvent::borrowed("item", "name"));
+    reporter.output(&name_len(&item));
+    reporter.output(&name_len(&None));
+
+    let shout = item.as_ref().map(|name| name.to_uppercase()); // A new String, item still has its own
+    reporter.event(&OwnershipEvent::borrowed("item", "name"));
+    reporter.message(&format!("shout: {shout:?}, item: {item:?}"));
+
+    let first = item.as_deref().and_then(|name| name.chars().next()); // Option<&str>, handy for comparing with literals
+    reporter.message(&format!("first: {first:?}, is a lantern: {}", item.as_deref() == Some("lantern")));
+
+    let len = item.map(|name| name.len()); // No as_ref, the String moves into the closure and is dropped there
+    reporter.event(&OwnershipEvent::moved("item", "name"));
+    reporter.event(&OwnershipEvent::dropped("name"));
+    reporter.message(&format!("len: {len:?}, item: error"));
+    Ok(())
+}
+
+// Greets through a reference, matching on it binds `name` as a &String
+pub fn greeting(visitor: &Option<String>) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    match visitor {
+        Some(name) => output.message(format!("hello, {name}")),
+        None => output.message("nobody to greet"),
+    };
+    output
+}
+
+const MOVE_OUT_OF_REFERENCE: (&str, &str) = (
+    "fn greeting(visitor: &Option<String>) -> String {\n    match *visitor {\n        Some(name) => name,\n        None => String::new(),\n    }\n}",
+    "error[E0507]: cannot move out of `visitor` as enum variant `Some` which is behind a shared reference\n --> src/main.rs:2:11\n  |\n2 |     match *visitor {\n  |           ^^^^^^^^\n3 |         Some(name) => name,\n  |              ----\n  |              |\n  |              data moved here\n  |              move occurs because `name` has type `String`, which does not implement the `Copy` trait",
+);
+
+// Matches by reference twice, then by value, which moves the String out of guest
+pub fn option_match_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    report_rejected(reporter, MOVE_OUT_OF_REFERENCE.0, MOVE_OUT_OF_REFERENCE.1);
+
+    let guest = Some(String::from("Ferris"));
+    reporter.event(&OwnershipEvent::borrowed("guest", "visitor"));
+    reporter.output(&greeting(&guest));
+
+    if let Some(name) = &guest {
+        // name is a &String, guest keeps its String
+        reporter.event(&OwnershipEvent::borrowed("guest", "name"));
+        reporter.message(&format!("{name} is still in guest: {guest:?}"));
+    }
+
+    if let Some(name) = guest {
+        // By value, the String moves out of guest and into name
+        reporter.event(&OwnershipEvent::moved("guest", "name"));
+        reporter.message(&format!("{name} moved out"));
+    }
+    reporter.message("guest: error"); // Partially moved, its String is gone
+    Ok(())
+}
+
+// The highest score, `for (name, score) in board` over a slice binds both by reference, name is a &String
+pub fn leader(board: &[(String, u32)]) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    let mut best: Option<(&String, &u32)> = None;
+    for (name, score) in board {
+        if best.is_none_or(|(_, best)| score > best) {
+            best = Some((name, score));
+        }
+    }
+    match best {
+        Some((name, score)) => output.message(format!("leader: {name} with {score}")),
+        None => output.message("no entries"),
+    };
+    output
+}
+
+// The same tuple bound with ref, ref mut, through a &mut and finally by value, which moves its String out
+pub fn binding_modes_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let mut entry = (String::from("ferris"), 3);
+    let (ref name, score) = entry; // name is a &String, score a copy of the u32
+    reporter.event(&OwnershipEvent::borrowed("entry.0", "name"));
+    reporter.event(&OwnershipEvent::copied("entry.1", "score"));
+    reporter.message(&format!("name: {name}, score: {score}, entry: {entry:?}"));
+
+    let (ref mut name, _) = entry; // A &mut String, the change is made to entry's String
+    reporter.event(&OwnershipEvent::borrowed_mut("entry.0", "name"));
+    name.push_str("_the_crab");
+    reporter.message(&format!("entry: {entry:?}"));
+
+    let (name, score) = &mut entry; // Matching on a &mut, both bindings are ref mut by default
+    reporter.event(&OwnershipEvent::borrowed_mut("entry", "name"));
+    name.push('!');
+    *score += 1;
+    reporter.message(&format!("entry: {entry:?}"));
+
+    let entries = vec![entry.clone(), (String::from("corro"), 7)];
+    reporter.event(&OwnershipEvent::cloned("entry", "entries[0]"));
+    reporter.event(&OwnershipEvent::borrowed("entries", "board"));
+    reporter.output(&leader(&entries));
+
+    let mut guest = Some(String::from("corro"));
+    if let Some(ref mut name) = guest {
+        // Changed in place, guest keeps its String
+        reporter.event(&OwnershipEvent::borrowed_mut("guest", "name"));
+        name.make_ascii_uppercase();
+    }
+    reporter.message(&format!("guest: {guest:?}"));
+
+    let (name, score) = entry; // By value, the String moves out and the u32 is copied
+    reporter.event(&OwnershipEvent::moved("entry.0", "name"));
+    reporter.event(&OwnershipEvent::copied("entry.1", "score"));
+    reporter.message(&format!("name: {name}, score: {score}, entry.1: {}", entry.1)); // entry.1 is still there, entry.0 isn't
+    reporter.message("entry.0: error, entry: error");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn take_and_replace_always_leave_an_option() {
+        let mut slot = Slot { item: Some(String::from("sword")) };
+        assert_eq!(slot.take_item().as_deref(), Some("sword"));
+        assert_eq!(slot.item, None);
+        assert_eq!(slot.take_item(), None);
+        assert_eq!(slot.replace_item(String::from("shield")), None);
+        assert_eq!(slot.replace_item(String::from("bow")).as_deref(), Some("shield"));
+        assert_eq!(slot.item.as_deref(), Some("bow"));
+    }
+
+    #[test]
+    fn name_len_and_greeting_only_borrow() {
+        let name = Some(String::from("lantern"));
+        assert_eq!(name_len(&name).messages, ["lantern: 7 bytes"]);
+        assert_eq!(name_len(&None).values, [("len".to_string(), "None".to_string())]);
+        assert_eq!(greeting(&name).messages, ["hello, lantern"]);
+        assert_eq!(greeting(&None).messages, ["nobody to greet"]);
+        assert_eq!(name.as_deref(), Some("lantern"));
+    }
+
+    #[test]
+    fn option_take_demo_empties_then_refills_the_slot() {
+        let mut output = DemoOutput::new();
+        option_take_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0507]")));
+        assert_eq!(
+            output.messages[output.messages.len() - 3..],
+            ["taken: Some(\"sword\"), slot.item: None", "old: None, slot.item: Some(\"shield\")", "old: Some(\"shield\"), slot.item: Some(\"bow\")"]
+        );
+        assert_eq!(output.events[1], OwnershipEvent::moved("slot.item", "taken"));
+    }
+
+    #[test]
+    fn option_as_ref_demo_borrows_until_map_takes_it() {
+        let mut output = DemoOutput::new();
+        option_as_ref_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            ["lantern: 7 bytes", "nothing: 0 bytes", "shout: Some(\"LANTERN\"), item: Some(\"lantern\")", "first: Some('l'), is a lantern: true", "len: Some(7), item: error"]
+        );
+        assert_eq!(output.events[output.events.len() - 2..], [OwnershipEvent::moved("item", "name"), OwnershipEvent::dropped("name")]);
+    }
+
+    #[test]
+    fn option_match_demo_moves_only_when_matching_by_value() {
+        let mut output = DemoOutput::new();
+        option_match_demo(&mut output).unwrap();
+        assert_eq!(output.messages[output.messages.len() - 4..], ["hello, Ferris", "Ferris is still in guest: Some(\"Ferris\")", "Ferris moved out", "guest: error"]);
+        assert_eq!(
+            output.events,
+            [OwnershipEvent::borrowed("guest", "visitor"), OwnershipEvent::borrowed("guest", "name"), OwnershipEvent::moved("guest", "name")]
+        );
+    }
+
+    #[test]
+    fn leader_keeps_the_first_of_a_tie() {
+        let board = [(String::from("ferris"), 7), (String::from("corro"), 7), (String::from("crab"), 2)];
+        assert_eq!(leader(&board).messages, ["leader: ferris with 7"]);
+        assert_eq!(leader(&[]).messages, ["no entries"]);
+    }
+
+    #[test]
+    fn binding_modes_demo_borrows_then_moves_the_string_out() {
+        let mut output = DemoOutput::new();
+        binding_modes_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            [
+                "name: ferris, score: 3, entry: (\"ferris\", 3)",
+                "entry: (\"ferris_the_crab\", 3)",
+                "entry: (\"ferris_the_crab!\", 4)",
+                "leader: corro with 7",
+                "guest: Some(\"CORRO\")",
+                "name: ferris_the_crab!, score: 4, entry.1: 4",
+                "entry.0: error, entry: error",
+            ]
+        );
+        assert_eq!(output.events[output.events.len() - 2..], [OwnershipEvent::moved("entry.0", "name"), OwnershipEvent::copied("entry.1", "score")]);
+    }
+
+    #[test]
+    fn binding_modes_has_quiz_questions() {
+        let questions: Vec<_> = crate::quiz::bank().into_iter().filter(|question| question.lesson.as_deref() == Some("binding_modes")).collect();
+        assert!(questions.len() >= 4, "{} questions", questions.len());
+        assert!(questions.iter().all(|question| question.topic == "patterns"));
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+
+use crate::json::{Json, ToJson};
+
+// Something that happened to a value's ownership while a demo ran
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OwnershipEvent {
+    // A Copy value was duplicated, both variables stay usable
+    Copy { from: String, to: String },
+    // Ownership moved, `from` can't be used anymore
+    Move { from: String, to: String },
+    // A deep copy was made on the heap, both variables own their own data
+    Clone { from: String, to: String },
+    // `by` borrowed `owner` immutably
+    Borrow { owner: String, by: String },
+    // `by` borrowed `owner` mutably
+    BorrowMut { owner: String, by: String },
+    // The owner went out of scope and its value was dropped
+    Drop { owner: String },
+    // A function started, logged for every demo with `--trace`
+    Call { function: String },
+    // A function returned, handing back ownership of whatever it returns
+    Return { function: String },
+}
+
+impl OwnershipEvent {
+    pub fn copied(from: &str, to: &str) -> Self {
+        Self::Copy { from: from.to_string(), to: to.to_string() }
+    }
+
+    pub fn moved(from: &str, to: &str) -> Self {
+        Self::Move { from: from.to_string(), to: to.to_string() }
+    }
+
+    pub fn cloned(from: &str, to: &str) -> Self {
+        Self::Clone { from: from.to_string(), to: to.to_string() }
+    }
+
+    pub fn borrowed(owner: &str, by: &str) -> Self {
+        Self::Borrow { owner: owner.to_string(), by: by.to_string() }
+    }
+
+    pub fn borrowed_mut(owner: &str, by: &str) -> Self {
+        Self::BorrowMut { owner: owner.to_string(), by: by.to_string() }
+    }
+
+    pub fn dropped(owner: &str) -> Self {
+        Self::Drop { owner: owner.to_string() }
+    }
+
+    pub fn called(function: &str) -> Self {
+        Self::Call { function: function.to_string() }
+    }
+
+    pub fn returned(function: &str) -> Self {
+        Self::Return { function: function.to_string() }
+    }
+}
+
+impl fmt::Display for OwnershipEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Copy { from, to } => write!(f, "{from} copied to {to}"),
+            Self::Move { from, to } => write!(f, "{from} moved to {to}"),
+            Self::Clone { from, to } => write!(f, "{from} cloned to {to}"),
+            Self::Borrow { owner, by } => write!(f, "{owner} borrowed by {by}"),
+            Self::BorrowMut { owner, by } => write!(f, "{owner} mutably borrowed by {by}"),
+            Self::Drop { owner } => write!(f, "{owner} dropped"),
+            Self::Call { function } => write!(f, "entered {function}"),
+            Self::Return { function } => write!(f, "returned from {function}"),
+        }
+    }
+}
+
+// What a demo produced: the lines it would have printed, the values it made and the ownership events along the way
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DemoOutput {
+    pub messages: Vec<String>,
+    // (variable, value) pairs
+    pub values: Vec<(String, String)>,
+    pub events: Vec<OwnershipEvent>,
+}
+
+impl DemoOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Adds a line of output
+    pub fn message(&mut self, message: impl Into<String>) -> &mut Self {
+        self.messages.push(message.into());
+        self
+    }
+
+    // Records a value the demo produced
+    pub fn value(&mut self, name: &str, value: impl fmt::Display) -> &mut Self {
+        self.values.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    // Records an ownership event
+    pub fn event(&mut self, event: OwnershipEvent) -> &mut Self {
+        self.events.push(event);
+        self
+    }
+
+    // Appends everything another demo produced, e.g. a function called by this demo
+    pub fn extend(&mut self, other: DemoOutput) -> &mut Self {
+        self.messages.extend(other.messages);
+        self.values.extend(other.values);
+        self.events.extend(other.events);
+        self
+    }
+}
+
+impl ToJson for OwnershipEvent {
+    fn to_json(&self) -> Json {
+        match self {
+            Self::Copy { from, to } => Json::object([("kind", "copy".into()), ("from", from.into()), ("to", to.into())]),
+            Self::Move { from, to } => Json::object([("kind", "move".into()), ("from", from.into()), ("to", to.into())]),
+            Self::Clone { from, to } => Json::object([("kind", "clone".into()), ("from", from.into()), ("to", to.into())]),
+            Self::Borrow { owner, by } => Json::object([("kind", "borrow".into()), ("owner", owner.into()), ("by", by.into())]),
+            Self::BorrowMut { owner, by } => {
+                Json::object([("kind", "borrow_mut".into()), ("owner", owner.into()), ("by", by.into())])
+            }
+            Self::Drop { owner } => Json::object([("kind", "drop".into()), ("owner", owner.into())]),
+            Self::Call { function } => Json::object([("kind", "call".into()), ("function", function.into())]),
+            Self::Return { function } => Json::object([("kind", "return".into()), ("function", function.into())]),
+        }
+    }
+}
+
+impl ToJson for DemoOutput {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("messages", Json::array(&self.messages)),
+            (
+                "values",
+                Json::array(self.values.iter().map(|(name, value)| Json::object([("name", name.into()), ("value", value.into())]))),
+            ),
+            ("events", Json::array(self.events.iter().map(ToJson::to_json))),
+        ])
+    }
+}
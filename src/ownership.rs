@@ -0,0 +1,415 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::diagram::Diagram;
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+use crate::tracked::{self, Tracker};
+
+// The ownership rules are in `notes` (`rust_day_6 notes ownership`)
+
+pub const TAUGHT: Taught = Taught { day: 6, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(makes_copy_demo {
+        name: "makes_copy",
+        summary: "Simple types are copied instead of moved",
+        topics: &["ownership", "copy"],
+        difficulty: Difficulty::Intro,
+        explanation: "Simple types like integers, bools and chars have a size known at compile time, so they are stored on the stack. Assigning them or passing them to a function copies the bits, and the original variable stays usable.",
+    }),
+    lesson!(take_ownership_demo {
+        name: "take_ownership",
+        summary: "Heap values are moved into new owners and functions",
+        topics: &["ownership", "moves"],
+        prerequisites: &["makes_copy"],
+        explanation: "A String's text has a dynamic size, so it is stored on the heap. Each value has exactly one owner: `let s2 = s1;` moves ownership to s2, and s1 can't be used anymore. `clone()` makes a second copy of the heap data instead, which is more expensive. Passing a String to a function moves it too, and the value is dropped when the function's parameter goes out of scope.",
+    }),
+    lesson!(give_ownership_demo {
+        name: "give_ownership",
+        summary: "Returning a value gives its ownership to the caller",
+        topics: &["ownership", "moves"],
+        prerequisites: &["take_ownership"],
+        explanation: "Returning a value moves it out of the function to whoever called it. The local variable goes out of scope, but the value isn't dropped because it has a new owner.",
+    }),
+    lesson!(takes_and_gives_back_demo {
+        name: "takes_and_gives_back",
+        summary: "A function can take ownership and return it again",
+        topics: &["ownership", "moves"],
+        prerequisites: &["take_ownership", "give_ownership"],
+        explanation: "A function can take ownership of a value and hand it back through its return value. It works, but passing ownership back and forth is tedious, which is what references are for.",
+    }),
+    lesson!(stack_and_heap_demo {
+        name: "stack_and_heap",
+        summary: "What a move and a clone do to a String's stack and heap parts",
+        topics: &["ownership", "moves"],
+        prerequisites: &["take_ownership"],
+        explanation: "A String is three words on the stack, a pointer, a length and a capacity, and the text itself on the heap, where it can grow. `let s2 = s1;` copies only the three words, so both point at the same heap bytes and s1 is made unusable to keep them from being freed twice. `clone()` allocates a new heap block and copies the bytes into it.",
+    }),
+    lesson!(tracked_demo {
+        name: "tracked",
+        summary: "Values that report their own moves, clones and drops",
+        topics: &["ownership", "moves"],
+        prerequisites: &["takes_and_gives_back"],
+        explanation: "`Tracked<T>` wraps a value and logs what happens to it: its Clone impl logs the clone and its Drop impl logs the drop the moment it happens, at the end of the owner's scope or when a function that took ownership returns. Run it with --verbose to watch the events in order.",
+    }),
+    lesson!(drop_order_demo {
+        name: "drop_order",
+        summary: "The order values are dropped in, in a scope, in a struct and in nested scopes",
+        topics: &["ownership", "scope"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["tracked"],
+        explanation: "A value is dropped when its owner goes out of scope, and the order is fixed: variables in the reverse order they were declared, so later ones can still use earlier ones while they are dropped. A struct's own Drop runs first, then its fields in the order they are declared. An inner scope's values are gone before the outer scope ends, and `let _ = value;` binds nothing, so the value is dropped on the spot.",
+    }),
+    lesson!(partial_moves_demo {
+        name: "partial_moves",
+        summary: "Moving a field out of a struct, and what Clone and Copy derives change",
+        topics: &["ownership", "moves", "structs"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["take_ownership"],
+        explanation: "Each field of a struct is owned by the struct, and a field can be moved out on its own: after `let name = user.name;` the other fields can still be used, but the struct as a whole can't (E0382, use of partially moved value) until the field is given a new value. `#[derive(Clone)]` lets you clone the field or the whole struct instead of moving. `#[derive(Clone, Copy)]` makes assigning the struct copy it, which is only allowed when every field is Copy, so not with a String in it (E0204).",
+    }),
+];
+
+// Makes copy of a simple data type
+pub fn makes_copy(simple: i32) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(simple.to_string()).value("simple", simple);
+    output
+}
+
+// Takes owndership of a complex data type
+pub fn take_ownership(complex: String) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(complex.as_str()).value("complex", &complex);
+    output.event(OwnershipEvent::dropped("complex")); // complex goes out of scope here, so the String is freed
+    output
+}
+
+// Gives ownership of return value to the place where the function is called
+#[allow(clippy::let_and_return)] // The binding makes the owner visible before it is given away
+pub fn give_ownership() -> String{
+    let my_string = String::from("world");
+    my_string
+}
+
+// Takes ownership, then gives back
+pub fn takes_and_gives_back(complex: String) -> String{
+    complex
+}
+
+// Simple types (int, bool, char) are copied, so the original stays usable
+pub fn makes_copy_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let x = 5;
+    let y = x; // Simple types (int, bool, char) can be copied simply like this
+    reporter.event(&OwnershipEvent::copied("x", "y"));
+    reporter.message(&format!("x: {x}, y: {y}"));
+
+    reporter.event(&OwnershipEvent::copied("x", "simple"));
+    reporter.output(&makes_copy(x));
+    reporter.message(&format!("x: {x}")); // In the same way, we can still print x after passing it into a function
+    Ok(())
+}
+
+// Assigning or passing a String moves it, clone makes a second owner
+pub fn take_ownership_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let s1 = String::from("hello");
+    let s2 = s1; // Moves s1 to s2, if s1 is called, we will get an error
+    reporter.event(&OwnershipEvent::moved("s1", "s2"));
+    let s3 = s2.clone(); // This actually clones s1 and adds s3 to the heap (more expensive)
+    reporter.event(&OwnershipEvent::cloned("s2", "s3"));
+    reporter.message(&format!("s1: error, s2: {s2}, s3: {s3}"));
+
+    reporter.event(&OwnershipEvent::moved("s3", "complex"));
+    reporter.output(&take_ownership(s3));
+    reporter.message("s3: error"); // In the same way, we can't print after it is passed into a function
+    Ok(())
+}
+
+// Functions can hand ownership of their return value to the caller
+pub fn give_ownership_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let s4 = give_ownership();
+    reporter.event(&OwnershipEvent::moved("my_string", "s4"));
+    reporter.message(&format!("s4: {}", s4)); // Functions can also give away ownership
+    reporter.value("s4", &s4);
+    Ok(())
+}
+
+// Ownership can go into a function and come back out through the return value
+pub fn takes_and_gives_back_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let s4 = give_ownership();
+    reporter.event(&OwnershipEvent::moved("my_string", "s4"));
+    let s5 = takes_and_gives_back(s4); // Takes away ownership while in function
+    reporter.event(&OwnershipEvent::moved("s4", "complex"));
+    reporter.event(&OwnershipEvent::moved("complex", "s5"));
+    reporter.message(&format!("s4: {}", s5)); // Gives back because the value is returned
+    reporter.value("s5", &s5);
+    Ok(())
+}
+
+// The take_ownership and takes_and_gives_back demos again, with every clone and drop logged by the value itself
+pub fn tracked_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let tracker = Tracker::new();
+    {
+        let s1 = tracker.track("s1", String::from("hello"));
+        let s2 = s1.moved_to("s2");
+        let s3 = s2.clone_as("s3");
+        reporter.message(&format!("s2: {s2}, s3: {s3}"));
+        tracker.report(reporter);
+
+        reporter.output(&tracked::take_ownership(s3)); // s3's String is dropped inside take_ownership
+        tracker.report(reporter);
+
+        let s4 = tracker.track("s4", String::from("world"));
+        let s5 = tracked::takes_and_gives_back(s4).moved_to("s5");
+        reporter.message(&format!("s5: {s5}"));
+        tracker.report(reporter);
+        reporter.message("end of scope");
+    } // s5, then s2, dropped in the reverse order they were declared
+    tracker.report(reporter);
+    Ok(())
+}
+
+// Draws s1 from the real pointer, length and capacity, then again after a move and after a clone
+pub fn stack_and_heap_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let s1 = String::from("hello");
+    reporter.message("let s1 = String::from(\"hello\");");
+    for line in Diagram::new().string("s1", &s1).render() {
+        reporter.message(&line);
+    }
+
+    let s2 = s1; // Only the pointer, length and capacity are copied
+    reporter.event(&OwnershipEvent::moved("s1", "s2"));
+    reporter.message("");
+    reporter.message("let s2 = s1; // a move, the same heap block");
+    for line in Diagram::new().string("s2", &s2).moved("s1", "s2").render() {
+        reporter.message(&line);
+    }
+
+    let s3 = s2.clone(); // A new heap block with a copy of the bytes
+    reporter.event(&OwnershipEvent::cloned("s2", "s3"));
+    reporter.message("");
+    reporter.message("let s3 = s2.clone(); // a clone, a new heap block");
+    for line in Diagram::new().string("s2", &s2).string("s3", &s3).render() {
+        reporter.message(&line);
+    }
+
+    let mut numbers = Vec::with_capacity(4);
+    numbers.extend([1, 2, 3]);
+    reporter.message("");
+    reporter.message("let mut numbers = Vec::with_capacity(4); numbers.extend([1, 2, 3]); // room for one more");
+    for line in Diagram::new().vec("numbers", &numbers).render() {
+        reporter.message(&line);
+    }
+    Ok(())
+}
+
+// Says so when it's dropped, into a log the drop_order demo reports from
+struct Noisy {
+    name: &'static str,
+    log: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        self.log.borrow_mut().push(self.name);
+    }
+}
+
+// Has a Drop of its own, which runs before either field is dropped
+struct Pair {
+    first: Noisy,
+    second: Noisy,
+    log: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl Drop for Pair {
+    fn drop(&mut self) {
+        self.log.borrow_mut().push("pair");
+    }
+}
+
+// What gets dropped at the end of each scope, in the order the Drop impls ran
+pub fn drop_order_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let noisy = |name| Noisy { name, log: Rc::clone(&log) };
+
+    reporter.message("let a, b, c; in one scope, the last declared is dropped first");
+    {
+        let _a = noisy("a");
+        let _b = noisy("b");
+        let _c = noisy("c");
+    } // c, b, a
+    report_drops(reporter, &log);
+
+    reporter.message("a struct's own Drop runs first, then its fields in the order they're declared");
+    {
+        let pair = Pair { first: noisy("pair.first"), second: noisy("pair.second"), log: Rc::clone(&log) };
+        reporter.message(&format!("pair holds {} and {}", pair.first.name, pair.second.name));
+    } // pair, pair.first, pair.second
+    report_drops(reporter, &log);
+
+    reporter.message("nested scopes, the inner one ends first");
+    {
+        let _outer = noisy("outer");
+        {
+            let _inner = noisy("inner");
+        } // inner
+        reporter.message("end of the inner scope");
+        report_drops(reporter, &log);
+    } // outer
+    reporter.message("end of the outer scope");
+    report_drops(reporter, &log);
+
+    reporter.message("let _ = value; binds nothing, the value doesn't wait for the end of the scope");
+    {
+        let _kept = noisy("kept");
+        let _ = noisy("unbound"); // Dropped right here
+        report_drops(reporter, &log);
+        reporter.message("still in the scope, kept is alive");
+    } // kept
+    report_drops(reporter, &log);
+    Ok(())
+}
+
+#[track_caller] // The drops show up next to the scope that ended in `show`
+fn report_drops(reporter: &mut dyn Reporter, log: &RefCell<Vec<&'static str>>) {
+    for name in log.borrow_mut().drain(..) {
+        reporter.event(&OwnershipEvent::dropped(name));
+        reporter.message(&format!("  dropping {name}"));
+    }
+}
+
+// A heap field and a Copy one. Clone is derived, but cloning is always asked for, assigning still moves
+#[derive(Clone)]
+pub struct User {
+    pub name: String,
+    pub age: u32,
+}
+
+// Only Copy fields, so the struct can be Copy too, a String field would make the derive an error (E0204)
+#[derive(Clone, Copy)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+// Takes the whole struct, every field has to be there
+pub fn describe(someone: User) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(format!("{} is {}", someone.name, someone.age)).value("someone.name", &someone.name);
+    output.event(OwnershipEvent::dropped("someone")); // someone goes out of scope, its name is dropped with it
+    output
+}
+
+// Reads a Position, which was copied in
+pub fn describe_position(position: Position) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(format!("at ({}, {})", position.x, position.y));
+    output
+}
+
+// Moves a field out of a struct, fills it back in, then avoids the move with clone and with a Copy struct
+pub fn partial_moves_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let mut user = User { name: String::from("Ferris"), age: 7 };
+    let name = user.name; // Moves only the String out, user is now partially moved
+    reporter.event(&OwnershipEvent::moved("user.name", "name"));
+    let age = user.age; // A u32 is Copy, reading it is fine
+    reporter.event(&OwnershipEvent::copied("user.age", "age"));
+    reporter.message(&format!("name: {name}, age: {age}, user.age: {}", user.age));
+    reporter.message("user.name: error, user: error"); // Passing user to describe here would be E0382, the name is missing
+
+    user.name = String::from("Corro"); // Giving the field a new value makes user whole again
+    reporter.event(&OwnershipEvent::moved("user", "someone"));
+    reporter.output(&describe(user));
+
+    let user = User { name, age }; // The name moves into a new struct
+    reporter.event(&OwnershipEvent::moved("name", "user.name"));
+    let copied_name = user.name.clone(); // Cloning the field leaves user whole
+    reporter.event(&OwnershipEvent::cloned("user.name", "copied_name"));
+    let twin = user.clone(); // Cloning the struct clones every field, the name gets its own heap copy
+    reporter.event(&OwnershipEvent::cloned("user", "twin"));
+    reporter.message(&format!("copied_name: {copied_name}"));
+    reporter.event(&OwnershipEvent::moved("twin", "someone"));
+    reporter.output(&describe(twin));
+    reporter.event(&OwnershipEvent::moved("user", "someone"));
+    reporter.output(&describe(user));
+
+    let start = Position { x: 1, y: 2 };
+    let end = start; // Copy, both stay usable, and so would start.x after `let x = start.x;`
+    reporter.event(&OwnershipEvent::copied("start", "end"));
+    reporter.event(&OwnershipEvent::copied("start", "position"));
+    reporter.output(&describe_position(start));
+    reporter.message(&format!("start: ({}, {}), end: ({}, {})", start.x, start.y, end.x, end.y));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn take_ownership_drops_what_it_was_given() {
+        let output = take_ownership(String::from("hello"));
+        assert_eq!(output.messages, ["hello"]);
+        assert_eq!(output.events, [OwnershipEvent::dropped("complex")]);
+    }
+
+    #[test]
+    fn take_ownership_demo_moves_clones_then_moves_into_the_function() {
+        let mut output = DemoOutput::new();
+        take_ownership_demo(&mut output).unwrap();
+        assert_eq!(output.messages, ["s1: error, s2: hello, s3: hello", "hello", "s3: error"]);
+        assert_eq!(output.values, [("complex".to_string(), "hello".to_string())]);
+        assert_eq!(
+            output.events,
+            [
+                OwnershipEvent::moved("s1", "s2"),
+                OwnershipEvent::cloned("s2", "s3"),
+                OwnershipEvent::moved("s3", "complex"),
+                OwnershipEvent::dropped("complex"),
+            ]
+        );
+    }
+
+    #[test]
+    fn makes_copy_demo_keeps_x_usable() {
+        let mut output = DemoOutput::new();
+        makes_copy_demo(&mut output).unwrap();
+        assert_eq!(output.messages, ["x: 5, y: 5", "5", "x: 5"]);
+        assert_eq!(output.events, [OwnershipEvent::copied("x", "y"), OwnershipEvent::copied("x", "simple")]);
+    }
+
+    #[test]
+    fn describe_drops_the_whole_struct() {
+        let output = describe(User { name: String::from("Ferris"), age: 7 });
+        assert_eq!(output.messages, ["Ferris is 7"]);
+        assert_eq!(output.values, [("someone.name".to_string(), "Ferris".to_string())]);
+        assert_eq!(output.events, [OwnershipEvent::dropped("someone")]);
+        assert_eq!(describe_position(Position { x: 1, y: 2 }).messages, ["at (1, 2)"]);
+    }
+
+    #[test]
+    fn partial_moves_demo_moves_the_name_out_and_back() {
+        let mut output = DemoOutput::new();
+        partial_moves_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            [
+                "name: Ferris, age: 7, user.age: 7",
+                "user.name: error, user: error",
+                "Corro is 7",
+                "copied_name: Ferris",
+                "Ferris is 7",
+                "Ferris is 7",
+                "at (1, 2)",
+                "start: (1, 2), end: (1, 2)",
+            ]
+        );
+        assert_eq!(output.events[..2], [OwnershipEvent::moved("user.name", "name"), OwnershipEvent::copied("user.age", "age")]);
+        assert!(output.events.contains(&OwnershipEvent::cloned("user", "twin")));
+        assert_eq!(output.events.last(), Some(&OwnershipEvent::copied("start", "position")));
+    }
+}
@@ -0,0 +1,10 @@
+// Everything needed to embed or extend the lessons with a single `use rust_day_6::prelude::*;`
+
+pub use crate::error::LessonError;
+pub use crate::lesson;
+pub use crate::lesson::{Difficulty, Lesson, LessonDef};
+pub use crate::output::{DemoOutput, OwnershipEvent};
+pub use crate::report::{BufferReporter, FileReporter, Reporter, StdoutReporter};
+pub use crate::runner::{LessonOutcome, LessonRunner, LessonRunnerBuilder, Order, Prerequisites};
+pub use crate::slices::{first_word, slicing};
+pub use crate::tracked::{Tracked, Tracker};
@@ -0,0 +1,910 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config;
+use crate::date::Date;
+use crate::error::LessonError;
+use crate::events::{Event, EventKind};
+use crate::exercises::{CheckResult, ExerciseInfo};
+use crate::flashcards::Card;
+use crate::json::{Json, ToJson};
+use crate::quiz::{self, Answer, QuizReport};
+use crate::review::{self, Review};
+
+// Upgrades progress.json one version at a time, MIGRATIONS[0] turns version 1 into version 2 and so on. A
+// change to the format gets a migration here, VERSION follows along.
+const MIGRATIONS: &[fn(&mut Json)] = &[quiz_answer_topics, review_schedules, event_log, flashcard_reviews];
+
+// The version `save` writes, files without a version are version 1
+pub const VERSION: u64 = MIGRATIONS.len() as u64 + 1;
+
+// Where progress used to be kept, one fact per line in the current directory, e.g. `hints move1 2`
+const LEGACY_PATH: &str = ".rust_day_6-state";
+
+// What's remembered between runs, saved as JSON in progress.json
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Progress {
+    // Lessons whose demo ran to the end without an error
+    pub lessons: BTreeSet<String>,
+    // Exercises that passed with the NOT DONE marker removed
+    pub exercises: BTreeSet<String>,
+    // Exercise name to its best (checkpoints passed, checkpoints), the partial credit
+    pub scores: BTreeMap<String, (usize, usize)>,
+    // Exercise name to how many of its hints have been shown
+    pub hints: BTreeMap<String, usize>,
+    // Quiz topic to (correct, answered) over every quiz taken
+    pub quiz: BTreeMap<String, (usize, usize)>,
+    // Every quiz taken, oldest first
+    pub quiz_history: Vec<QuizAttempt>,
+    // Exercise name to when it was first opened and when it first passed
+    pub timing: BTreeMap<String, Timing>,
+    // Ids of the achievements unlocked so far
+    pub achievements: BTreeSet<String>,
+    // Every time an exercise passed for the first time, oldest first
+    pub completions: Vec<Completion>,
+    // Exercises to pass each day, if the learner set a goal
+    pub daily_goal: Option<usize>,
+    // Quiz question id to when it's next due for review
+    pub question_reviews: BTreeMap<String, Review>,
+    // Exercise name to when it's next due to be redone
+    pub exercise_reviews: BTreeMap<String, Review>,
+    // Flashcard id to when it's next due to be shown again
+    pub card_reviews: BTreeMap<String, Review>,
+    // Everything that happened, oldest first
+    pub events: Vec<Event>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    pub date: Date,
+    pub exercise: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuizAttempt {
+    // When the quiz was taken, in seconds since the Unix epoch
+    pub at: u64,
+    // In the order they were asked
+    pub answers: Vec<Answer>,
+}
+
+// Seconds since the Unix epoch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    pub started: u64,
+    pub finished: Option<u64>,
+}
+
+impl Timing {
+    // How long the exercise took, or has taken so far if it hasn't passed yet
+    pub fn seconds(&self, now: u64) -> u64 {
+        self.finished.unwrap_or(now).saturating_sub(self.started)
+    }
+}
+
+// The current time in seconds since the Unix epoch
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs())
+}
+
+impl Progress {
+    // RUST_DAY_6_PROGRESS if it's set, otherwise progress.json in $XDG_DATA_HOME/rust_day_6, which defaults to
+    // ~/.local/share/rust_day_6, or in its profiles/<name> for a profile
+    pub fn path(profile: Option<&str>) -> PathBuf {
+        if let Some(path) = std::env::var_os("RUST_DAY_6_PROGRESS") {
+            return PathBuf::from(path);
+        }
+        let dir = config::xdg_dir("XDG_DATA_HOME", ".local/share");
+        match profile {
+            Some(profile) => config::profile_dir(&dir, profile).join("progress.json"),
+            None => dir.join("progress.json"),
+        }
+    }
+
+    // A missing file is just a fresh start. For the main progress file a `.rust_day_6-state` from before progress
+    // moved to JSON is read instead, and written out as JSON on the next save. Profiles came later and start fresh.
+    // A file from an older version is upgraded as it's read, and copied to e.g. progress.json.v1 first in case the
+    // upgrade gets something wrong
+    pub fn load(path: &Path) -> Result<Self, LessonError> {
+        match fs::metadata(path) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound && path == Self::path(None) => Self::load_legacy(Path::new(LEGACY_PATH)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            _ => {
+                let (progress, version) = Self::read_version(path)?;
+                let backup = path.with_extension(format!("json.v{version}"));
+                if version < VERSION && !backup.exists() {
+                    fs::copy(path, &backup)?;
+                }
+                Ok(progress)
+            }
+        }
+    }
+
+    // Like `load`, for a file that has to be there, e.g. one being imported
+    pub fn read(path: &Path) -> Result<Self, LessonError> {
+        Ok(Self::read_version(path)?.0)
+    }
+
+    // The progress in `path`, upgraded to the current version, and the version it was saved with
+    fn read_version(path: &Path) -> Result<(Self, u64), LessonError> {
+        let text = fs::read_to_string(path).map_err(|err| LessonError::BadInput(format!("can't read {}: {err}", path.display())))?;
+        let invalid = |err: LessonError| LessonError::BadInput(format!("{} is invalid: {err}", path.display()));
+        let mut json = Json::parse(&text).map_err(invalid)?;
+        let version = match json.get("version") {
+            None => 1,
+            Some(version) => version.as_u64().filter(|version| *version >= 1).ok_or_else(|| invalid(LessonError::BadInput("`version` should be a whole number from 1".to_string())))?,
+        };
+        // Saving would drop whatever a newer version added, so that's left alone
+        if version > VERSION {
+            return Err(LessonError::BadInput(format!(
+                "{} is from a newer rust_day_6 (version {version}, this one reads up to {VERSION}), upgrade to keep using it",
+                path.display()
+            )));
+        }
+        for migration in &MIGRATIONS[version as usize - 1..] {
+            migration(&mut json);
+        }
+        Ok((Self::from_json(&json).map_err(invalid)?, version))
+    }
+
+    // Writes to a temporary file first, so a crash halfway through can't leave half a progress file behind
+    pub fn save(&self, path: &Path) -> Result<(), LessonError> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let partial = path.with_extension("json.partial");
+        fs::write(&partial, format!("{}\n", self.to_json().pretty()))?;
+        fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    fn from_json(json: &Json) -> Result<Self, LessonError> {
+        let bad = |what: &str| LessonError::BadInput(what.to_string());
+        let names = |key: &str| -> Result<BTreeSet<String>, LessonError> {
+            let Some(items) = json.get(key) else {
+                return Ok(BTreeSet::new());
+            };
+            let items = items.as_array().ok_or_else(|| bad(&format!("`{key}` should be a list of names")))?;
+            items.iter().map(|item| item.as_str().map(str::to_string).ok_or_else(|| bad(&format!("`{key}` should be a list of names")))).collect()
+        };
+        let count = |value: Option<&Json>, what: &str| -> Result<usize, LessonError> {
+            value.and_then(Json::as_u64).map(|n| n as usize).ok_or_else(|| bad(&format!("{what} should be a whole number")))
+        };
+
+        let mut progress = Self { lessons: names("lessons")?, achievements: names("achievements")?, ..Self::default() };
+        for (name, exercise) in json.get("exercises").and_then(Json::as_object).unwrap_or_default() {
+            if exercise.get("passed").and_then(Json::as_bool) == Some(true) {
+                progress.exercises.insert(name.clone());
+            }
+            if let Some(score) = exercise.get("score") {
+                let score = score.as_array().unwrap_or_default();
+                let [passed, total] = score else {
+                    return Err(bad(&format!("the score of {name} should be [passed, checkpoints]")));
+                };
+                let what = format!("the score of {name}");
+                progress.scores.insert(name.clone(), (count(Some(passed), &what)?, count(Some(total), &what)?));
+            }
+            if let Some(hints) = exercise.get("hints") {
+                progress.hints.insert(name.clone(), count(Some(hints), &format!("the hints of {name}"))?);
+            }
+            if let Some(started) = exercise.get("started") {
+                let finished = match exercise.get("finished") {
+                    None | Some(Json::Null) => None,
+                    finished => Some(count(finished, &format!("when {name} finished"))? as u64),
+                };
+                progress.timing.insert(name.clone(), Timing { started: count(Some(started), &format!("when {name} started"))? as u64, finished });
+            }
+        }
+        for (topic, totals) in json.get("quiz").and_then(Json::as_object).unwrap_or_default() {
+            let what = format!("the quiz totals for {topic}");
+            progress.quiz.insert(topic.clone(), (count(totals.get("correct"), &what)?, count(totals.get("answered"), &what)?));
+        }
+        for attempt in json.get("quiz_history").and_then(Json::as_array).unwrap_or_default() {
+            let at = count(attempt.get("at"), "when a quiz was taken")? as u64;
+            let mut answers = Vec::new();
+            for answer in attempt.get("answers").and_then(Json::as_array).unwrap_or_default() {
+                let text = |key| answer.get(key).and_then(Json::as_str).map(str::to_string);
+                let (Some(question), Some(topic), Some(correct)) = (text("question"), text("topic"), answer.get("correct").and_then(Json::as_bool)) else {
+                    return Err(bad("each quiz answer needs a question, its topic and whether it was correct"));
+                };
+                let took = answer.get("took_ms").and_then(Json::as_u64).map(Duration::from_millis);
+                let timed_out = answer.get("timed_out").and_then(Json::as_bool).unwrap_or(false);
+                answers.push(Answer { question, topic, given: text("given").unwrap_or_default(), correct, took, timed_out });
+            }
+            progress.quiz_history.push(QuizAttempt { at, answers });
+        }
+        for completion in json.get("completions").and_then(Json::as_array).unwrap_or_default() {
+            let (Some(date), Some(exercise)) = (completion.get("date").and_then(Json::as_str), completion.get("exercise").and_then(Json::as_str)) else {
+                return Err(bad("each completion needs a date and an exercise"));
+            };
+            progress.completions.push(Completion { date: date.parse()?, exercise: exercise.to_string() });
+        }
+        progress.daily_goal = match json.get("daily_goal") {
+            None | Some(Json::Null) => None,
+            goal => Some(count(goal, "the daily goal")?),
+        };
+        for event in json.get("events").and_then(Json::as_array).unwrap_or_default() {
+            progress.events.push(Event::from_json(event)?);
+        }
+        let reviews = json.get("reviews");
+        for (key, schedule) in [("questions", &mut progress.question_reviews), ("exercises", &mut progress.exercise_reviews), ("cards", &mut progress.card_reviews)] {
+            for (card, review) in reviews.and_then(|reviews| reviews.get(key)).and_then(Json::as_object).unwrap_or_default() {
+                schedule.insert(card.clone(), Review::from_json(review)?);
+            }
+        }
+        Ok(progress)
+    }
+
+    // The line-per-fact format progress was saved in before it moved to JSON
+    fn load_legacy(path: &Path) -> Result<Self, LessonError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut progress = Self::default();
+        for (number, line) in text.lines().enumerate() {
+            let bad_line = || LessonError::BadInput(format!("{} line {}: can't read `{line}`", path.display(), number + 1));
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [] => {}
+                ["lesson", lesson] => {
+                    progress.lessons.insert(lesson.to_string());
+                }
+                ["exercise", exercise] => {
+                    progress.exercises.insert(exercise.to_string());
+                }
+                ["score", exercise, passed, total] => {
+                    let passed = passed.parse().map_err(|_| bad_line())?;
+                    let total = total.parse().map_err(|_| bad_line())?;
+                    progress.scores.insert(exercise.to_string(), (passed, total));
+                }
+                ["hints", exercise, count] => {
+                    progress.hints.insert(exercise.to_string(), count.parse().map_err(|_| bad_line())?);
+                }
+                ["quiz", topic, correct, answered] => {
+                    let correct = correct.parse().map_err(|_| bad_line())?;
+                    let answered = answered.parse().map_err(|_| bad_line())?;
+                    progress.quiz.insert(topic.to_string(), (correct, answered));
+                }
+                ["completed", date, exercise] => {
+                    let date = date.parse().map_err(|_| bad_line())?;
+                    progress.completions.push(Completion { date, exercise: exercise.to_string() });
+                }
+                ["goal", count] => {
+                    progress.daily_goal = Some(count.parse().map_err(|_| bad_line())?);
+                }
+                ["achievement", id] => {
+                    progress.achievements.insert(id.to_string());
+                }
+                ["started", exercise, at] => {
+                    progress.timing.entry(exercise.to_string()).or_default().started = at.parse().map_err(|_| bad_line())?;
+                }
+                ["finished", exercise, at] => {
+                    progress.timing.entry(exercise.to_string()).or_default().finished = Some(at.parse().map_err(|_| bad_line())?);
+                }
+                _ => return Err(bad_line()),
+            }
+        }
+        Ok(progress)
+    }
+
+    // Remembers how checking an exercise went, keeping its best score. The first check is when the exercise was
+    // opened, since `watch` and `verify` check an exercise before showing it
+    pub fn record_check(&mut self, info: &ExerciseInfo, result: &CheckResult) {
+        let exercise = info.name.as_str();
+        let (passed, total) = result.score();
+        self.log(EventKind::Checked { exercise: exercise.to_string(), topic: info.topic.clone(), passed, total, done: result.done() });
+        // Exercises passed before timing was tracked stay untimed rather than taking 0 seconds
+        if !self.exercises.contains(exercise) {
+            let now = now();
+            let timing = self.timing.entry(exercise.to_string()).or_insert(Timing { started: now, finished: None });
+            if result.done() {
+                timing.finished = Some(now);
+                self.completions.push(Completion { date: Date::from_timestamp(now), exercise: exercise.to_string() });
+            }
+        }
+        let best = self.scores.entry(exercise.to_string()).or_insert((passed, total));
+        // A changed number of checkpoints means the exercise changed, so the old score doesn't count
+        if best.1 != total || passed > best.0 {
+            *best = (passed, total);
+        }
+        if result.done() {
+            self.exercises.insert(exercise.to_string());
+        }
+
+        // The first pass schedules the first review, after that only a pass since the exercise was reset or
+        // broken again counts
+        let today = Date::today();
+        match (self.exercise_reviews.get_mut(exercise), result.done()) {
+            (None, true) => {
+                let quality = review::exercise_quality(self.hints.get(exercise).copied().unwrap_or(0));
+                self.exercise_reviews.insert(exercise.to_string(), Review::first(quality, today));
+            }
+            (Some(review), true) if review.retrying => review.grade(review::quiz_quality(true), today),
+            (Some(review), false) => review.retrying = true,
+            _ => {}
+        }
+    }
+
+    // Adds a finished quiz to the per-topic totals and the history
+    pub fn record_quiz(&mut self, report: &QuizReport) {
+        for answer in &report.answers {
+            let (correct, answered) = self.quiz.entry(answer.topic.clone()).or_default();
+            *correct += usize::from(answer.correct);
+            *answered += 1;
+        }
+        for answer in &report.answers {
+            self.log(EventKind::Answered {
+                question: answer.question.clone(),
+                topic: answer.topic.clone(),
+                correct: answer.correct,
+                took_ms: answer.took.map(|took| took.as_millis() as u64),
+            });
+        }
+        let today = Date::today();
+        for answer in &report.answers {
+            let quality = review::quiz_quality(answer.correct);
+            match self.question_reviews.get_mut(&answer.question) {
+                Some(review) => review.grade(quality, today),
+                None => {
+                    self.question_reviews.insert(answer.question.clone(), Review::first(quality, today));
+                }
+            }
+        }
+        if !report.answers.is_empty() {
+            self.quiz_history.push(QuizAttempt { at: now(), answers: report.answers.clone() });
+        }
+    }
+
+    // A flashcard the learner graded themselves, knowing it pushes the next showing further out
+    pub fn record_card(&mut self, card: &Card, knew: bool) {
+        self.log(EventKind::Flashcard { card: card.id.clone(), topic: card.topic.clone(), knew });
+        let (quality, today) = (review::quiz_quality(knew), Date::today());
+        match self.card_reviews.get_mut(&card.id) {
+            Some(review) => review.grade(quality, today),
+            None => {
+                self.card_reviews.insert(card.id.clone(), Review::first(quality, today));
+            }
+        }
+    }
+
+    // Folds in progress from another machine or profile. Merging the same file twice changes nothing the
+    // second time: sets are joined, the better score, the later hint and the earlier times win, and only quizzes
+    // that aren't in the history yet add to the quiz totals.
+    pub fn merge(&mut self, other: &Progress) {
+        self.lessons.extend(other.lessons.iter().cloned());
+        self.exercises.extend(other.exercises.iter().cloned());
+        self.achievements.extend(other.achievements.iter().cloned());
+        for (exercise, &(passed, total)) in &other.scores {
+            let best = self.scores.entry(exercise.clone()).or_insert((passed, total));
+            // Compared as fractions, as the two might have seen a different number of checkpoints
+            if passed * best.1 > best.0 * total {
+                *best = (passed, total);
+            }
+        }
+        for (exercise, &count) in &other.hints {
+            let shown = self.hints.entry(exercise.clone()).or_insert(count);
+            *shown = (*shown).max(count);
+        }
+        for (exercise, timing) in &other.timing {
+            let merged = self.timing.entry(exercise.clone()).or_insert(*timing);
+            merged.started = merged.started.min(timing.started);
+            merged.finished = match (merged.finished, timing.finished) {
+                (Some(mine), Some(theirs)) => Some(mine.min(theirs)),
+                (mine, theirs) => mine.or(theirs),
+            };
+        }
+
+        for attempt in &other.quiz_history {
+            if self.quiz_history.contains(attempt) {
+                continue;
+            }
+            for answer in &attempt.answers {
+                let (correct, answered) = self.quiz.entry(answer.topic.clone()).or_default();
+                *correct += usize::from(answer.correct);
+                *answered += 1;
+            }
+            self.quiz_history.push(attempt.clone());
+        }
+        self.quiz_history.sort_by_key(|attempt| attempt.at);
+        // Totals from before there was a history can't be told apart, so those only ever go up to the larger one
+        for (topic, &(correct, answered)) in &other.quiz {
+            let totals = self.quiz.entry(topic.clone()).or_default();
+            if answered > totals.1 {
+                *totals = (correct, answered);
+            }
+        }
+
+        // An exercise is only completed for the first time once, on the earlier of the two days
+        for completion in &other.completions {
+            match self.completions.iter_mut().find(|mine| mine.exercise == completion.exercise) {
+                Some(mine) => mine.date = mine.date.min(completion.date),
+                None => self.completions.push(completion.clone()),
+            }
+        }
+        self.completions.sort_by_key(|completion| completion.date);
+        self.daily_goal = self.daily_goal.or(other.daily_goal);
+
+        let known: HashSet<Event> = self.events.iter().cloned().collect();
+        self.events.extend(other.events.iter().filter(|event| !known.contains(event)).cloned());
+        self.events.sort_by_key(|event| event.at);
+
+        // The schedule from the most recent review wins
+        let schedules = [
+            (&mut self.question_reviews, &other.question_reviews),
+            (&mut self.exercise_reviews, &other.exercise_reviews),
+            (&mut self.card_reviews, &other.card_reviews),
+        ];
+        for (mine, theirs) in schedules {
+            for (card, review) in theirs {
+                let reviewed = |review: &Review| review.due.add_days(-i64::from(review.interval));
+                match mine.get_mut(card) {
+                    Some(existing) if reviewed(existing) >= reviewed(review) => {}
+                    Some(existing) => *existing = *review,
+                    None => {
+                        mine.insert(card.clone(), *review);
+                    }
+                }
+            }
+        }
+    }
+
+    // The quiz questions and the exercises due for review on `today`, most overdue first
+    pub fn due_reviews(&self, today: Date) -> (Vec<&str>, Vec<&str>) {
+        (due(&self.question_reviews, today), due(&self.exercise_reviews, today))
+    }
+
+    // The flashcards due to be shown again on `today`, most overdue first. Cards never shown aren't in here.
+    pub fn due_cards(&self, today: Date) -> Vec<&str> {
+        due(&self.card_reviews, today)
+    }
+
+    // The first day after `today` something is due
+    pub fn next_review(&self, today: Date) -> Option<Date> {
+        let reviews = self.question_reviews.values().chain(self.exercise_reviews.values()).chain(self.card_reviews.values());
+        reviews.map(|review| review.due).filter(|due| *due > today).min()
+    }
+
+    // Adds to the event log, as of now
+    pub fn log(&mut self, kind: EventKind) {
+        self.events.push(Event { at: now(), kind });
+    }
+
+    // How many exercises were completed on `date`
+    pub fn completed_on(&self, date: Date) -> usize {
+        self.completions.iter().filter(|completion| completion.date == date).count()
+    }
+
+    // Consecutive days with a completed exercise up to `today`. A streak that reached yesterday is still
+    // going, there's the rest of today to keep it up.
+    pub fn streak(&self, today: Date) -> usize {
+        let days: BTreeSet<Date> = self.completions.iter().map(|completion| completion.date).collect();
+        let mut day = if days.contains(&today) { today } else { today.previous() };
+        let mut streak = 0;
+        while days.contains(&day) {
+            streak += 1;
+            day = day.previous();
+        }
+        streak
+    }
+
+    // The most consecutive days with a completed exercise there have ever been
+    pub fn longest_streak(&self) -> usize {
+        let days: BTreeSet<Date> = self.completions.iter().map(|completion| completion.date).collect();
+        let mut longest = 0;
+        let mut current = 0;
+        let mut last: Option<Date> = None;
+        for day in days {
+            current = if last.is_some_and(|last| day.days_since(last) == 1) { current + 1 } else { 1 };
+            longest = longest.max(current);
+            last = Some(day);
+        }
+        longest
+    }
+
+    // Loads the progress at `path`, changes it and saves it again, returning what `change` did
+    pub fn update<T>(path: &Path, change: impl FnOnce(&mut Self) -> T) -> Result<T, LessonError> {
+        let mut progress = Self::load(path)?;
+        let changed = change(&mut progress);
+        progress.save(path)?;
+        Ok(changed)
+    }
+}
+
+// Version 2 keeps the topic of each quiz answer, and what was given, version 1 only had the question and whether it
+// was right. The topic is looked up in the question bank, which still has every question version 1 could have asked.
+fn quiz_answer_topics(json: &mut Json) {
+    let bank = quiz::bank();
+    let Some(Json::Array(history)) = json.get_mut("quiz_history") else {
+        return;
+    };
+    for attempt in history {
+        let Some(Json::Array(answers)) = attempt.get_mut("answers") else {
+            continue;
+        };
+        for answer in answers.iter_mut().filter(|answer| answer.get("topic").is_none()) {
+            let question = answer.get("question").and_then(Json::as_str);
+            let topic = bank.iter().find(|asked| Some(asked.id.as_str()) == question).map_or("unknown", |asked| asked.topic.as_str());
+            answer.set("topic", topic.into());
+        }
+    }
+}
+
+fn due(reviews: &BTreeMap<String, Review>, today: Date) -> Vec<&str> {
+    let mut due: Vec<(&String, &Review)> = reviews.iter().filter(|(_, review)| review.is_due(today)).collect();
+    due.sort_by_key(|(_, review)| review.due);
+    due.into_iter().map(|(card, _)| card.as_str()).collect()
+}
+
+// Version 3 adds review schedules, everything from before starts unscheduled and is scheduled the next time it's
+// answered or checked
+fn review_schedules(json: &mut Json) {
+    json.set("reviews", Json::object([("questions", Json::object(Vec::<(String, Json)>::new())), ("exercises", Json::object(Vec::<(String, Json)>::new()))]));
+}
+
+// Version 4 adds the event log, started off with the quiz answers from the history, the only thing from before
+// that has times
+fn event_log(json: &mut Json) {
+    let mut events = Vec::new();
+    for attempt in json.get("quiz_history").and_then(Json::as_array).unwrap_or_default() {
+        for answer in attempt.get("answers").and_then(Json::as_array).unwrap_or_default() {
+            let mut event = vec![("at".to_string(), attempt.get("at").cloned().unwrap_or(Json::Null)), ("event".to_string(), "answered".into())];
+            event.extend(["question", "topic", "correct"].map(|key| (key.to_string(), answer.get(key).cloned().unwrap_or(Json::Null))));
+            events.push(Json::Object(event));
+        }
+    }
+    json.set("events", Json::Array(events));
+}
+
+// Version 5 adds flashcard schedules, no card has been shown before
+fn flashcard_reviews(json: &mut Json) {
+    if let Some(reviews) = json.get_mut("reviews") {
+        reviews.set("cards", Json::object(Vec::<(String, Json)>::new()));
+    }
+}
+
+// Exercise results are grouped by exercise, e.g. "move1": {"passed": true, "score": [2, 2], "hints": 1, ...}
+impl ToJson for Progress {
+    fn to_json(&self) -> Json {
+        let mut names: BTreeSet<&String> = self.exercises.iter().collect();
+        names.extend(self.scores.keys().chain(self.hints.keys()).chain(self.timing.keys()));
+        let exercises = names.into_iter().map(|name| {
+            let mut fields = vec![("passed", self.exercises.contains(name).into())];
+            if let Some((passed, total)) = self.scores.get(name) {
+                fields.push(("score", Json::array([*passed, *total])));
+            }
+            if let Some(hints) = self.hints.get(name) {
+                fields.push(("hints", (*hints).into()));
+            }
+            if let Some(timing) = self.timing.get(name) {
+                fields.push(("started", timing.started.into()));
+                fields.push(("finished", timing.finished.into()));
+            }
+            (name.clone(), Json::object(fields))
+        });
+        let quiz = self.quiz.iter().map(|(topic, (correct, answered))| {
+            (topic.clone(), Json::object([("correct", (*correct).into()), ("answered", (*answered).into())]))
+        });
+        let history = self.quiz_history.iter().map(|attempt| {
+            let answers = attempt.answers.iter().map(|answer| {
+                let mut fields = vec![
+                    ("question", (&answer.question).into()),
+                    ("topic", (&answer.topic).into()),
+                    ("given", (&answer.given).into()),
+                    ("correct", answer.correct.into()),
+                ];
+                // Only there for answers that were timed
+                if let Some(took) = answer.took {
+                    fields.push(("took_ms", (took.as_millis() as u64).into()));
+                }
+                if answer.timed_out {
+                    fields.push(("timed_out", true.into()));
+                }
+                Json::object(fields)
+            });
+            Json::object([("at", attempt.at.into()), ("answers", Json::array(answers))])
+        });
+        let completions = self.completions.iter().map(|completion| {
+            Json::object([("date", completion.date.to_string().into()), ("exercise", (&completion.exercise).into())])
+        });
+        Json::object([
+            ("version", VERSION.into()),
+            ("lessons", Json::array(&self.lessons)),
+            ("exercises", Json::object(exercises)),
+            ("quiz", Json::object(quiz)),
+            ("quiz_history", Json::array(history)),
+            ("achievements", Json::array(&self.achievements)),
+            ("completions", Json::array(completions)),
+            ("daily_goal", self.daily_goal.into()),
+            (
+                "reviews",
+                Json::object([
+                    ("questions", Json::object(self.question_reviews.iter().map(|(card, review)| (card.clone(), review.to_json())))),
+                    ("exercises", Json::object(self.exercise_reviews.iter().map(|(card, review)| (card.clone(), review.to_json())))),
+                    ("cards", Json::object(self.card_reviews.iter().map(|(card, review)| (card.clone(), review.to_json())))),
+                ]),
+            ),
+            ("events", Json::array(self.events.iter().map(ToJson::to_json))),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A directory of its own for each test, removed again when it's dropped
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("rust_day_6-test-{}-{name}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn file(&self, name: &str, text: &str) -> PathBuf {
+            let path = self.0.join(name);
+            fs::write(&path, text).unwrap();
+            path
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    const VERSION_1: &str = r#"{
+        "lessons": ["slicing"],
+        "exercises": {"move1": {"passed": true, "score": [2, 2], "hints": 1}},
+        "quiz": {"moves": {"correct": 1, "answered": 2}},
+        "quiz_history": [{"at": 100, "answers": [
+            {"question": "moves-read-after-move", "correct": true},
+            {"question": "no-longer-asked", "correct": false}
+        ]}]
+    }"#;
+
+    #[test]
+    fn every_version_has_a_migration() {
+        assert_eq!(VERSION, MIGRATIONS.len() as u64 + 1);
+        assert_eq!(Progress::default().to_json().get("version").and_then(Json::as_u64), Some(VERSION));
+    }
+
+    #[test]
+    fn version_1_is_upgraded() {
+        let scratch = Scratch::new("version-1");
+        let progress = Progress::read(&scratch.file("progress.json", VERSION_1)).unwrap();
+        assert!(progress.lessons.contains("slicing") && progress.exercises.contains("move1"));
+        assert_eq!(progress.scores["move1"], (2, 2));
+        assert_eq!(progress.quiz["moves"], (1, 2));
+        // quiz_answer_topics
+        let topics: Vec<&str> = progress.quiz_history[0].answers.iter().map(|answer| answer.topic.as_str()).collect();
+        assert_eq!(topics, ["moves", "unknown"]);
+        // review_schedules and flashcard_reviews
+        assert!(progress.question_reviews.is_empty() && progress.exercise_reviews.is_empty() && progress.card_reviews.is_empty());
+        // event_log
+        assert_eq!(
+            progress.events,
+            [
+                Event { at: 100, kind: EventKind::Answered { question: "moves-read-after-move".to_string(), topic: "moves".to_string(), correct: true, took_ms: None } },
+                Event { at: 100, kind: EventKind::Answered { question: "no-longer-asked".to_string(), topic: "unknown".to_string(), correct: false, took_ms: None } },
+            ]
+        );
+    }
+
+    #[test]
+    fn each_migration_starts_where_the_file_left_off() {
+        // A version 4 file already has its events, only the flashcard schedules are added
+        let scratch = Scratch::new("version-4");
+        let events = r#"[{"at": 5, "event": "lesson", "lesson": "slicing"}]"#;
+        let text = format!(r#"{{"version": 4, "events": {events}, "reviews": {{"questions": {{}}, "exercises": {{}}}}}}"#);
+        let progress = Progress::read(&scratch.file("progress.json", &text)).unwrap();
+        assert_eq!(progress.events, [Event { at: 5, kind: EventKind::LessonCompleted { lesson: "slicing".to_string() } }]);
+    }
+
+    #[test]
+    fn load_backs_up_an_older_version_once() {
+        let scratch = Scratch::new("backup");
+        let path = scratch.file("progress.json", VERSION_1);
+        let upgraded = Progress::load(&path).unwrap();
+        let backup = scratch.0.join("progress.json.v1");
+        assert_eq!(fs::read_to_string(&backup).unwrap(), VERSION_1);
+
+        upgraded.save(&path).unwrap();
+        assert_eq!(Progress::load(&path).unwrap(), upgraded);
+        assert_eq!(fs::read_to_string(&backup).unwrap(), VERSION_1);
+        assert!(!scratch.0.join("progress.json.v5").exists());
+    }
+
+    #[test]
+    fn a_newer_version_is_left_alone() {
+        let scratch = Scratch::new("newer");
+        let path = scratch.file("progress.json", &format!(r#"{{"version": {}}}"#, VERSION + 1));
+        match Progress::load(&path) {
+            Err(LessonError::BadInput(message)) => assert!(message.contains("is from a newer rust_day_6"), "{message}"),
+            other => panic!("expected BadInput, got {other:?}"),
+        }
+        assert_eq!(fs::read_dir(&scratch.0).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn a_bad_version_is_rejected() {
+        let scratch = Scratch::new("bad-version");
+        for version in ["0", "\"2\"", "1.5"] {
+            let path = scratch.file("progress.json", &format!(r#"{{"version": {version}}}"#));
+            assert!(matches!(Progress::read(&path), Err(LessonError::BadInput(_))), "version {version}");
+        }
+    }
+
+    fn answer(question: &str, topic: &str, correct: bool) -> Answer {
+        Answer { question: question.to_string(), topic: topic.to_string(), given: String::new(), correct, took: None, timed_out: false }
+    }
+
+    // Progress as one machine might have it
+    fn progress(at: u64, day: u32) -> Progress {
+        let mut progress = Progress::default();
+        progress.lessons.insert(format!("lesson{day}"));
+        progress.exercises.insert(format!("move{day}"));
+        progress.quiz_history.push(QuizAttempt { at, answers: vec![answer("q1", "moves", true), answer("q2", "borrowing", false)] });
+        for answer in &progress.quiz_history[0].answers {
+            let (correct, answered) = progress.quiz.entry(answer.topic.clone()).or_default();
+            *correct += usize::from(answer.correct);
+            *answered += 1;
+        }
+        progress.completions.push(Completion { date: Date::from_ymd(2024, 3, day), exercise: "move1".to_string() });
+        progress.events.push(Event { at, kind: EventKind::LessonCompleted { lesson: format!("lesson{day}") } });
+        progress
+    }
+
+    #[test]
+    fn merge_joins_both() {
+        let mut mine = progress(100, 9);
+        mine.merge(&progress(200, 8));
+        assert_eq!(mine.lessons.len(), 2);
+        assert_eq!(mine.exercises.len(), 2);
+        assert_eq!(mine.quiz_history.iter().map(|attempt| attempt.at).collect::<Vec<_>>(), [100, 200]);
+        assert_eq!(mine.quiz["moves"], (2, 2));
+        assert_eq!(mine.quiz["borrowing"], (0, 2));
+        // Completed first on the 8th, on the other machine
+        assert_eq!(mine.completions, [Completion { date: Date::from_ymd(2024, 3, 8), exercise: "move1".to_string() }]);
+        assert_eq!(mine.events.iter().map(|event| event.at).collect::<Vec<_>>(), [100, 200]);
+    }
+
+    #[test]
+    fn merging_twice_changes_nothing() {
+        let theirs = progress(200, 8);
+        let mut once = progress(100, 9);
+        once.merge(&theirs);
+        let mut twice = once.clone();
+        twice.merge(&theirs);
+        assert_eq!(twice, once);
+        let mut itself = once.clone();
+        itself.merge(&once);
+        assert_eq!(itself, once);
+    }
+
+    #[test]
+    fn merge_keeps_the_best_of_each() {
+        let mut mine = Progress::default();
+        mine.scores.insert("move1".to_string(), (2, 4));
+        mine.hints.insert("move1".to_string(), 3);
+        mine.timing.insert("move1".to_string(), Timing { started: 50, finished: None });
+        let mut theirs = Progress::default();
+        theirs.scores.insert("move1".to_string(), (2, 3)); // A better fraction from fewer checkpoints
+        theirs.hints.insert("move1".to_string(), 1);
+        theirs.timing.insert("move1".to_string(), Timing { started: 80, finished: Some(90) });
+        theirs.daily_goal = Some(2);
+        mine.merge(&theirs);
+        assert_eq!(mine.scores["move1"], (2, 3));
+        assert_eq!(mine.hints["move1"], 3);
+        assert_eq!(mine.timing["move1"], Timing { started: 50, finished: Some(90) });
+        assert_eq!(mine.daily_goal, Some(2));
+    }
+
+    #[test]
+    fn merge_keeps_the_latest_review() {
+        let today = Date::from_ymd(2024, 3, 9);
+        let mut mine = Progress::default();
+        mine.question_reviews.insert("q1".to_string(), Review::first(5, today.add_days(-10)));
+        let mut theirs = Progress::default();
+        let latest = Review::first(5, today);
+        theirs.question_reviews.insert("q1".to_string(), latest);
+        mine.merge(&theirs);
+        assert_eq!(mine.question_reviews["q1"], latest);
+        theirs.question_reviews.insert("q1".to_string(), Review::first(5, today.add_days(-20)));
+        mine.merge(&theirs);
+        assert_eq!(mine.question_reviews["q1"], latest);
+    }
+
+    // A result with `passed` of `total` checkpoints passing, done when all of them do
+    fn checked(passed: usize, total: usize) -> CheckResult {
+        let checkpoints = (0..total).map(|index| crate::exercises::Checkpoint { name: format!("step {index}"), passed: index < passed }).collect();
+        CheckResult { passed: passed == total, marked: false, output: String::new(), checkpoints }
+    }
+
+    #[test]
+    fn record_check_keeps_the_best_score_until_the_exercise_changes() {
+        let info = ExerciseInfo {
+            name: "move1".to_string(),
+            path: "move1.rs".to_string(),
+            topic: "moves".to_string(),
+            mode: crate::exercises::Mode::Test,
+            difficulty: Default::default(),
+            error: None,
+            hints: Vec::new(),
+            solution: None,
+        };
+        let mut progress = Progress::default();
+        progress.record_check(&info, &checked(2, 3));
+        progress.record_check(&info, &checked(1, 3));
+        assert_eq!(progress.scores["move1"], (2, 3));
+        assert!(!progress.exercises.contains("move1"));
+        // A test was added, so the old 2 of 3 doesn't count anymore
+        progress.record_check(&info, &checked(1, 4));
+        assert_eq!(progress.scores["move1"], (1, 4));
+        progress.record_check(&info, &checked(4, 4));
+        assert_eq!(progress.scores["move1"], (4, 4));
+        assert!(progress.exercises.contains("move1"));
+        let totals: Vec<(usize, usize)> = progress
+            .events
+            .iter()
+            .filter_map(|event| match &event.kind {
+                EventKind::Checked { passed, total, .. } => Some((*passed, *total)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(totals, [(2, 3), (1, 3), (1, 4), (4, 4)]);
+    }
+
+    // Progress with one completion on each of `dates`, given as days since 2024-01-01
+    fn completed(days: &[i64]) -> Progress {
+        let start = Date::from_ymd(2024, 1, 1);
+        let completions = days.iter().enumerate().map(|(index, &day)| Completion { date: start.add_days(day), exercise: format!("exercise{index}") }).collect();
+        Progress { completions, ..Progress::default() }
+    }
+
+    #[test]
+    fn a_streak_is_still_going_until_a_whole_day_is_missed() {
+        let start = Date::from_ymd(2024, 1, 1);
+        let progress = completed(&[0, 1, 2, 2, 4, 5]);
+        assert_eq!(progress.streak(start.add_days(5)), 2);
+        assert_eq!(progress.streak(start.add_days(6)), 2); // Nothing yet today, yesterday still counts
+        assert_eq!(progress.streak(start.add_days(7)), 0);
+        assert_eq!(progress.streak(start.add_days(2)), 3);
+        assert_eq!(progress.streak(start.add_days(3)), 3);
+        assert_eq!(Progress::default().streak(start), 0);
+    }
+
+    #[test]
+    fn the_longest_streak_and_each_days_count() {
+        let start = Date::from_ymd(2024, 1, 1);
+        let progress = completed(&[0, 1, 2, 2, 4, 5]);
+        assert_eq!(progress.longest_streak(), 3);
+        assert_eq!(progress.completed_on(start.add_days(2)), 2);
+        assert_eq!(progress.completed_on(start.add_days(3)), 0);
+        assert_eq!(Progress::default().longest_streak(), 0);
+        // Across the end of a month
+        let progress = completed(&[29, 30, 31]);
+        assert_eq!(progress.longest_streak(), 3);
+    }
+
+    #[test]
+    fn completions_and_the_goal_are_saved() {
+        let scratch = Scratch::new("streaks");
+        let path = scratch.0.join("progress.json");
+        let mut progress = completed(&[0, 1]);
+        progress.daily_goal = Some(3);
+        progress.save(&path).unwrap();
+        let read = Progress::read(&path).unwrap();
+        assert_eq!(read.completions, progress.completions);
+        assert_eq!(read.daily_goal, Some(3));
+    }
+}
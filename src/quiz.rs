@@ -0,0 +1,889 @@
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::LessonError;
+use crate::json::{Json, ToJson};
+use crate::lesson::LessonDef;
+use crate::registry;
+use crate::report::{Reporter, WriterReporter};
+use crate::shuffle::Rng;
+use crate::style::{Role, Style};
+use crate::toml;
+
+// A question about one of the lessons
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Question {
+    // Stable identifier, e.g. "moves-read-after-move"
+    pub id: String,
+    pub topic: String,
+    pub prompt: String,
+    pub kind: QuestionKind,
+    // Shown after answering, right or wrong
+    pub explanation: String,
+    // The lesson that covers it, after a wrong answer its explanation is shown and its demo run
+    pub lesson: Option<String>,
+}
+
+// What sort of answer a question takes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuestionKind {
+    // Pick one of `choices`, `answer` is an index into them
+    MultipleChoice { choices: Vec<String>, answer: usize },
+    // The prompt is a statement, is it true?
+    TrueFalse { answer: bool },
+    // What `code` prints, a line of output per line of `output`
+    PredictOutput { code: String, output: String },
+    // `code` has a ___ in it, any of `answers` fills it
+    FillInBlank { code: String, answers: Vec<String> },
+    // Does `code` compile? `error` is what rustc says when it doesn't
+    WillItCompile { code: String, error: Option<CompileError> },
+}
+
+// e.g. E0382, "borrow of moved value: `s1`"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileError {
+    pub code: String,
+    pub message: String,
+}
+
+// What template questions fill in. `$a`, `$b` and `$c` become different variable names, `$n` and `$m`
+// different numbers and `$text` a word, in every part of the question, so the same concept can be asked
+// without the learner remembering the answer to `s1` and `s2`.
+const PLACEHOLDER_NAMES: &[&str] = &["a", "b", "c"];
+const PLACEHOLDER_NUMBERS: &[&str] = &["n", "m"];
+// None of these may appear in a template's code on its own, e.g. as a function parameter
+const VARIABLE_NAMES: &[&str] = &["s1", "s2", "s3", "first", "second", "greeting", "name", "word", "owner", "label", "title", "message"];
+const WORDS: &[&str] = &["hello", "world", "ferris", "crab", "rust", "cargo", "borrow", "slice"];
+
+// The "will it compile?" snippets, kept as data rather than code
+const WILL_IT_COMPILE: &str = include_str!("quiz/will_it_compile.toml");
+const SNIPPET_KEYS: &[&str] = &["id", "topic", "lesson", "code", "error", "message", "explanation"];
+
+// Where the learner writes in a fill-in-the-blank question
+pub const BLANK: &str = "___";
+
+// See `topic_weight`
+const MIN_WEIGHT: f64 = 0.1;
+
+impl Question {
+    pub fn multiple_choice(id: &str, topic: &str, prompt: &str, choices: &[&str], answer: usize, explanation: &str) -> Self {
+        let choices = choices.iter().map(|choice| choice.to_string()).collect();
+        Self::with_kind(id, topic, prompt, QuestionKind::MultipleChoice { choices, answer }, explanation)
+    }
+
+    pub fn true_false(id: &str, topic: &str, statement: &str, answer: bool, explanation: &str) -> Self {
+        Self::with_kind(id, topic, statement, QuestionKind::TrueFalse { answer }, explanation)
+    }
+
+    pub fn predict_output(id: &str, topic: &str, code: &str, output: &str, explanation: &str) -> Self {
+        let kind = QuestionKind::PredictOutput { code: code.to_string(), output: output.to_string() };
+        Self::with_kind(id, topic, "What does this print?", kind, explanation)
+    }
+
+    pub fn fill_in_blank(id: &str, topic: &str, prompt: &str, code: &str, answers: &[&str], explanation: &str) -> Self {
+        let kind = QuestionKind::FillInBlank { code: code.to_string(), answers: answers.iter().map(|answer| answer.to_string()).collect() };
+        Self::with_kind(id, topic, prompt, kind, explanation)
+    }
+
+    pub fn will_it_compile(id: &str, topic: &str, code: &str, error: Option<CompileError>, lesson: &str, explanation: &str) -> Self {
+        let kind = QuestionKind::WillItCompile { code: code.trim_end().to_string(), error };
+        Self::with_kind(id, topic, "Does this compile?", kind, explanation).with_lesson(lesson)
+    }
+
+    pub fn with_lesson(mut self, lesson: &str) -> Self {
+        self.lesson = Some(lesson.to_string());
+        self
+    }
+
+    // The question with its placeholders filled in, the same every time for the same `rng` state. A question
+    // without placeholders comes back as it is.
+    pub fn instantiate(&self, rng: &mut Rng) -> Self {
+        let mut names = VARIABLE_NAMES.to_vec();
+        rng.shuffle(&mut names);
+        let mut numbers: Vec<u32> = (1..100).collect();
+        rng.shuffle(&mut numbers);
+        let mut values: Vec<(&str, String)> = PLACEHOLDER_NAMES.iter().zip(names).map(|(placeholder, name)| (*placeholder, name.to_string())).collect();
+        values.extend(PLACEHOLDER_NUMBERS.iter().zip(numbers).map(|(placeholder, number)| (*placeholder, number.to_string())));
+        values.push(("text", WORDS[rng.below(WORDS.len())].to_string()));
+
+        let fill = |text: &String| fill_placeholders(text, &values);
+        let fill_all = |texts: &Vec<String>| texts.iter().map(fill).collect();
+        let kind = match &self.kind {
+            QuestionKind::MultipleChoice { choices, answer } => QuestionKind::MultipleChoice { choices: fill_all(choices), answer: *answer },
+            QuestionKind::TrueFalse { answer } => QuestionKind::TrueFalse { answer: *answer },
+            QuestionKind::PredictOutput { code, output } => QuestionKind::PredictOutput { code: fill(code), output: fill(output) },
+            QuestionKind::FillInBlank { code, answers } => QuestionKind::FillInBlank { code: fill(code), answers: fill_all(answers) },
+            QuestionKind::WillItCompile { code, error } => QuestionKind::WillItCompile {
+                code: fill(code),
+                error: error.as_ref().map(|error| CompileError { code: error.code.clone(), message: fill(&error.message) }),
+            },
+        };
+        let (id, topic, lesson) = (self.id.clone(), self.topic.clone(), self.lesson.clone());
+        Self { id, topic, prompt: fill(&self.prompt), kind, explanation: fill(&self.explanation), lesson }
+    }
+
+    fn with_kind(id: &str, topic: &str, prompt: &str, kind: QuestionKind, explanation: &str) -> Self {
+        Self { id: id.to_string(), topic: topic.to_string(), prompt: prompt.to_string(), kind, explanation: explanation.to_string(), lesson: None }
+    }
+
+    // Whether `input` is the right answer. Choices can be given by number (1-based), letter (a, b, ...) or text,
+    // true/false as t, f, yes or no, and typed answers are compared ignoring case and spacing.
+    pub fn check(&self, input: &str) -> bool {
+        match &self.kind {
+            QuestionKind::MultipleChoice { choices, answer } => parse_choice(choices, input) == Some(*answer),
+            QuestionKind::TrueFalse { answer } => parse_bool(input) == Some(*answer),
+            QuestionKind::PredictOutput { output, .. } => normalize(input) == normalize(output),
+            QuestionKind::FillInBlank { answers, .. } => answers.iter().any(|answer| normalize(input) == normalize(answer)),
+            QuestionKind::WillItCompile { error, .. } => parse_bool(input) == Some(error.is_none()),
+        }
+    }
+
+    // The right answer as it would be shown after a wrong one
+    pub fn expected(&self) -> String {
+        match &self.kind {
+            QuestionKind::MultipleChoice { choices, answer } => choices[*answer].clone(),
+            QuestionKind::TrueFalse { answer } => if *answer { "true" } else { "false" }.to_string(),
+            QuestionKind::PredictOutput { output, .. } => output.lines().collect::<Vec<_>>().join(" / "),
+            QuestionKind::FillInBlank { answers, .. } => answers[0].clone(),
+            QuestionKind::WillItCompile { error: None, .. } => "yes, it compiles".to_string(),
+            QuestionKind::WillItCompile { error: Some(_), .. } => "no".to_string(),
+        }
+    }
+}
+
+// The questions in will_it_compile.toml, `text` being a file like it
+pub fn snippets(text: &str) -> Result<Vec<Question>, LessonError> {
+    let data = toml::parse(text)?;
+    let mut questions = Vec::new();
+    for (index, table) in data.tables("snippets").into_iter().enumerate() {
+        let bad = |problem: String| LessonError::BadInput(format!("snippet {}: {problem}", index + 1));
+        if let Some((key, _)) = table.0.iter().find(|(key, _)| !SNIPPET_KEYS.contains(&key.as_str())) {
+            return Err(bad(format!("unknown key `{key}`, expected one of {}", SNIPPET_KEYS.join(", "))));
+        }
+        let field = |key: &str| table.str(key).ok_or_else(|| bad(format!("needs a `{key}` string")));
+        let error = match (table.str("error"), table.str("message")) {
+            (Some(code), Some(message)) => Some(CompileError { code: code.to_string(), message: message.to_string() }),
+            (None, None) => None,
+            (Some(_), None) => return Err(bad("an `error` needs the `message` rustc gives with it".to_string())),
+            (None, Some(_)) => return Err(bad("a `message` needs the `error` code it goes with".to_string())),
+        };
+        questions.push(Question::will_it_compile(field("id")?, field("topic")?, field("code")?, error, field("lesson")?, field("explanation")?));
+    }
+    Ok(questions)
+}
+
+// Replaces `$name` with its value, a `$` followed by anything else is left alone
+fn fill_placeholders(text: &str, values: &[(&str, String)]) -> String {
+    let mut filled = String::new();
+    let mut rest = text;
+    while let Some(dollar) = rest.find('$') {
+        filled.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        let length = after.find(|c: char| !c.is_ascii_lowercase()).unwrap_or(after.len());
+        match values.iter().find(|(placeholder, _)| *placeholder == &after[..length]) {
+            Some((_, value)) => {
+                filled.push_str(value);
+                rest = &after[length..];
+            }
+            None => {
+                filled.push('$');
+                rest = after;
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+fn parse_choice(choices: &[String], input: &str) -> Option<usize> {
+    let input = input.trim();
+    if let Ok(number) = input.parse::<usize>() {
+        return (1..=choices.len()).contains(&number).then(|| number - 1);
+    }
+    let mut chars = input.chars();
+    if let (Some(letter), None) = (chars.next(), chars.next()) {
+        let index = (letter.to_ascii_lowercase() as usize).wrapping_sub('a' as usize);
+        if index < choices.len() {
+            return Some(index);
+        }
+    }
+    choices.iter().position(|choice| choice.eq_ignore_ascii_case(input))
+}
+
+fn parse_bool(input: &str) -> Option<bool> {
+    match input.trim().to_ascii_lowercase().as_str() {
+        "t" | "true" | "y" | "yes" => Some(true),
+        "f" | "false" | "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+// Lowercase, whitespace collapsed and lines joined, so "Hello,  World" matches "hello, world". A predicted
+// output of several lines can be typed on one, with the lines separated by `/`.
+fn normalize(text: &str) -> String {
+    let text = text.trim().trim_matches(['"', '`']).to_lowercase();
+    text.split(['\n', '/']).map(|line| line.split_whitespace().collect::<Vec<_>>().join(" ")).filter(|line| !line.is_empty()).collect::<Vec<_>>().join("\n")
+}
+
+// How strongly a topic's questions are favoured, `history` being its (correct, answered) from the progress file.
+// Accuracy is counted as if one more answer was right and one wrong, so a topic never tried sits in the middle,
+// and even one that's always right keeps MIN_WEIGHT so it still comes up now and then.
+pub fn topic_weight(history: Option<(usize, usize)>) -> f64 {
+    let (correct, answered) = history.unwrap_or_default();
+    let accuracy = (correct as f64 + 1.0) / (answered as f64 + 2.0);
+    1.0 - accuracy + MIN_WEIGHT
+}
+
+// Draws up to `count` of `questions` without putting any back, each draw picking a question with a chance in
+// proportion to its weight. With every weight the same that's a uniform random pick.
+pub fn select(mut questions: Vec<Question>, count: usize, weight: impl Fn(&Question) -> f64, rng: &mut Rng) -> Vec<Question> {
+    let mut weights: Vec<f64> = questions.iter().map(weight).collect();
+    let mut selected = Vec::new();
+    while selected.len() < count && !questions.is_empty() {
+        let mut left = rng.unit() * weights.iter().sum::<f64>();
+        let mut index = 0;
+        while index + 1 < weights.len() && left >= weights[index] {
+            left -= weights[index];
+            index += 1;
+        }
+        weights.remove(index);
+        selected.push(questions.remove(index));
+    }
+    selected
+}
+
+// The questions that come with the crate, covering what the day 6 lessons demonstrate
+pub fn bank() -> Vec<Question> {
+    let mut bank = vec![
+        Question::multiple_choice(
+            "moves-read-after-move",
+            "moves",
+            "Can you read `$a` after `let $a = String::from(\"$text\"); let $b = $a;`?",
+            &["Yes", "No"],
+            1,
+            "`let $b = $a;` moves the String to $b. $a is no longer valid and using it is error E0382.",
+        )
+        .with_lesson("take_ownership"),
+        Question::multiple_choice(
+            "copy-read-after-copy",
+            "copy",
+            "Can you read `$a` after `let $a = $n; let $b = $a;`?",
+            &["Yes", "No"],
+            0,
+            "Integers are Copy, so `let $b = $a;` copies the value and $a stays usable.",
+        )
+        .with_lesson("makes_copy"),
+        Question::multiple_choice(
+            "borrow-mutable-count",
+            "references",
+            "How many mutable references to the same value can exist at the same time?",
+            &["None", "One", "Any number"],
+            1,
+            "One. In any scope you can have either one mutable reference or any number of immutable ones.",
+        )
+        .with_lesson("mutate_reference"),
+        Question::multiple_choice(
+            "borrow-mixed",
+            "references",
+            "Can an immutable and a mutable reference to the same value be used at the same time?",
+            &["Yes", "No"],
+            1,
+            "No, that's error E0502. Readers would see the value change underneath them.",
+        )
+        .with_lesson("mutate_reference"),
+        Question::multiple_choice(
+            "heap-string",
+            "ownership",
+            "Where is the text of a `String` stored?",
+            &["On the stack", "On the heap"],
+            1,
+            "A String can grow, so its size isn't known at compile time and its text lives on the heap. The pointer, length and capacity live on the stack.",
+        )
+        .with_lesson("take_ownership"),
+        Question::multiple_choice(
+            "drop-scope",
+            "ownership",
+            "What happens to a value when its owner goes out of scope?",
+            &["Nothing until the garbage collector runs", "It is dropped", "It moves to the caller"],
+            1,
+            "Rule 3 of ownership: when the owner goes out of scope, the value is dropped right there.",
+        )
+        .with_lesson("take_ownership"),
+        Question::multiple_choice(
+            "clone-cost",
+            "moves",
+            "What does `let $b = $a.clone();` do for a String?",
+            &["Copies the pointer only", "Copies the heap data too", "Moves $a"],
+            1,
+            "clone makes a deep copy: new heap memory with the same text. Both $a and $b stay valid, but it costs an allocation.",
+        )
+        .with_lesson("take_ownership"),
+        Question::multiple_choice(
+            "slicing-output",
+            "slices",
+            "What does `slicing(\"hello world\")` return?",
+            &["\"hello\"", "\"hello wo\"", "\"hello world\""],
+            1,
+            "slicing returns everything before the first 'r', which is \"hello wo\".",
+        )
+        .with_lesson("slicing"),
+        Question::true_false(
+            "moves-into-function",
+            "moves",
+            "After `take_ownership(s3);` the caller can still print `s3`.",
+            false,
+            "Passing a String to a function moves it, just like assigning it. s3 belongs to `complex` inside the function and is dropped when it returns.",
+        )
+        .with_lesson("take_ownership"),
+        Question::predict_output(
+            "moves-takes-and-gives-back",
+            "moves",
+            "fn takes_and_gives_back(complex: String) -> String {\n    complex\n}\n\nlet $a = String::from(\"$text\");\nlet $b = takes_and_gives_back($a);\nprintln!(\"$b: {$b}\");",
+            "$b: $text",
+            "Ownership goes into the function with $a and comes back out through the return value into $b.",
+        )
+        .with_lesson("takes_and_gives_back"),
+        Question::predict_output(
+            "copy-after-assign",
+            "copy",
+            "let $a = $n;\nlet $b = $a;\nprintln!(\"$a: {$a}, $b: {$b}\");",
+            "$a: $n, $b: $n",
+            "i32 is Copy, `let $b = $a;` makes a second $n and both variables stay usable.",
+        )
+        .with_lesson("makes_copy"),
+        Question::true_false(
+            "copy-string",
+            "copy",
+            "`String` is a Copy type.",
+            false,
+            "Copying a String would mean copying its heap data, which Rust never does behind your back. It's moved, or cloned when you ask for it.",
+        )
+        .with_lesson("take_ownership"),
+        Question::fill_in_blank(
+            "copy-types",
+            "copy",
+            "Name a type that is Copy, so `let b = a;` leaves `a` usable.",
+            "let a: ___ = ...;\nlet b = a;\nprintln!(\"{a}\");",
+            &["i32", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i64", "i128", "isize", "f32", "f64", "bool", "char"],
+            "Integers, floats, bool and char are Copy, as are tuples and arrays of them. Anything owning heap memory isn't.",
+        )
+        .with_lesson("makes_copy"),
+        Question::true_false(
+            "borrow-shared-count",
+            "references",
+            "Any number of `&s` references can be used at the same time.",
+            true,
+            "Immutable references only read, so any number of them can coexist, as long as there's no `&mut s` in between.",
+        )
+        .with_lesson("reference"),
+        Question::fill_in_blank(
+            "borrow-mut-signature",
+            "references",
+            "Finish the signature so the function can push onto the caller's String without owning it.",
+            "fn mutate_reference(my_mut_string: ___) {\n    my_mut_string.push_str(\" world!\");\n}",
+            &["&mut String", "&mut std::string::String"],
+            "A mutable reference, `&mut String`, lends the String out for changing. The caller passes `&mut my_mut_string`.",
+        )
+        .with_lesson("mutate_reference"),
+        Question::predict_output(
+            "slices-range",
+            "slices",
+            "let s = \"hello world\";\nprintln!(\"{}\", &s[0..5]);\nprintln!(\"{}\", &s[6..]);",
+            "hello\nworld",
+            "A range slice borrows bytes start..end of the string, end excluded. Leaving out the end goes to the end of the string.",
+        )
+        .with_lesson("slicing"),
+        Question::fill_in_blank(
+            "slices-param",
+            "slices",
+            "Which parameter type lets `first_word` take both a `&String` and a string literal?",
+            "fn first_word(text: ___) -> &str",
+            &["&str"],
+            "&String coerces to &str, and literals are &str already, so &str takes both.",
+        )
+        .with_lesson("slicing"),
+        Question::multiple_choice(
+            "patterns-ref",
+            "patterns",
+            "With `$a` a `(String, u32)`, what type does `$b` get in `let (ref $b, $c) = $a;`?",
+            &["String", "&String", "&mut String"],
+            1,
+            "`ref` binds by reference, $b is a &String borrowing from $a, which keeps its String. $c is a copy of the u32 either way.",
+        )
+        .with_lesson("binding_modes"),
+        Question::true_false(
+            "patterns-default-binding",
+            "patterns",
+            "In `if let Some($a) = &guest { ... }`, with guest an `Option<String>`, the String moves out of guest into `$a`.",
+            false,
+            "Matching on a reference switches the default binding mode to ref, $a is a &String and guest is untouched. Without the `&` it would move.",
+        )
+        .with_lesson("binding_modes"),
+        Question::fill_in_blank(
+            "patterns-ref-mut",
+            "patterns",
+            "Finish the pattern so the String is changed in place instead of moved out of guest.",
+            "if let Some(___ $a) = guest {\n    $a.push('!');\n}\nprintln!(\"{guest:?}\");",
+            &["ref mut"],
+            "`ref mut` binds a &mut String into guest. Matching on `&mut guest` with a plain `Some($a)` does the same.",
+        )
+        .with_lesson("binding_modes"),
+        Question::predict_output(
+            "patterns-mut-reference",
+            "patterns",
+            "let mut pair = (String::from(\"$text\"), $n);\nlet ($a, $b) = &mut pair;\n$a.push('!');\n*$b = $m;\nprintln!(\"{} {}\", pair.0, pair.1);",
+            "$text! $m",
+            "Both bindings are ref mut because the pattern matches a &mut, so the changes are made to pair itself.",
+        )
+        .with_lesson("binding_modes"),
+    ];
+    bank.extend(snippets(WILL_IT_COMPILE).expect("the built-in will_it_compile.toml is valid"));
+    bank
+}
+
+// How one question went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub question: String,
+    pub topic: String,
+    pub given: String,
+    pub correct: bool,
+    // From showing the question to the answer coming in, None for answers saved before it was measured
+    pub took: Option<Duration>,
+    // The time limit ran out first, `given` is empty and it counts as wrong
+    pub timed_out: bool,
+}
+
+// The result of a whole quiz
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuizReport {
+    pub answers: Vec<Answer>,
+}
+
+impl QuizReport {
+    pub fn correct(&self) -> usize {
+        self.answers.iter().filter(|answer| answer.correct).count()
+    }
+
+    pub fn total(&self) -> usize {
+        self.answers.len()
+    }
+
+    // (topic, average time to answer, timeouts) for the topics with timed answers, sorted by topic
+    pub fn latency_by_topic(&self) -> Vec<(String, Duration, usize)> {
+        let mut topics: BTreeMap<&str, (Vec<Duration>, usize)> = BTreeMap::new();
+        for answer in &self.answers {
+            if let Some(took) = answer.took {
+                let (times, timeouts) = topics.entry(&answer.topic).or_default();
+                times.push(took);
+                *timeouts += usize::from(answer.timed_out);
+            }
+        }
+        topics.into_iter().map(|(topic, (times, timeouts))| (topic.to_string(), times.iter().sum::<Duration>() / times.len() as u32, timeouts)).collect()
+    }
+}
+
+impl ToJson for QuizReport {
+    fn to_json(&self) -> Json {
+        let answers = self.answers.iter().map(|answer| {
+            Json::object([
+                ("question", (&answer.question).into()),
+                ("topic", (&answer.topic).into()),
+                ("given", (&answer.given).into()),
+                ("correct", answer.correct.into()),
+                ("took_ms", answer.took.map(|took| took.as_millis() as u64).into()),
+                ("timed_out", answer.timed_out.into()),
+            ])
+        });
+        let latency = self.latency_by_topic().into_iter().map(|(topic, average, timeouts)| {
+            Json::object([("topic", topic.into()), ("average_ms", (average.as_millis() as u64).into()), ("timed_out", timeouts.into())])
+        });
+        Json::object([
+            ("correct", self.correct().into()),
+            ("total", self.total().into()),
+            ("answers", Json::array(answers)),
+            ("latency", Json::array(latency)),
+        ])
+    }
+}
+
+// Asks each question on `out`, reads answers from `input` and gives feedback straight away.
+// Stops early, with what was answered so far, when the input ends or the answer is "q".
+pub fn run_quiz(
+    questions: &[Question],
+    input: &mut dyn AnswerSource,
+    out: &mut dyn Write,
+    style: Style,
+) -> Result<QuizReport, LessonError> {
+    run_quiz_with(questions, input, out, style, TimeLimit::default(), &mut |_, _| Ok(()))
+}
+
+// Like `run_quiz` within `limit`, calling `on_answer` as each answer comes in, e.g. to write it down with the
+// time. Only a source that can time out, like `ThreadedInput`, stops waiting when the time is up.
+pub fn run_quiz_with(
+    questions: &[Question],
+    input: &mut dyn AnswerSource,
+    out: &mut dyn Write,
+    style: Style,
+    limit: TimeLimit,
+    on_answer: &mut dyn FnMut(&Question, &Answer) -> Result<(), LessonError>,
+) -> Result<QuizReport, LessonError> {
+    let mut report = QuizReport::default();
+    let deadline = limit.per_quiz.map(|total| Instant::now() + total);
+    for (number, question) in questions.iter().enumerate() {
+        // Whichever runs out first, the question's own limit or what's left of the quiz's
+        let left = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        let timeout = match (limit.per_question, left) {
+            (Some(per_question), Some(left)) => Some(per_question.min(left)),
+            (per_question, left) => per_question.or(left),
+        };
+        let mut heading = format!("Question {} of {}", number + 1, questions.len());
+        if let Some(timeout) = timeout {
+            heading.push_str(&format!(" ({}s)", timeout.as_secs_f64().ceil()));
+        }
+        input.discard_pending();
+        writeln!(out)?;
+        writeln!(out, "{}", style.paint(Role::Heading, &heading))?;
+        writeln!(out, "{}", style.code_spans(&question.prompt))?;
+        match &question.kind {
+            QuestionKind::MultipleChoice { choices, .. } => {
+                for (index, choice) in choices.iter().enumerate() {
+                    writeln!(out, "  {}) {}", (b'a' + index as u8) as char, choice)?;
+                }
+            }
+            QuestionKind::TrueFalse { .. } => writeln!(out, "{}", style.paint(Role::Dim, "true or false"))?,
+            QuestionKind::PredictOutput { code, output } => {
+                write_code(out, code, style)?;
+                if output.lines().count() > 1 {
+                    writeln!(out, "{}", style.paint(Role::Dim, "one line per printed line, separated by /"))?;
+                }
+            }
+            QuestionKind::FillInBlank { code, .. } => {
+                write_code(out, code, style)?;
+                writeln!(out, "{}", style.paint(Role::Dim, &format!("what goes in the {BLANK}?")))?;
+            }
+            QuestionKind::WillItCompile { code, .. } => {
+                write_code(out, code, style)?;
+                writeln!(out, "{}", style.paint(Role::Dim, "yes or no"))?;
+            }
+        }
+        write!(out, "> ")?;
+        out.flush()?;
+
+        let asked = Instant::now();
+        let (line, timed_out) = match input.read_answer(timeout)? {
+            Reply::Line(line) if line.trim().eq_ignore_ascii_case("q") => {
+                writeln!(out)?;
+                break;
+            }
+            Reply::Line(line) => (line, false),
+            Reply::TimedOut => (String::new(), true),
+            Reply::Closed => {
+                writeln!(out)?;
+                break;
+            }
+        };
+        let took = asked.elapsed();
+
+        let correct = !timed_out && question.check(&line);
+        if timed_out {
+            writeln!(out)?;
+            writeln!(out, "{} The answer is: {}", style.paint(Role::Error, "Time's up."), question.expected())?;
+        } else if correct {
+            writeln!(out, "{}", style.paint(Role::Success, "Correct!"))?;
+        } else {
+            writeln!(out, "{} The answer is: {}", style.paint(Role::Error, "Not quite."), question.expected())?;
+        }
+        // What rustc actually says, before the why
+        if let QuestionKind::WillItCompile { error: Some(error), .. } = &question.kind {
+            writeln!(out, "{}", style.paint(Role::Error, &format!("error[{}]: {}", error.code, error.message)))?;
+        }
+        writeln!(out, "{}", style.code_spans(&question.explanation))?;
+        // A lesson that isn't compiled in (see the lessons-* features) has nothing to show
+        if let Some(lesson) = question.lesson.as_deref().and_then(registry::find) {
+            if correct {
+                writeln!(out, "{}", style.paint(Role::Dim, &format!("`rust_day_6 run {}` covers this", lesson.name)))?;
+            } else {
+                show_lesson(out, lesson, style)?;
+            }
+        }
+
+        let answer = Answer { question: question.id.clone(), topic: question.topic.clone(), given: line.trim().to_string(), correct, took: Some(took), timed_out };
+        on_answer(question, &answer)?;
+        report.answers.push(answer);
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            let skipped = questions.len() - number - 1;
+            if skipped > 0 {
+                writeln!(out, "{}", style.paint(Role::Dim, &format!("the quiz's time is up, {skipped} question{} left unasked", if skipped == 1 { "" } else { "s" })))?;
+            }
+            break;
+        }
+    }
+    Ok(report)
+}
+
+// After a wrong answer, what the lesson says about it and its demo run there and then, with every move, copy
+// and borrow shown as it happens
+fn show_lesson(out: &mut dyn Write, lesson: &LessonDef, style: Style) -> Result<(), LessonError> {
+    writeln!(out)?;
+    writeln!(out, "{}", style.paint(Role::Heading, &format!("From the lesson {}: {}", lesson.name, lesson.summary)))?;
+    writeln!(out, "{}", style.code_spans(lesson.explanation))?;
+    writeln!(out)?;
+    writeln!(out, "{}", style.paint(Role::Dim, &format!("Running its demo, `rust_day_6 run {}` shows it again:", lesson.name)))?;
+    let mut reporter = WriterReporter::new(&mut *out).show_details(true).style(style);
+    (lesson.run)(&mut reporter)?;
+    reporter.finish()
+}
+
+// How long answers may take, no limit when neither is set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeLimit {
+    pub per_question: Option<Duration>,
+    pub per_quiz: Option<Duration>,
+}
+
+// What came back when waiting for an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    Line(String),
+    TimedOut,
+    // The input ended
+    Closed,
+}
+
+// Where answers come from, a plain reader doesn't know how to stop waiting and ignores the timeout
+pub trait AnswerSource {
+    fn read_answer(&mut self, timeout: Option<Duration>) -> Result<Reply, LessonError>;
+
+    // Throws away lines that came in before the next question is shown, e.g. an answer typed after its question
+    // timed out, so it isn't taken as the answer to the next one
+    fn discard_pending(&mut self) {}
+}
+
+impl<R: BufRead> AnswerSource for R {
+    fn read_answer(&mut self, _timeout: Option<Duration>) -> Result<Reply, LessonError> {
+        let mut line = String::new();
+        match self.read_line(&mut line)? {
+            0 => Ok(Reply::Closed),
+            _ => Ok(Reply::Line(line)),
+        }
+    }
+}
+
+// Reads lines on a thread of its own, so waiting for one can time out
+pub struct ThreadedInput {
+    lines: Receiver<io::Result<String>>,
+    // Typed by someone watching the questions, rather than piped in ahead of time in order
+    interactive: bool,
+}
+
+impl ThreadedInput {
+    pub fn stdin() -> Self {
+        let interactive = io::stdin().is_terminal();
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::stdin().lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { lines, interactive }
+    }
+}
+
+impl AnswerSource for ThreadedInput {
+    fn read_answer(&mut self, timeout: Option<Duration>) -> Result<Reply, LessonError> {
+        let line = match timeout {
+            Some(timeout) => match self.lines.recv_timeout(timeout) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => return Ok(Reply::TimedOut),
+                Err(RecvTimeoutError::Disconnected) => return Ok(Reply::Closed),
+            },
+            None => match self.lines.recv() {
+                Ok(line) => line,
+                Err(_) => return Ok(Reply::Closed),
+            },
+        };
+        Ok(Reply::Line(line?))
+    }
+
+    fn discard_pending(&mut self) {
+        if self.interactive {
+            while self.lines.try_recv().is_ok() {}
+        }
+    }
+}
+
+fn write_code(out: &mut dyn Write, code: &str, style: Style) -> Result<(), LessonError> {
+    for line in code.lines() {
+        if line.is_empty() {
+            writeln!(out)?;
+        } else {
+            writeln!(out, "    {}", style.paint(Role::Code, line))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn late_answer_is_discarded_before_the_next_question() {
+        let (sender, lines) = mpsc::channel();
+        let mut input = ThreadedInput { lines, interactive: true };
+        assert_eq!(input.read_answer(Some(Duration::from_millis(10))).unwrap(), Reply::TimedOut);
+        sender.send(Ok("late".to_string())).unwrap(); // Typed after the first question timed out
+        input.discard_pending();
+        sender.send(Ok("b".to_string())).unwrap();
+        assert_eq!(input.read_answer(Some(Duration::from_secs(5))).unwrap(), Reply::Line("b".to_string()));
+    }
+
+    #[test]
+    fn piped_answers_are_kept() {
+        let (sender, lines) = mpsc::channel();
+        let mut input = ThreadedInput { lines, interactive: false };
+        sender.send(Ok("a".to_string())).unwrap();
+        input.discard_pending();
+        assert_eq!(input.read_answer(Some(Duration::from_secs(5))).unwrap(), Reply::Line("a".to_string()));
+    }
+
+    fn choice() -> Question {
+        Question::multiple_choice("q", "moves", "Which moves?", &["let b = a;", "let b = &a;", "let b = a.clone();"], 0, "")
+    }
+
+    #[test]
+    fn choices_by_number_letter_or_text() {
+        let question = choice();
+        for input in ["1", " 1 ", "a", "A", "let b = a;", "LET B = A;"] {
+            assert!(question.check(input), "{input:?}");
+        }
+        for input in ["2", "b", "let b = &a;"] {
+            assert!(!question.check(input), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn out_of_range_choices_are_wrong() {
+        let choices: Vec<String> = ["x", "y"].map(String::from).to_vec();
+        assert_eq!(parse_choice(&choices, "0"), None);
+        assert_eq!(parse_choice(&choices, "3"), None);
+        assert_eq!(parse_choice(&choices, "c"), None);
+        assert_eq!(parse_choice(&choices, "?"), None);
+        assert_eq!(parse_choice(&choices, "-1"), None);
+        assert_eq!(parse_choice(&choices, ""), None);
+        assert_eq!(parse_choice(&choices, "99999999999999999999999"), None);
+        assert_eq!(parse_choice(&choices, "B"), Some(1));
+    }
+
+    #[test]
+    fn true_false_and_will_it_compile_take_yes_and_no() {
+        let statement = Question::true_false("q", "moves", "A move copies the heap data", false, "");
+        assert!(statement.check("f") && statement.check(" No ") && statement.check("FALSE"));
+        assert!(!statement.check("t") && !statement.check("maybe"));
+        let compiles = Question::will_it_compile("q", "moves", "let a = 1;", None, "makes_copy", "");
+        assert!(compiles.check("yes") && !compiles.check("n"));
+        let error = CompileError { code: "E0382".to_string(), message: "borrow of moved value".to_string() };
+        let rejected = Question::will_it_compile("q", "moves", "let b = a;", Some(error), "take_ownership", "");
+        assert!(rejected.check("no") && !rejected.check("y"));
+    }
+
+    #[test]
+    fn typed_answers_ignore_case_and_spacing() {
+        assert_eq!(normalize("  Hello,   World  "), "hello, world");
+        assert_eq!(normalize("\"hello\""), "hello");
+        assert_eq!(normalize("`&mut String`"), "&mut string");
+        assert_eq!(normalize("hello\n\n  world "), "hello\nworld");
+        assert_eq!(normalize("hello / world"), "hello\nworld");
+
+        let output = Question::predict_output("q", "moves", "", "s1: hello\ns2: hello", "");
+        assert!(output.check("S1: hello / s2:  hello") && output.check("s1: hello\ns2: hello"));
+        assert!(!output.check("s1: hello"));
+        let blank = Question::fill_in_blank("q", "borrowing", "", "fn f(s: ___)", &["&mut String", "&mut str"], "");
+        assert!(blank.check("&MUT  string") && blank.check("&mut str") && !blank.check("&String"));
+    }
+
+    fn values() -> Vec<(&'static str, String)> {
+        vec![("a", "s1".to_string()), ("b", "name".to_string()), ("text", "crab".to_string())]
+    }
+
+    #[test]
+    fn placeholders_are_filled() {
+        assert_eq!(fill_placeholders("let $b = $a;", &values()), "let name = s1;");
+        assert_eq!(fill_placeholders("String::from(\"$text\")", &values()), "String::from(\"crab\")");
+        assert_eq!(fill_placeholders("no placeholders", &values()), "no placeholders");
+    }
+
+    #[test]
+    fn repeated_placeholders_get_the_same_value() {
+        assert_eq!(fill_placeholders("$a, $a and {$a}", &values()), "s1, s1 and {s1}");
+    }
+
+    #[test]
+    fn unknown_placeholders_are_left_alone() {
+        assert_eq!(fill_placeholders("$c costs $5, $abc, $", &values()), "$c costs $5, $abc, $");
+        assert_eq!(fill_placeholders("$$a", &values()), "$s1");
+    }
+
+    #[test]
+    fn instantiate_is_the_same_for_the_same_seed() {
+        let template = Question::multiple_choice("q", "moves", "After `let $b = $a;` can `$a` be used? ($n)", &["Yes, $a", "No"], 1, "$a moved to $b");
+        let first = template.instantiate(&mut Rng::new(7));
+        assert_eq!(first, template.instantiate(&mut Rng::new(7)));
+        assert!(!first.prompt.contains('$') && !first.explanation.contains('$'));
+        let QuestionKind::MultipleChoice { choices, answer } = &first.kind else { unreachable!() };
+        assert_eq!(*answer, 1);
+        // $a and $b are always different names, and $a is the same one everywhere
+        let (a, b) = first.explanation.split_once(" moved to ").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(choices[0], format!("Yes, {a}"));
+        assert!(first.prompt.starts_with(&format!("After `let {b} = {a};` can `{a}` be used?")));
+    }
+
+    #[test]
+    fn a_question_without_placeholders_is_unchanged() {
+        let question = choice();
+        assert_eq!(question.instantiate(&mut Rng::new(1)), question);
+    }
+
+    #[test]
+    fn weak_topics_weigh_more() {
+        assert_eq!(topic_weight(None), topic_weight(Some((0, 0))));
+        assert!(topic_weight(Some((0, 10))) > topic_weight(None));
+        assert!(topic_weight(None) > topic_weight(Some((10, 10))));
+        // Never quite zero, so a mastered topic still comes up
+        assert!(topic_weight(Some((1000, 1000))) > MIN_WEIGHT);
+    }
+
+    #[test]
+    fn select_favours_weak_topics() {
+        let pool: Vec<Question> = (0..10)
+            .map(|index| Question::true_false(&format!("q{index}"), if index < 5 { "weak" } else { "strong" }, "", true, ""))
+            .collect();
+        let weight = |question: &Question| topic_weight(Some(if question.topic == "weak" { (1, 10) } else { (9, 10) }));
+        let mut rng = Rng::new(42);
+        let mut weak = 0;
+        for _ in 0..200 {
+            weak += select(pool.clone(), 3, weight, &mut rng).iter().filter(|question| question.topic == "weak").count();
+        }
+        // 600 questions drawn, half the pool is weak, and weak ones are drawn far more than half the time
+        assert!(weak > 400, "{weak} of 600 were weak");
+    }
+
+    #[test]
+    fn select_draws_each_question_at_most_once() {
+        let pool: Vec<Question> = (0..4).map(|index| Question::true_false(&format!("q{index}"), "moves", "", true, "")).collect();
+        let selected = select(pool.clone(), 10, |_| 1.0, &mut Rng::new(3));
+        assert_eq!(selected.len(), 4);
+        let mut ids: Vec<&str> = selected.iter().map(|question| question.id.as_str()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, ["q0", "q1", "q2", "q3"]);
+        assert!(select(Vec::new(), 3, |_| 1.0, &mut Rng::new(3)).is_empty());
+        assert_eq!(select(pool, 2, |_| 1.0, &mut Rng::new(3)).len(), 2);
+    }
+}
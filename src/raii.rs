@@ -0,0 +1,345 @@
+use std::cell::RefCell;
+
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::OwnershipEvent;
+use crate::refcell::{self, Journal};
+use crate::report::Reporter;
+
+// A value is dropped when its owner goes away, at a point you can read off the code, and `impl Drop` runs your own
+// code right then. So a type that holds a resource can give it back in `drop`, flushing and closing a file,
+// unlocking a Mutex, putting a setting back, and it happens on every way out of the scope: the end, an early
+// return, a `?`, a panic. That's RAII, the resource's lifetime is its owner's. The demos' resources write what
+// they do into a Journal, which the demo then prints.
+
+pub const TAUGHT: Taught = Taught { day: 14, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(drop_trait_demo {
+        name: "drop_trait",
+        summary: "impl Drop runs cleanup code when the owner goes out of scope",
+        topics: &["ownership", "traits", "scope"],
+        prerequisites: &["drop_order", "refcell_basics"],
+        explanation: "`impl Drop for LogFile { fn drop(&mut self) { .. } }` is called by the compiler when a LogFile's owner is done with it, and only then: at the end of the scope it was declared in, when a function that took it by value returns, or when an early `return` or `?` leaves the scope. You can't call `file.drop()` yourself (E0040), a value dropped twice would free twice, `drop(file)` moves it into a function that ends at once instead. So a file that flushes and closes in its Drop is closed on every path, without a `close()` to forget.",
+    }),
+    lesson!(scope_guard_demo {
+        name: "scope_guard",
+        summary: "A ScopeGuard runs a closure when it's dropped, however the scope ends",
+        topics: &["ownership", "traits", "closures"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["drop_trait", "fn_traits"],
+        explanation: "A guard is a value whose only job is its Drop. `ScopeGuard::new(|| restore())` holds the FnOnce and calls it in `drop`, so whatever the scope changed is put back at the end, after an early return and while a panic unwinds too. A MutexGuard and a RefMut are guards like that, dropping them unlocks or ends the borrow. `dismiss()` takes the closure out first, for when the change should be kept.",
+    }),
+    lesson!(early_drop_demo {
+        name: "early_drop",
+        summary: "drop(value) ends a value early, and with it the borrow it holds",
+        topics: &["ownership", "borrowing", "scope"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["drop_trait", "borrow_checker"],
+        explanation: "A plain `&mut text` ends when it's last used. A value that holds one and implements Drop is different: its `drop` will run at the end of the scope and might use the reference then, so the borrow lasts until there, and reading `text` before it is rejected (E0502). `std::mem::drop(edit)` drops it on the spot, which ends the borrow. There's nothing special about it, it's `fn drop<T>(_x: T) {}`: taking the value by value makes the parameter its owner, and the parameter goes out of scope straight away.",
+    }),
+];
+
+// Prints what the resources did since the last time
+fn report_journal(reporter: &mut dyn Reporter, journal: &Journal) {
+    for entry in journal.take() {
+        reporter.message(&format!("  journal: {entry}"));
+    }
+}
+
+// A file-like resource: opened when it's made, written to through a buffer, flushed and closed by its Drop
+pub struct LogFile<'a> {
+    pub name: String,
+    lines: Vec<String>,
+    journal: &'a Journal,
+}
+
+impl<'a> LogFile<'a> {
+    pub fn open(name: &str, journal: &'a Journal) -> Self {
+        journal.write(&format!("open {name}"));
+        LogFile { name: name.to_string(), lines: Vec::new(), journal }
+    }
+
+    // Only buffered, nothing reaches the file until it's closed
+    pub fn write(&mut self, line: &str) {
+        self.lines.push(line.to_string());
+    }
+}
+
+impl Drop for LogFile<'_> {
+    fn drop(&mut self) {
+        let lines = if self.lines.len() == 1 { "line" } else { "lines" };
+        self.journal.write(&format!("flush {} {lines} to {} and close it", self.lines.len(), self.name));
+    }
+}
+
+// Writes to a file and gives up halfway when told to, the file is closed either way
+pub fn save(journal: &Journal, fail: bool) -> Result<(), LessonError> {
+    let mut file = LogFile::open("save.log", journal);
+    file.write("started");
+    if fail {
+        return Err(LessonError::BadInput("disk full".to_string())); // file is dropped on the way out
+    }
+    file.write("finished");
+    Ok(())
+} // file dropped here
+
+// Takes ownership of a file, which is closed when this returns
+pub fn archive(file: LogFile) {
+    file.journal.write(&format!("archive {}", file.name));
+}
+
+const EXPLICIT_DROP: (&str, &str) = (
+    "let file = LogFile::open(\"notes.log\", &journal);\nfile.drop();",
+    "error[E0040]: explicit use of destructor method\n --> src/main.rs:2:6\n  |\n2 | file.drop();\n  |      ^^^^ explicit destructor calls not allowed",
+);
+
+// Files closed at the end of a scope, in reverse order, on an early return and inside a function that took one
+pub fn drop_trait_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let (code, error) = EXPLICIT_DROP;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    let journal = Journal::new();
+    {
+        let mut notes = LogFile::open("notes.log", &journal);
+        let mut errors = LogFile::open("errors.log", &journal);
+        notes.write("hello");
+        notes.write("world");
+        errors.write("nothing yet");
+        reporter.message("end of scope");
+    } // errors, then notes, the reverse of the order they were declared in
+    reporter.event(&OwnershipEvent::dropped("errors"));
+    reporter.event(&OwnershipEvent::dropped("notes"));
+    report_journal(reporter, &journal);
+
+    for fail in [false, true] {
+        let result = save(&journal, fail);
+        reporter.message(&format!("save(fail: {fail}): {result:?}"));
+        report_journal(reporter, &journal);
+    }
+
+    let file = LogFile::open("old.log", &journal);
+    archive(file); // file moves into archive, which is the owner when it's closed
+    reporter.event(&OwnershipEvent::moved("file", "archive"));
+    reporter.event(&OwnershipEvent::dropped("file"));
+    report_journal(reporter, &journal);
+    Ok(())
+}
+
+// Runs a closure when it's dropped, unless it was dismissed
+pub struct ScopeGuard<F: FnOnce()> {
+    on_drop: Option<F>,
+}
+
+impl<F: FnOnce()> ScopeGuard<F> {
+    pub fn new(on_drop: F) -> Self {
+        ScopeGuard { on_drop: Some(on_drop) }
+    }
+
+    // Keeps the change, drop still runs but finds no closure to call
+    pub fn dismiss(mut self) {
+        self.on_drop = None;
+    }
+}
+
+impl<F: FnOnce()> Drop for ScopeGuard<F> {
+    fn drop(&mut self) {
+        if let Some(on_drop) = self.on_drop.take() {
+            on_drop();
+        }
+    }
+}
+
+// Turns the setting on for the rest of the scope, the guard turns it back however the scope is left
+fn verbose_while(setting: &RefCell<String>, journal: &Journal, how: &str) -> Result<(), LessonError> {
+    let previous = setting.replace(String::from("verbose"));
+    let _restore = ScopeGuard::new(|| {
+        journal.write(&format!("guard puts back {previous:?}"));
+        *setting.borrow_mut() = previous;
+    }); // `_restore`, not `_`, which would drop the guard right here
+    journal.write(&format!("setting is {:?}", setting.borrow()));
+    match how {
+        "return early" => return Err(LessonError::BadInput("gave up".to_string())),
+        "panic" => panic!("something broke"),
+        _ => journal.write("finished"),
+    }
+    Ok(())
+} // _restore dropped here
+
+// The setting put back on each way out of the scope, then a guard dismissed to keep a change
+pub fn scope_guard_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let journal = Journal::new();
+    let setting = RefCell::new(String::from("quiet"));
+    for how in ["finish", "return early"] {
+        let result = verbose_while(&setting, &journal, how);
+        reporter.message(&format!("{how}: {result:?}"));
+        report_journal(reporter, &journal);
+        reporter.message(&format!("setting: {:?}", setting.borrow()));
+    }
+    let caught = refcell::catch_quietly(|| verbose_while(&setting, &journal, "panic"));
+    if let Err(message) = caught {
+        reporter.message(&format!("panic: {message}"));
+        report_journal(reporter, &journal); // The guard ran while the panic unwound past it
+        reporter.message(&format!("setting: {:?}", setting.borrow()));
+    }
+    reporter.message("");
+
+    let mut names = vec![String::from("ferris")];
+    let saved = names.clone();
+    names.push(String::from("corro"));
+    let rollback = ScopeGuard::new(|| journal.write(&format!("roll back to {saved:?}")));
+    rollback.dismiss(); // The change is wanted, so the guard is dropped without calling its closure
+    report_journal(reporter, &journal);
+    reporter.message(&format!("dismissed, nothing rolled back: {names:?}"));
+    Ok(())
+}
+
+// A change to a String in progress, marked as saved when it's dropped
+pub struct Edit<'a> {
+    text: &'a mut String,
+}
+
+impl<'a> Edit<'a> {
+    pub fn new(text: &'a mut String) -> Self {
+        Edit { text }
+    }
+
+    pub fn push(&mut self, words: &str) {
+        self.text.push_str(words);
+    }
+}
+
+impl Drop for Edit<'_> {
+    fn drop(&mut self) {
+        self.text.push_str(" (saved)"); // Uses the &mut, which is why the borrow lasts until the drop
+    }
+}
+
+// What mem::drop is, written out
+pub fn discard<T>(_value: T) {} // _value owns it now, and goes out of scope straight away
+
+const BORROW_UNTIL_DROP: (&str, &str) = (
+    "fn main() {\n    let mut text = String::from(\"draft\");\n    let edit = Edit { text: &mut text };\n    println!(\"{text}\");\n}",
+    "error[E0502]: cannot borrow `text` as immutable because it is also borrowed as mutable\n --> src/main.rs:4:16\n  |\n3 |     let edit = Edit { text: &mut text };\n  |                             --------- mutable borrow occurs here\n4 |     println!(\"{text}\");\n  |                ^^^^ immutable borrow occurs here\n5 | }\n  | - mutable borrow might be used here, when `edit` is dropped and runs the `Drop` code for type `Edit`",
+);
+
+// The borrow rustc keeps alive for Drop, ended early with drop(), then drop() written by hand
+pub fn early_drop_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let (code, error) = BORROW_UNTIL_DROP;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    let mut text = String::from("draft");
+    let mut edit = Edit::new(&mut text);
+    reporter.event(&OwnershipEvent::borrowed_mut("text", "edit"));
+    edit.push(", second try");
+    drop(edit); // Runs Edit's drop now, and the &mut text ends with it
+    reporter.event(&OwnershipEvent::dropped("edit"));
+    reporter.message(&format!("text after drop(edit): {text}"));
+
+    let journal = Journal::new();
+    let file = LogFile::open("scratch.log", &journal);
+    reporter.event(&OwnershipEvent::moved("file", "_value"));
+    discard(file); // Does exactly what drop(file) would
+    reporter.event(&OwnershipEvent::dropped("_value"));
+    report_journal(reporter, &journal);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::DemoOutput;
+
+    // The journal lines a demo printed, without the "  journal: "
+    fn journal_lines(output: &DemoOutput) -> Vec<&str> {
+        output.messages.iter().filter_map(|message| message.strip_prefix("  journal: ")).collect()
+    }
+
+    #[test]
+    fn save_closes_the_file_on_both_ways_out() {
+        let journal = Journal::new();
+        assert!(save(&journal, false).is_ok());
+        assert!(matches!(save(&journal, true), Err(LessonError::BadInput(message)) if message == "disk full"));
+        assert_eq!(
+            journal.take(),
+            ["open save.log", "flush 2 lines to save.log and close it", "open save.log", "flush 1 line to save.log and close it"]
+        );
+    }
+
+    #[test]
+    fn a_scope_guard_runs_unless_dismissed() {
+        let ran = RefCell::new(Vec::new());
+        drop(ScopeGuard::new(|| ran.borrow_mut().push("dropped")));
+        ScopeGuard::new(|| ran.borrow_mut().push("dismissed")).dismiss();
+        assert_eq!(*ran.borrow(), ["dropped"]);
+    }
+
+    #[test]
+    fn verbose_while_puts_the_setting_back_after_a_panic() {
+        let journal = Journal::new();
+        let setting = RefCell::new(String::from("quiet"));
+        assert_eq!(refcell::catch_quietly(|| verbose_while(&setting, &journal, "panic")).unwrap_err(), "something broke");
+        assert_eq!(*setting.borrow(), "quiet");
+        assert_eq!(journal.take(), ["setting is \"verbose\"", "guard puts back \"quiet\""]);
+    }
+
+    #[test]
+    fn drop_trait_demo_closes_in_reverse_order() {
+        let mut output = DemoOutput::new();
+        drop_trait_demo(&mut output).unwrap();
+        assert_eq!(
+            journal_lines(&output),
+            [
+                "open notes.log",
+                "open errors.log",
+                "flush 1 line to errors.log and close it",
+                "flush 2 lines to notes.log and close it",
+                "open save.log",
+                "flush 2 lines to save.log and close it",
+                "open save.log",
+                "flush 1 line to save.log and close it",
+                "open old.log",
+                "archive old.log",
+                "flush 0 lines to old.log and close it",
+            ]
+        );
+        assert!(output.messages.contains(&"save(fail: true): Err(BadInput(\"disk full\"))".to_string()));
+    }
+
+    #[test]
+    fn scope_guard_demo_restores_the_setting_every_way() {
+        let mut output = DemoOutput::new();
+        scope_guard_demo(&mut output).unwrap();
+        let settings = output.messages.iter().filter(|message| message.starts_with("setting: ")).count();
+        assert_eq!(settings, 3);
+        assert!(output.messages.iter().filter(|message| message.starts_with("setting: ")).all(|message| message == "setting: \"quiet\""));
+        assert!(output.messages.contains(&"panic: something broke".to_string()));
+        assert_eq!(journal_lines(&output).iter().filter(|line| **line == "guard puts back \"quiet\"").count(), 3);
+        assert!(!journal_lines(&output).iter().any(|line| line.starts_with("roll back")));
+        assert_eq!(output.messages.last().unwrap(), "dismissed, nothing rolled back: [\"ferris\", \"corro\"]");
+    }
+
+    #[test]
+    fn early_drop_demo_ends_the_borrow_with_the_drop() {
+        let mut output = DemoOutput::new();
+        early_drop_demo(&mut output).unwrap();
+        assert!(output.messages.contains(&"text after drop(edit): draft, second try (saved)".to_string()));
+        assert_eq!(journal_lines(&output), ["open scratch.log", "flush 0 lines to scratch.log and close it"]);
+        assert_eq!(
+            output.events,
+            [OwnershipEvent::borrowed_mut("text", "edit"), OwnershipEvent::dropped("edit"), OwnershipEvent::moved("file", "_value"), OwnershipEvent::dropped("_value")]
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::rc::Rc;
+
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+use crate::tracked::Tracker;
+
+// An Rc<T> is a Box that can have several owners. The value sits on the heap next to a strong count, each
+// Rc::clone adds one owner and each dropped Rc takes one away, and the value is dropped with the last of them.
+// Owners share it, so nobody gets to change it through the Rc.
+
+pub const TAUGHT: Taught = Taught { day: 11, order: 2 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(rc_clone_demo {
+        name: "rc_clone",
+        summary: "Rc::clone adds an owner and bumps the strong count, the value isn't copied",
+        topics: &["smart-pointers", "ownership"],
+        prerequisites: &["box_basics", "stack_and_heap"],
+        explanation: "`Rc::new(value)` moves the value to the heap with a strong count of 1. `Rc::clone(&a)` makes another pointer to the same value and adds 1 to the count, it never copies the value, so it's cheap however big the value is. `a.clone()` does the same thing, `Rc::clone(&a)` is just the way of writing it that doesn't look like a deep copy. `Rc::strong_count(&a)` reads the count and `Rc::ptr_eq` checks two Rcs point at the same value.",
+    }),
+    lesson!(rc_scopes_demo {
+        name: "rc_scopes",
+        summary: "The count goes down as owners go out of scope, the value goes with the last one",
+        topics: &["smart-pointers", "ownership"],
+        prerequisites: &["rc_clone", "tracked"],
+        explanation: "Each Rc is an owner like any other variable: it's dropped at the end of its scope, or when it's moved into a function that returns, and dropping it takes 1 off the strong count. The value itself is only dropped when the count reaches 0, so it lives exactly as long as its longest-lived owner, whichever that turns out to be. Passing `&Rc<T>` lends it without touching the count.",
+    }),
+    lesson!(rc_immutable_demo {
+        name: "rc_immutable",
+        summary: "Rc only gives shared access, changing the value is rejected",
+        topics: &["smart-pointers", "borrowing"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["rc_scopes", "mutate_reference"],
+        explanation: "An Rc derefs to `&T`, never `&mut T`, because any other owner could be reading the value at the same time, so `shared.push_str(..)` is rejected (E0596). `Rc::get_mut` hands out a `&mut T` only while the count is 1, when nobody else can see it. `Rc::make_mut` clones the value first if it's shared, so the change only shows through this Rc. Changing a value all the owners see takes a RefCell inside the Rc.",
+    }),
+];
+
+// Reads the count through a reference, lending the Rc doesn't change it
+pub fn count(label: &str, rc: &Rc<String>) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(format!("{label}: strong_count = {}", Rc::strong_count(rc))).value("strong_count", Rc::strong_count(rc));
+    output
+}
+
+// Two more owners of the same String, then a String clone next to them for comparison
+pub fn rc_clone_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let a = Rc::new(String::from("ferris"));
+    reporter.event(&OwnershipEvent::borrowed("a", "rc"));
+    reporter.output(&count("after Rc::new", &a));
+
+    let b = Rc::clone(&a); // A second pointer, the String isn't copied
+    reporter.output(&count("after Rc::clone(&a)", &a));
+    let c = a.clone(); // The same as Rc::clone, it's Rc's Clone impl
+    reporter.output(&count("after a.clone()", &a));
+    reporter.message(&format!("a, b and c point at one String: {}, b: {b}, c: {c}", Rc::ptr_eq(&a, &b) && Rc::ptr_eq(&b, &c)));
+
+    let text = String::clone(&a); // Cloning the String itself, through the Rc, is a real copy
+    reporter.event(&OwnershipEvent::cloned("*a", "text"));
+    reporter.message(&format!("text: {text} is its own String, the count is still {}", Rc::strong_count(&a)));
+    Ok(())
+}
+
+// Takes an Rc by value, one more owner until it returns
+pub fn keep_for_a_while<T>(shared: Rc<T>) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(format!("inside the function: strong_count = {}", Rc::strong_count(&shared)));
+    output
+} // shared dropped here, the count goes back down
+
+// Clones an Rc of a Tracked String into a scope and a function, and drops the owners one by one
+pub fn rc_scopes_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let tracker = Tracker::new();
+    let first = Rc::new(tracker.track("name", String::from("ferris")));
+    reporter.message(&format!("strong_count = {}", Rc::strong_count(&first)));
+    {
+        let second = Rc::clone(&first);
+        reporter.message(&format!("in the block, strong_count = {}, second: {}", Rc::strong_count(&first), *second));
+    } // second dropped here
+    reporter.event(&OwnershipEvent::dropped("second"));
+    reporter.message(&format!("after the block, strong_count = {}", Rc::strong_count(&first)));
+
+    let third = Rc::clone(&first);
+    reporter.event(&OwnershipEvent::moved("third", "shared"));
+    reporter.output(&keep_for_a_while(third));
+    reporter.event(&OwnershipEvent::dropped("shared"));
+    reporter.message(&format!("after the call, strong_count = {}", Rc::strong_count(&first)));
+
+    let last = Rc::clone(&first);
+    drop(first); // The first owner goes, but last still has the String
+    reporter.event(&OwnershipEvent::dropped("first"));
+    tracker.report(reporter);
+    reporter.message(&format!("first: error, last: {}, strong_count = {}", *last, Rc::strong_count(&last)));
+    drop(last); // The count reaches 0, the String is dropped with it
+    reporter.event(&OwnershipEvent::dropped("last"));
+    tracker.report(reporter);
+    Ok(())
+}
+
+const CHANGE_THROUGH_RC: (&str, &str) = (
+    "let shared = Rc::new(String::from(\"ferris\"));\nlet other = Rc::clone(&shared);\nshared.push_str(\"_the_crab\");\nprintln!(\"{other}\");",
+    "error[E0596]: cannot borrow data in an `Rc` as mutable\n --> src/main.rs:3:1\n  |\n3 | shared.push_str(\"_the_crab\");\n  | ^^^^^^ cannot borrow as mutable",
+);
+
+// The rejected change, then get_mut while there's one owner and make_mut once there are two
+pub fn rc_immutable_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let (code, error) = CHANGE_THROUGH_RC;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    let mut shared = Rc::new(String::from("ferris"));
+    if let Some(name) = Rc::get_mut(&mut shared) {
+        // The only owner, so a &mut String is safe
+        reporter.event(&OwnershipEvent::borrowed_mut("shared", "name"));
+        name.push_str("_the_crab");
+    }
+    reporter.message(&format!("get_mut with one owner: {shared}"));
+
+    let other = Rc::clone(&shared);
+    reporter.message(&format!("get_mut with two owners: {:?}", Rc::get_mut(&mut shared)));
+    Rc::make_mut(&mut shared).push('!'); // Shared, so make_mut clones the String and shared points at the copy
+    reporter.event(&OwnershipEvent::cloned("*shared", "shared"));
+    reporter.message(&format!("make_mut: shared: {shared}, other: {other}, same String: {}", Rc::ptr_eq(&shared, &other)));
+    reporter.message(&format!("strong_count: shared {}, other {}", Rc::strong_count(&shared), Rc::strong_count(&other)));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_and_keep_for_a_while_only_borrow_or_hold_one_owner() {
+        let rc = Rc::new(String::from("ferris"));
+        let output = count("now", &rc);
+        assert_eq!(output.messages, ["now: strong_count = 1"]);
+        assert_eq!(output.values, [("strong_count".to_string(), "1".to_string())]);
+        assert_eq!(keep_for_a_while(Rc::clone(&rc)).messages, ["inside the function: strong_count = 2"]);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn rc_clone_demo_counts_owners_of_one_string() {
+        let mut output = DemoOutput::new();
+        rc_clone_demo(&mut output).unwrap();
+        let counts: Vec<&str> = output.values.iter().map(|(_, value)| value.as_str()).collect();
+        assert_eq!(counts, ["1", "2", "3"]);
+        assert_eq!(output.messages[3], "a, b and c point at one String: true, b: ferris, c: ferris");
+        assert_eq!(output.messages[4], "text: ferris is its own String, the count is still 3");
+    }
+
+    #[test]
+    fn rc_scopes_demo_drops_the_string_with_the_last_owner() {
+        let mut output = DemoOutput::new();
+        rc_scopes_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            [
+                "strong_count = 1",
+                "in the block, strong_count = 2, second: ferris",
+                "after the block, strong_count = 1",
+                "inside the function: strong_count = 2",
+                "after the call, strong_count = 1",
+                "first: error, last: ferris, strong_count = 1",
+            ]
+        );
+        assert_eq!(output.events.last(), Some(&OwnershipEvent::dropped("name")));
+        assert_eq!(output.events[output.events.len() - 2], OwnershipEvent::dropped("last"));
+    }
+
+    #[test]
+    fn rc_immutable_demo_mutates_only_a_sole_owner() {
+        let mut output = DemoOutput::new();
+        rc_immutable_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0596]")));
+        assert_eq!(
+            output.messages[output.messages.len() - 4..],
+            [
+                "get_mut with one owner: ferris_the_crab",
+                "get_mut with two owners: None",
+                "make_mut: shared: ferris_the_crab!, other: ferris_the_crab, same String: false",
+                "strong_count: shared 1, other 1",
+            ]
+        );
+    }
+}
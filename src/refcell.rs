@@ -0,0 +1,267 @@
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+use crate::runner;
+
+// The reference rule, from `notes references`: in any scope you can have either one mutable or any number of
+// immutable references. rustc checks it at compile time. A RefCell<T> checks the same rule while the program runs:
+// borrow() and borrow_mut() count the borrows that are alive, so a `&RefCell` can still hand out a `&mut T`, and
+// breaking the rule is a panic instead of a compile error.
+
+pub const TAUGHT: Taught = Taught { day: 11, order: 3 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(refcell_basics_demo {
+        name: "refcell_basics",
+        summary: "RefCell lets you change a value through a shared reference, checking borrows at runtime",
+        topics: &["smart-pointers", "borrowing"],
+        prerequisites: &["mutate_reference", "rc_immutable"],
+        explanation: "A `RefCell<T>` owns its value like a Box does, but lends it out through `&self`: `borrow()` returns a `Ref<T>` that reads like a `&T`, and `borrow_mut()` a `RefMut<T>` that works like a `&mut T`. The cell counts the Refs and RefMuts that are alive and ends each borrow when its guard is dropped. That's interior mutability, a method taking `&self` can change what's inside, which is useful when the owner is shared and nobody can get a `&mut` to it.",
+    }),
+    lesson!(refcell_panic_demo {
+        name: "refcell_panic",
+        summary: "Two borrow_mut()s at once compile fine and panic when they run",
+        topics: &["smart-pointers", "borrowing"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["refcell_basics", "borrow_checker"],
+        explanation: "Two `&mut name` at once is rejected by rustc (E0499). The same thing with a RefCell, two `cell.borrow_mut()` guards alive together, compiles, because rustc can't see inside the cell, and the second call panics with \"already borrowed\" when it runs. The rule hasn't gone away, it's just checked later, and a mistake costs a crash instead of a compile error. Drop the first guard before taking the second, or use `try_borrow_mut`, which returns an Err instead of panicking.",
+    }),
+    lesson!(rc_refcell_demo {
+        name: "rc_refcell",
+        summary: "Rc<RefCell<T>> is a value with several owners that any of them can change",
+        topics: &["smart-pointers", "ownership"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["refcell_basics", "rc_scopes"],
+        explanation: "Rc gives several owners but only shared access, RefCell gives mutable access through a shared reference, so together each owner can `borrow_mut()` the one value and every other owner sees the change. The runtime check still applies across owners: two of them holding `borrow_mut()` guards at once panics just like one owner taking two.",
+    }),
+];
+
+// A log that can be written to through &self, the entries are behind a RefCell
+#[derive(Default)]
+pub struct Journal {
+    entries: RefCell<Vec<String>>,
+}
+
+impl Journal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Takes &self, yet pushes onto the entries, the RefMut ends at the end of the line
+    pub fn write(&self, entry: &str) {
+        self.entries.borrow_mut().push(entry.to_string());
+    }
+
+    pub fn entries(&self) -> Vec<String> {
+        self.entries.borrow().clone()
+    }
+
+    // The entries written so far, leaving the journal empty for the next ones
+    pub fn take(&self) -> Vec<String> {
+        self.entries.take()
+    }
+}
+
+// Whether a borrow of each kind would be handed out right now
+pub fn borrow_state(cell: &RefCell<Vec<String>>) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    let can_borrow = cell.try_borrow().is_ok(); // On its own line, a Ref kept alive by the format! would block the borrow_mut
+    let can_borrow_mut = cell.try_borrow_mut().is_ok();
+    output.message(format!("can borrow: {can_borrow}, can borrow_mut: {can_borrow_mut}"));
+    output
+}
+
+// Writes through two shared references to one Journal, then watches the cell's borrows come and go
+pub fn refcell_basics_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let journal = Journal::new();
+    let morning = &journal;
+    let evening = &journal; // Two shared references, neither is a &mut
+    reporter.event(&OwnershipEvent::borrowed("journal", "morning"));
+    reporter.event(&OwnershipEvent::borrowed("journal", "evening"));
+    morning.write("woke up");
+    evening.write("went to sleep");
+    reporter.message(&format!("entries: {:?}", journal.entries()));
+
+    let cell = RefCell::new(vec![String::from("ferris")]);
+    reporter.output(&borrow_state(&cell));
+    let first = cell.borrow(); // A Ref, like a &Vec<String>
+    let second = cell.borrow(); // Any number of Refs at once is fine
+    reporter.event(&OwnershipEvent::borrowed("cell", "first"));
+    reporter.event(&OwnershipEvent::borrowed("cell", "second"));
+    reporter.message(&format!("first: {first:?}, second: {second:?}"));
+    reporter.output(&borrow_state(&cell));
+    drop(first);
+    drop(second); // Both Refs gone, a borrow_mut is allowed again
+    reporter.event(&OwnershipEvent::dropped("first"));
+    reporter.event(&OwnershipEvent::dropped("second"));
+
+    let mut names = cell.borrow_mut(); // A RefMut, like a &mut Vec<String>
+    reporter.event(&OwnershipEvent::borrowed_mut("cell", "names"));
+    names.push(String::from("corro"));
+    reporter.output(&borrow_state(&cell));
+    drop(names);
+    reporter.event(&OwnershipEvent::dropped("names"));
+    reporter.message(&format!("cell: {:?}", cell.borrow()));
+    Ok(())
+}
+
+// Runs `f` and catches its panic, with the default hook switched off so the panic isn't printed to stderr as well
+pub fn catch_quietly<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(hook);
+    result.map_err(|payload| runner::panic_message(payload.as_ref()))
+}
+
+const TWO_MUTABLE_BORROWS: (&str, &str) = (
+    "let mut name = String::from(\"ferris\");\nlet first = &mut name;\nlet second = &mut name;\nfirst.push('!');\nsecond.push('?');",
+    "error[E0499]: cannot borrow `name` as mutable more than once at a time\n --> src/main.rs:3:14\n  |\n2 | let first = &mut name;\n  |             --------- first mutable borrow occurs here\n3 | let second = &mut name;\n  |              ^^^^^^^^^ second mutable borrow occurs here\n4 | first.push('!');\n  | ----- first borrow later used here",
+);
+
+// The compile-time rejection, then the same two borrows through a RefCell, which panic, then the two fixes
+pub fn refcell_panic_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let (code, error) = TWO_MUTABLE_BORROWS;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    let cell = RefCell::new(String::from("ferris"));
+    reporter.message("    let first = cell.borrow_mut();");
+    reporter.message("    let second = cell.borrow_mut();");
+    let caught = catch_quietly(|| {
+        let mut first = cell.borrow_mut();
+        let mut second = cell.borrow_mut(); // Compiles, and panics right here
+        first.push('!');
+        second.push('?');
+    });
+    if let Err(message) = caught {
+        reporter.event(&OwnershipEvent::borrowed_mut("cell", "first"));
+        reporter.message(&format!("panicked: {message}"));
+        reporter.message("the panic unwound past `first`, dropping it, so the cell isn't borrowed anymore");
+    }
+    reporter.message(&format!("cell: {:?}", cell.borrow()));
+
+    let mut first = cell.borrow_mut();
+    first.push('!');
+    drop(first); // Ends the first borrow before the second starts
+    reporter.event(&OwnershipEvent::dropped("first"));
+    cell.borrow_mut().push('?');
+    reporter.message(&format!("one after the other: {:?}", cell.borrow()));
+
+    let guard = cell.borrow_mut();
+    match cell.try_borrow_mut() {
+        Ok(_) => reporter.message("try_borrow_mut: Ok"),
+        Err(err) => reporter.message(&format!("try_borrow_mut while guard is alive: Err({err}), no panic")),
+    }
+    drop(guard);
+    Ok(())
+}
+
+// Two owners push onto one Vec, each sees what the other wrote
+pub fn rc_refcell_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let alice = Rc::new(RefCell::new(Vec::new()));
+    let bob = Rc::clone(&alice);
+    reporter.message(&format!("strong_count = {}", Rc::strong_count(&alice)));
+
+    alice.borrow_mut().push(String::from("alice was here"));
+    reporter.event(&OwnershipEvent::borrowed_mut("alice", "borrow_mut"));
+    bob.borrow_mut().push(String::from("bob was here"));
+    reporter.event(&OwnershipEvent::borrowed_mut("bob", "borrow_mut"));
+    reporter.message(&format!("alice sees {:?}", alice.borrow()));
+    reporter.message(&format!("bob sees {:?}", bob.borrow()));
+
+    let held = alice.borrow_mut(); // alice's guard is alive, so bob can't have one too
+    reporter.message(&format!("bob.try_borrow_mut() while alice holds one: {}", if bob.try_borrow_mut().is_ok() { "Ok" } else { "Err" }));
+    drop(held);
+    drop(alice);
+    reporter.event(&OwnershipEvent::dropped("alice"));
+    reporter.message(&format!("alice: error, bob still has {} entries, strong_count = {}", bob.borrow().len(), Rc::strong_count(&bob)));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn journal_writes_through_shared_references() {
+        let journal = Journal::new();
+        let (a, b) = (&journal, &journal);
+        a.write("one");
+        b.write("two");
+        assert_eq!(journal.entries(), ["one", "two"]);
+        assert_eq!(journal.take(), ["one", "two"]);
+        assert!(journal.entries().is_empty());
+    }
+
+    #[test]
+    fn borrow_state_reports_what_the_cell_would_hand_out() {
+        let cell = RefCell::new(Vec::new());
+        assert_eq!(borrow_state(&cell).messages, ["can borrow: true, can borrow_mut: true"]);
+        let shared = cell.borrow();
+        assert_eq!(borrow_state(&cell).messages, ["can borrow: true, can borrow_mut: false"]);
+        drop(shared);
+        let _unique = cell.borrow_mut();
+        assert_eq!(borrow_state(&cell).messages, ["can borrow: false, can borrow_mut: false"]);
+    }
+
+    #[test]
+    fn catch_quietly_returns_the_value_or_the_panic_message() {
+        assert_eq!(catch_quietly(|| 1 + 1), Ok(2));
+        assert_eq!(catch_quietly(|| -> i32 { panic!("broke") }), Err("broke".to_string()));
+    }
+
+    #[test]
+    fn refcell_basics_demo_tracks_refs_and_refmuts() {
+        let mut output = DemoOutput::new();
+        refcell_basics_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            [
+                "entries: [\"woke up\", \"went to sleep\"]",
+                "can borrow: true, can borrow_mut: true",
+                "first: [\"ferris\"], second: [\"ferris\"]",
+                "can borrow: true, can borrow_mut: false",
+                "can borrow: false, can borrow_mut: false",
+                "cell: [\"ferris\", \"corro\"]",
+            ]
+        );
+        assert_eq!(output.events[6], OwnershipEvent::borrowed_mut("cell", "names"));
+    }
+
+    #[test]
+    fn refcell_panic_demo_panics_on_the_second_borrow_mut() {
+        let mut output = DemoOutput::new();
+        refcell_panic_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0499]")));
+        assert!(output.messages.iter().any(|message| message.starts_with("panicked: ") && message.contains("already borrowed")));
+        assert!(output.messages.iter().any(|message| message == "one after the other: \"ferris!?\""));
+        assert_eq!(output.events, [OwnershipEvent::borrowed_mut("cell", "first"), OwnershipEvent::dropped("first")]);
+    }
+
+    #[test]
+    fn rc_refcell_demo_shares_one_journal() {
+        let mut output = DemoOutput::new();
+        rc_refcell_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            [
+                "strong_count = 2",
+                "alice sees [\"alice was here\", \"bob was here\"]",
+                "bob sees [\"alice was here\", \"bob was here\"]",
+                "bob.try_borrow_mut() while alice holds one: Err",
+                "alice: error, bob still has 2 entries, strong_count = 1",
+            ]
+        );
+    }
+}
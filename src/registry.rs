@@ -0,0 +1,89 @@
+use crate::days;
+use crate::lesson::{LessonDef, Taught};
+
+// Every module's `LESSONS` with where it's taught, in curriculum order. build.rs collects them, see `lesson!`.
+pub static MODULES: &[(Taught, &[LessonDef])] = include!(concat!(env!("OUT_DIR"), "/lessons.rs"));
+
+// All lessons of the crate, day by day in curriculum order
+pub fn lessons() -> impl Iterator<Item = &'static LessonDef> {
+    days::DAYS.iter().flat_map(|day| day.lessons())
+}
+
+// Looks up a lesson by name
+pub fn find(name: &str) -> Option<&'static LessonDef> {
+    lessons().find(|lesson| lesson.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+
+    // (module name, source) of each file in `dir`
+    fn sources(dir: &Path) -> Vec<(String, String)> {
+        let mut sources = Vec::new();
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|extension| extension == "rs") {
+                sources.push((path.file_stem().unwrap().to_string_lossy().into_owned(), fs::read_to_string(&path).unwrap()));
+            }
+        }
+        sources
+    }
+
+    fn has_lessons(source: &str) -> bool {
+        source.lines().any(|line| line.starts_with("pub static LESSONS: &[LessonDef]"))
+    }
+
+    #[test]
+    fn every_lessons_list_is_registered() {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let lists = sources(&src).into_iter().chain(sources(&src.join("days"))).filter(|(_, source)| has_lessons(source)).count();
+        let compiled = MODULES.len();
+        // Feature-gated modules aren't in MODULES when they're off
+        assert!(compiled <= lists && compiled > 0, "{compiled} lesson lists registered, {lists} in the source");
+        #[cfg(all(feature = "lessons-basics", feature = "lessons-ownership", feature = "lessons-concurrency", feature = "lessons-collections"))]
+        assert_eq!(compiled, lists);
+    }
+
+    // A day that's feature-gated off takes its modules' lessons with it, e.g. iterators on day 10
+    #[test]
+    fn every_module_is_taught_on_a_day() {
+        let days_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/days");
+        let days: String = sources(&days_dir).into_iter().map(|(_, source)| source).collect();
+        for (taught, lessons) in MODULES {
+            assert!(days.contains(&format!("number: {},", taught.day)), "{} is taught on day {}, which doesn't exist", lessons[0].name, taught.day);
+        }
+        assert!(MODULES.windows(2).all(|pair| (pair[0].0.day, pair[0].0.order) < (pair[1].0.day, pair[1].0.order)));
+    }
+
+    #[test]
+    fn names_are_unique() {
+        let mut names = BTreeSet::new();
+        for lesson in lessons() {
+            assert!(names.insert(lesson.name), "two lessons are called {}", lesson.name);
+        }
+    }
+
+    // A prerequisite that isn't compiled in has to be a lesson of a day that's feature-gated off, not a typo
+    #[test]
+    fn prerequisites_are_registered() {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let sources: String = sources(&src).into_iter().chain(sources(&src.join("days"))).map(|(_, source)| source).collect();
+        for lesson in lessons() {
+            for prerequisite in lesson.prerequisites {
+                let defined = sources.contains(&format!("name: \"{prerequisite}\","));
+                assert!(find(prerequisite).is_some() || defined, "{} builds on {prerequisite}, which isn't a lesson", lesson.name);
+            }
+        }
+    }
+
+    #[test]
+    fn find_by_name() {
+        assert_eq!(find("slicing").map(|lesson| lesson.function), Some("slicing_demo"));
+        assert!(find("no_such_lesson").is_none());
+    }
+}
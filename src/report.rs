@@ -0,0 +1,478 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::allocations::Allocations;
+use crate::error::LessonError;
+use crate::lesson::Lesson;
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::style::{Role, Style};
+
+// Where lesson output goes, demos report to this instead of printing
+pub trait Reporter {
+    // Called before a lesson's demo runs
+    fn begin_lesson(&mut self, _lesson: &dyn Lesson) {}
+
+    // Announces a lesson, only called when the runner is verbose
+    fn heading(&mut self, _lesson: &dyn Lesson) {}
+
+    // A line the demo wants shown
+    fn message(&mut self, message: &str);
+
+    // A value the demo produced
+    fn value(&mut self, _name: &str, _value: &str) {}
+
+    // Something the learner should know about before the demo, e.g. a skipped prerequisite
+    fn warning(&mut self, message: &str) {
+        self.message(&format!("warning: {message}"));
+    }
+
+    // Something that happened to ownership. `Location::caller()` in an implementation is the line of the demo
+    // that reported it, wrappers forwarding it keep that, which is how `show` puts events next to the code.
+    #[track_caller]
+    fn event(&mut self, _event: &OwnershipEvent) {}
+
+    // How much the demo allocated, after it finished. Only called when built with the count-allocations feature.
+    fn allocations(&mut self, allocations: &Allocations) {
+        self.message(&allocations.to_string());
+    }
+
+    // Called after a lesson's demo finished
+    fn end_lesson(&mut self, _lesson: &dyn Lesson) {}
+
+    // Flushes buffered output and returns the first error hit while reporting, if any
+    fn finish(&mut self) -> Result<(), LessonError> {
+        Ok(())
+    }
+
+    // Reports everything a helper function produced, in the order messages, values, events
+    #[track_caller]
+    fn output(&mut self, output: &DemoOutput) {
+        for message in &output.messages {
+            self.message(message);
+        }
+        for (name, value) in &output.values {
+            self.value(name, value);
+        }
+        for event in &output.events {
+            self.event(event);
+        }
+    }
+}
+
+// Collecting into a DemoOutput is the simplest reporter there is
+impl Reporter for DemoOutput {
+    fn message(&mut self, message: &str) {
+        DemoOutput::message(self, message);
+    }
+
+    fn value(&mut self, name: &str, value: &str) {
+        DemoOutput::value(self, name, value);
+    }
+
+    fn event(&mut self, event: &OwnershipEvent) {
+        DemoOutput::event(self, event.clone());
+    }
+}
+
+// So a reporter picked at runtime, e.g. wrapped or not, can be handed to a runner
+impl<R: Reporter + ?Sized> Reporter for Box<R> {
+    fn begin_lesson(&mut self, lesson: &dyn Lesson) {
+        (**self).begin_lesson(lesson);
+    }
+
+    fn heading(&mut self, lesson: &dyn Lesson) {
+        (**self).heading(lesson);
+    }
+
+    fn message(&mut self, message: &str) {
+        (**self).message(message);
+    }
+
+    fn value(&mut self, name: &str, value: &str) {
+        (**self).value(name, value);
+    }
+
+    fn warning(&mut self, message: &str) {
+        (**self).warning(message);
+    }
+
+    fn event(&mut self, event: &OwnershipEvent) {
+        (**self).event(event);
+    }
+
+    fn allocations(&mut self, allocations: &Allocations) {
+        (**self).allocations(allocations);
+    }
+
+    fn end_lesson(&mut self, lesson: &dyn Lesson) {
+        (**self).end_lesson(lesson);
+    }
+
+    fn finish(&mut self) -> Result<(), LessonError> {
+        (**self).finish()
+    }
+
+    fn output(&mut self, output: &DemoOutput) {
+        (**self).output(output);
+    }
+}
+
+// Shared by the reporters that write text, keeps the first write error for `finish`
+struct TextSink<W: Write> {
+    out: W,
+    show_details: bool,
+    style: Style,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> TextSink<W> {
+    fn new(out: W) -> Self {
+        Self { out, show_details: false, style: Style::plain(), error: None }
+    }
+
+    fn line(&mut self, args: std::fmt::Arguments<'_>) {
+        if self.error.is_none() {
+            if let Err(err) = self.out.write_fmt(args).and_then(|_| self.out.write_all(b"\n")) {
+                self.error = Some(err);
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.error.is_none() {
+            if let Err(err) = self.out.flush() {
+                self.error = Some(err);
+            }
+        }
+    }
+
+}
+
+impl<W: Write> Reporter for TextSink<W> {
+    fn heading(&mut self, lesson: &dyn Lesson) {
+        let heading = format!("== {}: {} ==", lesson.name(), lesson.summary());
+        let heading = self.style.paint(Role::Heading, &heading);
+        self.line(format_args!("{heading}"));
+    }
+
+    fn message(&mut self, message: &str) {
+        self.line(format_args!("{message}"));
+    }
+
+    fn warning(&mut self, message: &str) {
+        let warning = self.style.paint(Role::Event, &format!("warning: {message}"));
+        self.line(format_args!("{warning}"));
+    }
+
+    fn value(&mut self, name: &str, value: &str) {
+        if self.show_details {
+            let value = self.style.paint(Role::Code, &format!("{value:?}"));
+            self.line(format_args!("  value: {name} = {value}"));
+        }
+    }
+
+    fn event(&mut self, event: &OwnershipEvent) {
+        if self.show_details {
+            let event = self.style.paint(Role::Event, &event.to_string());
+            self.line(format_args!("  ownership: {event}"));
+        }
+    }
+
+    fn allocations(&mut self, allocations: &Allocations) {
+        let allocations = self.style.paint(Role::Dim, &allocations.to_string());
+        self.line(format_args!("{allocations}"));
+    }
+
+    fn end_lesson(&mut self, _lesson: &dyn Lesson) {
+        self.flush();
+    }
+
+    fn finish(&mut self) -> Result<(), LessonError> {
+        self.flush();
+        match self.error.take() {
+            Some(err) => Err(err.into()),
+            None => Ok(()),
+        }
+    }
+}
+
+// Prints lesson output to the terminal
+pub struct StdoutReporter(TextSink<io::Stdout>);
+
+impl StdoutReporter {
+    pub fn new() -> Self {
+        Self(TextSink::new(io::stdout()))
+    }
+
+    // Also print values and ownership events, not just messages
+    pub fn show_details(mut self, show: bool) -> Self {
+        self.0.show_details = show;
+        self
+    }
+
+    // Colors for headings, values and events, plain text by default
+    pub fn style(mut self, style: Style) -> Self {
+        self.0.style = style;
+        self
+    }
+}
+
+impl Default for StdoutReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Writes lesson output to a file, in the same format as StdoutReporter
+pub struct FileReporter(TextSink<BufWriter<File>>);
+
+impl FileReporter {
+    // Creates (or truncates) the file at `path`
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, LessonError> {
+        Ok(Self(TextSink::new(BufWriter::new(File::create(path)?))))
+    }
+
+    // Also write values and ownership events, not just messages
+    pub fn show_details(mut self, show: bool) -> Self {
+        self.0.show_details = show;
+        self
+    }
+}
+
+// Writes lesson output to anything, e.g. into the middle of a quiz, in the same format as StdoutReporter
+pub struct WriterReporter<W: Write>(TextSink<W>);
+
+impl<W: Write> WriterReporter<W> {
+    pub fn new(out: W) -> Self {
+        Self(TextSink::new(out))
+    }
+
+    // Also write values and ownership events, not just messages
+    pub fn show_details(mut self, show: bool) -> Self {
+        self.0.show_details = show;
+        self
+    }
+
+    // Colors for headings, values and events, plain text by default
+    pub fn style(mut self, style: Style) -> Self {
+        self.0.style = style;
+        self
+    }
+}
+
+// Keeps each lesson's output in memory, clones share the same buffer so one can be handed to a runner
+// and the other read afterwards
+#[derive(Clone, Default)]
+pub struct BufferReporter {
+    lessons: Rc<RefCell<Vec<(String, DemoOutput)>>>,
+}
+
+impl BufferReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // (lesson name, output) for every lesson reported so far
+    pub fn lessons(&self) -> Vec<(String, DemoOutput)> {
+        self.lessons.borrow().clone()
+    }
+
+    // All messages reported so far, one per line
+    pub fn text(&self) -> String {
+        let lessons = self.lessons.borrow();
+        let mut text = String::new();
+        for message in lessons.iter().flat_map(|(_, output)| output.messages.iter()) {
+            text.push_str(message);
+            text.push('\n');
+        }
+        text
+    }
+
+    fn current(&self) -> std::cell::RefMut<'_, DemoOutput> {
+        let mut lessons = self.lessons.borrow_mut();
+        if lessons.is_empty() {
+            lessons.push((String::new(), DemoOutput::new()));
+        }
+        std::cell::RefMut::map(lessons, |lessons| &mut lessons.last_mut().unwrap().1)
+    }
+}
+
+impl Reporter for BufferReporter {
+    fn begin_lesson(&mut self, lesson: &dyn Lesson) {
+        self.lessons.borrow_mut().push((lesson.name().to_string(), DemoOutput::new()));
+    }
+
+    fn message(&mut self, message: &str) {
+        Reporter::message(&mut *self.current(), message);
+    }
+
+    fn value(&mut self, name: &str, value: &str) {
+        Reporter::value(&mut *self.current(), name, value);
+    }
+
+    fn event(&mut self, event: &OwnershipEvent) {
+        Reporter::event(&mut *self.current(), event);
+    }
+}
+
+// StdoutReporter, FileReporter and WriterReporter are thin wrappers, forward everything to their TextSink
+macro_rules! forward_to_sink {
+    ($reporter:ty $(, $writer:ident)?) => {
+        impl$(<$writer: Write>)? Reporter for $reporter {
+            fn heading(&mut self, lesson: &dyn Lesson) {
+                self.0.heading(lesson);
+            }
+
+            fn message(&mut self, message: &str) {
+                self.0.message(message);
+            }
+
+            fn warning(&mut self, message: &str) {
+                self.0.warning(message);
+            }
+
+            fn value(&mut self, name: &str, value: &str) {
+                self.0.value(name, value);
+            }
+
+            fn event(&mut self, event: &OwnershipEvent) {
+                self.0.event(event);
+            }
+
+            fn allocations(&mut self, allocations: &Allocations) {
+                self.0.allocations(allocations);
+            }
+
+            fn end_lesson(&mut self, lesson: &dyn Lesson) {
+                self.0.end_lesson(lesson);
+            }
+
+            fn finish(&mut self) -> Result<(), LessonError> {
+                self.0.finish()
+            }
+        }
+    };
+}
+
+forward_to_sink!(StdoutReporter);
+forward_to_sink!(FileReporter);
+forward_to_sink!(WriterReporter<W>, W);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::LessonRunner;
+
+    #[test]
+    fn a_demo_reports_into_a_demo_output() {
+        let mut output = DemoOutput::new();
+        crate::slices::slicing_demo(&mut output).unwrap();
+        assert_eq!(output.messages, ["hello wo", "hello"]);
+        assert_eq!(output.values, [("word".to_string(), "hello wo".to_string())]);
+        assert_eq!(
+            output.events,
+            [OwnershipEvent::borrowed("my_string_literal", "word"), OwnershipEvent::borrowed("my_string_literal", "hello")]
+        );
+    }
+
+    #[test]
+    fn output_reports_messages_then_values_then_events() {
+        let mut helper = DemoOutput::new();
+        helper.event(OwnershipEvent::dropped("complex")).value("complex", "hello").message("hello");
+        let mut output = DemoOutput::new();
+        output.output(&helper);
+        assert_eq!(output, helper);
+    }
+
+    #[test]
+    fn buffer_reporter_keeps_each_lesson_apart() {
+        let buffer = BufferReporter::new();
+        let mut runner = LessonRunner::builder().reporter(buffer.clone()).trace(true).build();
+        runner.run("slicing").unwrap();
+        runner.run("layout").unwrap();
+
+        let lessons = buffer.lessons();
+        let names: Vec<&str> = lessons.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["slicing", "layout"]);
+        let slicing = &lessons[0].1;
+        assert_eq!(slicing.messages[..2], ["hello wo", "hello"]);
+        assert_eq!(slicing.events.first(), Some(&OwnershipEvent::called("slicing_demo")));
+        assert_eq!(slicing.events.last(), Some(&OwnershipEvent::returned("slicing_demo")));
+        assert!(buffer.text().starts_with("hello wo\nhello\n"));
+    }
+
+    #[test]
+    fn buffer_reporter_outside_a_lesson() {
+        let mut buffer = BufferReporter::new();
+        buffer.message("before any lesson");
+        assert_eq!(buffer.lessons().len(), 1);
+        assert_eq!(buffer.lessons()[0].0, "");
+        assert_eq!(buffer.text(), "before any lesson\n");
+    }
+
+    #[test]
+    fn an_unknown_lesson_reports_nothing() {
+        let buffer = BufferReporter::new();
+        let mut runner = LessonRunner::builder().reporter(buffer.clone()).build();
+        assert!(matches!(runner.run("no_such_lesson"), Err(LessonError::UnknownLesson(name)) if name == "no_such_lesson"));
+        assert!(buffer.lessons().is_empty());
+    }
+
+    // A writer that fails every write, like a closed pipe
+    struct Broken;
+
+    impl Write for Broken {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writer_reporter_writes_messages() {
+        let mut out = Vec::new();
+        let mut reporter = WriterReporter::new(&mut out);
+        crate::slices::slicing_demo(&mut reporter).unwrap();
+        reporter.finish().unwrap();
+        drop(reporter);
+        assert_eq!(String::from_utf8(out).unwrap(), "hello wo\nhello\n");
+    }
+
+    #[test]
+    fn details_add_values_and_events() {
+        let mut out = Vec::new();
+        let mut reporter = WriterReporter::new(&mut out).show_details(true);
+        crate::slices::slicing_demo(&mut reporter).unwrap();
+        drop(reporter);
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], format!("  ownership: {}", OwnershipEvent::borrowed("my_string_literal", "word")));
+        assert_eq!(lines[1..3], ["hello wo", "  value: word = \"hello wo\""]);
+    }
+
+    #[test]
+    fn finish_returns_the_first_write_error() {
+        let mut reporter = WriterReporter::new(Broken);
+        reporter.message("lost");
+        reporter.message("lost too");
+        assert!(matches!(reporter.finish(), Err(LessonError::Io(err)) if err.kind() == io::ErrorKind::BrokenPipe));
+        assert!(reporter.finish().is_ok());
+    }
+
+    #[test]
+    fn file_reporter_writes_what_stdout_would() {
+        let path = std::env::temp_dir().join(format!("rust_day_6-test-{}-report.txt", std::process::id()));
+        let mut reporter = FileReporter::create(&path).unwrap();
+        crate::slices::slicing_demo(&mut reporter).unwrap();
+        reporter.finish().unwrap();
+        let text = std::fs::read_to_string(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(text.unwrap(), "hello wo\nhello\n");
+    }
+}
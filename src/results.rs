@@ -0,0 +1,252 @@
+use std::error::Error;
+use std::io;
+use std::num::ParseIntError;
+
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::registry;
+use crate::report::Reporter;
+use crate::slices;
+
+// Result is an enum like Option, Ok holds the value and Err the error, and both are owned like any other value.
+// `?` hands an error to the caller by moving it up the call stack, converting it with From on the way.
+
+pub const TAUGHT: Taught = Taught { day: 8, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(result_basics_demo {
+        name: "result_basics",
+        summary: "Result holds either the value or the error, and matching it moves out like an Option",
+        topics: &["errors", "ownership"],
+        prerequisites: &["option_match"],
+        explanation: "A function that can fail returns `Result<T, E>`, `Ok(value)` or `Err(error)`, and the caller has to look inside to get the value. Matching by value moves the value or the error out, `as_ref()` borrows them instead, just like with Option. `unwrap_or` and `map_err` take the Result by value too, they use it up.",
+    }),
+    lesson!(question_mark_demo {
+        name: "question_mark",
+        summary: "? returns early with the error, moving it to the caller",
+        topics: &["errors", "ownership"],
+        prerequisites: &["result_basics"],
+        explanation: "`value?` is a match: on Ok it unwraps the value, on Err it returns from the function right there with the error. The error isn't copied, it moves up one call, and up again at each `?`, until someone matches on it. On the way `?` calls `From::from` on it, so a function returning `Result<T, MyError>` can use `?` on anything MyError converts from.",
+    }),
+    lesson!(lesson_errors_demo {
+        name: "lesson_errors",
+        summary: "How the crate's own LessonError is made from other errors and passed up",
+        topics: &["errors", "ownership"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["question_mark"],
+        explanation: "Everything that can go wrong in this crate is a `LessonError`. An `io::Error` becomes `LessonError::Io` through its From impl, which is what every `?` on a file operation does, and the io::Error is moved inside it, still reachable through `Error::source`. A missing value becomes an error with `ok_or_else`. A `LessonError` also converts into a `Box<dyn Error>`, which is how `main` functions often report whatever went wrong.",
+    }),
+];
+
+// Parses an age, the error is a ParseIntError that the caller owns
+pub fn parse_age(text: &str) -> Result<u32, ParseIntError> {
+    text.trim().parse::<u32>()
+}
+
+// Matches a Result by reference, so the caller keeps the value or the error
+pub fn describe(result: &Result<u32, ParseIntError>) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    match result {
+        Ok(age) => output.message(format!("age: {age}")),
+        Err(err) => output.message(format!("not an age: {err}")),
+    };
+    output
+}
+
+// Parses a good and a bad age, looks at them through a reference, then takes the values and the error out
+pub fn result_basics_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let good = parse_age("42");
+    let bad = parse_age("forty-two");
+    reporter.event(&OwnershipEvent::borrowed("good", "result"));
+    reporter.output(&describe(&good));
+    reporter.event(&OwnershipEvent::borrowed("bad", "result"));
+    reporter.output(&describe(&bad));
+
+    if let Err(err) = bad.as_ref() {
+        // err is a &ParseIntError, bad still has its error
+        reporter.event(&OwnershipEvent::borrowed("bad", "err"));
+        reporter.message(&format!("bad.as_ref(): Err({err}), bad.is_err(): {}", bad.is_err()));
+    }
+
+    let age = good.unwrap_or(0); // A u32 is Copy, but unwrap_or still takes good by value
+    reporter.event(&OwnershipEvent::moved("good", "unwrap_or"));
+    reporter.message(&format!("age: {age}, good: error"));
+
+    let message = bad.map_err(|err| err.to_string()); // The ParseIntError moves into the closure and becomes a String
+    reporter.event(&OwnershipEvent::moved("bad", "map_err"));
+    reporter.message(&format!("message: {message:?}, bad: error"));
+    Ok(())
+}
+
+// An age that's a number but couldn't be right
+#[derive(Debug)]
+pub enum AgeError {
+    NotANumber(ParseIntError),
+    TooOld(u32),
+}
+
+// So `?` can turn a ParseIntError into an AgeError
+impl From<ParseIntError> for AgeError {
+    fn from(err: ParseIntError) -> Self {
+        AgeError::NotANumber(err)
+    }
+}
+
+// Parses and checks an age, each `?` either unwraps or returns the error to the caller
+pub fn checked_age(text: &str) -> Result<u32, AgeError> {
+    let age = parse_age(text)?; // On Err, the ParseIntError is converted and moved out of here
+    if age > 150 {
+        return Err(AgeError::TooOld(age));
+    }
+    Ok(age)
+}
+
+// One more level up, the AgeError moves through without being looked at
+pub fn years_until_hundred(text: &str) -> Result<u32, AgeError> {
+    let age = checked_age(text)?;
+    Ok(100u32.saturating_sub(age))
+}
+
+// Sends three inputs up through two functions, the errors arrive at the top where they are matched
+pub fn question_mark_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    for text in ["42", "forty-two", "200"] {
+        reporter.event(&OwnershipEvent::called("years_until_hundred"));
+        let result = years_until_hundred(text);
+        match result {
+            Ok(years) => reporter.message(&format!("{text}: {years} years until 100")),
+            Err(AgeError::NotANumber(err)) => {
+                // Made by parse, converted by the `?` in checked_age, passed on by the one in years_until_hundred
+                reporter.event(&OwnershipEvent::moved("result", "err"));
+                reporter.message(&format!("{text}: not a number, {err}"));
+            }
+            Err(AgeError::TooOld(age)) => reporter.message(&format!("{text}: {age} is too old")),
+        }
+        reporter.event(&OwnershipEvent::returned("years_until_hundred"));
+    }
+    Ok(())
+}
+
+// A stand-in for a file read that fails, like `fs::read_to_string` on a missing file
+fn read_notes() -> io::Result<String> {
+    Err(io::Error::new(io::ErrorKind::NotFound, "notes.txt: no such file"))
+}
+
+// The `?` converts the io::Error into a LessonError::Io, moving it inside
+fn load_notes() -> Result<String, LessonError> {
+    let notes = read_notes()?;
+    Ok(notes)
+}
+
+// A missing lesson isn't an error until something needs it, ok_or_else makes one
+fn lesson_summary(name: &str) -> Result<&'static str, LessonError> {
+    let lesson = registry::find(name).ok_or_else(|| LessonError::UnknownLesson(name.to_string()))?;
+    Ok(lesson.summary)
+}
+
+// Any error converts into a Box<dyn Error>, `?` does it here
+fn first_letters(text: &str) -> Result<&str, Box<dyn Error>> {
+    let letters = slices::slice_range(text, 0, 2)?;
+    Ok(letters)
+}
+
+// Makes LessonErrors the ways the crate does, then looks inside them
+pub fn lesson_errors_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let err = load_notes().unwrap_err();
+    reporter.event(&OwnershipEvent::moved("io::Error", "LessonError::Io"));
+    reporter.message(&format!("load_notes: {err}"));
+    if let Some(source) = err.source() {
+        // Borrowed from inside err, which still owns it
+        reporter.event(&OwnershipEvent::borrowed("err", "source"));
+        reporter.message(&format!("  caused by: {source} ({:?})", source.downcast_ref::<io::Error>().map(io::Error::kind)));
+    }
+
+    for name in ["longest", "no_such_lesson"] {
+        match lesson_summary(name) {
+            Ok(summary) => reporter.message(&format!("{name}: {summary}")),
+            Err(err) => reporter.message(&format!("{name}: {err}")),
+        }
+    }
+
+    for text in ["hello", "héllo"] {
+        match first_letters(text) {
+            Ok(letters) => reporter.message(&format!("first_letters({text:?}): {letters}")),
+            Err(err) => {
+                // The LessonError moved into the Box, downcasting borrows it back out
+                reporter.event(&OwnershipEvent::moved("LessonError", "Box<dyn Error>"));
+                let kind = match err.downcast_ref::<LessonError>() {
+                    Some(LessonError::SliceBoundary { .. }) => "a LessonError::SliceBoundary",
+                    Some(_) => "another LessonError",
+                    None => "not a LessonError",
+                };
+                reporter.message(&format!("first_letters({text:?}): {err}, {kind}"));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_age_trims_and_rejects_words() {
+        assert_eq!(parse_age(" 42\n"), Ok(42));
+        assert!(parse_age("forty-two").is_err());
+        assert!(parse_age("-1").is_err());
+    }
+
+    #[test]
+    fn question_mark_converts_and_passes_errors_up() {
+        assert_eq!(years_until_hundred("42").unwrap(), 58);
+        assert_eq!(years_until_hundred("120").unwrap(), 0);
+        assert!(matches!(checked_age("forty-two"), Err(AgeError::NotANumber(_))));
+        assert!(matches!(years_until_hundred("200"), Err(AgeError::TooOld(200))));
+        assert!(matches!(checked_age("150"), Ok(150)));
+    }
+
+    #[test]
+    fn result_basics_demo_borrows_then_consumes_both_results() {
+        let mut output = DemoOutput::new();
+        result_basics_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            [
+                "age: 42",
+                "not an age: invalid digit found in string",
+                "bad.as_ref(): Err(invalid digit found in string), bad.is_err(): true",
+                "age: 42, good: error",
+                "message: Err(\"invalid digit found in string\"), bad: error",
+            ]
+        );
+        assert_eq!(output.events[output.events.len() - 2..], [OwnershipEvent::moved("good", "unwrap_or"), OwnershipEvent::moved("bad", "map_err")]);
+    }
+
+    #[test]
+    fn question_mark_demo_matches_every_outcome() {
+        let mut output = DemoOutput::new();
+        question_mark_demo(&mut output).unwrap();
+        assert_eq!(output.messages, ["42: 58 years until 100", "forty-two: not a number, invalid digit found in string", "200: 200 is too old"]);
+        assert_eq!(output.events.iter().filter(|event| matches!(event, OwnershipEvent::Call { .. })).count(), 3);
+    }
+
+    #[test]
+    fn lesson_errors_keep_their_source() {
+        let err = load_notes().unwrap_err();
+        assert!(matches!(err, LessonError::Io(_)));
+        assert_eq!(err.source().and_then(|source| source.downcast_ref::<io::Error>()).map(io::Error::kind), Some(io::ErrorKind::NotFound));
+        assert!(matches!(lesson_summary("no_such_lesson"), Err(LessonError::UnknownLesson(name)) if name == "no_such_lesson"));
+        assert_eq!(first_letters("hello").unwrap(), "he");
+        assert!(first_letters("héllo").unwrap_err().downcast_ref::<LessonError>().is_some_and(|err| matches!(err, LessonError::SliceBoundary { .. })));
+    }
+
+    #[test]
+    fn lesson_errors_demo_says_what_each_error_is() {
+        let mut output = DemoOutput::new();
+        lesson_errors_demo(&mut output).unwrap();
+        assert_eq!(output.messages[0], "load_notes: notes.txt: no such file");
+        assert_eq!(output.messages[3], "no_such_lesson: no lesson named `no_such_lesson`");
+        assert!(output.messages.last().unwrap().ends_with("a LessonError::SliceBoundary"));
+    }
+}
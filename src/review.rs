@@ -0,0 +1,173 @@
+use crate::date::Date;
+use crate::error::LessonError;
+use crate::json::{Json, ToJson};
+
+// The ease every card starts with, in hundredths, SM-2's 2.5
+const START_EASE: u32 = 250;
+// No card gets easier to forget than this, SM-2's 1.3
+const MIN_EASE: u32 = 130;
+
+// When a quiz question or an exercise is next due for review, scheduled the SM-2 way: every good answer
+// stretches the interval by the card's ease, a bad one starts it over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Review {
+    // Good answers in a row
+    pub repetitions: u32,
+    // Days between the last review and the next one
+    pub interval: u32,
+    // How quickly the interval grows, in hundredths so the progress file round-trips exactly
+    pub ease: u32,
+    pub due: Date,
+    // For exercises, reset or broken since they last passed, so the next pass counts as a review. Checking a
+    // solved exercise again isn't remembering anything.
+    pub retrying: bool,
+}
+
+impl Review {
+    // A card seen for the first time today, graded like any other review
+    pub fn first(quality: u8, today: Date) -> Self {
+        let mut review = Self { repetitions: 0, interval: 0, ease: START_EASE, due: today, retrying: false };
+        review.grade(quality, today);
+        review
+    }
+
+    // Records a review with SM-2's quality from 0 (blackout) to 5 (perfect), 3 and up counts as remembered
+    pub fn grade(&mut self, quality: u8, today: Date) {
+        let quality = quality.min(5);
+        if quality >= 3 {
+            self.interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => self.interval.saturating_mul(self.ease).div_ceil(100),
+            };
+            self.repetitions = self.repetitions.saturating_add(1);
+        } else {
+            self.repetitions = 0;
+            self.interval = 1;
+        }
+        // EF' = EF + (0.1 - (5 - q) * (0.08 + (5 - q) * 0.02)), in hundredths
+        let miss = u32::from(5 - quality);
+        self.ease = self.ease.saturating_add(10).saturating_sub(miss * (8 + miss * 2)).max(MIN_EASE);
+        self.due = today.add_days(i64::from(self.interval));
+        self.retrying = false;
+    }
+
+    pub fn is_due(&self, today: Date) -> bool {
+        self.due <= today
+    }
+
+    pub fn from_json(json: &Json) -> Result<Self, LessonError> {
+        let bad = || LessonError::BadInput("each review needs repetitions, interval, ease and due".to_string());
+        let count = |key| json.get(key).and_then(Json::as_u64).and_then(|n| u32::try_from(n).ok()).ok_or_else(bad);
+        // Out of range values are rejected rather than cut down to fit, they'd overflow the scheduling
+        let ease = match json.get("ease") {
+            Some(Json::Number(ease)) if *ease > 0.0 => u32::try_from((ease * 100.0).round() as u64).map_err(|_| bad())?,
+            _ => return Err(bad()),
+        };
+        Ok(Self {
+            repetitions: count("repetitions")?,
+            interval: count("interval")?,
+            ease,
+            due: json.get("due").and_then(Json::as_str).ok_or_else(bad)?.parse()?,
+            retrying: json.get("retrying").and_then(Json::as_bool).unwrap_or(false),
+        })
+    }
+}
+
+impl ToJson for Review {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("repetitions", self.repetitions.into()),
+            ("interval", self.interval.into()),
+            ("ease", (f64::from(self.ease) / 100.0).into()),
+            ("due", self.due.to_string().into()),
+            ("retrying", self.retrying.into()),
+        ])
+    }
+}
+
+// How well a quiz answer went, on SM-2's scale
+pub fn quiz_quality(correct: bool) -> u8 {
+    if correct {
+        4
+    } else {
+        1
+    }
+}
+
+// How well an exercise went, each hint taken off a perfect pass
+pub fn exercise_quality(hints: usize) -> u8 {
+    5 - hints.min(2) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> Date {
+        Date::from_ymd(2024, 3, 9)
+    }
+
+    #[test]
+    fn good_answers_stretch_the_interval() {
+        let mut review = Review::first(4, today());
+        assert_eq!((review.repetitions, review.interval, review.ease), (1, 1, 250));
+        assert_eq!(review.due, today().add_days(1));
+        review.grade(4, review.due);
+        assert_eq!((review.repetitions, review.interval), (2, 6));
+        let due = review.due;
+        review.grade(5, due);
+        // 6 days times the ease of 2.5, then a perfect answer adds 0.1
+        assert_eq!((review.repetitions, review.interval, review.ease), (3, 15, 260));
+        assert_eq!(review.due, due.add_days(15));
+    }
+
+    #[test]
+    fn a_bad_answer_starts_over() {
+        let mut review = Review::first(5, today());
+        review.grade(5, today());
+        review.grade(1, today());
+        assert_eq!((review.repetitions, review.interval), (0, 1));
+        assert_eq!(review.due, today().add_days(1));
+        assert!(!review.is_due(today()) && review.is_due(today().add_days(1)));
+    }
+
+    #[test]
+    fn ease_never_drops_below_the_floor() {
+        let mut review = Review::first(0, today());
+        for _ in 0..10 {
+            review.grade(0, today());
+        }
+        assert_eq!(review.ease, MIN_EASE);
+    }
+
+    #[test]
+    fn huge_intervals_saturate() {
+        let mut review = Review { repetitions: u32::MAX, interval: u32::MAX, ease: u32::MAX, due: today(), retrying: false };
+        review.grade(5, today());
+        assert_eq!(review.repetitions, u32::MAX);
+        assert_eq!(review.interval, u32::MAX.div_ceil(100));
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut review = Review::first(4, today());
+        review.retrying = true;
+        assert_eq!(Review::from_json(&review.to_json()).unwrap(), review);
+    }
+
+    #[test]
+    fn out_of_range_values_are_rejected() {
+        let json = |interval: &str, ease: &str| Json::parse(&format!(r#"{{"repetitions": 1, "interval": {interval}, "ease": {ease}, "due": "2024-03-09"}}"#)).unwrap();
+        assert!(Review::from_json(&json("6", "2.5")).is_ok());
+        for (interval, ease) in [("4294967296", "2.5"), ("6", "42949672.96"), ("6", "1e300"), ("6", "0"), ("-1", "2.5"), ("1.5", "2.5")] {
+            assert!(matches!(Review::from_json(&json(interval, ease)), Err(LessonError::BadInput(_))), "interval {interval}, ease {ease}");
+        }
+    }
+
+    #[test]
+    fn qualities() {
+        assert_eq!((quiz_quality(true), quiz_quality(false)), (4, 1));
+        assert_eq!([0, 1, 2, 5].map(exercise_quality), [5, 4, 3, 3]);
+    }
+}
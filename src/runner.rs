@@ -0,0 +1,424 @@
+use std::collections::BTreeSet;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use crate::allocations::{self, Allocations};
+use crate::error::LessonError;
+use crate::json::{Json, ToJson};
+use crate::lesson::{Difficulty, Lesson, LessonDef};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::{days, registry};
+use crate::report::{Reporter, StdoutReporter};
+
+// The order lessons are run in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    // The order the lessons were registered in, which for the crate's own lessons is the curriculum
+    #[default]
+    Curriculum,
+    // Sorted by lesson name
+    Alphabetical,
+    // Curriculum order, backwards
+    Reverse,
+}
+
+// What happens when a lesson runs before its prerequisites are done
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Prerequisites {
+    // Run it anyway without saying anything, for embedding the lessons
+    #[default]
+    Ignore,
+    // Run it, but send a warning to the reporter first
+    Warn,
+    // Don't run it, it fails with `LessonError::MissingPrerequisites`
+    Block,
+}
+
+// How one lesson went in `LessonRunner::run_each`
+#[derive(Debug)]
+pub struct LessonOutcome {
+    pub name: String,
+    pub duration: Duration,
+    pub result: Result<(), LessonError>,
+}
+
+impl ToJson for LessonOutcome {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("name", (&self.name).into()),
+            ("duration_ms", (self.duration.as_secs_f64() * 1000.0).into()),
+            ("ok", self.result.is_ok().into()),
+            ("error", self.result.as_ref().err().map(ToString::to_string).into()),
+        ])
+    }
+}
+
+// Holds the registered lessons and executes them, sending their output to a single reporter
+pub struct LessonRunner {
+    lessons: Vec<Box<dyn Lesson>>,
+    verbose: bool,
+    trace: bool,
+    reporter: Box<dyn Reporter>,
+    // Names of the lessons that ran without an error, in the order they ran
+    completed: Vec<String>,
+    prerequisites: Prerequisites,
+    // Lessons done before this runner was made, they count as prerequisites too
+    done: BTreeSet<String>,
+}
+
+impl Default for LessonRunner {
+    fn default() -> Self {
+        Self {
+            lessons: Vec::new(),
+            verbose: false,
+            trace: false,
+            reporter: Box::new(StdoutReporter::new()),
+            completed: Vec::new(),
+            prerequisites: Prerequisites::Ignore,
+            done: BTreeSet::new(),
+        }
+    }
+}
+
+impl LessonRunner {
+    // Creates a runner with no lessons registered, reporting to stdout
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Creates a runner with every lesson of the crate, in curriculum order
+    pub fn with_default_lessons() -> Self {
+        Self::builder().build()
+    }
+
+    // Starts configuring a runner over the crate's lessons
+    pub fn builder() -> LessonRunnerBuilder {
+        LessonRunnerBuilder::default()
+    }
+
+    // Adds a lesson to the end of the registry
+    pub fn register(&mut self, lesson: impl Lesson + 'static) -> &mut Self {
+        self.lessons.push(Box::new(lesson));
+        self
+    }
+
+    // The registered lessons, in run order
+    pub fn lessons(&self) -> impl Iterator<Item = &dyn Lesson> {
+        self.lessons.iter().map(|lesson| lesson.as_ref())
+    }
+
+    // Finds a registered lesson by its name
+    pub fn find(&self, name: &str) -> Option<&dyn Lesson> {
+        self.lessons().find(|lesson| lesson.name() == name)
+    }
+
+    // Lessons that have run successfully so far
+    pub fn completed(&self) -> &[String] {
+        &self.completed
+    }
+
+    // Runs a single lesson by name
+    pub fn run(&mut self, name: &str) -> Result<(), LessonError> {
+        let Some(index) = self.lessons.iter().position(|lesson| lesson.name() == name) else {
+            return Err(LessonError::UnknownLesson(name.to_string()));
+        };
+        let result = self.run_at(index);
+        self.reporter.finish()?;
+        result
+    }
+
+    // Runs every registered lesson in order, stopping at the first one that fails
+    pub fn run_all(&mut self) -> Result<(), LessonError> {
+        let result = (0..self.lessons.len()).try_for_each(|index| self.run_at(index));
+        self.reporter.finish()?;
+        result
+    }
+
+    // Runs every registered lesson in order, carrying on past failures (including panics) and timing each one
+    pub fn run_each(&mut self) -> Result<Vec<LessonOutcome>, LessonError> {
+        let mut outcomes = Vec::with_capacity(self.lessons.len());
+        for index in 0..self.lessons.len() {
+            let started = Instant::now();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| self.run_at(index)))
+                .unwrap_or_else(|payload| Err(LessonError::Panicked(panic_message(payload.as_ref()))));
+            outcomes.push(LessonOutcome {
+                name: self.lessons[index].name().to_string(),
+                duration: started.elapsed(),
+                result,
+            });
+        }
+        self.reporter.finish()?;
+        Ok(outcomes)
+    }
+
+    // The prerequisites of a lesson that weren't done before and haven't run yet. Ones that aren't compiled in,
+    // e.g. from a feature that's off, can't be run, so they don't hold anything up
+    pub fn missing_prerequisites(&self, lesson: &dyn Lesson) -> Vec<String> {
+        lesson
+            .prerequisites()
+            .iter()
+            .filter(|name| registry::find(name).is_some() || self.find(name).is_some())
+            .filter(|name| !self.done.contains(**name) && !self.completed.iter().any(|completed| completed == *name))
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    fn run_at(&mut self, index: usize) -> Result<(), LessonError> {
+        let lesson = self.lessons[index].as_ref();
+        let missing = match self.prerequisites {
+            Prerequisites::Ignore => Vec::new(),
+            Prerequisites::Warn | Prerequisites::Block => self.missing_prerequisites(lesson),
+        };
+        if !missing.is_empty() && self.prerequisites == Prerequisites::Block {
+            return Err(LessonError::MissingPrerequisites { lesson: lesson.name().to_string(), missing });
+        }
+        self.reporter.begin_lesson(lesson);
+        if !missing.is_empty() {
+            let warning = LessonError::MissingPrerequisites { lesson: lesson.name().to_string(), missing };
+            self.reporter.warning(&warning.to_string());
+        }
+        if self.verbose {
+            self.reporter.heading(lesson);
+        }
+        if self.trace {
+            self.reporter.event(&OwnershipEvent::called(lesson.function()));
+        }
+        let before = allocations::counts();
+        let result = lesson.run(&mut Uncounted(self.reporter.as_mut()));
+        if let (Some(before), Some(after)) = (before, allocations::counts()) {
+            self.reporter.allocations(&after.since(&before));
+        }
+        if self.trace && result.is_ok() {
+            self.reporter.event(&OwnershipEvent::returned(lesson.function()));
+        }
+        self.reporter.end_lesson(lesson);
+        if result.is_ok() {
+            self.completed.push(lesson.name().to_string());
+        }
+        result
+    }
+}
+
+// Hands everything on to the runner's reporter with allocation counting paused, so printing the demo's messages
+// doesn't count as the demo allocating
+struct Uncounted<'a>(&'a mut dyn Reporter);
+
+impl Reporter for Uncounted<'_> {
+    fn begin_lesson(&mut self, lesson: &dyn Lesson) {
+        let _paused = allocations::pause();
+        self.0.begin_lesson(lesson);
+    }
+
+    fn heading(&mut self, lesson: &dyn Lesson) {
+        let _paused = allocations::pause();
+        self.0.heading(lesson);
+    }
+
+    fn message(&mut self, message: &str) {
+        let _paused = allocations::pause();
+        self.0.message(message);
+    }
+
+    fn value(&mut self, name: &str, value: &str) {
+        let _paused = allocations::pause();
+        self.0.value(name, value);
+    }
+
+    fn warning(&mut self, message: &str) {
+        let _paused = allocations::pause();
+        self.0.warning(message);
+    }
+
+    fn event(&mut self, event: &OwnershipEvent) {
+        let _paused = allocations::pause();
+        self.0.event(event);
+    }
+
+    fn allocations(&mut self, allocations: &Allocations) {
+        self.0.allocations(allocations);
+    }
+
+    fn end_lesson(&mut self, lesson: &dyn Lesson) {
+        let _paused = allocations::pause();
+        self.0.end_lesson(lesson);
+    }
+
+    fn finish(&mut self) -> Result<(), LessonError> {
+        let _paused = allocations::pause();
+        self.0.finish()
+    }
+
+    fn output(&mut self, output: &DemoOutput) {
+        let _paused = allocations::pause();
+        self.0.output(output);
+    }
+}
+
+// The text passed to `panic!`, when it was a string
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+// A thread that panicked hands its panic to join, it becomes a LessonError
+pub fn panicked(payload: Box<dyn std::any::Any + Send>) -> LessonError {
+    LessonError::Panicked(panic_message(payload.as_ref()))
+}
+
+// Configures which lessons a `LessonRunner` runs and how
+//
+//     let mut runner = LessonRunner::builder().topic("ownership").verbose(true).build();
+#[derive(Default)]
+pub struct LessonRunnerBuilder {
+    day: Option<u8>,
+    topics: Vec<String>,
+    difficulty: Option<Difficulty>,
+    verbose: bool,
+    trace: bool,
+    reporter: Option<Box<dyn Reporter>>,
+    order: Order,
+    extra: Vec<Box<dyn Lesson>>,
+    prerequisites: Prerequisites,
+    done: BTreeSet<String>,
+}
+
+impl LessonRunnerBuilder {
+    // Only include the lessons of one day, a day that doesn't exist leaves the runner empty
+    pub fn day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    // Only include lessons tagged with this topic, can be called several times
+    pub fn topic(mut self, topic: impl Into<String>) -> Self {
+        self.topics.push(topic.into());
+        self
+    }
+
+    // Only include lessons of this difficulty
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = Some(difficulty);
+        self
+    }
+
+    // Print each lesson's name and summary before running it
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    // Report entering and returning from each demo function as ownership events, around the events the demo
+    // reports itself
+    pub fn trace(mut self, trace: bool) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    // Where lesson output goes, a StdoutReporter if not set
+    pub fn reporter(mut self, reporter: impl Reporter + 'static) -> Self {
+        self.reporter = Some(Box::new(reporter));
+        self
+    }
+
+    // The order lessons are run in
+    pub fn order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    // What to do when a lesson runs before the lessons it builds on, `done` are the ones finished earlier
+    // (e.g. in a previous session)
+    pub fn prerequisites(mut self, prerequisites: Prerequisites, done: impl IntoIterator<Item = String>) -> Self {
+        self.prerequisites = prerequisites;
+        self.done = done.into_iter().collect();
+        self
+    }
+
+    // Adds a lesson after the crate's own ones, the topic filter applies to it too
+    pub fn register(mut self, lesson: impl Lesson + 'static) -> Self {
+        self.extra.push(Box::new(lesson));
+        self
+    }
+
+    pub fn build(self) -> LessonRunner {
+        let source: Vec<&'static LessonDef> = match self.day {
+            Some(day) => days::day(day).into_iter().flat_map(|day| day.lessons()).collect(),
+            None => registry::lessons().collect(),
+        };
+        let mut lessons: Vec<Box<dyn Lesson>> = source
+            .into_iter()
+            .map(|lesson| Box::new(lesson) as Box<dyn Lesson>)
+            .chain(self.extra)
+            .filter(|lesson| {
+                self.topics.is_empty()
+                    || lesson.topics().iter().any(|topic| self.topics.iter().any(|wanted| wanted == topic))
+            })
+            .filter(|lesson| self.difficulty.is_none_or(|wanted| lesson.difficulty() == wanted))
+            .collect();
+
+        match self.order {
+            Order::Curriculum => {}
+            Order::Alphabetical => lessons.sort_by(|a, b| a.name().cmp(b.name())),
+            Order::Reverse => lessons.reverse(),
+        }
+
+        LessonRunner {
+            lessons,
+            verbose: self.verbose,
+            trace: self.trace,
+            reporter: self.reporter.unwrap_or_else(|| Box::new(StdoutReporter::new())),
+            completed: Vec::new(),
+            prerequisites: self.prerequisites,
+            done: self.done,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::BufferReporter;
+
+    fn blocking_runner(done: &[&str]) -> LessonRunner {
+        LessonRunner::builder()
+            .reporter(BufferReporter::new())
+            .prerequisites(Prerequisites::Block, done.iter().map(|name| name.to_string()))
+            .build()
+    }
+
+    #[test]
+    fn a_lesson_waits_for_its_prerequisites() {
+        let mut runner = blocking_runner(&[]);
+        let slicing = registry::find("slicing").unwrap();
+        let compiled: Vec<&str> = slicing.prerequisites.iter().copied().filter(|name| registry::find(name).is_some()).collect();
+        assert_eq!(runner.missing_prerequisites(slicing), compiled);
+        if !compiled.is_empty() {
+            assert!(matches!(runner.run("slicing"), Err(LessonError::MissingPrerequisites { missing, .. }) if missing == compiled));
+        }
+        let mut runner = blocking_runner(&compiled);
+        runner.run("slicing").unwrap();
+    }
+
+    // Whatever features are on, running every lesson in curriculum order is never blocked
+    #[test]
+    fn the_curriculum_runs_in_order() {
+        let mut runner = blocking_runner(&[]);
+        let names: Vec<String> = runner.lessons().map(|lesson| lesson.name().to_string()).collect();
+        for name in names {
+            let missing = runner.missing_prerequisites(runner.find(&name).unwrap());
+            assert!(missing.is_empty(), "{name} comes before {missing:?}, or they aren't compiled in");
+            runner.completed.push(name);
+        }
+    }
+
+    #[test]
+    fn a_lesson_nobody_registered_is_unknown() {
+        let mut runner = blocking_runner(&[]);
+        assert!(matches!(runner.run("no_such_lesson"), Err(LessonError::UnknownLesson(_))));
+    }
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::config;
+use crate::error::LessonError;
+use crate::explain::{self, ErrorExplanation};
+use crate::json::{Json, ToJson};
+
+// One error or warning rustc reported about a snippet
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    // "error" or "warning"
+    pub level: String,
+    // e.g. "E0382", or a lint name like "unused_variables"
+    pub code: Option<String>,
+    pub message: String,
+    // Where the primary span starts, 1-based
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    // The message as rustc would print it, with the source lines and its own help
+    pub rendered: String,
+}
+
+impl Diagnostic {
+    // The lesson and fix for an ownership or borrowing error, None for anything else
+    pub fn explanation(&self) -> Option<&'static ErrorExplanation> {
+        self.code.as_deref().and_then(explain::lookup)
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.level == "error"
+    }
+
+    // One line of rustc's JSON output, None for the summaries at the end ("aborting due to 2 previous errors")
+    fn from_json(json: &Json) -> Option<Self> {
+        let code = json.get("code").and_then(|code| code.get("code")).and_then(Json::as_str).map(str::to_string);
+        let spans = json.get("spans").and_then(Json::as_array).unwrap_or_default();
+        if code.is_none() && spans.is_empty() {
+            return None;
+        }
+        let primary = spans.iter().find(|span| span.get("is_primary").and_then(Json::as_bool) == Some(true));
+        let position = |key| primary.and_then(|span| span.get(key)).and_then(Json::as_u64).map(|n| n as usize);
+        Some(Self {
+            level: json.get("level").and_then(Json::as_str).unwrap_or("error").to_string(),
+            code,
+            message: json.get("message").and_then(Json::as_str).unwrap_or_default().to_string(),
+            line: position("line_start"),
+            column: position("column_start"),
+            rendered: json.get("rendered").and_then(Json::as_str).unwrap_or_default().trim_end().to_string(),
+        })
+    }
+}
+
+// Compiles `file` without running it and returns what rustc had to say, an empty list means it compiled cleanly.
+// Snippets without a `fn main` are checked as a library, so a lone function is fine.
+pub fn check(file: &Path) -> Result<Vec<Diagnostic>, LessonError> {
+    let source = fs::read_to_string(file).map_err(|err| LessonError::BadInput(format!("can't read {}: {err}", file.display())))?;
+    let out_dir = config::build_dir("check")?;
+
+    let mut rustc = Command::new(std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()));
+    // Metadata is enough for the borrow checker to run, and skips code generation
+    rustc.args(["--edition", "2021", "--error-format=json", "--emit=metadata", "-A", "dead_code", "--out-dir"]).arg(&out_dir);
+    if !source.contains("fn main") {
+        rustc.args(["--crate-type", "lib"]);
+    }
+    let compiled = rustc.arg(file).output().map_err(|err| LessonError::BadInput(format!("couldn't run rustc: {err}")))?;
+
+    let mut diagnostics = Vec::new();
+    for line in String::from_utf8_lossy(&compiled.stderr).lines() {
+        // Anything that isn't JSON, e.g. an ICE, is passed through as it is
+        match Json::parse(line) {
+            Ok(json) => diagnostics.extend(Diagnostic::from_json(&json)),
+            Err(_) if line.trim().is_empty() => {}
+            Err(_) => diagnostics.push(Diagnostic {
+                level: "error".to_string(),
+                code: None,
+                message: line.to_string(),
+                line: None,
+                column: None,
+                rendered: line.to_string(),
+            }),
+        }
+    }
+    if !compiled.status.success() && !diagnostics.iter().any(Diagnostic::is_error) {
+        return Err(LessonError::BadInput(format!("rustc failed on {} without saying why", file.display())));
+    }
+    Ok(diagnostics)
+}
+
+impl ToJson for Diagnostic {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("level", self.level.as_str().into()),
+            ("code", self.code.as_deref().into()),
+            ("message", self.message.as_str().into()),
+            ("line", self.line.into()),
+            ("column", self.column.into()),
+            ("rendered", self.rendered.as_str().into()),
+            ("explanation", self.explanation().map_or(Json::Null, ToJson::to_json)),
+        ])
+    }
+}
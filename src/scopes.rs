@@ -0,0 +1,203 @@
+use std::cell::RefCell;
+
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::OwnershipEvent;
+use crate::report::Reporter;
+use crate::simulator::Program;
+use crate::tracked::Tracker;
+
+// The reference rule says "in any scope", which reads as if a `&mut` lasted until its `}`. Since non-lexical
+// lifetimes it doesn't: a borrow lasts from the `&` to the reference's last use, so a `&mut` and later `&`s can
+// share a scope as long as the `&mut` is done first. Scopes still matter for what they own, the values declared
+// in a `{ }` are dropped at its end, shadowed ones included, and a value that borrows something and has a Drop
+// holds its borrow until then.
+
+pub const TAUGHT: Taught = Taught { day: 6, order: 3 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(shadowing_scopes_demo {
+        name: "shadowing_scopes",
+        summary: "A shadowed variable is hidden, not dropped, and comes back after an inner scope",
+        topics: &["variables", "scope", "ownership"],
+        prerequisites: &["shadowing_basics", "drop_order"],
+        explanation: "`let name = ..;` a second time makes a new variable and hides the first, but the first value isn't dropped there: it lives to the end of its scope like any other, so a reference to it taken earlier stays valid. Shadowing inside a `{ }` hides the outer variable only until the `}`, then the outer one is the one you see again. `let v = v;` after filling a `let mut v` shadows it with an immutable one, freezing it.",
+    }),
+    lesson!(scope_blocks_demo {
+        name: "scope_blocks",
+        summary: "An inner { } drops what it owns early, and ends the borrows those values hold",
+        topics: &["borrowing", "scope"],
+        prerequisites: &["mutate_reference", "drop_order"],
+        explanation: "Everything declared inside a `{ }` is dropped at its `}`, in reverse order, before the rest of the outer scope runs. For a plain reference that changes nothing, its borrow ended at its last use anyway. It matters for values that hold a borrow until they're dropped, like a RefCell's RefMut or a Mutex's guard: putting them in a block gives back the borrow at the `}`. A block is also an expression, so `let total = { .. };` can build a value from temporaries that are gone by the next line.",
+    }),
+    lesson!(nll_borrows_demo {
+        name: "nll_borrows",
+        summary: "A borrow lasts until its last use, not the end of the scope",
+        topics: &["borrowing", "references", "lifetimes"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["scope_blocks", "borrow_checker"],
+        explanation: "\"One mutable reference per scope\" is what the rule looked like before Rust 2018. With non-lexical lifetimes the borrow checker looks at where each reference is last used: `let r1 = &mut s;` can be followed by `let r2 = &s;` and `let r3 = &s;` in the same scope, as long as r1 isn't used after them. What's rejected is overlap, a `&mut` used again while a `&` taken after it is still alive (E0502), or two `&mut`s both still to be used (E0499). The simulator checks the programs the same way.",
+    }),
+];
+
+// A shadowed name whose first value outlives the shadowing, and an inner scope that shadows only till its end
+pub fn shadowing_scopes_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let tracker = Tracker::new();
+    {
+        let name = tracker.track("name (first)", String::from("ferris"));
+        let earlier = &name; // A reference to the first value
+        let name = tracker.track("name (second)", name.len().to_string()); // Shadows name, the first String isn't dropped
+        reporter.message(&format!("name: {}, earlier is still valid: {}", *name, **earlier));
+        tracker.report(reporter); // Nothing to report, no drop yet
+        reporter.message("end of scope");
+    } // The second name, then the first, dropped in reverse order, both owned by this scope
+    tracker.report(reporter);
+    reporter.message("");
+
+    let x = 5;
+    {
+        let x = x * 2; // Hides the outer x inside this block only
+        reporter.message(&format!("inner x: {x}"));
+    }
+    reporter.message(&format!("outer x after the block: {x}"));
+
+    let mut names = Vec::new();
+    for name in ["ferris", "corro"] {
+        names.push(name.to_string());
+    }
+    let names = names; // Shadowed by an immutable binding, names.push would be E0596 from here on
+    reporter.event(&OwnershipEvent::moved("names (mut)", "names"));
+    reporter.message(&format!("names: {names:?}, frozen"));
+    Ok(())
+}
+
+// Borrows given back at the end of inner blocks, then a block computing a value
+pub fn scope_blocks_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let mut s = String::from("hello");
+    {
+        let r1 = &mut s;
+        r1.push_str(" world");
+        reporter.event(&OwnershipEvent::borrowed_mut("s", "r1"));
+    } // r1 goes out of scope, though its borrow already ended at its last use
+    let r2 = &mut s;
+    r2.push('!');
+    reporter.event(&OwnershipEvent::borrowed_mut("s", "r2"));
+    reporter.message(&format!("s: {s}, the block wasn't needed, r1 was done after its push"));
+
+    let cell = RefCell::new(vec![1, 2]);
+    {
+        let mut numbers = cell.borrow_mut(); // A RefMut, its borrow lasts until it's dropped
+        numbers.push(3);
+        reporter.event(&OwnershipEvent::borrowed_mut("cell", "numbers"));
+    } // Dropped here, so the cell isn't borrowed anymore
+    reporter.event(&OwnershipEvent::dropped("numbers"));
+    reporter.message(&format!("cell.borrow() after the block: {:?}, can borrow_mut: {}", cell.borrow(), cell.try_borrow_mut().is_ok()));
+    reporter.message("without the block, numbers would be alive until the end of the function and cell.borrow() would panic");
+
+    let tracker = Tracker::new();
+    let total = {
+        let words = tracker.track("words", vec!["one", "two", "three"]);
+        words.iter().map(|word| word.len()).sum::<usize>()
+    }; // words dropped here, only total is left
+    tracker.report(reporter);
+    reporter.message(&format!("total: {total}"));
+    Ok(())
+}
+
+// Programs for the simulator, from a &mut followed by shared borrows to the overlaps it rejects
+const NLL_PROGRAMS: &[&str] = &[
+    "let mut s = String::from(\"hello\");\nlet r1 = &mut s;\nprintln!(\"{}\", r1); // r1's last use, its borrow ends here\nlet r2 = &s;\nlet r3 = &s;\nprintln!(\"{}\", r2);\nprintln!(\"{}\", r3);",
+    "let mut s = String::from(\"hello\");\nlet r1 = &mut s;\nlet r2 = &s;\nprintln!(\"{}\", r1);\nprintln!(\"{}\", r2);",
+    "let mut s = String::from(\"hello\");\nlet r1 = &mut s;\nprintln!(\"{}\", r1);\nlet r2 = &mut s; // Same scope, r1 is done\nprintln!(\"{}\", r2);",
+];
+
+// The same s borrowed mutably and then immutably in one scope, for real, then through the simulator
+pub fn nll_borrows_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let mut s = String::from("hello");
+    let r1 = &mut s;
+    r1.push_str(" world");
+    reporter.event(&OwnershipEvent::borrowed_mut("s", "r1")); // r1 isn't used after this line
+    let r2 = &s;
+    let r3 = &s; // Any number of shared borrows once r1 is done, in the same scope
+    reporter.event(&OwnershipEvent::borrowed("s", "r2"));
+    reporter.event(&OwnershipEvent::borrowed("s", "r3"));
+    reporter.message(&format!("r2: {r2}, r3: {r3}"));
+    let r4 = &mut s; // And a &mut again once they're done too
+    r4.push('!');
+    reporter.event(&OwnershipEvent::borrowed_mut("s", "r4"));
+    reporter.message(&format!("s: {s}"));
+    reporter.message("");
+
+    for (index, source) in NLL_PROGRAMS.iter().enumerate() {
+        if index > 0 {
+            reporter.message("");
+        }
+        let program = Program::parse(source)?;
+        for line in &program.lines {
+            reporter.message(&format!("    {line}"));
+        }
+        match program.check() {
+            Ok(()) => reporter.message("compiles"),
+            Err(rejection) => {
+                for line in program.render(&rejection).lines() {
+                    reporter.message(line);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::DemoOutput;
+
+    #[test]
+    fn shadowing_scopes_demo_drops_both_names_at_the_end() {
+        let mut output = DemoOutput::new();
+        shadowing_scopes_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            ["name: 6, earlier is still valid: ferris", "end of scope", "", "inner x: 10", "outer x after the block: 5", "names: [\"ferris\", \"corro\"], frozen"]
+        );
+        assert_eq!(
+            output.events,
+            [OwnershipEvent::dropped("name (second)"), OwnershipEvent::dropped("name (first)"), OwnershipEvent::moved("names (mut)", "names")]
+        );
+    }
+
+    #[test]
+    fn scope_blocks_demo_ends_the_refmut_with_its_block() {
+        let mut output = DemoOutput::new();
+        scope_blocks_demo(&mut output).unwrap();
+        assert_eq!(output.messages[0], "s: hello world!, the block wasn't needed, r1 was done after its push");
+        assert_eq!(output.messages[1], "cell.borrow() after the block: [1, 2, 3], can borrow_mut: false");
+        assert_eq!(output.messages.last().unwrap(), "total: 11");
+        assert_eq!(output.events[3..], [OwnershipEvent::dropped("numbers"), OwnershipEvent::dropped("words")]);
+    }
+
+    #[test]
+    fn nll_programs_check_the_way_rustc_does() {
+        let checks: Vec<bool> = NLL_PROGRAMS.iter().map(|source| Program::parse(source).unwrap().check().is_ok()).collect();
+        assert_eq!(checks, [true, false, true]);
+    }
+
+    #[test]
+    fn nll_borrows_demo_borrows_again_after_the_last_use() {
+        let mut output = DemoOutput::new();
+        nll_borrows_demo(&mut output).unwrap();
+        assert_eq!(output.messages[..2], ["r2: hello world, r3: hello world", "s: hello world!"]);
+        assert_eq!(output.messages.iter().filter(|message| *message == "compiles").count(), 2);
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0502]")));
+        assert_eq!(
+            output.events,
+            [
+                OwnershipEvent::borrowed_mut("s", "r1"),
+                OwnershipEvent::borrowed("s", "r2"),
+                OwnershipEvent::borrowed("s", "r3"),
+                OwnershipEvent::borrowed_mut("s", "r4"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use crate::json::{Json, ToJson};
+use crate::lesson::Lesson;
+
+// Characters of context shown on each side of a match
+const CONTEXT: usize = 30;
+
+// One place a search term was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    pub lesson: String,
+    // Which part of the lesson matched: "name", "summary", "topics" or "explanation"
+    pub field: &'static str,
+    // The text around the match, with "..." where it was cut
+    pub context: String,
+}
+
+// Case-insensitive search through lesson names, summaries, topics and explanations, at most one hit per field
+pub fn search<'a>(lessons: impl IntoIterator<Item = &'a dyn Lesson>, query: &str) -> Vec<SearchHit> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut hits = Vec::new();
+    for lesson in lessons {
+        let topics = lesson.topics().join(", ");
+        let fields = [
+            ("name", lesson.name()),
+            ("summary", lesson.summary()),
+            ("topics", topics.as_str()),
+            ("explanation", lesson.explanation()),
+        ];
+        for (field, text) in fields {
+            if let Some(context) = find_with_context(text, &query) {
+                hits.push(SearchHit { lesson: lesson.name().to_string(), field, context });
+            }
+        }
+    }
+    hits
+}
+
+// The text around the first match of an already lowercased query
+fn find_with_context(text: &str, query: &str) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars.iter().flat_map(|c| c.to_lowercase()).collect();
+    let query: Vec<char> = query.chars().collect();
+    if lower.len() != chars.len() {
+        // Lowercasing changed the length (rare, e.g. 'İ'), fall back to showing the whole text
+        return text.to_lowercase().contains(&query.iter().collect::<String>()).then(|| text.to_string());
+    }
+
+    let start = lower.windows(query.len()).position(|window| window == query.as_slice())?;
+    let from = start.saturating_sub(CONTEXT);
+    let to = (start + query.len() + CONTEXT).min(chars.len());
+
+    let mut context = String::new();
+    if from > 0 {
+        context.push_str("...");
+    }
+    context.extend(&chars[from..to]);
+    if to < chars.len() {
+        context.push_str("...");
+    }
+    Some(context)
+}
+
+impl ToJson for SearchHit {
+    fn to_json(&self) -> Json {
+        Json::object([("lesson", (&self.lesson).into()), ("field", self.field.into()), ("context", (&self.context).into())])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::LessonDef;
+
+    const BORROWS: LessonDef = LessonDef {
+        name: "borrow_rules",
+        summary: "One &mut or any number of &",
+        topics: &["borrowing", "references"],
+        explanation: "A reference lets a function use a value without taking ownership of it, and the borrow checker makes sure no reference outlives the value it points at.",
+        ..LessonDef::EMPTY
+    };
+
+    fn lessons() -> Vec<&'static dyn Lesson> {
+        vec![&BORROWS]
+    }
+
+    #[test]
+    fn one_hit_per_matching_field() {
+        let hits = search(lessons(), "BORROW");
+        let fields: Vec<&str> = hits.iter().map(|hit| hit.field).collect();
+        assert_eq!(fields, ["name", "topics", "explanation"]);
+        assert!(hits.iter().all(|hit| hit.lesson == "borrow_rules"));
+        assert_eq!(hits[0].context, "borrow_rules");
+    }
+
+    #[test]
+    fn blank_or_missing_queries_find_nothing() {
+        assert!(search(lessons(), "   ").is_empty());
+        assert!(search(lessons(), "lifetime elision").is_empty());
+    }
+
+    #[test]
+    fn long_text_is_cut_around_the_match() {
+        let context = find_with_context(BORROWS.explanation, "checker").unwrap();
+        assert_eq!(context, "...nership of it, and the borrow checker makes sure no reference outli...");
+        assert_eq!(find_with_context("a short one", "short"), Some("a short one".to_string()));
+    }
+
+    #[test]
+    fn hits_as_json() {
+        let hit = SearchHit { lesson: "borrow_rules".to_string(), field: "name", context: "borrow_rules".to_string() };
+        assert_eq!(hit.to_json().to_string(), r#"{"lesson":"borrow_rules","field":"name","context":"borrow_rules"}"#);
+    }
+}
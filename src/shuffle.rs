@@ -0,0 +1,103 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// A small seeded random number generator (SplitMix64), enough to shuffle questions and exercises
+// reproducibly without pulling in a dependency
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // A number in 0..bound, `bound` must not be 0
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    // A number in 0.0..1.0
+    pub fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Fisher-Yates, every order is equally likely
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// A seed that's different on every run, for when the user didn't pick one
+pub fn seed_from_time() -> u64 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    // Small enough to type back in with --seed
+    (now.as_nanos() % 1_000_000) as u64
+}
+
+// Shuffles `items` with `seed`, the same seed always gives the same order
+pub fn shuffle<T>(items: &mut [T], seed: u64) {
+    Rng::new(seed).shuffle(items);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_splitmix64_reference() {
+        let mut rng = Rng::new(0);
+        assert_eq!(rng.next_u64(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(rng.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_order() {
+        let shuffled = |seed| {
+            let mut items: Vec<u32> = (0..20).collect();
+            shuffle(&mut items, seed);
+            items
+        };
+        assert_eq!(shuffled(7), shuffled(7));
+        assert_ne!(shuffled(7), shuffled(8));
+        // Seeds typed back in with --seed have to keep giving the order they gave before
+        let mut short = ["a", "b", "c", "d", "e"];
+        shuffle(&mut short, 42);
+        assert_eq!(short, ["b", "c", "a", "e", "d"]);
+    }
+
+    #[test]
+    fn shuffling_is_a_permutation() {
+        for seed in 0..50 {
+            let mut items: Vec<u32> = (0..37).collect();
+            shuffle(&mut items, seed);
+            let mut sorted = items.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..37).collect::<Vec<_>>(), "seed {seed}");
+        }
+        let mut empty: [u32; 0] = [];
+        shuffle(&mut empty, 1);
+        let mut one = [9];
+        shuffle(&mut one, 1);
+        assert_eq!(one, [9]);
+    }
+
+    #[test]
+    fn below_and_unit_stay_in_range() {
+        let mut rng = Rng::new(3);
+        for bound in 1..100 {
+            assert!(rng.below(bound) < bound);
+            assert!((0.0..1.0).contains(&rng.unit()));
+        }
+    }
+}
@@ -0,0 +1,633 @@
+use std::fmt;
+
+use crate::error::LessonError;
+
+// A borrow checker for programs small enough to fit on a slide: owners, moves, borrows and scopes, nothing else.
+// It applies the ownership and reference rules from `notes` the way rustc does since non-lexical lifetimes, a
+// borrow lasts until the reference is last used rather than to the end of its scope.
+//
+//     let s1 = String::new();
+//     let r = &s1;
+//     let s2 = s1;              // error[E0505]: cannot move out of `s1` because it is borrowed
+//     println!("{}", r);
+//
+// Every owner can be mutated, as if it was declared `let mut`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    // `let name = String::new();`, a new owner in the current scope
+    Let(String),
+    // `let to = from;`, or a copy when `from` is a shared reference
+    Move { from: String, to: String },
+    // `let by = &owner;`
+    Borrow { owner: String, by: String },
+    // `let by = &mut owner;`
+    BorrowMut { owner: String, by: String },
+    // `println!("{}", name);`, reads the value, through the reference if it is one
+    Use(String),
+    // `let name;`, declared now and given a value later by an Assign
+    Declare(String),
+    // `to = from;`, `by = &owner;` or `by = &mut owner;`, the Move, Borrow or BorrowMut inside it but into a
+    // variable that was declared earlier, possibly in an outer scope
+    Assign(Box<Instruction>),
+    // `{`
+    BeginScope,
+    // `}`, everything declared since the matching `{` is dropped
+    EndScope,
+}
+
+impl Instruction {
+    // (the variable it reads, the variable it declares)
+    fn names(&self) -> (Option<&str>, Option<&str>) {
+        match self {
+            Instruction::Let(name) => (None, Some(name)),
+            Instruction::Move { from, to } => (Some(from), Some(to)),
+            Instruction::Borrow { owner, by } | Instruction::BorrowMut { owner, by } => (Some(owner), Some(by)),
+            Instruction::Use(name) => (Some(name), None),
+            Instruction::Declare(name) => (None, Some(name)),
+            // Gives an existing variable a new value, that's neither reading it nor shadowing it
+            Instruction::Assign(inner) => (inner.names().0, None),
+            Instruction::BeginScope | Instruction::EndScope => (None, None),
+        }
+    }
+}
+
+// The Rust it stands for, which `Program::parse` reads back
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Let(name) => write!(f, "let {name} = String::new();"),
+            Instruction::Move { from, to } => write!(f, "let {to} = {from};"),
+            Instruction::Borrow { owner, by } => write!(f, "let {by} = &{owner};"),
+            Instruction::BorrowMut { owner, by } => write!(f, "let {by} = &mut {owner};"),
+            Instruction::Use(name) => write!(f, "println!(\"{{}}\", {name});"),
+            Instruction::Declare(name) => write!(f, "let {name};"),
+            Instruction::Assign(inner) => {
+                let inner = inner.to_string();
+                write!(f, "{}", inner.strip_prefix("let ").unwrap_or(&inner))
+            }
+            Instruction::BeginScope => write!(f, "{{"),
+            Instruction::EndScope => write!(f, "}}"),
+        }
+    }
+}
+
+// The instructions and the source they came from, so a rejection can point at the line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    pub lines: Vec<String>,
+    // (index into `lines`, the instruction on it)
+    pub steps: Vec<(usize, Instruction)>,
+}
+
+impl Program {
+    // Written out one instruction per line, indented by scope
+    pub fn new(instructions: Vec<Instruction>) -> Self {
+        let mut depth: usize = 0;
+        let mut lines = Vec::new();
+        for instruction in &instructions {
+            if *instruction == Instruction::EndScope {
+                depth = depth.saturating_sub(1);
+            }
+            lines.push(format!("{}{instruction}", "    ".repeat(depth)));
+            if *instruction == Instruction::BeginScope {
+                depth += 1;
+            }
+        }
+        Self { lines, steps: instructions.into_iter().enumerate().collect() }
+    }
+
+    // The same Rust-looking lines `new` writes, plus blank lines and `//` comments. `let x = <anything else>;`
+    // is a new owner, and a `println!` is a Use of each of its arguments, `{x}` in the format string included.
+    pub fn parse(text: &str) -> Result<Self, LessonError> {
+        let lines: Vec<String> = text.lines().map(str::to_string).collect();
+        let mut steps = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            let code = line.split("//").next().unwrap_or_default().trim();
+            if code.is_empty() {
+                continue;
+            }
+            let instructions = parse_line(code).ok_or_else(|| {
+                LessonError::BadInput(format!("line {}: expected `let`, `println!`, `{{` or `}}`, found `{code}`", index + 1))
+            })?;
+            steps.extend(instructions.into_iter().map(|instruction| (index, instruction)));
+        }
+        Ok(Self { lines, steps })
+    }
+
+    // Ok if the borrow checker would accept it, otherwise the first error it would report
+    pub fn check(&self) -> Result<(), Rejection> {
+        Checker { steps: &self.steps, bindings: Vec::new(), depth: 0 }.run()
+    }
+
+    // The rejection the way rustc prints it, the lines it's about with the spans underlined
+    //
+    //     error[E0382]: borrow of moved value: `s1`
+    //      --> line 3
+    //       |
+    //     2 | let s2 = s1;
+    //       |          -- value moved here
+    //     3 | println!("{}", s1);
+    //       |                ^^ value borrowed here after move
+    pub fn render(&self, rejection: &Rejection) -> String {
+        let mut text = format!("{rejection}\n");
+        let Some(primary) = rejection.labels.first() else {
+            return text;
+        };
+        let line_of = |label: &Label| self.steps[label.step].0;
+        let mut lines: Vec<usize> = rejection.labels.iter().map(line_of).collect();
+        lines.sort_unstable();
+        lines.dedup();
+        let width = lines.last().map_or(1, |line| (line + 1).to_string().len());
+        text.push_str(&format!("{:width$}--> line {}\n", "", line_of(primary) + 1));
+        text.push_str(&format!("{:width$} |\n", ""));
+        let mut previous: Option<usize> = None;
+        for line in lines {
+            if previous.is_some_and(|previous| line > previous + 1) {
+                text.push_str("...\n");
+            }
+            previous = Some(line);
+            let source = &self.lines[line];
+            text.push_str(&format!("{:>width$} | {source}\n", line + 1));
+            for (index, label) in rejection.labels.iter().enumerate().filter(|(_, label)| line_of(label) == line) {
+                let (start, len) = span(source, &self.steps[label.step].1);
+                let marker = if index == 0 { "^" } else { "-" };
+                text.push_str(&format!("{:width$} | {}{} {}\n", "", " ".repeat(start), marker.repeat(len.max(1)), label.text));
+            }
+        }
+        text
+    }
+}
+
+// The instructions on one line, more than one for a `println!` with several arguments
+fn parse_line(code: &str) -> Option<Vec<Instruction>> {
+    match code {
+        "{" => return Some(vec![Instruction::BeginScope]),
+        "}" => return Some(vec![Instruction::EndScope]),
+        _ => {}
+    }
+    let statement = code.strip_suffix(';')?.trim();
+    if let Some(arguments) = statement.strip_prefix("println!(") {
+        let arguments = arguments.strip_suffix(')')?;
+        let (format, rest) = arguments.strip_prefix('"')?.split_once('"')?;
+        let mut names: Vec<&str> = format.split('{').skip(1).filter_map(|part| part.split_once('}')).map(|(name, _)| name.split(':').next().unwrap_or_default().trim()).filter(|name| !name.is_empty()).collect();
+        names.extend(rest.split(',').map(str::trim).filter(|name| !name.is_empty()));
+        return names.iter().all(|name| is_identifier(name)).then(|| names.iter().map(|name| Instruction::Use(name.to_string())).collect());
+    }
+    parse_statement(statement).map(|instruction| vec![instruction])
+}
+
+fn parse_statement(statement: &str) -> Option<Instruction> {
+    if let Some(binding) = statement.strip_prefix("let ") {
+        let binding = binding.trim_start();
+        let binding = binding.strip_prefix("mut ").unwrap_or(binding);
+        let Some((name, value)) = binding.split_once('=') else {
+            return is_identifier(binding.trim()).then(|| Instruction::Declare(binding.trim().to_string()));
+        };
+        return match value_of(name.trim(), value.trim())? {
+            Some(instruction) => Some(instruction),
+            None => Some(Instruction::Let(name.trim().to_string())),
+        };
+    }
+    // Only giving a reference or a moved value to a declared variable, not `x = String::new();`
+    let (name, value) = statement.split_once('=')?;
+    value_of(name.trim(), value.trim())?.map(|instruction| Instruction::Assign(Box::new(instruction)))
+}
+
+// The Move, Borrow or BorrowMut `name = value` is, Some(None) when the value is something else, None when it
+// doesn't parse
+fn value_of(name: &str, value: &str) -> Option<Option<Instruction>> {
+    if !is_identifier(name) {
+        return None;
+    }
+    let name = name.to_string();
+    if let Some(owner) = value.strip_prefix("&mut ") {
+        let owner = owner.trim();
+        return is_identifier(owner).then(|| Some(Instruction::BorrowMut { owner: owner.to_string(), by: name }));
+    }
+    if let Some(owner) = value.strip_prefix('&') {
+        let owner = owner.trim();
+        return is_identifier(owner).then(|| Some(Instruction::Borrow { owner: owner.to_string(), by: name }));
+    }
+    Some(is_identifier(value).then(|| Instruction::Move { from: value.to_string(), to: name }))
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_') && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+// Where to underline an instruction in its line: the variable it reads (and the & in front of a borrow), the
+// variable a `let` declares, or the brace
+fn span(line: &str, instruction: &Instruction) -> (usize, usize) {
+    let instruction = match instruction {
+        Instruction::Assign(inner) => inner,
+        instruction => instruction,
+    };
+    let name = match instruction.names() {
+        (Some(used), _) => used,
+        (None, Some(declared)) => declared,
+        (None, None) => if *instruction == Instruction::BeginScope { "{" } else { "}" },
+    };
+    let Some(start) = find_word(line, name) else {
+        let indent = line.len() - line.trim_start().len();
+        return (indent, line.trim().chars().count());
+    };
+    let mut from = start;
+    if matches!(instruction, Instruction::Borrow { .. } | Instruction::BorrowMut { .. }) {
+        from = line[..start].rfind('&').unwrap_or(start);
+    }
+    (line[..from].chars().count(), line[from..start].chars().count() + name.chars().count())
+}
+
+// The byte offset of the last whole-word `word` in `line`, e.g. `s1` in `let s2 = s1;`
+fn find_word(line: &str, word: &str) -> Option<usize> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    line.rmatch_indices(word).map(|(start, _)| start).find(|&start| {
+        let before = line[..start].chars().next_back();
+        let after = line[start + word.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+// Why a program doesn't compile: rustc's error code and message, and the steps it points at, the primary one
+// (where the error happened) first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    // None for errors without one, like an unmatched `}`
+    pub code: Option<&'static str>,
+    pub message: String,
+    pub labels: Vec<Label>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    // Index into `Program::steps`
+    pub step: usize,
+    pub text: String,
+}
+
+impl Rejection {
+    fn new(code: &'static str, message: String, labels: Vec<(usize, String)>) -> Self {
+        let labels = labels.into_iter().map(|(step, text)| Label { step, text }).collect();
+        Self { code: Some(code), message, labels }
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.code {
+            Some(code) => write!(f, "error[{code}]: {}", self.message),
+            None => write!(f, "error: {}", self.message),
+        }
+    }
+}
+
+// A `let`, from the step that made it until its scope ends
+#[derive(Debug, Clone)]
+struct Binding {
+    name: String,
+    depth: usize,
+    kind: Kind,
+    // The step that moved the value out of it
+    moved: Option<usize>,
+    in_scope: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Owner,
+    // `owner` is the binding it points at, `at` the step where that was borrowed, which copies and moves of the
+    // reference keep
+    Ref { owner: usize, mutable: bool, at: usize },
+    // `let r;`, nothing assigned yet
+    Uninit,
+}
+
+struct Checker<'a> {
+    steps: &'a [(usize, Instruction)],
+    // Every binding so far, out of scope ones too so a Ref's owner index stays valid
+    bindings: Vec<Binding>,
+    depth: usize,
+}
+
+impl Checker<'_> {
+    fn run(mut self) -> Result<(), Rejection> {
+        for (step, (_, instruction)) in self.steps.iter().enumerate() {
+            self.step(step, instruction, false)?;
+        }
+        Ok(())
+    }
+
+    // `assign` is set for the instruction inside an Assign, which gives `to` or `by` a value instead of declaring it
+    fn step(&mut self, step: usize, instruction: &Instruction, assign: bool) -> Result<(), Rejection> {
+        match instruction {
+            Instruction::Let(name) => self.bind(step, name, Kind::Owner, assign)?,
+            Instruction::Move { from, to } => self.moved(step, from, to, assign)?,
+            Instruction::Borrow { owner, by } => self.borrow(step, owner, by, false, assign)?,
+            Instruction::BorrowMut { owner, by } => self.borrow(step, owner, by, true, assign)?,
+            Instruction::Use(name) => self.used(step, name)?,
+            Instruction::Declare(name) => self.bind(step, name, Kind::Uninit, false)?,
+            Instruction::Assign(inner) => self.step(step, inner, true)?,
+            Instruction::BeginScope => self.depth += 1,
+            Instruction::EndScope => self.end_scope(step)?,
+        }
+        Ok(())
+    }
+
+    // A new binding in the current scope, or with `assign`, a new value for the one already called `name`
+    fn bind(&mut self, step: usize, name: &str, kind: Kind, assign: bool) -> Result<(), Rejection> {
+        if !assign {
+            self.bindings.push(Binding { name: name.to_string(), depth: self.depth, kind, moved: None, in_scope: true });
+            return Ok(());
+        }
+        let id = self.find(step, name)?;
+        self.bindings[id].kind = kind;
+        self.bindings[id].moved = None;
+        Ok(())
+    }
+
+    fn find(&self, step: usize, name: &str) -> Result<usize, Rejection> {
+        self.bindings.iter().rposition(|binding| binding.in_scope && binding.name == name).ok_or_else(|| {
+            Rejection::new("E0425", format!("cannot find value `{name}` in this scope"), vec![(step, "not found in this scope".to_string())])
+        })
+    }
+
+    // The binding `name` refers to at `step`, which must have a value that wasn't moved out of. `verb` is how
+    // it's used, "use" or "borrow".
+    fn resolve(&self, step: usize, name: &str, verb: &str) -> Result<usize, Rejection> {
+        let id = self.find(step, name)?;
+        if let Kind::Uninit = self.bindings[id].kind {
+            return Err(Rejection::new("E0381", format!("used binding `{name}` isn't initialized"), vec![(step, format!("`{name}` used here but it isn't initialized"))]));
+        }
+        if let Some(moved) = self.bindings[id].moved {
+            let used = if verb == "use" { "used" } else { "borrowed" };
+            return Err(Rejection::new(
+                "E0382",
+                format!("{verb} of moved value: `{name}`"),
+                vec![(step, format!("value {used} here after move")), (moved, "value moved here".to_string())],
+            ));
+        }
+        Ok(id)
+    }
+
+    // The step after `step` where the binding is next read, while it's still in scope and not shadowed. `depth`
+    // is the scope depth right after `step`.
+    fn next_use(&self, id: usize, step: usize, mut depth: usize) -> Option<usize> {
+        let binding = &self.bindings[id];
+        // The depth of the `let` shadowing it, until that scope ends, maybe one from before `step`
+        let mut shadowed = self.bindings[id + 1..].iter().filter(|other| other.in_scope && other.name == binding.name).map(|other| other.depth).min().filter(|&shadowed| shadowed <= depth);
+        for (index, (_, instruction)) in self.steps.iter().enumerate().skip(step + 1) {
+            let (used, declared) = instruction.names();
+            if shadowed.is_none() && used == Some(binding.name.as_str()) {
+                return Some(index);
+            }
+            // A new value, the borrow it held is over
+            if let Instruction::Assign(inner) = instruction {
+                if shadowed.is_none() && inner.names().1 == Some(binding.name.as_str()) {
+                    return None;
+                }
+            }
+            match instruction {
+                Instruction::BeginScope => depth += 1,
+                Instruction::EndScope => {
+                    if depth == binding.depth {
+                        return None;
+                    }
+                    depth -= 1;
+                    if shadowed.is_some_and(|shadowed| depth < shadowed) {
+                        shadowed = None;
+                    }
+                }
+                _ => {}
+            }
+            if shadowed.is_none() && declared == Some(binding.name.as_str()) {
+                shadowed = Some(depth);
+            }
+        }
+        None
+    }
+
+    // A reference to `owner` that's still going to be used after `step`, other than the ones in `except`: (the
+    // step it was borrowed at, the step it's used at next)
+    fn live_loan(&self, owner: usize, step: usize, mutable: bool, except: &[usize]) -> Option<(usize, usize)> {
+        self.bindings.iter().enumerate().find_map(|(id, binding)| match binding.kind {
+            Kind::Ref { owner: of, mutable: is_mut, at } if of == owner && is_mut == mutable && binding.in_scope && binding.moved.is_none() && !except.contains(&id) => {
+                self.next_use(id, step, self.depth).map(|later| (at, later))
+            }
+            _ => None,
+        })
+    }
+
+    fn moved(&mut self, step: usize, from: &str, to: &str, assign: bool) -> Result<(), Rejection> {
+        let id = self.resolve(step, from, "use")?;
+        let kind = self.bindings[id].kind;
+        match kind {
+            Kind::Owner => {
+                if let Some((at, later)) = self.live_loan(id, step, false, &[]).or_else(|| self.live_loan(id, step, true, &[])) {
+                    return Err(Rejection::new(
+                        "E0505",
+                        format!("cannot move out of `{from}` because it is borrowed"),
+                        vec![(step, format!("move out of `{from}` occurs here")), (at, format!("borrow of `{from}` occurs here")), (later, "borrow later used here".to_string())],
+                    ));
+                }
+                self.bindings[id].moved = Some(step);
+            }
+            // Shared references are Copy, the old one can still be used
+            Kind::Ref { mutable: false, .. } => {}
+            Kind::Ref { mutable: true, .. } => self.bindings[id].moved = Some(step),
+            Kind::Uninit => unreachable!("resolve rejects uninitialized bindings"),
+        }
+        self.bind(step, to, kind, assign)
+    }
+
+    fn borrow(&mut self, step: usize, name: &str, by: &str, mutable: bool, assign: bool) -> Result<(), Rejection> {
+        let id = self.resolve(step, name, "borrow")?;
+        // Borrowing through a reference borrows what it points at
+        let owner = match self.bindings[id].kind {
+            Kind::Owner => id,
+            Kind::Ref { mutable: false, .. } if mutable => {
+                return Err(Rejection::new(
+                    "E0596",
+                    format!("cannot borrow `*{name}` as mutable, as it is behind a `&` reference"),
+                    vec![(step, format!("`{name}` is a `&` reference, so the data it refers to cannot be borrowed as mutable"))],
+                ));
+            }
+            Kind::Ref { owner, .. } => owner,
+            Kind::Uninit => unreachable!("resolve rejects uninitialized bindings"),
+        };
+        let owner_name = self.bindings[owner].name.clone();
+        // Neither the reference borrowed through nor the one being given a new value conflict with this borrow
+        let mut except = vec![id];
+        if assign {
+            except.push(self.find(step, by)?);
+        }
+        if let Some((at, later)) = self.live_loan(owner, step, true, &except) {
+            return Err(if mutable {
+                Rejection::new(
+                    "E0499",
+                    format!("cannot borrow `{owner_name}` as mutable more than once at a time"),
+                    vec![(step, "second mutable borrow occurs here".to_string()), (at, "first mutable borrow occurs here".to_string()), (later, "first borrow later used here".to_string())],
+                )
+            } else {
+                Rejection::new(
+                    "E0502",
+                    format!("cannot borrow `{owner_name}` as immutable because it is also borrowed as mutable"),
+                    vec![(step, "immutable borrow occurs here".to_string()), (at, "mutable borrow occurs here".to_string()), (later, "mutable borrow later used here".to_string())],
+                )
+            });
+        }
+        if mutable {
+            if let Some((at, later)) = self.live_loan(owner, step, false, &except) {
+                return Err(Rejection::new(
+                    "E0502",
+                    format!("cannot borrow `{owner_name}` as mutable because it is also borrowed as immutable"),
+                    vec![(step, "mutable borrow occurs here".to_string()), (at, "immutable borrow occurs here".to_string()), (later, "immutable borrow later used here".to_string())],
+                ));
+            }
+        }
+        self.bind(step, by, Kind::Ref { owner, mutable, at: step }, assign)
+    }
+
+    // Reading an owner borrows it for a moment, which a live &mut doesn't allow
+    fn used(&mut self, step: usize, name: &str) -> Result<(), Rejection> {
+        let id = self.resolve(step, name, "borrow")?;
+        if let Kind::Owner = self.bindings[id].kind {
+            if let Some((at, later)) = self.live_loan(id, step, true, &[]) {
+                return Err(Rejection::new(
+                    "E0502",
+                    format!("cannot borrow `{name}` as immutable because it is also borrowed as mutable"),
+                    vec![(step, "immutable borrow occurs here".to_string()), (at, "mutable borrow occurs here".to_string()), (later, "mutable borrow later used here".to_string())],
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // Drops everything declared in the scope, which nothing outside it may still be pointing at
+    fn end_scope(&mut self, step: usize) -> Result<(), Rejection> {
+        if self.depth == 0 {
+            return Err(Rejection { code: None, message: "unexpected closing delimiter: `}`".to_string(), labels: vec![Label { step, text: "unexpected closing delimiter".to_string() }] });
+        }
+        for (id, binding) in self.bindings.iter().enumerate() {
+            if !binding.in_scope || binding.depth != self.depth || binding.moved.is_some() || !matches!(binding.kind, Kind::Owner) {
+                continue;
+            }
+            for (reference, other) in self.bindings.iter().enumerate() {
+                let Kind::Ref { owner, at, .. } = other.kind else { continue };
+                if owner != id || !other.in_scope || other.depth >= self.depth {
+                    continue;
+                }
+                if let Some(later) = self.next_use(reference, step, self.depth - 1) {
+                    return Err(Rejection::new(
+                        "E0597",
+                        format!("`{}` does not live long enough", binding.name),
+                        vec![(at, "borrowed value does not live long enough".to_string()), (step, format!("`{}` dropped here while still borrowed", binding.name)), (later, "borrow later used here".to_string())],
+                    ));
+                }
+            }
+        }
+        let depth = self.depth;
+        for binding in self.bindings.iter_mut().filter(|binding| binding.depth == depth) {
+            binding.in_scope = false;
+        }
+        self.depth -= 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The error code a program is rejected with, None if it's accepted
+    fn code(source: &str) -> Option<&'static str> {
+        Program::parse(source).unwrap().check().err().map(|rejection| rejection.code.unwrap_or("none"))
+    }
+
+    #[test]
+    fn parse_reads_each_kind_of_line() {
+        let program = Program::parse("let mut s = String::from(\"hello\"); // an owner\n\nlet r = &s;\nlet m = &mut s;\nlet t = s;\nlet x;\nx = &t;\n{\n}\nprintln!(\"{r} {}\", m);").unwrap();
+        let steps: Vec<Instruction> = program.steps.into_iter().map(|(_, instruction)| instruction).collect();
+        assert_eq!(
+            steps,
+            [
+                Instruction::Let("s".to_string()),
+                Instruction::Borrow { owner: "s".to_string(), by: "r".to_string() },
+                Instruction::BorrowMut { owner: "s".to_string(), by: "m".to_string() },
+                Instruction::Move { from: "s".to_string(), to: "t".to_string() },
+                Instruction::Declare("x".to_string()),
+                Instruction::Assign(Box::new(Instruction::Borrow { owner: "t".to_string(), by: "x".to_string() })),
+                Instruction::BeginScope,
+                Instruction::EndScope,
+                Instruction::Use("r".to_string()),
+                Instruction::Use("m".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_what_it_does_not_know() {
+        match Program::parse("let s = String::new();\ns.push_str(\"x\");") {
+            Err(LessonError::BadInput(message)) => assert_eq!(message, "line 2: expected `let`, `println!`, `{` or `}`, found `s.push_str(\"x\");`"),
+            other => panic!("expected BadInput, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn new_writes_what_parse_reads() {
+        let instructions = vec![
+            Instruction::Declare("r".to_string()),
+            Instruction::BeginScope,
+            Instruction::Let("x".to_string()),
+            Instruction::Assign(Box::new(Instruction::Borrow { owner: "x".to_string(), by: "r".to_string() })),
+            Instruction::EndScope,
+            Instruction::Use("r".to_string()),
+        ];
+        let program = Program::new(instructions);
+        assert_eq!(program.lines[3], "    r = &x;");
+        assert_eq!(Program::parse(&program.lines.join("\n")).unwrap(), program);
+    }
+
+    #[test]
+    fn a_borrow_ends_at_its_last_use() {
+        assert_eq!(code("let mut s = String::new();\nlet r1 = &s;\nprintln!(\"{}\", r1);\nlet r2 = &mut s;\nprintln!(\"{}\", r2);"), None);
+        assert_eq!(code("let s = String::new();\nlet r = &s;\nlet t = r;\nprintln!(\"{}, {}\", r, t);"), None);
+    }
+
+    #[test]
+    fn rejections_have_rustc_codes() {
+        assert_eq!(code("let s1 = String::new();\nlet s2 = s1;\nprintln!(\"{}\", s1);"), Some("E0382"));
+        assert_eq!(code("let s1 = String::new();\nlet r = &s1;\nlet s2 = s1;\nprintln!(\"{}\", r);"), Some("E0505"));
+        assert_eq!(code("let mut s = String::new();\nlet r1 = &mut s;\nlet r2 = &mut s;\nprintln!(\"{}, {}\", r1, r2);"), Some("E0499"));
+        assert_eq!(code("let mut s = String::new();\nlet r1 = &s;\nlet r2 = &mut s;\nprintln!(\"{}\", r1);"), Some("E0502"));
+        assert_eq!(code("let mut s = String::new();\nlet r = &mut s;\nprintln!(\"{}\", s);\nprintln!(\"{}\", r);"), Some("E0502"));
+        assert_eq!(code("let s = String::new();\nlet r = &s;\nlet m = &mut r;"), Some("E0596"));
+        assert_eq!(code("let r;\n{\n    let x = String::new();\n    r = &x;\n}\nprintln!(\"{}\", r);"), Some("E0597"));
+        assert_eq!(code("let r;\nprintln!(\"{}\", r);"), Some("E0381"));
+        assert_eq!(code("println!(\"{}\", nobody);"), Some("E0425"));
+        assert_eq!(code("}"), Some("none"));
+    }
+
+    #[test]
+    fn shadowing_in_a_scope_ends_with_it() {
+        assert_eq!(code("let s = String::new();\nlet r = &s;\n{\n    let r = String::new();\n    let t = s;\n    println!(\"{}\", r);\n}"), None);
+        assert_eq!(code("let s = String::new();\nlet r = &s;\n{\n    let r = String::new();\n}\nlet t = s;\nprintln!(\"{}\", r);"), Some("E0505"));
+    }
+
+    #[test]
+    fn render_underlines_the_spans() {
+        let program = Program::parse("let s1 = String::new();\nlet s2 = s1;\nprintln!(\"{}\", s1);").unwrap();
+        let rejection = program.check().unwrap_err();
+        assert_eq!(
+            program.render(&rejection),
+            "error[E0382]: borrow of moved value: `s1`\n --> line 3\n  |\n2 | let s2 = s1;\n  |          -- value moved here\n3 | println!(\"{}\", s1);\n  |                ^^ value borrowed here after move\n"
+        );
+    }
+
+    #[test]
+    fn render_skips_lines_between_labels() {
+        let program = Program::parse("let s1 = String::new();\nlet r = &s1;\nlet other = String::new();\nlet s2 = s1;\nprintln!(\"{}\", r);").unwrap();
+        let text = program.render(&program.check().unwrap_err());
+        assert!(text.contains("2 | let r = &s1;\n  |         --- borrow of `s1` occurs here\n...\n4 | let s2 = s1;\n  |          ^^ move out of `s1` occurs here\n"), "{text}");
+    }
+}
@@ -0,0 +1,118 @@
+use crate::error::LessonError;
+use crate::lesson;
+use crate::layout;
+use crate::lesson::{LessonDef, Taught};
+use crate::output::OwnershipEvent;
+use crate::report::Reporter;
+
+pub const TAUGHT: Taught = Taught { day: 6, order: 4 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(slicing_demo {
+        name: "slicing",
+        summary: "String slices borrow part of a string",
+        topics: &["slices"],
+        prerequisites: &["reference"],
+        explanation: "A slice borrows part of a collection instead of copying it. `&s[0..i]` is a `&str` pointing into the original string, and string literals are slices too. Slice indexes are byte offsets, so they must fall on character boundaries.",
+    }),
+    lesson!(layout_demo {
+        name: "layout",
+        summary: "How big a &str, a String and an Option<Box<T>> really are",
+        topics: &["slices", "ownership"],
+        prerequisites: &["slicing"],
+        explanation: "`std::mem::size_of` gives a type's size in bytes. A `&str` is two words, a pointer to the bytes and a length, while a `String` is three, adding the capacity of its heap buffer, which is why slicing never copies. A type with an impossible bit pattern, like a Box that is never null, has a niche: `Option` stores None in it and takes no extra space.",
+    }),
+];
+
+// Slices can be used on collections such as strings, vectors, arrays, and hash maps
+#[allow(clippy::redundant_slicing)] // Spelled out to show the full range slice
+pub fn slicing(my_string: &str) -> &str {
+    let bytes = my_string.as_bytes(); // Converts the string to array of bytes
+
+    for (i, &item) in bytes.iter().enumerate() { // Iterate over the array of bytes, and enumerate tuple of (index, element reference)
+        if item == b'r' { // Stops at the index where 'r' is
+            return &my_string[0..i]; // Returns the slice up until where 'r' is
+        }
+    }
+
+    &my_string[..] // If the character isn't found, return the whole string
+}
+
+// The first word of a string, everything up to the first whitespace, as a slice of the input
+pub fn first_word(text: &str) -> &str {
+    let bytes = text.as_bytes();
+
+    for (i, &item) in bytes.iter().enumerate() {
+        if item.is_ascii_whitespace() {
+            return &text[..i];
+        }
+    }
+
+    text
+}
+
+// Same as `&my_string[start..end]`, but returns an error instead of panicking when an index is out of range
+// or lands inside a multi-byte character
+pub fn slice_range(my_string: &str, start: usize, end: usize) -> Result<&str, LessonError> {
+    for index in [start, end] {
+        if !my_string.is_char_boundary(index) {
+            return Err(LessonError::SliceBoundary { index, len: my_string.len() });
+        }
+    }
+    if start > end {
+        return Err(LessonError::BadInput(format!("slice starts at {start} but ends at {end}")));
+    }
+    Ok(&my_string[start..end])
+}
+
+// Taking part of a string without copying it
+pub fn slicing_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let my_string_literal = "hello world";
+    //let hello = &my_mut_string[..=5];
+    //let world = &my_mut_string[6..];
+    let word = slicing(my_string_literal);
+    reporter.event(&OwnershipEvent::borrowed("my_string_literal", "word"));
+    reporter.message(word);
+    reporter.value("word", word);
+
+    let hello = slice_range(my_string_literal, 0, 5)?;
+    reporter.event(&OwnershipEvent::borrowed("my_string_literal", "hello"));
+    reporter.message(hello);
+    Ok(())
+}
+
+// size_of and align_of for the string types and a few demo structs, measured rather than claimed
+pub fn layout_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let layouts = layout::layouts();
+    for line in layout::table(&layouts) {
+        reporter.message(&line);
+    }
+
+    let str_size = std::mem::size_of::<&str>();
+    let string_size = std::mem::size_of::<String>();
+    reporter.value("size_of::<&str>()", &str_size.to_string());
+    reporter.value("size_of::<String>()", &string_size.to_string());
+    reporter.message(&format!("A slice of a String is {} bytes smaller than the String, it has no capacity to track", string_size - str_size));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_word_stops_at_any_ascii_whitespace() {
+        assert_eq!(first_word("hello world"), "hello");
+        assert_eq!(first_word("tab\tseparated"), "tab");
+        assert_eq!(first_word("line\nbreak"), "line");
+        assert_eq!(first_word("single"), "single");
+        assert_eq!(first_word(" leading"), "");
+        assert_eq!(first_word(""), "");
+        assert_eq!(first_word("héllo wörld"), "héllo");
+    }
+
+    #[test]
+    fn first_word_borrows_from_its_input() {
+        let text = String::from("hello world");
+        assert!(std::ptr::eq(first_word(&text).as_ptr(), text.as_ptr()));
+    }
+}
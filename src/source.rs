@@ -0,0 +1,63 @@
+// The lesson modules' own code, so `show` can print a demo as it's written. The paths are the ones
+// `Location::caller()` reports, relative to the crate root.
+const FILES: &[(&str, &str)] = &[
+    ("src/days/day1.rs", include_str!("days/day1.rs")),
+    ("src/days/day2.rs", include_str!("days/day2.rs")),
+    ("src/days/day3.rs", include_str!("days/day3.rs")),
+    ("src/days/day4.rs", include_str!("days/day4.rs")),
+    ("src/days/day5.rs", include_str!("days/day5.rs")),
+    ("src/ownership.rs", include_str!("ownership.rs")),
+    ("src/borrowing.rs", include_str!("borrowing.rs")),
+    ("src/slices.rs", include_str!("slices.rs")),
+    ("src/options.rs", include_str!("options.rs")),
+    ("src/lifetimes.rs", include_str!("lifetimes.rs")),
+    ("src/results.rs", include_str!("results.rs")),
+    ("src/vectors.rs", include_str!("vectors.rs")),
+    ("src/hash_maps.rs", include_str!("hash_maps.rs")),
+    ("src/closures.rs", include_str!("closures.rs")),
+    ("src/iterators.rs", include_str!("iterators.rs")),
+    ("src/boxes.rs", include_str!("boxes.rs")),
+    ("src/rc.rs", include_str!("rc.rs")),
+    ("src/refcell.rs", include_str!("refcell.rs")),
+    ("src/weak.rs", include_str!("weak.rs")),
+    ("src/threads.rs", include_str!("threads.rs")),
+    ("src/locks.rs", include_str!("locks.rs")),
+    ("src/channels.rs", include_str!("channels.rs")),
+    ("src/generics.rs", include_str!("generics.rs")),
+    ("src/trait_objects.rs", include_str!("trait_objects.rs")),
+    ("src/deref.rs", include_str!("deref.rs")),
+    ("src/cow.rs", include_str!("cow.rs")),
+    ("src/raii.rs", include_str!("raii.rs")),
+    ("src/copy_clone.rs", include_str!("copy_clone.rs")),
+    ("src/scopes.rs", include_str!("scopes.rs")),
+    ("src/statics.rs", include_str!("statics.rs")),
+    ("src/strings.rs", include_str!("strings.rs")),
+];
+
+// A function's code, from its `fn` line to the closing brace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    pub file: &'static str,
+    // 1-based, of the `fn` line
+    pub first_line: usize,
+    pub lines: Vec<&'static str>,
+}
+
+impl Source {
+    // Whether the line `line` of `file` is part of this function
+    pub fn contains(&self, file: &str, line: usize) -> bool {
+        file == self.file && (self.first_line..self.first_line + self.lines.len()).contains(&line)
+    }
+}
+
+// The function named `name` in the lesson modules, found by its `fn` line. It ends at the first `}` at the start of
+// a line, which is how every function in these modules ends.
+pub fn function(name: &str) -> Option<Source> {
+    let signature = format!("fn {name}(");
+    FILES.iter().find_map(|(file, text)| {
+        let lines: Vec<&str> = text.lines().collect();
+        let start = lines.iter().position(|line| line.trim_start().strip_prefix("pub ").unwrap_or(line.trim_start()).starts_with(&signature))?;
+        let end = lines[start..].iter().position(|line| line.starts_with('}'))? + start;
+        Some(Source { file, first_line: start + 1, lines: lines[start..=end].to_vec() })
+    })
+}
@@ -0,0 +1,226 @@
+use std::fmt::Debug;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::OwnershipEvent;
+use crate::report::Reporter;
+use crate::runner;
+
+// `'static` is the lifetime of the whole program. A string literal is a `&'static str` because its bytes are part
+// of the binary, nothing ever drops them. A `static` is a value like that with a name and one address, a `const` is
+// a value pasted into each place it's used. As a bound, `T: 'static` doesn't mean the value lives forever, it means
+// T holds no borrow that could end: an owned String is `'static`, a `&String` to a local isn't, which is what
+// thread::spawn asks of its closure.
+
+pub const TAUGHT: Taught = Taught { day: 7, order: 2 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(static_str_demo {
+        name: "static_str",
+        summary: "A string literal is a &'static str, its bytes live in the binary",
+        topics: &["lifetimes", "strings"],
+        prerequisites: &["dangling_references", "slicing"],
+        explanation: "`let my_string_literal = \"hello world\";` is a `&'static str`: the text is stored in the program itself, loaded before main runs and never freed, so a reference to it is valid for as long as the program is. That's why `fn greeting() -> &'static str { \"hello\" }` compiles where `dangle` didn't, there's no local for it to outlive. A String's bytes are on the heap and dropped with it, so `&owned` can't be a `&'static str` (E0597). `String::leak` makes one at runtime by never freeing the String, fine for something made once per program.",
+    }),
+    lesson!(const_vs_static_demo {
+        name: "const_vs_static",
+        summary: "A const is copied into each use, a static is one value at one address",
+        topics: &["lifetimes", "variables"],
+        prerequisites: &["static_str"],
+        explanation: "`const MAX_NAMES: usize = 3;` has no address of its own, each use gets the value as if you had typed it there, so `let mut names = EMPTY;` takes a fresh Vec every time. `static CALLS: AtomicUsize` is one value for the whole program, a `&CALLS` is a `&'static AtomicUsize` and every function sees the same one. Both are made at compile time, so only const fns can build them (E0015, LazyLock is for the rest), and because any thread can reach a static its type has to be Sync: a static Cell is rejected (E0277), an atomic or a Mutex isn't. Use const for values, static when the one address or shared state is the point.",
+    }),
+    lesson!(static_bounds_demo {
+        name: "static_bounds",
+        summary: "T: 'static means no borrow that could end, not a value that lives forever",
+        topics: &["lifetimes", "concurrency", "traits"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["const_vs_static"],
+        explanation: "`thread::spawn<F: FnOnce() -> T + Send + 'static>` can't know when the thread will end, so the closure may only hold things that stay valid however long that is: owned values, and references that are `'static` themselves, a literal or a static. An owned String passes, it's dropped whenever its owner is, it just doesn't depend on anyone else. A reference to a caller's data doesn't, even moved in (E0521), and neither does `&name` passed to any `T: 'static` parameter (E0597). Box<dyn Any> and the runner's `impl Lesson + 'static` ask the same thing.",
+    }),
+];
+
+// Code that rustc rejects and what it says, cut short before the notes
+fn report_rejected(reporter: &mut dyn Reporter, (code, error): (&str, &str)) {
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+}
+
+// Hands back a literal, there's nothing local to dangle
+pub fn greeting() -> &'static str {
+    "hello world"
+}
+
+const STRING_AS_STATIC: (&str, &str) = (
+    "fn main() {\n    let owned = String::from(\"hello\");\n    let text: &'static str = &owned;\n    println!(\"{text}\");\n}",
+    "error[E0597]: `owned` does not live long enough\n --> src/main.rs:3:30\n  |\n2 |     let owned = String::from(\"hello\");\n  |         ----- binding `owned` declared here\n3 |     let text: &'static str = &owned;\n  |               ------------   ^^^^^^ borrowed value does not live long enough\n  |               |\n  |               type annotation requires that `owned` is borrowed for `'static`\n4 |     println!(\"{text}\");\n5 | }\n  | - `owned` dropped here while still borrowed",
+);
+
+// A literal is the same bytes on every call, a String can only be borrowed for 'static once it's leaked
+pub fn static_str_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let my_string_literal: &'static str = "hello world"; // Part of the binary, valid for the whole program
+    reporter.message(&format!("my_string_literal: {my_string_literal}, a &'static str"));
+    let first = greeting();
+    let second = greeting();
+    reporter.message(&format!("greeting() twice, the same bytes both times: {}", first.as_ptr() == second.as_ptr())); // Nothing was made, both point into the binary
+    reporter.message("");
+
+    report_rejected(reporter, STRING_AS_STATIC);
+
+    let owned = String::from("made at runtime");
+    let leaked: &'static str = owned.leak(); // Never freed, so the reference can last the rest of the program
+    reporter.event(&OwnershipEvent::moved("owned", "leaked"));
+    reporter.message(&format!("leaked: {leaked}, owned: error, its heap bytes now belong to no one"));
+    Ok(())
+}
+
+// Copied into each use, there's no one EMPTY to change
+const EMPTY: Vec<String> = Vec::new();
+const MAX_NAMES: usize = 3;
+
+// One counter for the whole program, atomic because every thread can reach it
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+static NAMES: [&str; MAX_NAMES] = ["ferris", "corro", "rusty"];
+
+// Counts its calls in the one CALLS
+pub fn counted_call() -> usize {
+    CALLS.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+const STATIC_CELL: (&str, &str) = (
+    "use std::cell::Cell;\nstatic COUNT: Cell<u32> = Cell::new(0);",
+    "error[E0277]: `Cell<u32>` cannot be shared between threads safely\n --> src/main.rs:2:15\n  |\n2 | static COUNT: Cell<u32> = Cell::new(0);\n  |               ^^^^^^^^^ `Cell<u32>` cannot be shared between threads safely",
+);
+
+const STATIC_STRING: (&str, &str) = (
+    "static GREETING: String = String::from(\"hello\");",
+    "error[E0015]: cannot call non-const associated function `<String as From<&str>>::from` in statics\n --> src/main.rs:1:27\n  |\n1 | static GREETING: String = String::from(\"hello\");\n  |                           ^^^^^^^^^^^^^^^^^^^^^",
+);
+
+// A const used twice as two values, a static reached from a thread at the same address, then what a static can't be
+pub fn const_vs_static_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let mut names = EMPTY; // A new Vec, as if `Vec::new()` was written here
+    names.push(String::from("ferris"));
+    let others = EMPTY; // Another new one, the push above didn't touch any EMPTY
+    reporter.message(&format!("names: {names:?}, others: {others:?}, MAX_NAMES: {MAX_NAMES}"));
+
+    let before = CALLS.load(Ordering::Relaxed);
+    counted_call();
+    counted_call();
+    let their_calls = thread::spawn(|| {
+        // Nothing captured, a static isn't a local
+        counted_call();
+        ptr::from_ref(&CALLS).addr()
+    });
+    let same_calls = their_calls.join().map_err(runner::panicked)? == ptr::from_ref(&CALLS).addr();
+    let calls = CALLS.load(Ordering::Relaxed) - before;
+    reporter.message(&format!("3 calls, 1 on another thread, CALLS went up by {calls}, the thread's &CALLS is at the same address: {same_calls}"));
+    let names_ref: &'static [&str] = &NAMES; // A static can be borrowed for 'static, it's never dropped
+    reporter.message(&format!("NAMES: {names_ref:?}, &NAMES in two places is one address: {}", ptr::eq(names_ref, &NAMES)));
+    reporter.message("");
+
+    report_rejected(reporter, STATIC_STRING);
+    report_rejected(reporter, STATIC_CELL);
+    reporter.message("`static CALLS: AtomicUsize` is the Sync version, and LazyLock builds a static String on first use");
+    Ok(())
+}
+
+// Takes any value with no borrow that could end, like thread::spawn's closure
+pub fn keep<T: Debug + 'static>(value: T) -> String {
+    format!("kept {value:?}")
+}
+
+const KEEP_BORROW: (&str, &str) = (
+    "fn main() {\n    let name = String::from(\"ferris\");\n    keep(&name);\n}",
+    "error[E0597]: `name` does not live long enough\n --> src/main.rs:3:10\n  |\n2 |     let name = String::from(\"ferris\");\n  |         ---- binding `name` declared here\n3 |     keep(&name);\n  |     -----^^^^^-\n  |     |    |\n  |     |    borrowed value does not live long enough\n  |     argument requires that `name` is borrowed for `'static`\n4 | }\n  | - `name` dropped here while still borrowed",
+);
+
+const SPAWN_PARAMETER: (&str, &str) = (
+    "fn count(names: &[String]) -> usize {\n    let handle = thread::spawn(move || names.len());\n    handle.join().unwrap()\n}",
+    "error[E0521]: borrowed data escapes outside of function\n --> src/main.rs:2:18\n  |\n1 | fn count(names: &[String]) -> usize {\n  |          -----  - let's call the lifetime of this reference `'1`\n  |          |\n  |          `names` is a reference that is only valid in the function body\n2 |     let handle = thread::spawn(move || names.len());\n  |                  ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^\n  |                  |\n  |                  `names` escapes the function body here\n  |                  argument requires that `'1` must outlive `'static`",
+);
+
+// What passes a 'static bound and what doesn't, for keep and then for thread::spawn
+pub fn static_bounds_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let name = String::from("ferris");
+    reporter.message(&keep(greeting())); // A &'static str
+    let names: &'static [&str] = &NAMES; // A reference, but to a static
+    reporter.message(&keep(names));
+    reporter.event(&OwnershipEvent::moved("name", "value"));
+    reporter.message(&keep(name)); // Owned, 'static though it's dropped at the end of keep
+    reporter.message("");
+    report_rejected(reporter, KEEP_BORROW);
+
+    let literal = greeting();
+    let name = String::from("corro");
+    let handle = thread::spawn(move || format!("{literal}, {name}")); // A copied &'static str and a moved String
+    reporter.event(&OwnershipEvent::copied("literal", "thread"));
+    reporter.event(&OwnershipEvent::moved("name", "thread"));
+    reporter.message(&handle.join().map_err(runner::panicked)?);
+    reporter.message(&thread::spawn(|| format!("NAMES from a thread without move: {}", NAMES.join(", "))).join().map_err(runner::panicked)?);
+    reporter.message("");
+    report_rejected(reporter, SPAWN_PARAMETER);
+    reporter.message("a parameter's borrow ends when the caller's does, thread::scope or an owned copy fix it (thread_move)");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::DemoOutput;
+
+    #[test]
+    fn greeting_is_the_same_literal_every_call() {
+        assert!(ptr::eq(greeting(), greeting()));
+        assert_eq!(keep(greeting()), "kept \"hello world\"");
+        assert_eq!(keep(String::from("ferris")), "kept \"ferris\"");
+    }
+
+    #[test]
+    fn static_str_demo_leaks_to_get_a_static() {
+        let mut output = DemoOutput::new();
+        static_str_demo(&mut output).unwrap();
+        assert_eq!(output.messages[..2], ["my_string_literal: hello world, a &'static str", "greeting() twice, the same bytes both times: true"]);
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0597]")));
+        assert_eq!(output.messages.last().unwrap(), "leaked: made at runtime, owned: error, its heap bytes now belong to no one");
+        assert_eq!(output.events, [OwnershipEvent::moved("owned", "leaked")]);
+    }
+
+    // The only test that calls counted_call, so CALLS going up by exactly 3 isn't raced by another test
+    #[test]
+    fn const_vs_static_demo_copies_consts_and_shares_statics() {
+        let mut output = DemoOutput::new();
+        const_vs_static_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages[..3],
+            [
+                "names: [\"ferris\"], others: [], MAX_NAMES: 3",
+                "3 calls, 1 on another thread, CALLS went up by 3, the thread's &CALLS is at the same address: true",
+                "NAMES: [\"ferris\", \"corro\", \"rusty\"], &NAMES in two places is one address: true",
+            ]
+        );
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0015]")));
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0277]")));
+    }
+
+    #[test]
+    fn static_bounds_demo_keeps_owned_and_static_values() {
+        let mut output = DemoOutput::new();
+        static_bounds_demo(&mut output).unwrap();
+        assert_eq!(output.messages[..3], ["kept \"hello world\"", "kept [\"ferris\", \"corro\", \"rusty\"]", "kept \"ferris\""]);
+        assert!(output.messages.contains(&"hello world, corro".to_string()));
+        assert!(output.messages.contains(&"NAMES from a thread without move: ferris, corro, rusty".to_string()));
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0521]")));
+        assert_eq!(
+            output.events,
+            [OwnershipEvent::moved("name", "value"), OwnershipEvent::copied("literal", "thread"), OwnershipEvent::moved("name", "thread")]
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::events::EventKind;
+use crate::json::{Json, ToJson};
+use crate::progress::Progress;
+
+// Fewer answers than this and the trend is noise
+const TREND_MIN_ANSWERS: usize = 4;
+
+// How one topic is going, worked out from the event log
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TopicStats {
+    pub topic: String,
+    // Every quiz answer there's been, including ones from before the event log
+    pub answered: usize,
+    pub correct: usize,
+    // How long an answer took on average, over the answers that were timed
+    pub average_ms: Option<u64>,
+    // Exercises checked at least once, and those that got to done
+    pub exercises: usize,
+    pub solved: usize,
+    // Checks it took to get an exercise done, averaged over the solved ones
+    pub average_checks: Option<f64>,
+    // Accuracy in the later half of the topic's answers minus the earlier half, in percentage points
+    pub trend: Option<i64>,
+}
+
+impl TopicStats {
+    pub fn accuracy(&self) -> Option<usize> {
+        (self.correct * 100).checked_div(self.answered)
+    }
+}
+
+// Per-topic stats, sorted by topic
+pub fn by_topic(progress: &Progress) -> Vec<TopicStats> {
+    let mut answers: BTreeMap<&str, Vec<bool>> = BTreeMap::new();
+    let mut times: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
+    // Topic to exercise to (checks so far, checks it took to get done)
+    let mut checks: BTreeMap<&str, BTreeMap<&str, (usize, Option<usize>)>> = BTreeMap::new();
+    // Oldest first, the way the progress file keeps them
+    for event in &progress.events {
+        match &event.kind {
+            EventKind::Answered { topic, correct, took_ms, .. } => {
+                answers.entry(topic).or_default().push(*correct);
+                times.entry(topic).or_default().extend(took_ms);
+            }
+            EventKind::Checked { exercise, topic, done, .. } => {
+                let (count, took) = checks.entry(topic).or_default().entry(exercise).or_default();
+                // Checks after the first done are re-runs and reviews, not attempts at solving it
+                if took.is_none() {
+                    *count += 1;
+                    if *done {
+                        *took = Some(*count);
+                    }
+                }
+            }
+            EventKind::HintShown { .. } | EventKind::LessonCompleted { .. } | EventKind::Flashcard { .. } => {}
+        }
+    }
+
+    let topics: BTreeSet<&str> = progress.quiz.keys().map(String::as_str).chain(answers.keys().copied()).chain(checks.keys().copied()).collect();
+    topics
+        .into_iter()
+        .map(|topic| {
+            let answers = answers.get(topic).map(Vec::as_slice).unwrap_or_default();
+            let exercises = checks.get(topic).map(|exercises| exercises.values().collect::<Vec<_>>()).unwrap_or_default();
+            let took: Vec<usize> = exercises.iter().filter_map(|(_, took)| *took).collect();
+            let times = times.get(topic).map(Vec::as_slice).unwrap_or_default();
+            // The running totals have everything, the log only what happened since it was started
+            let logged = (answers.iter().filter(|correct| **correct).count(), answers.len());
+            let (correct, answered) = progress.quiz.get(topic).copied().filter(|(_, answered)| *answered >= logged.1).unwrap_or(logged);
+            TopicStats {
+                topic: topic.to_string(),
+                answered,
+                correct,
+                average_ms: (!times.is_empty()).then(|| times.iter().sum::<u64>() / times.len() as u64),
+                exercises: exercises.len(),
+                solved: took.len(),
+                average_checks: (!took.is_empty()).then(|| took.iter().sum::<usize>() as f64 / took.len() as f64),
+                trend: trend(answers),
+            }
+        })
+        .collect()
+}
+
+fn trend(answers: &[bool]) -> Option<i64> {
+    if answers.len() < TREND_MIN_ANSWERS {
+        return None;
+    }
+    let (earlier, later) = answers.split_at(answers.len() / 2);
+    let percent = |half: &[bool]| (half.iter().filter(|correct| **correct).count() * 100 / half.len()) as i64;
+    Some(percent(later) - percent(earlier))
+}
+
+impl ToJson for TopicStats {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("topic", (&self.topic).into()),
+            ("answered", self.answered.into()),
+            ("correct", self.correct.into()),
+            ("accuracy", self.accuracy().into()),
+            ("average_ms", self.average_ms.into()),
+            ("exercises", self.exercises.into()),
+            ("solved", self.solved.into()),
+            ("average_checks", self.average_checks.into()),
+            ("trend", self.trend.into()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::Event;
+
+    fn answered(topic: &str, correct: bool, took_ms: Option<u64>) -> EventKind {
+        EventKind::Answered { question: format!("{topic}-question"), topic: topic.to_string(), correct, took_ms }
+    }
+
+    fn checked(exercise: &str, topic: &str, done: bool) -> EventKind {
+        EventKind::Checked { exercise: exercise.to_string(), topic: topic.to_string(), passed: usize::from(done), total: 1, done }
+    }
+
+    fn progress(kinds: Vec<EventKind>) -> Progress {
+        let events = kinds.into_iter().enumerate().map(|(at, kind)| Event { at: at as u64, kind }).collect();
+        Progress { events, ..Progress::default() }
+    }
+
+    #[test]
+    fn answers_are_counted_timed_and_trended() {
+        let progress = progress(vec![
+            answered("moves", false, Some(1000)),
+            answered("moves", false, None),
+            answered("moves", true, Some(3000)),
+            answered("moves", true, None),
+            answered("slices", true, None),
+        ]);
+        let stats = by_topic(&progress);
+        let topics: Vec<&str> = stats.iter().map(|stats| stats.topic.as_str()).collect();
+        assert_eq!(topics, ["moves", "slices"]);
+        let moves = &stats[0];
+        assert_eq!((moves.correct, moves.answered, moves.accuracy()), (2, 4, Some(50)));
+        assert_eq!(moves.average_ms, Some(2000));
+        assert_eq!(moves.trend, Some(100));
+        // Too few answers for a trend
+        assert_eq!(stats[1].trend, None);
+        assert_eq!(stats[1].average_ms, None);
+    }
+
+    #[test]
+    fn checks_count_until_the_exercise_is_done() {
+        let progress = progress(vec![
+            checked("move1", "moves", false),
+            checked("move1", "moves", false),
+            checked("move1", "moves", true),
+            checked("move1", "moves", false), // A review after it was done
+            checked("move2", "moves", true),
+            checked("move3", "moves", false),
+        ]);
+        let moves = &by_topic(&progress)[0];
+        assert_eq!((moves.exercises, moves.solved), (3, 2));
+        assert_eq!(moves.average_checks, Some(2.0));
+        assert_eq!(moves.accuracy(), None);
+    }
+
+    #[test]
+    fn running_totals_cover_answers_from_before_the_log() {
+        let mut progress = progress(vec![answered("moves", true, None)]);
+        progress.quiz.insert("moves".to_string(), (7, 10));
+        progress.quiz.insert("lifetimes".to_string(), (1, 2));
+        let stats = by_topic(&progress);
+        assert_eq!(stats.iter().map(|stats| (stats.topic.as_str(), stats.correct, stats.answered)).collect::<Vec<_>>(), [("lifetimes", 1, 2), ("moves", 7, 10)]);
+        // Totals behind the log, e.g. from a merge, give way to it
+        progress.quiz.insert("moves".to_string(), (0, 0));
+        assert_eq!(by_topic(&progress)[1].answered, 1);
+    }
+
+    #[test]
+    fn trend_compares_the_later_half_with_the_earlier() {
+        assert_eq!(trend(&[true, true, false, false]), Some(-100));
+        assert_eq!(trend(&[true, false, true, true, true]), Some(50)); // 1 of 2, then 3 of 3
+        assert_eq!(trend(&[true, true, true]), None);
+    }
+
+    #[test]
+    fn stats_as_json() {
+        let stats = TopicStats { topic: "moves".to_string(), answered: 4, correct: 3, ..TopicStats::default() };
+        let json = stats.to_json();
+        assert_eq!(json.get("accuracy").and_then(Json::as_u64), Some(75));
+        assert_eq!(json.get("trend"), Some(&Json::Null));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::borrowing;
+use crate::diagram::Diagram;
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::OwnershipEvent;
+use crate::report::Reporter;
+
+// A String is a pointer, a length and a capacity, the size of the heap block it has. `push_str` writes into the
+// spare capacity while there is some, and when there isn't it asks for a bigger block, which the allocator can
+// only sometimes grow where it is: otherwise the bytes are copied somewhere new and the old block is freed. A
+// `&str` into the String points at the old block either way, and that's the memory story behind "no `&mut` while
+// a `&` is alive". The addresses change from run to run, so the demos letter the blocks instead.
+
+pub const TAUGHT: Taught = Taught { day: 6, order: 5 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(string_capacity_demo {
+        name: "string_capacity",
+        summary: "len() is what's in a String, capacity() is how much fits before it reallocates",
+        topics: &["strings", "ownership", "performance"],
+        prerequisites: &["mutate_reference", "layout"],
+        explanation: "`String::new()` allocates nothing, capacity 0. The first `push_str` allocates a block, and each one that doesn't fit asks for a bigger one, at least double the old capacity, so a String built up a piece at a time only reallocates now and then and the pushes stay cheap on average. `String::from(\"Hello world\")` is exactly full, so the `push_str` in `mutate_reference` has to reallocate. When you know the final size, `String::with_capacity(n)` asks for it once and no push after it reallocates.",
+    }),
+    lesson!(reallocation_demo {
+        name: "reallocation",
+        summary: "A push that reallocates moves the bytes, so a &str into the String can't live across it",
+        topics: &["strings", "borrowing", "slices"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["string_capacity", "slicing", "borrow_checker"],
+        explanation: "`let word = &s[..5];` is a pointer into s's heap block. If `s.push_str(..)` had to reallocate, the bytes moved to a new block and the old one was freed, and `word` would now point at freed memory, a dangling pointer that reading is undefined behaviour. rustc doesn't know whether a push will reallocate, and a `&mut String` could as well `clear()` or `shrink_to_fit()`, so it rejects any `&mut s` while `word` is still to be used (E0502). Ending the slice first, or keeping an index instead of a reference, is what compiles.",
+    }),
+];
+
+// A letter for each heap block in the order they come up, the same address gets the same letter
+fn block(blocks: &mut Vec<usize>, ptr: *const u8) -> char {
+    let address = ptr as usize;
+    let index = match blocks.iter().position(|seen| *seen == address) {
+        Some(index) => index,
+        None => {
+            blocks.push(address);
+            blocks.len() - 1
+        }
+    };
+    (b'A' + (index % 26) as u8) as char
+}
+
+// A String grown by push_str from nothing, with and without with_capacity, then the push in mutate_reference
+pub fn string_capacity_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let mut s = String::new();
+    reporter.message(&format!("String::new(): len {}, capacity {}", s.len(), s.capacity()));
+    let mut blocks = Vec::new();
+    let mut neighbours = Vec::new(); // Other allocations, as a program has, so a block can't always grow in place
+    let mut reallocations = 0;
+    reporter.message("push_str  len  capacity  block");
+    for _ in 0..7 {
+        let capacity = s.capacity();
+        s.push_str("hello");
+        neighbours.push(String::from("next door"));
+        let grew = if capacity == 0 {
+            ", allocated"
+        } else if s.capacity() > capacity {
+            reallocations += 1;
+            ", reallocated"
+        } else {
+            ""
+        };
+        reporter.message(&format!("  hello   {:>3}  {:>8}  {}{grew}", s.len(), s.capacity(), block(&mut blocks, s.as_ptr())));
+    }
+    reporter.message(&format!("{reallocations} reallocations for 7 pushes, {} of them moved the bytes to a new block", blocks.len() - 1));
+    reporter.message("");
+
+    let mut sized = String::with_capacity(35); // The final size, asked for up front
+    let start = sized.as_ptr();
+    for _ in 0..7 {
+        sized.push_str("hello");
+    }
+    reporter.message(&format!("with_capacity(35): len {}, capacity {}, the same block all along: {}", sized.len(), sized.capacity(), sized.as_ptr() == start));
+
+    let mut small = String::with_capacity(8);
+    small.push_str("hello");
+    for line in Diagram::new().string("small", &small).render() {
+        reporter.message(&line);
+    }
+    reporter.message("the three empty cells are capacity, pushing up to 3 more bytes won't reallocate");
+    reporter.message("");
+
+    let mut my_mut_string = String::from("Hello world"); // Exactly full, so the push has to reallocate
+    let (len, capacity) = (my_mut_string.len(), my_mut_string.capacity());
+    reporter.event(&OwnershipEvent::borrowed_mut("my_mut_string", "my_mut_string"));
+    reporter.output(&borrowing::mutate_reference(&mut my_mut_string));
+    reporter.message(&format!("before: len {len}, capacity {capacity}, full. after: len {}, capacity {}", my_mut_string.len(), my_mut_string.capacity()));
+    Ok(())
+}
+
+const SLICE_ACROSS_PUSH: (&str, &str) = (
+    "let mut s = String::from(\"hello\");\nlet word = &s[..5];\ns.push_str(\" world\");\nprintln!(\"{word}\");",
+    "error[E0502]: cannot borrow `s` as mutable because it is also borrowed as immutable\n --> src/main.rs:3:1\n  |\n2 | let word = &s[..5];\n  |             - immutable borrow occurs here\n3 | s.push_str(\" world\");\n  | ^^^^^^^^^^^^^^^^^^^^ mutable borrow occurs here\n4 | println!(\"{word}\");\n  |            ---- immutable borrow later used here",
+);
+
+// What rustc rejects, then the same push made with the slice ended first, showing where the bytes went
+pub fn reallocation_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let (code, error) = SLICE_ACROSS_PUSH;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    let mut blocks = Vec::new();
+    let mut s = String::from("hello");
+    let _neighbour = String::from("next door"); // Takes the memory after s's block, so it likely can't grow in place
+    let word = &s[..5];
+    reporter.event(&OwnershipEvent::borrowed("s", "word"));
+    let word_at = block(&mut blocks, word.as_ptr()); // Only the letter is kept, not the borrow
+    reporter.message(&format!("word: {word}, points into block {word_at}, s: len {}, capacity {}, full", s.len(), s.capacity()));
+
+    s.push_str(", world and everyone in it"); // word was last used above, so this &mut is allowed
+    reporter.event(&OwnershipEvent::borrowed_mut("s", "push_str"));
+    let now_at = block(&mut blocks, s.as_ptr());
+    reporter.message(&format!("after push_str: len {}, capacity {}, s's bytes are in block {now_at}", s.len(), s.capacity()));
+    if now_at == word_at {
+        reporter.message("this time the allocator grew the block in place, rustc can't know that ahead of time, so it's rejected all the same");
+    } else {
+        reporter.message(&format!("block {word_at} was freed by the push, a word still around would point at freed memory"));
+    }
+    reporter.message("");
+
+    let mut s = String::from("hello");
+    let end = s.len(); // Where the word ends, an index rather than a reference, so nothing to invalidate
+    s.push_str(", world and everyone in it");
+    reporter.message(&format!("keep an index instead: end {end}, &s[..end] after the push: {}", &s[..end]));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::DemoOutput;
+
+    #[test]
+    fn block_letters_follow_first_sight() {
+        let mut blocks = Vec::new();
+        let letters: String = [0x10, 0x20, 0x10, 0x30, 0x20].iter().map(|&address| block(&mut blocks, address as *const u8)).collect();
+        assert_eq!(letters, "ABACB");
+    }
+
+    // The block letters and growth in between depend on the allocator, so these stick to what std promises
+    #[test]
+    fn string_capacity_demo_grows_from_nothing() {
+        let mut output = DemoOutput::new();
+        string_capacity_demo(&mut output).unwrap();
+        assert_eq!(output.messages[..2], ["String::new(): len 0, capacity 0", "push_str  len  capacity  block"]);
+        assert!(output.messages[2].ends_with("A, allocated"));
+        assert!(output.messages.contains(&"with_capacity(35): len 35, capacity 35, the same block all along: true".to_string()));
+        assert!(output.messages.iter().any(|message| message.starts_with("before: len 11, capacity 11, full. after: len 25")));
+        assert_eq!(output.values, [("my_mut_string".to_string(), "Hello world(added) world!".to_string())]);
+    }
+
+    #[test]
+    fn reallocation_demo_pushes_once_the_slice_is_done() {
+        let mut output = DemoOutput::new();
+        reallocation_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0502]")));
+        assert!(output.messages.contains(&"word: hello, points into block A, s: len 5, capacity 5, full".to_string()));
+        assert_eq!(output.messages.last().unwrap(), "keep an index instead: end 5, &s[..end] after the push: hello");
+        assert_eq!(output.events, [OwnershipEvent::borrowed("s", "word"), OwnershipEvent::borrowed_mut("s", "push_str")]);
+    }
+}
@@ -0,0 +1,82 @@
+use std::env;
+use std::io::{self, IsTerminal};
+
+// What a piece of text is, which decides its color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Heading,
+    Code,
+    Event,
+    Error,
+    Success,
+    Dim,
+}
+
+impl Role {
+    fn code(self) -> &'static str {
+        match self {
+            Role::Heading => "1;34", // Bold blue
+            Role::Code => "36",      // Cyan
+            Role::Event => "33",     // Yellow
+            Role::Error => "1;31",   // Bold red
+            Role::Success => "32",   // Green
+            Role::Dim => "2",
+        }
+    }
+}
+
+// Adds ANSI colors to text, or passes it through untouched when colors are off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    enabled: bool,
+}
+
+impl Style {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    // No colors at all
+    pub fn plain() -> Self {
+        Self::new(false)
+    }
+
+    // Colors on stdout only when it's a terminal, `NO_COLOR` isn't set (https://no-color.org) and `no_color` is false
+    pub fn detect(no_color: bool) -> Self {
+        let no_color_env = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Self::new(!no_color && !no_color_env && io::stdout().is_terminal())
+    }
+
+    // The same choice for stderr, which may be redirected when stdout isn't
+    pub fn for_stderr(self) -> Self {
+        Self::new(self.enabled && io::stderr().is_terminal())
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn paint(&self, role: Role, text: &str) -> String {
+        if self.enabled {
+            format!("\x1b[{}m{text}\x1b[0m", role.code())
+        } else {
+            text.to_string()
+        }
+    }
+
+    // Colors the `backticked` parts of a sentence as code, keeping the backticks only when colors are off
+    pub fn code_spans(&self, text: &str) -> String {
+        if !self.enabled {
+            return text.to_string();
+        }
+        let mut styled = String::new();
+        for (index, part) in text.split('`').enumerate() {
+            if index % 2 == 1 {
+                styled.push_str(&self.paint(Role::Code, part));
+            } else {
+                styled.push_str(part);
+            }
+        }
+        styled
+    }
+}
@@ -0,0 +1,207 @@
+use std::sync::Arc;
+use std::thread;
+
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::OwnershipEvent;
+use crate::report::Reporter;
+use crate::runner;
+
+// A spawned thread can outlive its spawner, so its closure has to own what it uses (`move`), values several threads
+// share go in an Arc, and a `&mut` on each of two threads is the data race the borrow checker rules out
+
+pub const TAUGHT: Taught = Taught { day: 12, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(thread_move_demo {
+        name: "thread_move",
+        summary: "thread::spawn needs a move closure, the thread owns what it uses",
+        topics: &["concurrency", "closures", "ownership"],
+        prerequisites: &["closure_captures"],
+        explanation: "`thread::spawn` takes a closure that must be `'static`: the thread might still be running when the function that spawned it returns, so a closure borrowing one of its locals is rejected (E0373). `move` makes the closure own its captures, a String moves into the thread and can't be used after, a Copy value is copied. `join()` waits for the thread and hands back what its closure returned, which is how ownership comes back. `thread::scope` is the exception: its threads are all joined before the scope ends, so they may borrow.",
+    }),
+    lesson!(arc_shared_demo {
+        name: "arc_shared",
+        summary: "Arc shares one value between threads, Rc isn't allowed to",
+        topics: &["concurrency", "smart-pointers"],
+        prerequisites: &["thread_move", "rc_scopes"],
+        explanation: "`Arc<T>` is Rc with an atomic strong count, so clones can be made and dropped on different threads at once without the count going wrong. Rc doesn't do that, so it isn't `Send`, and moving one into a thread is rejected (E0277). Each thread gets its own `Arc::clone`, moved into its closure, and they all point at the same value. Like Rc, an Arc only hands out `&T`: the threads can read the value together, none of them can change it.",
+    }),
+    lesson!(race_conditions_demo {
+        name: "race_conditions",
+        summary: "One &mut at a time is what stops two threads changing the same value",
+        topics: &["concurrency", "borrowing"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["arc_shared", "mutate_reference"],
+        explanation: "A data race is two threads touching the same memory at once with at least one of them writing. Two scoped threads that both do `count += 1` each need a `&mut count`, and two mutable borrows at once is rejected (E0499), the same rule as on one thread. What's allowed is giving each thread a `&mut` to a different part: `iter_mut()` or `chunks_mut()` split a slice into pieces that don't overlap. Changing the one same value from several threads takes a lock, a Mutex, which hands out the `&mut` to one thread at a time.",
+    }),
+];
+
+// Code that rustc rejects and what it says, cut short before the notes
+#[track_caller] // So `show` puts the rejection next to the demo line that reported it
+fn report_rejected(reporter: &mut dyn Reporter, code: &str, error: &str) {
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+}
+
+const SPAWN_BORROWS: (&str, &str) = (
+    "let name = String::from(\"ferris\");\nlet handle = thread::spawn(|| {\n    println!(\"hello, {name}\");\n});\nhandle.join().unwrap();",
+    "error[E0373]: closure may outlive the current function, but it borrows `name`, which is owned by the current function\n --> src/main.rs:2:28\n  |\n2 | let handle = thread::spawn(|| {\n  |                            ^^ may outlive borrowed value `name`\n3 |     println!(\"hello, {name}\");\n  |                       ---- `name` is borrowed here",
+);
+
+// The rejected borrow, then a String moved into a thread and back out through join, a copied i32 and a scoped borrow
+pub fn thread_move_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    report_rejected(reporter, SPAWN_BORROWS.0, SPAWN_BORROWS.1);
+
+    let name = String::from("ferris");
+    let handle = thread::spawn(move || {
+        // name is owned by the thread now
+        let greeting = format!("hello from a thread, {name}");
+        (name, greeting)
+    });
+    reporter.event(&OwnershipEvent::moved("name", "thread"));
+    reporter.message("name: error");
+    let (name, greeting) = handle.join().map_err(runner::panicked)?; // The thread's return value moves back to us
+    reporter.event(&OwnershipEvent::moved("thread", "name"));
+    reporter.message(&format!("{greeting}, name is back: {name}"));
+
+    let count = 21;
+    let doubled = thread::spawn(move || count * 2).join().map_err(runner::panicked)?; // An i32 is Copy, the thread gets a copy
+    reporter.event(&OwnershipEvent::copied("count", "thread"));
+    reporter.message(&format!("doubled: {doubled}, count is still {count}"));
+
+    let len = thread::scope(|scope| scope.spawn(|| name.len()).join()).map_err(runner::panicked)?; // Joined before the scope ends, so borrowing is fine
+    reporter.event(&OwnershipEvent::borrowed("name", "scoped thread"));
+    reporter.message(&format!("a scoped thread borrowed name, len: {len}, name: {name}"));
+    Ok(())
+}
+
+const RC_ACROSS_THREADS: (&str, &str) = (
+    "let shared = Rc::new(String::from(\"ferris\"));\nlet copy = Rc::clone(&shared);\nthread::spawn(move || println!(\"{copy}\"));",
+    "error[E0277]: `Rc<String>` cannot be sent between threads safely\n --> src/main.rs:3:15\n  |\n3 | thread::spawn(move || println!(\"{copy}\"));\n  | ------------- -------^^^^^^^^^^^^^^^^^^^\n  | |             |\n  | |             `Rc<String>` cannot be sent between threads safely\n  | |             within this `{closure@src/main.rs:3:15: 3:22}`\n  | required by a bound introduced by this call",
+);
+
+// Rc rejected, then three threads reading one Vec through their own Arc clones
+pub fn arc_shared_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    report_rejected(reporter, RC_ACROSS_THREADS.0, RC_ACROSS_THREADS.1);
+
+    let names = Arc::new(vec![String::from("ferris"), String::from("corro"), String::from("rusty")]);
+    let mut handles = Vec::new();
+    for index in 0..names.len() {
+        let shared = Arc::clone(&names); // One more owner, moved into the thread below
+        reporter.event(&OwnershipEvent::moved("shared", &format!("thread {index}")));
+        handles.push(thread::spawn(move || format!("thread {index} reads {} of {} names", shared[index], shared.len())));
+    }
+    for handle in handles {
+        // Joined in order, so the lines come out the same every run
+        reporter.message(&handle.join().map_err(runner::panicked)?);
+    }
+    reporter.message(&format!("every thread has finished and dropped its clone, strong_count = {}", Arc::strong_count(&names)));
+    Ok(())
+}
+
+const TWO_THREADS_ONE_COUNT: (&str, &str) = (
+    "let mut count = 0;\nthread::scope(|s| {\n    s.spawn(|| count += 1);\n    s.spawn(|| count += 1);\n});",
+    "error[E0499]: cannot borrow `count` as mutable more than once at a time\n --> src/main.rs:4:13\n  |\n2 | thread::scope(|s| {\n  |                - has type `&'1 Scope<'1, '_>`\n3 |     s.spawn(|| count += 1);\n  |     ----------------------\n  |     |       |  |\n  |     |       |  first borrow occurs due to use of `count` in closure\n  |     |       first mutable borrow occurs here\n  |     argument requires that `count` is borrowed for `'1`\n4 |     s.spawn(|| count += 1);\n  |             ^^ ----- second borrow occurs due to use of `count` in closure\n  |             |\n  |             second mutable borrow occurs here",
+);
+
+// The race rustc rejects, then threads that each get a &mut to their own part of a slice
+pub fn race_conditions_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    report_rejected(reporter, TWO_THREADS_ONE_COUNT.0, TWO_THREADS_ONE_COUNT.1);
+
+    let mut counts = [0; 4];
+    thread::scope(|scope| {
+        for (index, count) in counts.iter_mut().enumerate() {
+            // Each count is a different &mut, so no two threads touch the same one
+            scope.spawn(move || *count += (index + 1) * 10);
+        }
+    });
+    reporter.event(&OwnershipEvent::borrowed_mut("counts[i]", "thread i"));
+    reporter.message(&format!("one &mut each: {counts:?}"));
+
+    let mut numbers: Vec<u32> = (1..=8).collect();
+    thread::scope(|scope| {
+        for chunk in numbers.chunks_mut(3) {
+            // A &mut [u32] of up to three numbers per thread, the chunks don't overlap
+            scope.spawn(move || chunk.iter_mut().for_each(|number| *number *= *number));
+        }
+    });
+    reporter.event(&OwnershipEvent::borrowed_mut("numbers", "chunks_mut"));
+    reporter.message(&format!("squared in chunks of three: {numbers:?}"));
+    reporter.message("changing one value from several threads needs a lock, a Mutex hands out its &mut one thread at a time");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::DemoOutput;
+
+    // The messages after the rejected code and its blank line
+    fn after_rejection(output: &DemoOutput) -> &[String] {
+        let blank = output.messages.iter().position(String::is_empty).unwrap();
+        &output.messages[blank + 1..]
+    }
+
+    #[test]
+    fn report_rejected_indents_the_code_and_ends_with_a_blank_line() {
+        let mut output = DemoOutput::new();
+        report_rejected(&mut output, "let a = 1;\nlet b = a;", "error: nope");
+        assert_eq!(output.messages, ["    let a = 1;", "    let b = a;", "error: nope", ""]);
+    }
+
+    #[test]
+    fn thread_move_demo_moves_name_there_and_back() {
+        let mut output = DemoOutput::new();
+        thread_move_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0373]")));
+        assert_eq!(
+            after_rejection(&output),
+            [
+                "name: error",
+                "hello from a thread, ferris, name is back: ferris",
+                "doubled: 42, count is still 21",
+                "a scoped thread borrowed name, len: 6, name: ferris",
+            ]
+        );
+        assert_eq!(
+            output.events,
+            [
+                OwnershipEvent::moved("name", "thread"),
+                OwnershipEvent::moved("thread", "name"),
+                OwnershipEvent::copied("count", "thread"),
+                OwnershipEvent::borrowed("name", "scoped thread"),
+            ]
+        );
+    }
+
+    #[test]
+    fn arc_shared_demo_reads_in_join_order() {
+        let mut output = DemoOutput::new();
+        arc_shared_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0277]")));
+        assert_eq!(
+            after_rejection(&output),
+            [
+                "thread 0 reads ferris of 3 names",
+                "thread 1 reads corro of 3 names",
+                "thread 2 reads rusty of 3 names",
+                "every thread has finished and dropped its clone, strong_count = 1",
+            ]
+        );
+        assert_eq!(output.events.len(), 3);
+    }
+
+    #[test]
+    fn race_conditions_demo_gives_each_thread_its_own_part() {
+        let mut output = DemoOutput::new();
+        race_conditions_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0499]")));
+        assert_eq!(after_rejection(&output)[..2], ["one &mut each: [10, 20, 30, 40]", "squared in chunks of three: [1, 4, 9, 16, 25, 36, 49, 64]"]);
+    }
+}
@@ -0,0 +1,453 @@
+use crate::error::LessonError;
+
+// The part of TOML the exercise manifest, the config file and the quiz data need: `key = value` pairs, `[table]`
+// and `[[array.of.tables]]` headers, strings (multi-line ones too), integers, booleans and arrays. No dotted keys,
+// dates or inline tables.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Bool(bool),
+    Array(Vec<Value>),
+    Table(Table),
+}
+
+// Keys keep the order they appear in
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Table(pub Vec<(String, Value)>);
+
+impl Table {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0.iter().find(|(name, _)| name == key).map(|(_, value)| value)
+    }
+
+    fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.0.iter_mut().find(|(name, _)| name == key).map(|(_, value)| value)
+    }
+
+    pub fn str(&self, key: &str) -> Option<&str> {
+        match self.get(key)? {
+            Value::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn bool(&self, key: &str) -> Option<bool> {
+        match self.get(key)? {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn integer(&self, key: &str) -> Option<i64> {
+        match self.get(key)? {
+            Value::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn table(&self, key: &str) -> Option<&Table> {
+        match self.get(key)? {
+            Value::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    // The strings in an array, None if it isn't an array of strings
+    pub fn strings(&self, key: &str) -> Option<Vec<&str>> {
+        match self.get(key)? {
+            Value::Array(items) => items
+                .iter()
+                .map(|item| match item {
+                    Value::String(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+
+    // The tables of a `[[key]]` array, empty if there are none
+    pub fn tables(&self, key: &str) -> Vec<&Table> {
+        match self.get(key) {
+            Some(Value::Array(items)) => items
+                .iter()
+                .filter_map(|item| match item {
+                    Value::Table(table) => Some(table),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+// Parses a whole document into its root table
+pub fn parse(text: &str) -> Result<Table, LessonError> {
+    let mut parser = Parser { chars: text.chars().collect(), pos: 0, line: 1 };
+    let mut root = Table::default();
+    // Where `key = value` lines go, the path of the last header
+    let mut current: Vec<String> = Vec::new();
+
+    loop {
+        parser.skip_blank_lines();
+        let Some(c) = parser.peek() else { break };
+        if c == '[' {
+            parser.pos += 1;
+            let array = parser.eat('[');
+            let path = parser.header_path()?;
+            if !parser.eat(']') || (array && !parser.eat(']')) {
+                return Err(parser.error("expected `]` to close the header"));
+            }
+            open_header(&mut root, &path, array).map_err(|message| parser.error(&message))?;
+            current = path;
+        } else {
+            let key = parser.key()?;
+            parser.skip_spaces();
+            if !parser.eat('=') {
+                return Err(parser.error(&format!("expected `=` after `{key}`")));
+            }
+            parser.skip_spaces();
+            let value = parser.value()?;
+            let table = table_at(&mut root, &current).map_err(|message| parser.error(&message))?;
+            if table.get(&key).is_some() {
+                return Err(parser.error(&format!("`{key}` is set twice")));
+            }
+            table.0.push((key, value));
+        }
+        parser.end_of_line()?;
+    }
+    Ok(root)
+}
+
+// Creates the table a header names, for `[[name]]` by adding another table to the array
+fn open_header(root: &mut Table, path: &[String], array: bool) -> Result<(), String> {
+    let (last, parents) = path.split_last().expect("headers have at least one key");
+    let parent = table_at(root, parents)?;
+    match (parent.get_mut(last), array) {
+        (None, false) => parent.0.push((last.clone(), Value::Table(Table::default()))),
+        (None, true) => parent.0.push((last.clone(), Value::Array(vec![Value::Table(Table::default())]))),
+        (Some(Value::Array(items)), true) => items.push(Value::Table(Table::default())),
+        _ => return Err(format!("`{}` is defined twice", path.join("."))),
+    }
+    Ok(())
+}
+
+// The table at `path`, the last one of an array of tables, creating plain tables as needed
+fn table_at<'a>(root: &'a mut Table, path: &[String]) -> Result<&'a mut Table, String> {
+    let mut table = root;
+    for (depth, key) in path.iter().enumerate() {
+        if table.get(key).is_none() {
+            table.0.push((key.clone(), Value::Table(Table::default())));
+        }
+        table = match table.get_mut(key) {
+            Some(Value::Table(child)) => child,
+            Some(Value::Array(items)) => match items.last_mut() {
+                Some(Value::Table(child)) => child,
+                _ => return Err(format!("`{}` is not a table", path[..=depth].join("."))), // `x = [1]` then `[x.y]`
+            },
+            _ => return Err(format!("`{}` is not a table", path[..=depth].join("."))),
+        };
+    }
+    Ok(table)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn error(&self, message: &str) -> LessonError {
+        LessonError::TomlParse { line: self.line, message: message.to_string() }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        if self.peek() == Some('#') {
+            while !matches!(self.peek(), None | Some('\n')) {
+                self.pos += 1;
+            }
+        }
+    }
+
+    // Spaces, comments and newlines, e.g. between items of a multi-line array
+    fn skip_blank_lines(&mut self) {
+        loop {
+            self.skip_spaces();
+            self.skip_comment();
+            match self.peek() {
+                Some('\n') => {
+                    self.pos += 1;
+                    self.line += 1;
+                }
+                Some('\r') => self.pos += 1,
+                _ => return,
+            }
+        }
+    }
+
+    // Only a comment may follow a value or header on its line
+    fn end_of_line(&mut self) -> Result<(), LessonError> {
+        self.skip_spaces();
+        self.skip_comment();
+        self.eat('\r');
+        match self.peek() {
+            None => Ok(()),
+            Some('\n') => {
+                self.pos += 1;
+                self.line += 1;
+                Ok(())
+            }
+            Some(c) => Err(self.error(&format!("unexpected `{c}` after the value"))),
+        }
+    }
+
+    fn key(&mut self) -> Result<String, LessonError> {
+        if self.peek() == Some('"') {
+            return self.basic_string();
+        }
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(self.error("expected a key"));
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    // `a.b.c` inside a header
+    fn header_path(&mut self) -> Result<Vec<String>, LessonError> {
+        let mut path = Vec::new();
+        loop {
+            self.skip_spaces();
+            path.push(self.key()?);
+            self.skip_spaces();
+            if !self.eat('.') {
+                return Ok(path);
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, LessonError> {
+        match self.peek() {
+            Some('"') => Ok(Value::String(self.basic_string()?)),
+            Some('\'') if self.chars[self.pos..].starts_with(&['\''; 3]) => Ok(Value::String(self.multiline_literal_string()?)),
+            Some('\'') => Ok(Value::String(self.literal_string()?)),
+            Some('[') => self.array(),
+            Some('t' | 'f') => {
+                let word = self.key()?;
+                match word.as_str() {
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    _ => Err(self.error(&format!("`{word}` isn't a value, strings need quotes"))),
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '+' => {
+                let start = self.pos;
+                self.pos += 1;
+                while matches!(self.peek(), Some(c) if c.is_ascii_digit() || c == '_') {
+                    self.pos += 1;
+                }
+                let number: String = self.chars[start..self.pos].iter().filter(|&&c| c != '_').collect();
+                number.parse().map(Value::Integer).map_err(|_| self.error(&format!("`{number}` isn't a whole number")))
+            }
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn basic_string(&mut self) -> Result<String, LessonError> {
+        self.pos += 1; // Opening quote
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                None | Some('\n') => return Err(self.error("string is missing its closing `\"`")),
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(text);
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    let escaped = match self.peek() {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some(c) => return Err(self.error(&format!("unknown escape `\\{c}`"))),
+                        None => return Err(self.error("string is missing its closing `\"`")),
+                    };
+                    text.push(escaped);
+                    self.pos += 1;
+                }
+                Some(c) => {
+                    text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    // 'single quoted', no escapes at all, handy for paths and backslashes
+    fn literal_string(&mut self) -> Result<String, LessonError> {
+        self.pos += 1;
+        let start = self.pos;
+        while !matches!(self.peek(), None | Some('\'' | '\n')) {
+            self.pos += 1;
+        }
+        if !self.eat('\'') {
+            return Err(self.error("string is missing its closing `'`"));
+        }
+        Ok(self.chars[start..self.pos - 1].iter().collect())
+    }
+
+    // '''several lines''', no escapes either, for code. A newline straight after the opening quotes isn't part of
+    // the string.
+    fn multiline_literal_string(&mut self) -> Result<String, LessonError> {
+        let start_line = self.line;
+        self.pos += 3;
+        self.eat('\r');
+        if self.eat('\n') {
+            self.line += 1;
+        }
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                None => {
+                    self.line = start_line;
+                    return Err(self.error("string is missing its closing `'''`"));
+                }
+                Some('\'') if self.chars[self.pos..].starts_with(&['\''; 3]) => {
+                    let text = self.chars[start..self.pos].iter().collect();
+                    self.pos += 3;
+                    return Ok(text);
+                }
+                Some(c) => {
+                    if c == '\n' {
+                        self.line += 1;
+                    }
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    // Items may be spread over several lines, with a trailing comma
+    fn array(&mut self) -> Result<Value, LessonError> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        loop {
+            self.skip_blank_lines();
+            if self.eat(']') {
+                return Ok(Value::Array(items));
+            }
+            items.push(self.value()?);
+            self.skip_blank_lines();
+            if self.eat(']') {
+                return Ok(Value::Array(items));
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected `,` or `]` in the array"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The message of a TomlParse error, and the line it was on
+    fn parse_error(text: &str) -> (usize, String) {
+        match parse(text) {
+            Err(LessonError::TomlParse { line, message }) => (line, message),
+            other => panic!("expected a TomlParse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn header_opens_a_table() {
+        let root = parse("name = \"ferris\"\n\n[display]\ncolor = true\nwidth = 80\n").unwrap();
+        assert_eq!(root.str("name"), Some("ferris"));
+        let display = root.table("display").unwrap();
+        assert_eq!(display.bool("color"), Some(true));
+        assert_eq!(display.integer("width"), Some(80));
+    }
+
+    #[test]
+    fn dotted_header_opens_nested_tables() {
+        let root = parse("[a.b]\nkey = 'value'\n").unwrap();
+        assert_eq!(root.table("a").and_then(|a| a.table("b")).and_then(|b| b.str("key")), Some("value"));
+    }
+
+    #[test]
+    fn array_of_tables_adds_one_table_per_header() {
+        let root = parse("[[exercise]]\nname = \"move1\"\n\n[[exercise]]\nname = \"move2\"\ntopics = [\"ownership\", \"moves\",]\n").unwrap();
+        let exercises = root.tables("exercise");
+        assert_eq!(exercises.len(), 2);
+        assert_eq!(exercises[0].str("name"), Some("move1"));
+        assert_eq!(exercises[1].str("name"), Some("move2"));
+        assert_eq!(exercises[1].strings("topics"), Some(vec!["ownership", "moves"]));
+    }
+
+    #[test]
+    fn sub_table_goes_in_the_last_array_entry() {
+        let root = parse("[[exercise]]\nname = \"one\"\n[[exercise]]\nname = \"two\"\n[exercise.hint]\ntext = \"look\"\n").unwrap();
+        let exercises = root.tables("exercise");
+        assert!(exercises[0].table("hint").is_none());
+        assert_eq!(exercises[1].table("hint").and_then(|hint| hint.str("text")), Some("look"));
+    }
+
+    #[test]
+    fn multiline_string_keeps_its_lines() {
+        let root = parse("code = '''\nfn main() {\n    println!(\"hi\");\n}\n'''\nafter = 1\n").unwrap();
+        assert_eq!(root.str("code"), Some("fn main() {\n    println!(\"hi\");\n}\n"));
+        assert_eq!(root.integer("after"), Some(1));
+    }
+
+    #[test]
+    fn header_under_a_value_is_an_error() {
+        assert_eq!(parse_error("color = true\n[color.x]\n"), (2, "`color` is not a table".to_string()));
+    }
+
+    #[test]
+    fn header_under_an_array_of_values_is_an_error() {
+        assert_eq!(parse_error("x = [1]\n[x.y]\n"), (2, "`x` is not a table".to_string()));
+        assert_eq!(parse_error("x = []\n[x.y]\n"), (2, "`x` is not a table".to_string()));
+    }
+
+    #[test]
+    fn deeper_header_under_a_value_is_an_error() {
+        assert_eq!(parse_error("[a]\nb = 1\n[a.b.c]\nd = 2\n"), (3, "`a.b` is not a table".to_string()));
+    }
+
+    #[test]
+    fn table_defined_twice_is_an_error() {
+        assert_eq!(parse_error("[a]\n[a]\n"), (2, "`a` is defined twice".to_string()));
+    }
+
+    #[test]
+    fn key_set_twice_is_an_error() {
+        assert_eq!(parse_error("a = 1\na = 2\n"), (2, "`a` is set twice".to_string()));
+    }
+}
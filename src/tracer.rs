@@ -0,0 +1,479 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::error::LessonError;
+use crate::json::{Json, ToJson};
+use crate::lesson::Lesson;
+use crate::output::OwnershipEvent;
+use crate::report::Reporter;
+use crate::style::{Role, Style};
+
+// Passes everything on to another reporter and keeps each lesson's ownership events in `Traces`, for a
+// Timeline once the lesson is done
+pub struct OwnershipTracer<R: Reporter> {
+    inner: R,
+    traces: Traces,
+}
+
+// The events of every lesson traced so far, clones share them so one can be handed to a tracer and the other
+// read afterwards
+#[derive(Debug, Clone, Default)]
+pub struct Traces(Rc<RefCell<Vec<Trace>>>);
+
+// (lesson name, its events in order)
+pub type Trace = (String, Vec<OwnershipEvent>);
+
+impl Traces {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Every lesson traced so far, in the order they ran
+    pub fn lessons(&self) -> Vec<Trace> {
+        self.0.borrow().clone()
+    }
+}
+
+impl<R: Reporter> OwnershipTracer<R> {
+    pub fn new(inner: R, traces: Traces) -> Self {
+        Self { inner, traces }
+    }
+}
+
+impl<R: Reporter> Reporter for OwnershipTracer<R> {
+    fn begin_lesson(&mut self, lesson: &dyn Lesson) {
+        self.traces.0.borrow_mut().push((lesson.name().to_string(), Vec::new()));
+        self.inner.begin_lesson(lesson);
+    }
+
+    fn heading(&mut self, lesson: &dyn Lesson) {
+        self.inner.heading(lesson);
+    }
+
+    fn message(&mut self, message: &str) {
+        self.inner.message(message);
+    }
+
+    fn value(&mut self, name: &str, value: &str) {
+        self.inner.value(name, value);
+    }
+
+    fn warning(&mut self, message: &str) {
+        self.inner.warning(message);
+    }
+
+    fn event(&mut self, event: &OwnershipEvent) {
+        if let Some((_, events)) = self.traces.0.borrow_mut().last_mut() {
+            events.push(event.clone());
+        }
+        self.inner.event(event);
+    }
+
+    fn end_lesson(&mut self, lesson: &dyn Lesson) {
+        self.inner.end_lesson(lesson);
+    }
+
+    fn finish(&mut self) -> Result<(), LessonError> {
+        self.inner.finish()
+    }
+}
+
+// What a variable's lane shows at one step of a Timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    // Not an owner (yet, or anymore)
+    Empty,
+    // Owns its value, nothing happened to it this step
+    Alive,
+    // Became an owner, by a move, copy or clone into it
+    Owns,
+    MovedOut,
+    CopiedFrom,
+    ClonedFrom,
+    Borrowed,
+    BorrowedMut,
+    // Is a reference, taken this step
+    Borrows,
+    Dropped,
+}
+
+impl Cell {
+    fn as_str(self) -> &'static str {
+        match self {
+            Cell::Empty => "empty",
+            Cell::Alive => "alive",
+            Cell::Owns => "owns",
+            Cell::MovedOut => "moved_out",
+            Cell::CopiedFrom => "copied_from",
+            Cell::ClonedFrom => "cloned_from",
+            Cell::Borrowed => "borrowed",
+            Cell::BorrowedMut => "borrowed_mut",
+            Cell::Borrows => "borrows",
+            Cell::Dropped => "dropped",
+        }
+    }
+
+    // Four columns wide, so the lanes line up
+    fn glyph(self) -> &'static str {
+        match self {
+            Cell::Empty => "    ",
+            Cell::Alive => "────",
+            Cell::Owns => "●───",
+            Cell::MovedOut => "──▶ ",
+            Cell::CopiedFrom => "─c──",
+            Cell::ClonedFrom => "─+──",
+            Cell::Borrowed => "─&──",
+            Cell::BorrowedMut => "─&m─",
+            Cell::Borrows => "◆   ",
+            Cell::Dropped => "──✗ ",
+        }
+    }
+}
+
+// A lesson's ownership events as a lane per variable and a column per event, e.g. for take_ownership
+//
+//     step     1   2   3   4
+//     s1       ──▶
+//     s2       ●────+──────────
+//     s3           ●─────▶
+//     complex          ●─────✗
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timeline {
+    pub events: Vec<OwnershipEvent>,
+    // (variable, a cell per event), in the order the variables first come up
+    pub lanes: Vec<(String, Vec<Cell>)>,
+}
+
+impl Timeline {
+    // Calls and returns aren't about any one variable, they're left out
+    pub fn new(events: &[OwnershipEvent]) -> Self {
+        let events: Vec<OwnershipEvent> = events.iter().filter(|event| !effects(event).is_empty()).cloned().collect();
+        let mut lanes: Vec<(String, Vec<Cell>)> = Vec::new();
+        // A variable whose first event is giving something away owned a value from before the trace started
+        let mut alive: Vec<bool> = Vec::new();
+        for (step, event) in events.iter().enumerate() {
+            for (variable, cell) in effects(event) {
+                let index = match lanes.iter().position(|(name, _)| name == variable) {
+                    Some(index) => index,
+                    None => {
+                        lanes.push((variable.to_string(), vec![Cell::Empty; step]));
+                        let owned_before = !matches!(cell, Cell::Owns | Cell::Borrows);
+                        alive.push(owned_before);
+                        if owned_before {
+                            lanes.last_mut().unwrap().1.fill(Cell::Alive);
+                        }
+                        lanes.len() - 1
+                    }
+                };
+                // Borrowing from itself, e.g. a parameter named like the variable it borrows, shows once
+                let cells = &mut lanes[index].1;
+                if cells.len() > step {
+                    continue;
+                }
+                cells.push(cell);
+                alive[index] = !matches!(cell, Cell::MovedOut | Cell::Dropped | Cell::Borrows);
+            }
+            for ((_, cells), alive) in lanes.iter_mut().zip(&alive) {
+                if cells.len() == step {
+                    cells.push(if *alive { Cell::Alive } else { Cell::Empty });
+                }
+            }
+        }
+        Self { events, lanes }
+    }
+
+    // Where each variable stands after the last event, its latest cell that isn't Empty. A variable that comes
+    // back, like a parameter in a second call, shows how it is now.
+    pub fn states(&self) -> Vec<(&str, Cell)> {
+        self.lanes
+            .iter()
+            .filter_map(|(name, cells)| cells.iter().rev().find(|cell| **cell != Cell::Empty).map(|cell| (name.as_str(), *cell)))
+            .collect()
+    }
+
+    // The lanes, then what happened at each step
+    pub fn render(&self, style: Style) -> String {
+        let width = self.lanes.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0).max("step".len());
+        let mut text = String::new();
+        let steps: String = (1..=self.events.len()).map(|step| format!("{step:<4}")).collect();
+        text.push_str(&style.paint(Role::Dim, &format!("{:<width$}  {}", "step", steps.trim_end())));
+        text.push('\n');
+        for (name, cells) in &self.lanes {
+            let lane: String = cells
+                .iter()
+                .map(|cell| match cell {
+                    Cell::Dropped => style.paint(Role::Error, cell.glyph()),
+                    Cell::Empty | Cell::Alive => cell.glyph().to_string(),
+                    _ => style.paint(Role::Event, cell.glyph()),
+                })
+                .collect();
+            text.push_str(&format!("{name:<width$}  {}\n", lane.trim_end()));
+        }
+        text.push('\n');
+        for (step, event) in self.events.iter().enumerate() {
+            text.push_str(&format!("{:>3}  {event}\n", step + 1));
+        }
+        text
+    }
+}
+
+// (variable, what its lane shows) for everyone an event involves
+fn effects(event: &OwnershipEvent) -> Vec<(&str, Cell)> {
+    match event {
+        OwnershipEvent::Copy { from, to } => vec![(from, Cell::CopiedFrom), (to, Cell::Owns)],
+        OwnershipEvent::Move { from, to } => vec![(from, Cell::MovedOut), (to, Cell::Owns)],
+        OwnershipEvent::Clone { from, to } => vec![(from, Cell::ClonedFrom), (to, Cell::Owns)],
+        OwnershipEvent::Borrow { owner, by } => vec![(owner, Cell::Borrowed), (by, Cell::Borrows)],
+        OwnershipEvent::BorrowMut { owner, by } => vec![(owner, Cell::BorrowedMut), (by, Cell::Borrows)],
+        OwnershipEvent::Drop { owner } => vec![(owner, Cell::Dropped)],
+        OwnershipEvent::Call { .. } | OwnershipEvent::Return { .. } => vec![],
+    }
+    .into_iter()
+    .map(|(variable, cell)| (variable.as_str(), cell))
+    .collect()
+}
+
+impl ToJson for Timeline {
+    fn to_json(&self) -> Json {
+        let lanes = self.lanes.iter().map(|(variable, cells)| {
+            Json::object([("variable", variable.into()), ("cells", Json::array(cells.iter().map(|cell| cell.as_str())))])
+        });
+        Json::object([("events", Json::array(self.events.iter().map(ToJson::to_json))), ("lanes", Json::array(lanes))])
+    }
+}
+
+// A Graphviz digraph of a lesson's events for `export dot`: a node per variable, an edge per copy, move and
+// clone from the old owner to the new one, and a dotted edge from each reference to what it borrows. Owners
+// that were dropped are greyed out, ones that moved their value away are dashed.
+pub fn dot(lesson: &str, events: &[OwnershipEvent]) -> String {
+    let timeline = Timeline::new(events);
+    let mut dot = format!("digraph {} {{\n", quote(lesson));
+    dot.push_str("    rankdir=LR;\n");
+    dot.push_str(&format!("    label={};\n", quote(&format!("{lesson}: owners, moves and borrows"))));
+    dot.push_str("    node [shape=box, style=rounded, fontname=\"monospace\"];\n");
+    dot.push_str("    edge [fontname=\"monospace\", fontsize=10];\n\n");
+    for (variable, cells) in &timeline.lanes {
+        let (label, last) = node(variable, cells);
+        let label = label.join("\n");
+        let attributes = match last {
+            Some(Cell::Dropped) => format!("label={}, style=\"rounded,filled\", fillcolor=lightgrey", quote(&label)),
+            Some(Cell::MovedOut) => format!("label={}, style=\"rounded,dashed\"", quote(&label)),
+            Some(Cell::Borrows) => format!("label={}, shape=ellipse", quote(&format!("&{variable}"))),
+            _ => format!("label={}", quote(&label)),
+        };
+        dot.push_str(&format!("    {} [{attributes}];\n", quote(variable)));
+    }
+    dot.push('\n');
+    // Numbered like the timeline's steps, which the node labels use, so calls and returns don't count
+    for (step, event) in timeline.events.iter().enumerate() {
+        let step = step + 1;
+        let edge = match event {
+            OwnershipEvent::Copy { from, to } => format!("{} -> {} [label=\"{step}. copy\", color=grey40]", quote(from), quote(to)),
+            OwnershipEvent::Move { from, to } => format!("{} -> {} [label=\"{step}. move\", penwidth=2]", quote(from), quote(to)),
+            OwnershipEvent::Clone { from, to } => format!("{} -> {} [label=\"{step}. clone\", style=dashed]", quote(from), quote(to)),
+            OwnershipEvent::Borrow { owner, by } => format!("{} -> {} [label=\"{step}. &\", style=dotted, color=blue]", quote(by), quote(owner)),
+            OwnershipEvent::BorrowMut { owner, by } => format!("{} -> {} [label=\"{step}. &mut\", style=dotted, color=red]", quote(by), quote(owner)),
+            OwnershipEvent::Drop { .. } | OwnershipEvent::Call { .. } | OwnershipEvent::Return { .. } => continue,
+        };
+        dot.push_str(&format!("    {edge};\n"));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+// A variable's node in a graph: the lines of its label, and the last thing that happened to it other than
+// staying alive. A name can be reused, e.g. `complex` in every call of a function, so every move out and drop
+// is listed.
+fn node(variable: &str, cells: &[Cell]) -> (Vec<String>, Option<Cell>) {
+    let mut label = vec![variable.to_string()];
+    for (step, cell) in cells.iter().enumerate() {
+        match cell {
+            Cell::MovedOut => label.push(format!("moved out at {}", step + 1)),
+            Cell::Dropped => label.push(format!("dropped at {}", step + 1)),
+            _ => {}
+        }
+    }
+    (label, cells.iter().rev().find(|cell| !matches!(cell, Cell::Empty | Cell::Alive)).copied())
+}
+
+// The same graph as `dot` as a Mermaid flowchart for `export mermaid`, which renders inside a ```mermaid block in
+// Markdown on GitHub, GitLab and most wikis. Owners are boxes, references rounded, and the styles follow `dot`.
+pub fn mermaid(lesson: &str, events: &[OwnershipEvent]) -> String {
+    let timeline = Timeline::new(events);
+    let mut chart = String::from("flowchart LR\n");
+    chart.push_str(&format!("    %% {lesson}: owners, moves and borrows\n"));
+    chart.push_str("    classDef dropped fill:#ddd,color:#666\n");
+    chart.push_str("    classDef moved stroke-dasharray:5 5\n");
+    // Mermaid ids can't have spaces or punctuation, `complex` and `pair.first` become n0, n1, ...
+    let id = |variable: &str| timeline.lanes.iter().position(|(name, _)| name == variable).map_or_else(|| "n".to_string(), |index| format!("n{index}"));
+    for (variable, cells) in &timeline.lanes {
+        let (label, last) = node(variable, cells);
+        let label = label.iter().map(|line| mermaid_text(line)).collect::<Vec<_>>().join("<br>");
+        let node = match last {
+            Some(Cell::Borrows) => format!("{}([\"{}\"])", id(variable), mermaid_text(&format!("&{variable}"))),
+            Some(Cell::Dropped) => format!("{}[\"{label}\"]:::dropped", id(variable)),
+            Some(Cell::MovedOut) => format!("{}[\"{label}\"]:::moved", id(variable)),
+            _ => format!("{}[\"{label}\"]", id(variable)),
+        };
+        chart.push_str(&format!("    {node}\n"));
+    }
+    // linkStyle goes by the index of the edge, in the order they're written
+    let mut edges = 0;
+    let mut styles = Vec::new();
+    for (step, event) in timeline.events.iter().enumerate() {
+        let step = step + 1;
+        let (from, arrow, label, to, color) = match event {
+            OwnershipEvent::Copy { from, to } => (from, "-->", "copy", to, Some("grey")),
+            OwnershipEvent::Move { from, to } => (from, "==>", "move", to, None),
+            OwnershipEvent::Clone { from, to } => (from, "-.->", "clone", to, None),
+            OwnershipEvent::Borrow { owner, by } => (by, "-.->", "&", owner, Some("blue")),
+            OwnershipEvent::BorrowMut { owner, by } => (by, "-.->", "&mut", owner, Some("red")),
+            OwnershipEvent::Drop { .. } | OwnershipEvent::Call { .. } | OwnershipEvent::Return { .. } => continue,
+        };
+        chart.push_str(&format!("    {} {arrow}|\"{step}. {}\"| {}\n", id(from), mermaid_text(label), id(to)));
+        if let Some(color) = color {
+            styles.push(format!("    linkStyle {edges} stroke:{color}\n"));
+        }
+        edges += 1;
+    }
+    for style in styles {
+        chart.push_str(&style);
+    }
+    chart
+}
+
+// Text for inside a quoted Mermaid label, which takes HTML entities spelled with # instead of &
+fn mermaid_text(text: &str) -> String {
+    text.replace('&', "#amp;").replace('"', "#quot;").replace('<', "#lt;").replace('>', "#gt;")
+}
+
+// A DOT string, `\n` in it starts a new line of a label
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::LessonDef;
+
+    // A bit of everything, the call and return around it show up in no lane and in no graph
+    fn events() -> Vec<OwnershipEvent> {
+        vec![
+            OwnershipEvent::called("takes_ownership"),
+            OwnershipEvent::moved("s1", "s2"),
+            OwnershipEvent::cloned("s2", "s3"),
+            OwnershipEvent::borrowed("s3", "r"),
+            OwnershipEvent::borrowed_mut("s2", "m"),
+            OwnershipEvent::copied("x", "y"),
+            OwnershipEvent::returned("takes_ownership"),
+            OwnershipEvent::dropped("s3"),
+        ]
+    }
+
+    #[test]
+    fn timeline_golden() {
+        let expected = "\
+step  1   2   3   4   5   6
+s1    ──▶
+s2    ●────+───────&m─────────
+s3        ●────&────────────✗
+r             ◆
+m                 ◆
+x     ─────────────────c──────
+y                     ●───────
+
+  1  s1 moved to s2
+  2  s2 cloned to s3
+  3  s3 borrowed by r
+  4  s2 mutably borrowed by m
+  5  x copied to y
+  6  s3 dropped
+";
+        assert_eq!(Timeline::new(&events()).render(Style::plain()), expected);
+    }
+
+    #[test]
+    fn tracer_keeps_each_lessons_events_and_passes_them_on() {
+        let traces = Traces::new();
+        let inner = crate::report::BufferReporter::new();
+        let mut tracer = OwnershipTracer::new(inner.clone(), traces.clone());
+        let lessons: Vec<&LessonDef> = crate::registry::lessons().take(2).collect();
+        let events = events();
+        for (lesson, events) in lessons.iter().zip(events.chunks(4)) {
+            tracer.begin_lesson(*lesson);
+            for event in events {
+                tracer.event(event);
+            }
+            tracer.end_lesson(*lesson);
+        }
+        let traced: Vec<Trace> = lessons.iter().zip(events.chunks(4)).map(|(lesson, events)| (lesson.name.to_string(), events.to_vec())).collect();
+        assert_eq!(traces.lessons(), traced);
+        assert_eq!(inner.lessons().iter().map(|(_, output)| output.events.clone()).collect::<Vec<_>>(), traced.into_iter().map(|(_, events)| events).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn states_are_where_each_variable_ended_up() {
+        let timeline = Timeline::new(&events());
+        assert_eq!(timeline.states(), [
+            ("s1", Cell::MovedOut),
+            ("s2", Cell::Alive),
+            ("s3", Cell::Dropped),
+            ("r", Cell::Borrows),
+            ("m", Cell::Borrows),
+            ("x", Cell::Alive),
+            ("y", Cell::Alive),
+        ]);
+    }
+
+    #[test]
+    fn dot_golden() {
+        let expected = r#"digraph "take \"it\"" {
+    rankdir=LR;
+    label="take \"it\": owners, moves and borrows";
+    node [shape=box, style=rounded, fontname="monospace"];
+    edge [fontname="monospace", fontsize=10];
+
+    "s1" [label="s1\nmoved out at 1", style="rounded,dashed"];
+    "s2" [label="s2"];
+    "s3" [label="s3\ndropped at 6", style="rounded,filled", fillcolor=lightgrey];
+    "r" [label="&r", shape=ellipse];
+    "m" [label="&m", shape=ellipse];
+    "x" [label="x"];
+    "y" [label="y"];
+
+    "s1" -> "s2" [label="1. move", penwidth=2];
+    "s2" -> "s3" [label="2. clone", style=dashed];
+    "r" -> "s3" [label="3. &", style=dotted, color=blue];
+    "m" -> "s2" [label="4. &mut", style=dotted, color=red];
+    "x" -> "y" [label="5. copy", color=grey40];
+}
+"#;
+        assert_eq!(dot("take \"it\"", &events()), expected);
+    }
+
+    #[test]
+    fn mermaid_golden() {
+        let expected = r##"flowchart LR
+    %% take "it": owners, moves and borrows
+    classDef dropped fill:#ddd,color:#666
+    classDef moved stroke-dasharray:5 5
+    n0["s1<br>moved out at 1"]:::moved
+    n1["s2"]
+    n2["s3<br>dropped at 6"]:::dropped
+    n3(["#amp;r"])
+    n4(["#amp;m"])
+    n5["x"]
+    n6["y"]
+    n0 ==>|"1. move"| n1
+    n1 -.->|"2. clone"| n2
+    n3 -.->|"3. #amp;"| n2
+    n4 -.->|"4. #amp;mut"| n1
+    n5 -->|"5. copy"| n6
+    linkStyle 2 stroke:blue
+    linkStyle 3 stroke:red
+    linkStyle 4 stroke:grey
+"##;
+        assert_eq!(mermaid("take \"it\"", &events()), expected);
+    }
+}
@@ -0,0 +1,144 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+
+// A value that tells its Tracker what happens to it: its Clone and Drop impls log the clone and the drop as they
+// happen, where a plain String would do the same silently. Moves can't be caught that way, moving runs no code at
+// all, so `moved_to` stands in for `let s2 = s1;` and logs the move.
+//
+//     let tracker = Tracker::new();
+//     let s1 = tracker.track("s1", String::from("hello"));
+//     let s2 = s1.moved_to("s2");
+//     let s3 = s2.clone_as("s3");
+//     drop(s3);
+//     tracker.report(reporter); // s1 moved to s2, s2 cloned to s3, s3 dropped
+pub struct Tracked<T> {
+    name: String,
+    value: T,
+    events: Rc<RefCell<Vec<OwnershipEvent>>>,
+}
+
+// Where Tracked values log to, clones share the same log
+#[derive(Debug, Clone, Default)]
+pub struct Tracker {
+    events: Rc<RefCell<Vec<OwnershipEvent>>>,
+}
+
+impl Tracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // `value`, owned by the variable `name`
+    pub fn track<T>(&self, name: &str, value: T) -> Tracked<T> {
+        Tracked { name: name.to_string(), value, events: Rc::clone(&self.events) }
+    }
+
+    // Everything logged since the last `take`, oldest first
+    pub fn take(&self) -> Vec<OwnershipEvent> {
+        self.events.take()
+    }
+
+    // Hands everything logged so far to `reporter` as ownership events
+    #[track_caller]
+    pub fn report(&self, reporter: &mut dyn Reporter) {
+        for event in self.take() {
+            reporter.event(&event);
+        }
+    }
+}
+
+impl<T> Tracked<T> {
+    // The variable that owns it now
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // `let to = self;`, ownership moves to `to`
+    pub fn moved_to(mut self, to: &str) -> Self {
+        self.log(OwnershipEvent::moved(&self.name, to));
+        self.name = to.to_string();
+        self
+    }
+
+    // `&self`, lent to `by`
+    pub fn borrow_as(&self, by: &str) -> &T {
+        self.log(OwnershipEvent::borrowed(&self.name, by));
+        &self.value
+    }
+
+    // `&mut self`, lent to `by`
+    pub fn borrow_mut_as(&mut self, by: &str) -> &mut T {
+        self.log(OwnershipEvent::borrowed_mut(&self.name, by));
+        &mut self.value
+    }
+
+    fn log(&self, event: OwnershipEvent) {
+        self.events.borrow_mut().push(event);
+    }
+}
+
+impl<T: Clone> Tracked<T> {
+    // `let to = self.clone();`
+    pub fn clone_as(&self, to: &str) -> Self {
+        self.log(OwnershipEvent::cloned(&self.name, to));
+        Self { name: to.to_string(), value: self.value.clone(), events: Rc::clone(&self.events) }
+    }
+}
+
+// A plain `.clone()` doesn't know which variable it's going into, rename it with `moved_to`
+impl<T: Clone> Clone for Tracked<T> {
+    fn clone(&self) -> Self {
+        self.clone_as(&format!("{} (clone)", self.name))
+    }
+}
+
+// The owner went out of scope
+impl<T> Drop for Tracked<T> {
+    fn drop(&mut self) {
+        self.log(OwnershipEvent::dropped(&self.name));
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Tracked<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Tracked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Tracked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tracked").field("name", &self.name).field("value", &self.value).finish()
+    }
+}
+
+// `ownership::take_ownership` with a Tracked String, the parameter becomes the owner and drops it on return
+pub fn take_ownership(complex: Tracked<String>) -> DemoOutput {
+    let complex = complex.moved_to("complex");
+    let mut output = DemoOutput::new();
+    output.message(complex.as_str()).value("complex", &complex);
+    output
+} // complex dropped here, the Tracker logs it
+
+// `ownership::takes_and_gives_back` with a Tracked String, ownership goes in and comes back out
+pub fn takes_and_gives_back(complex: Tracked<String>) -> Tracked<String> {
+    complex.moved_to("complex")
+}
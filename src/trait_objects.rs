@@ -0,0 +1,238 @@
+use std::fmt::Display;
+use std::mem;
+
+use crate::error::LessonError;
+use crate::generics::{self, Crab};
+use crate::layout::{self, Layout};
+use crate::lesson;
+use crate::lesson::{Difficulty, Lesson, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+use crate::runner::LessonRunner;
+
+// A generic function is copied for each T, a function taking `&dyn Display` is compiled once and works out which
+// `fmt` to call while it runs. The `dyn Display` has no size of its own, so it's always behind a pointer, and
+// that pointer is two words: one to the value, one to the vtable, the table of T's methods that the call goes
+// through. That's what lets one Vec hold values of different types, and it's how this crate keeps its lessons:
+// the runner holds a `Vec<Box<dyn Lesson>>`, and every demo is handed a `&mut dyn Reporter`.
+
+pub const TAUGHT: Taught = Taught { day: 13, order: 2 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(trait_objects_demo {
+        name: "trait_objects",
+        summary: "A &dyn Display is a fat pointer, to the value and to its type's vtable",
+        topics: &["generics", "traits"],
+        prerequisites: &["monomorphization", "box_basics"],
+        explanation: "`fn show_dyn(value: &dyn Display)` takes any type that implements Display, like `show<T: Display>`, but there's only one copy of it: the reference carries a second pointer, to a vtable with the type's `fmt`, its size and its drop, and the call looks `fmt` up there. So `&dyn Display` is two words where `&i32` is one. A Vec has one element type, so `vec![5, String::from(\"hello\")]` is rejected (E0308), but a `Vec<Box<dyn Display>>` holds both, each Box owning its value and pointing at its vtable.",
+    }),
+    lesson!(dyn_lessons_demo {
+        name: "dyn_lessons",
+        summary: "The lesson runner keeps its lessons as Box<dyn Lesson>",
+        topics: &["generics", "traits"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["trait_objects"],
+        explanation: "`LessonRunner::register(lesson: impl Lesson + 'static)` is generic, one copy per lesson type, and all it does is box the lesson into a `Box<dyn Lesson>` so the crate's LessonDefs and your own lesson types fit in one Vec. Running one calls `run` through the vtable, and the demo gets its reporter as a `&mut dyn Reporter`, so the same demo prints to stdout, a transcript or a test without being compiled again. Only traits whose methods can go in a vtable can be `dyn`: `clone()` returns a Self, and a dyn caller doesn't know how big that is, so Clone requires `Self: Sized` and `dyn Clone` is rejected (E0038).",
+    }),
+];
+
+// Borrows any value it can print through a trait object, the one copy works for every type
+pub fn show_dyn(value: &dyn Display) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(format!("{value}"));
+    output
+}
+
+// The pointers the lessons measure, thin ones to sized types and fat ones to trait objects
+pub fn dyn_layouts() -> Vec<Layout> {
+    vec![
+        Layout::of::<&i32>("&i32", "a pointer"),
+        Layout::of::<&dyn Display>("&dyn Display", "a pointer to the value and one to the vtable"),
+        Layout::of::<Box<i32>>("Box<i32>", "a pointer to the heap"),
+        Layout::of::<Box<dyn Display>>("Box<dyn Display>", "the same two pointers, and the Box owns the value"),
+        Layout::of::<&LessonDef>("&LessonDef", "a pointer to one lesson's static data"),
+        Layout::of::<&dyn Lesson>("&dyn Lesson", "what `runner.lessons()` yields"),
+        Layout::of::<Box<dyn Lesson>>("Box<dyn Lesson>", "one element of the runner's Vec"),
+        Layout::of::<&mut dyn Reporter>("&mut dyn Reporter", "what every demo is handed"),
+    ]
+}
+
+// Prints the rows of the layout table for the named types
+fn layout_rows(reporter: &mut dyn Reporter, names: &[&str]) {
+    let layouts: Vec<Layout> = dyn_layouts().into_iter().filter(|layout| names.contains(&layout.name)).collect();
+    for line in layout::table(&layouts) {
+        reporter.message(&line);
+    }
+}
+
+const ONE_ELEMENT_TYPE: (&str, &str) = (
+    "let items = vec![5, String::from(\"hello\")];",
+    "error[E0308]: mismatched types\n --> src/main.rs:1:21\n  |\n1 | let items = vec![5, String::from(\"hello\")];\n  |                     ^^^^^^^^^^^^^^^^^^^^^ expected integer, found `String`",
+);
+
+// show and show_dyn side by side, the fat pointer's first word, then values of three types in one Vec
+pub fn trait_objects_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let x = 5;
+    reporter.event(&OwnershipEvent::borrowed("x", "value"));
+    reporter.output(&generics::show(&x)); // show::<i32>, a copy made for i32
+    reporter.event(&OwnershipEvent::borrowed("x", "value"));
+    reporter.output(&show_dyn(&x)); // The one show_dyn, told about i32 by the vtable
+    reporter.message("");
+
+    layout_rows(reporter, &["&i32", "&dyn Display", "Box<i32>", "Box<dyn Display>"]);
+    let thin: &i32 = &x;
+    let fat: &dyn Display = &x; // The same reference, with the vtable pointer added
+    let data = fat as *const dyn Display as *const () as usize; // The first word, cast down to a thin pointer
+    reporter.message(&format!("fat's first word points at x: {}", data == thin as *const i32 as usize));
+    reporter.message(&format!("size_of_val(fat), the size the vtable has for the value: {}", mem::size_of_val(fat)));
+    reporter.message("");
+
+    let (code, error) = ONE_ELEMENT_TYPE;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    let name = String::from("hello");
+    let crab = Crab { name: String::from("ferris") };
+    let items: Vec<Box<dyn Display>> = vec![Box::new(x), Box::new(name), Box::new(crab)]; // Three types, one element type
+    reporter.event(&OwnershipEvent::copied("x", "items[0]"));
+    reporter.event(&OwnershipEvent::moved("name", "items[1]"));
+    reporter.event(&OwnershipEvent::moved("crab", "items[2]"));
+    for item in &items {
+        reporter.output(&show_dyn(item.as_ref()));
+    }
+    drop(items); // Each Box drops its value through the vtable's drop, the String's and the Crab's are freed
+    reporter.event(&OwnershipEvent::dropped("items"));
+    Ok(())
+}
+
+// A lesson type of its own, not a LessonDef, that the runner can hold next to the crate's
+pub struct Recap {
+    pub topic: String,
+}
+
+impl Lesson for Recap {
+    fn name(&self) -> &str {
+        "recap"
+    }
+
+    fn summary(&self) -> &str {
+        "A lesson that isn't a LessonDef"
+    }
+
+    fn run(&self, reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+        reporter.message(&format!("Recap::run, recapping {}", self.topic));
+        Ok(())
+    }
+}
+
+// A LessonDef made on the spot, its run is a plain function pointer
+fn greeting() -> LessonDef {
+    LessonDef {
+        name: "greeting",
+        summary: "A LessonDef like the ones lesson! makes",
+        run: |reporter| {
+            reporter.message("LessonDef::run, through its fn pointer");
+            Ok(())
+        },
+        ..LessonDef::EMPTY
+    }
+}
+
+const DYN_CLONE: (&str, &str) = (
+    "let values: Vec<Box<dyn Clone>> = Vec::new();",
+    "error[E0038]: the trait `Clone` is not dyn compatible\n --> src/main.rs:1:25\n  |\n1 | let values: Vec<Box<dyn Clone>> = Vec::new();\n  |                         ^^^^^ `Clone` is not dyn compatible",
+);
+
+// Two lesson types registered in one runner, run through &dyn Lesson with this demo's own &mut dyn Reporter
+pub fn dyn_lessons_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    layout_rows(reporter, &["&LessonDef", "&dyn Lesson", "Box<dyn Lesson>", "&mut dyn Reporter"]);
+    reporter.message("");
+
+    let mut runner = LessonRunner::new();
+    runner.register(greeting()).register(Recap { topic: String::from("trait objects") }); // register::<LessonDef>, then register::<Recap>
+    reporter.event(&OwnershipEvent::moved("greeting()", "runner"));
+    reporter.event(&OwnershipEvent::moved("recap", "runner"));
+    for lesson in runner.lessons() {
+        // A &dyn Lesson each, name and run are looked up in that lesson's vtable
+        reporter.message(&format!("{}: {}", lesson.name(), lesson.summary()));
+        lesson.run(reporter)?; // Handing on the &mut dyn Reporter this demo was given
+    }
+    let crate_lessons = LessonRunner::with_default_lessons().lessons().count();
+    reporter.message(&format!("the crate's own runner holds {crate_lessons} Box<dyn Lesson>s, each boxing a &'static LessonDef that `impl Lesson for &L` forwards to"));
+    reporter.message("");
+
+    let (code, error) = DYN_CLONE;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trait_objects_are_two_words_and_sized_pointers_one() {
+        for layout in dyn_layouts() {
+            let words = if layout.name.contains("dyn") { 2 } else { 1 };
+            assert_eq!(layout.size, words * size_of::<usize>(), "{}", layout.name);
+        }
+    }
+
+    #[test]
+    fn show_dyn_prints_any_display() {
+        assert_eq!(show_dyn(&5).messages, ["5"]);
+        assert_eq!(show_dyn(&Crab { name: String::from("ferris") }).messages, ["ferris the crab"]);
+    }
+
+    #[test]
+    fn recap_and_greeting_run_as_lessons() {
+        let recap = Recap { topic: String::from("boxes") };
+        assert_eq!(recap.name(), "recap");
+        let mut output = DemoOutput::new();
+        recap.run(&mut output).unwrap();
+        (greeting().run)(&mut output).unwrap();
+        assert_eq!(output.messages, ["Recap::run, recapping boxes", "LessonDef::run, through its fn pointer"]);
+    }
+
+    #[test]
+    fn trait_objects_demo_holds_three_types_in_one_vec() {
+        let mut output = DemoOutput::new();
+        trait_objects_demo(&mut output).unwrap();
+        assert_eq!(output.messages[..2], ["5", "5"]);
+        assert!(output.messages.contains(&"fat's first word points at x: true".to_string()));
+        assert!(output.messages.contains(&"size_of_val(fat), the size the vtable has for the value: 4".to_string()));
+        assert_eq!(output.messages[output.messages.len() - 3..], ["5", "hello", "ferris the crab"]);
+        assert_eq!(
+            output.events[2..],
+            [
+                OwnershipEvent::copied("x", "items[0]"),
+                OwnershipEvent::moved("name", "items[1]"),
+                OwnershipEvent::moved("crab", "items[2]"),
+                OwnershipEvent::dropped("items"),
+            ]
+        );
+    }
+
+    #[test]
+    fn dyn_lessons_demo_runs_both_lesson_types() {
+        let mut output = DemoOutput::new();
+        dyn_lessons_demo(&mut output).unwrap();
+        let start = output.messages.iter().position(|message| message.starts_with("greeting: ")).unwrap();
+        assert_eq!(
+            output.messages[start..start + 4],
+            ["greeting: A LessonDef like the ones lesson! makes", "LessonDef::run, through its fn pointer", "recap: A lesson that isn't a LessonDef", "Recap::run, recapping trait objects"]
+        );
+        let count = LessonRunner::with_default_lessons().lessons().count();
+        assert!(output.messages[start + 4].starts_with(&format!("the crate's own runner holds {count} Box<dyn Lesson>s")));
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0038]")));
+    }
+}
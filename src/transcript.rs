@@ -0,0 +1,134 @@
+use std::cell::RefCell;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::date::Date;
+use crate::error::LessonError;
+use crate::lesson::Lesson;
+use crate::output::OwnershipEvent;
+use crate::progress;
+use crate::report::Reporter;
+
+// A study session written down as it happens, for `--transcript session.log`. Every line gets a timestamp, e.g.
+//
+//     2024-03-09 14:02:11  lesson slicing: Taking part of a string
+//     2024-03-09 14:02:11    first word: hello
+//     2024-03-09 14:05:40  quiz slices-range (slices): 2, correct
+//
+// Clones append to the same file, so one can go to the lesson runner and another stay with the command.
+#[derive(Clone)]
+pub struct Transcript {
+    path: PathBuf,
+    file: Rc<RefCell<File>>,
+}
+
+impl Transcript {
+    // Opens `path` for appending, earlier sessions stay in the file, and starts a new session with `command`
+    pub fn open(path: impl AsRef<Path>, command: &str) -> Result<Self, LessonError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|err| LessonError::BadInput(format!("can't write the transcript to {}: {err}", path.display())))?;
+        let transcript = Self { path, file: Rc::new(RefCell::new(file)) };
+        transcript.record("session", format!("rust_day_6 {command}").trim_end())?;
+        Ok(transcript)
+    }
+
+    // Writes `text` under `kind`, e.g. "explain", lines after the first are indented under it
+    pub fn record(&self, kind: &str, text: &str) -> Result<(), LessonError> {
+        let stamp = timestamp(progress::now());
+        let mut lines = text.lines();
+        let mut entry = format!("{stamp}  {kind} {}\n", lines.next().unwrap_or_default());
+        for line in lines {
+            entry.push_str(&format!("{:width$}    {line}\n", "", width = stamp.len()));
+        }
+        self.file
+            .borrow_mut()
+            .write_all(entry.as_bytes())
+            .map_err(|err| LessonError::BadInput(format!("can't write the transcript to {}: {err}", self.path.display())))
+    }
+}
+
+// e.g. 2024-03-09 14:02:11, in UTC like the rest of the dates
+fn timestamp(seconds: u64) -> String {
+    let time = seconds % 86_400;
+    format!("{} {:02}:{:02}:{:02}", Date::from_timestamp(seconds), time / 3600, time % 3600 / 60, time % 60)
+}
+
+// Passes lesson output on to another reporter and writes it into the transcript as well, keeping the first
+// write error for `finish`
+pub struct TranscriptReporter<R: Reporter> {
+    inner: R,
+    transcript: Transcript,
+    show_details: bool,
+    error: Option<LessonError>,
+}
+
+impl<R: Reporter> TranscriptReporter<R> {
+    pub fn new(inner: R, transcript: Transcript) -> Self {
+        Self { inner, transcript, show_details: false, error: None }
+    }
+
+    // Also write values and ownership events, not just messages
+    pub fn show_details(mut self, show: bool) -> Self {
+        self.show_details = show;
+        self
+    }
+
+    fn record(&mut self, kind: &str, text: &str) {
+        if self.error.is_none() {
+            self.error = self.transcript.record(kind, text).err();
+        }
+    }
+}
+
+impl<R: Reporter> Reporter for TranscriptReporter<R> {
+    fn begin_lesson(&mut self, lesson: &dyn Lesson) {
+        self.record("lesson", &format!("{}: {}", lesson.name(), lesson.summary()));
+        self.inner.begin_lesson(lesson);
+    }
+
+    fn heading(&mut self, lesson: &dyn Lesson) {
+        self.inner.heading(lesson);
+    }
+
+    fn message(&mut self, message: &str) {
+        self.record(" ", message);
+        self.inner.message(message);
+    }
+
+    fn value(&mut self, name: &str, value: &str) {
+        if self.show_details {
+            self.record(" ", &format!("value: {name} = {value:?}"));
+        }
+        self.inner.value(name, value);
+    }
+
+    fn warning(&mut self, message: &str) {
+        self.record(" ", &format!("warning: {message}"));
+        self.inner.warning(message);
+    }
+
+    fn event(&mut self, event: &OwnershipEvent) {
+        if self.show_details {
+            self.record(" ", &format!("ownership: {event}"));
+        }
+        self.inner.event(event);
+    }
+
+    fn end_lesson(&mut self, lesson: &dyn Lesson) {
+        self.inner.end_lesson(lesson);
+    }
+
+    fn finish(&mut self) -> Result<(), LessonError> {
+        self.inner.finish()?;
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::mem;
+
+use crate::diagram::Diagram;
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+
+// A Vec owns its elements, so everything the ownership rules say about a String goes for a String in a Vec:
+// putting it in moves it, and taking it out needs a method that leaves the Vec in a valid state.
+
+pub const TAUGHT: Taught = Taught { day: 9, order: 1 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(vec_push_demo {
+        name: "vec_push",
+        summary: "Pushing a String into a Vec moves it, the Vec owns its elements",
+        topics: &["collections", "ownership"],
+        prerequisites: &["take_ownership"],
+        explanation: "`names.push(name)` moves the String into the Vec, which owns it from then on, and `name` can't be used anymore. Push a clone to keep your own. The elements live in one heap block the Vec points at, and dropping the Vec drops every element with it. `&names[1..]` borrows part of it as a slice, a `&[String]`, without copying anything.",
+    }),
+    lesson!(vec_iteration_demo {
+        name: "vec_iteration",
+        summary: "Iterating by reference, by mutable reference and with into_iter()",
+        topics: &["collections", "references"],
+        prerequisites: &["vec_push", "reference"],
+        explanation: "`for name in &names` borrows each element, `name` is a `&String` and the Vec is untouched. `for name in &mut names` hands out `&mut String`s, so the elements can be changed in place. `for name in names`, which is `names.into_iter()`, moves each String out to the loop and consumes the Vec, it can't be used afterwards.",
+    }),
+    lesson!(vec_indexing_demo {
+        name: "vec_indexing",
+        summary: "Why v[0] of a Vec<String> can't be moved out, and what takes an element instead",
+        topics: &["collections", "ownership"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["vec_iteration"],
+        explanation: "`let first = names[0];` would move the String out and leave a hole in the Vec, so rustc rejects it (E0507). Borrow it with `&names[0]` or clone it. To take ownership, use a method that keeps the Vec whole: `pop` takes the last, `remove(i)` shifts everything after i down, `swap_remove(i)` moves the last element into the gap, which is quicker but changes the order, and `mem::take(&mut names[i])` leaves an empty String behind.",
+    }),
+];
+
+// Lists the elements of a slice of the Vec, borrowing them
+pub fn list(names: &[String]) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    output.message(names.join(", ")).value("len", names.len());
+    output
+}
+
+// Moves three Strings into a Vec, one as a clone, then borrows part of it as a slice
+pub fn vec_push_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let mut names = Vec::new();
+    let ferris = String::from("ferris");
+    names.push(ferris); // ferris moves into the Vec
+    reporter.event(&OwnershipEvent::moved("ferris", "names[0]"));
+
+    let corro = String::from("corro");
+    names.push(corro.clone()); // A clone goes in, corro keeps its own
+    reporter.event(&OwnershipEvent::cloned("corro", "names[1]"));
+    names.push(String::from("rusty"));
+    reporter.message(&format!("ferris: error, corro: {corro}, names: {names:?}"));
+    for line in Diagram::new().vec("names", &names).render() {
+        reporter.message(&line);
+    }
+    reporter.message("each cell is a whole String, its own pointer, length and capacity, with the text in a block of its own");
+
+    let rest = &names[1..]; // A &[String], borrowing the last two elements
+    reporter.event(&OwnershipEvent::borrowed("names", "rest"));
+    reporter.output(&list(rest));
+
+    drop(names); // Drops the Vec and every String in it
+    reporter.event(&OwnershipEvent::dropped("names"));
+    reporter.message(&format!("corro: {corro}")); // Still there, it was a clone that went in
+    Ok(())
+}
+
+// Reads by reference, changes through mutable references, then consumes the Vec with into_iter
+pub fn vec_iteration_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let mut names = vec![String::from("ferris"), String::from("corro")];
+
+    reporter.event(&OwnershipEvent::borrowed("names", "name"));
+    for name in &names {
+        // name is a &String
+        reporter.message(&format!("{name} has {} letters", name.len()));
+    }
+
+    reporter.event(&OwnershipEvent::borrowed_mut("names", "name"));
+    for name in &mut names {
+        // name is a &mut String, the change is made inside the Vec
+        name.push_str("_the_crab");
+    }
+    reporter.message(&format!("names: {names:?}"));
+
+    let lengths: Vec<usize> = names.iter().map(|name| name.len()).collect(); // iter() borrows like &names
+    reporter.message(&format!("lengths: {lengths:?}, names still usable: {}", names.len()));
+
+    reporter.event(&OwnershipEvent::moved("names", "into_iter"));
+    for name in names {
+        // name is a String, moved out of the Vec, and dropped at the end of each pass
+        reporter.event(&OwnershipEvent::moved("names[i]", "name"));
+        reporter.message(&format!("took {name}"));
+        reporter.event(&OwnershipEvent::dropped("name"));
+    }
+    reporter.message("names: error"); // into_iter consumed it
+    Ok(())
+}
+
+const MOVE_OUT_OF_INDEX: (&str, &str) = (
+    "let names = vec![String::from(\"ferris\")];\nlet first = names[0];",
+    "error[E0507]: cannot move out of index of `Vec<String>`\n --> src/main.rs:2:13\n  |\n2 | let first = names[0];\n  |             ^^^^^^^^ move occurs because value has type `String`, which does not implement the `Copy` trait",
+);
+
+// The rejected move out of an index, then the ways of borrowing or taking an element that rustc accepts
+pub fn vec_indexing_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let (code, error) = MOVE_OUT_OF_INDEX;
+    for line in code.lines() {
+        reporter.message(&format!("    {line}"));
+    }
+    for line in error.lines() {
+        reporter.message(line);
+    }
+    reporter.message("");
+
+    let mut names: Vec<String> = ["ferris", "corro", "rusty", "gopher"].map(String::from).to_vec();
+    let first = &names[0]; // Borrowing is fine
+    reporter.event(&OwnershipEvent::borrowed("names[0]", "first"));
+    let copy = names[0].clone(); // So is cloning
+    reporter.event(&OwnershipEvent::cloned("names[0]", "copy"));
+    reporter.message(&format!("first: {first}, copy: {copy}"));
+
+    let last = names.pop(); // An Option, the Vec might have been empty
+    reporter.event(&OwnershipEvent::moved("names[3]", "last"));
+    reporter.message(&format!("pop: {last:?}, names: {names:?}"));
+
+    let removed = names.remove(0); // Everything after index 0 shifts down one place
+    reporter.event(&OwnershipEvent::moved("names[0]", "removed"));
+    reporter.message(&format!("remove(0): {removed}, names: {names:?}"));
+
+    names.extend(["gopher", "duke"].map(String::from));
+    reporter.message(&format!("names: {names:?}"));
+    let swapped = names.swap_remove(0); // The last element moves into index 0, nothing else moves
+    reporter.event(&OwnershipEvent::moved("names[0]", "swapped"));
+    reporter.event(&OwnershipEvent::moved("names[3]", "names[0]"));
+    reporter.message(&format!("swap_remove(0): {swapped}, names: {names:?}"));
+
+    let taken = mem::take(&mut names[1]); // An empty String takes its place, the Vec keeps its length
+    reporter.event(&OwnershipEvent::moved("names[1]", "taken"));
+    reporter.message(&format!("mem::take(&mut names[1]): {taken}, names: {names:?}"));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_borrows_the_slice() {
+        let names = [String::from("ferris"), String::from("corro"), String::from("rusty")];
+        let output = list(&names[1..]);
+        assert_eq!(output.messages, ["corro, rusty"]);
+        assert_eq!(output.values, [("len".to_string(), "2".to_string())]);
+        assert_eq!(list(&[]).messages, [""]);
+    }
+
+    #[test]
+    fn vec_push_demo_keeps_the_clone_after_the_vec_is_gone() {
+        let mut output = DemoOutput::new();
+        vec_push_demo(&mut output).unwrap();
+        assert_eq!(output.messages[0], "ferris: error, corro: corro, names: [\"ferris\", \"corro\", \"rusty\"]");
+        assert!(output.messages.iter().any(|message| message.contains("│ ferris │ corro │ rusty │")));
+        assert_eq!(output.messages[output.messages.len() - 2..], ["corro, rusty", "corro: corro"]);
+        assert_eq!(
+            output.events,
+            [
+                OwnershipEvent::moved("ferris", "names[0]"),
+                OwnershipEvent::cloned("corro", "names[1]"),
+                OwnershipEvent::borrowed("names", "rest"),
+                OwnershipEvent::dropped("names"),
+            ]
+        );
+    }
+
+    #[test]
+    fn vec_iteration_demo_borrows_changes_then_consumes() {
+        let mut output = DemoOutput::new();
+        vec_iteration_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages,
+            [
+                "ferris has 6 letters",
+                "corro has 5 letters",
+                "names: [\"ferris_the_crab\", \"corro_the_crab\"]",
+                "lengths: [15, 14], names still usable: 2",
+                "took ferris_the_crab",
+                "took corro_the_crab",
+                "names: error",
+            ]
+        );
+        assert_eq!(output.events[2], OwnershipEvent::moved("names", "into_iter"));
+    }
+
+    #[test]
+    fn vec_indexing_demo_takes_elements_out_without_moving_out_of_an_index() {
+        let mut output = DemoOutput::new();
+        vec_indexing_demo(&mut output).unwrap();
+        assert!(output.messages.iter().any(|message| message.starts_with("error[E0507]: cannot move out of index")));
+        assert_eq!(
+            output.messages[output.messages.len() - 6..],
+            [
+                "first: ferris, copy: ferris",
+                "pop: Some(\"gopher\"), names: [\"ferris\", \"corro\", \"rusty\"]",
+                "remove(0): ferris, names: [\"corro\", \"rusty\"]",
+                "names: [\"corro\", \"rusty\", \"gopher\", \"duke\"]",
+                "swap_remove(0): corro, names: [\"duke\", \"rusty\", \"gopher\"]",
+                "mem::take(&mut names[1]): rusty, names: [\"duke\", \"\", \"gopher\"]",
+            ]
+        );
+        assert_eq!(output.events.last(), Some(&OwnershipEvent::moved("names[1]", "taken")));
+    }
+}
@@ -0,0 +1,264 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use crate::allocations;
+use crate::error::LessonError;
+use crate::lesson;
+use crate::lesson::{Difficulty, LessonDef, Taught};
+use crate::output::{DemoOutput, OwnershipEvent};
+use crate::report::Reporter;
+use crate::tracked::{Tracked, Tracker};
+
+// An Rc is freed when its strong count reaches 0, so two Rcs that own each other never get there: dropping the
+// variables takes each count down to 1, held by the other, and both leak. A Weak<T> points at an Rc's value
+// without owning it, it bumps the weak count instead, which doesn't keep the value alive. Cycles are broken by
+// making one direction weak, in a tree the child's pointer back to its parent.
+
+pub const TAUGHT: Taught = Taught { day: 11, order: 4 };
+pub static LESSONS: &[LessonDef] = &[
+    lesson!(rc_cycle_demo {
+        name: "rc_cycle",
+        summary: "Two Rcs that own each other are never dropped, the memory leaks",
+        topics: &["smart-pointers", "ownership"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["rc_refcell"],
+        explanation: "If a owns an Rc of b and b owns an Rc of a, each strong count is 2. Dropping the variables `a` and `b` takes both counts to 1, which is the other one's pointer, and neither reaches 0, so nothing is dropped and nothing is freed. Rust's ownership rules don't prevent this, a leak is safe, just wasteful. With the count-allocations feature the demo counts the allocations that are never freed.",
+    }),
+    lesson!(weak_fix_demo {
+        name: "weak_fix",
+        summary: "Rc::downgrade makes a Weak that points at the value without owning it",
+        topics: &["smart-pointers", "ownership"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["rc_cycle"],
+        explanation: "`Rc::downgrade(&a)` returns a `Weak<T>` and adds 1 to the weak count, not the strong count, so it doesn't keep the value alive. To use it, `upgrade()` returns an `Option<Rc<T>>`: Some while an owner still has the value, None once it's been dropped. Making one side of the cycle a Weak means the strong counts can reach 0 again, and both values are dropped.",
+    }),
+    lesson!(weak_tree_demo {
+        name: "weak_tree",
+        summary: "A tree where parents own their children and children hold a Weak to their parent",
+        topics: &["smart-pointers", "ownership"],
+        difficulty: Difficulty::Challenge,
+        prerequisites: &["weak_fix", "recursive_types"],
+        explanation: "Each node owns its children through `RefCell<Vec<Rc<Node>>>`, and points at its parent through `RefCell<Weak<Node>>`. A parent going away should drop its children, a child going away shouldn't drop its parent, so the ownership only runs downwards. A leaf's `parent.borrow().upgrade()` finds the parent while it's alive, and None after, instead of keeping it around forever.",
+    }),
+];
+
+// Runs `f` and counts the allocations it never freed, None when the crate doesn't count allocations
+fn unfreed(f: impl FnOnce()) -> Option<usize> {
+    let before = allocations::counts();
+    f();
+    let after = allocations::counts();
+    let spent = after?.since(&before?);
+    Some(spent.allocations.saturating_sub(spent.frees))
+}
+
+// What the allocation counter saw, or how to get it to count
+fn unfreed_message(unfreed: Option<usize>) -> String {
+    match unfreed {
+        Some(count) => format!("allocations never freed: {count}"),
+        None => "run with `cargo run --features count-allocations` to count the allocations never freed".to_string(),
+    }
+}
+
+// A person who may own another, the Rc in `friend` is what makes the cycle
+pub struct Person {
+    pub name: Tracked<String>,
+    pub friend: RefCell<Option<Rc<Person>>>,
+}
+
+impl Person {
+    pub fn new(tracker: &Tracker, name: &str) -> Rc<Person> {
+        Rc::new(Person { name: tracker.track(&format!("{name}.name"), name.to_string()), friend: RefCell::new(None) })
+    }
+}
+
+// Two people owning each other, dropped at the end of the closure in the demo, neither of them freed. Weaks to
+// them come back so the counts can still be read afterwards
+fn leak_pair(tracker: &Tracker) -> ((usize, usize), Weak<Person>, Weak<Person>) {
+    let a = Person::new(tracker, "a");
+    let b = Person::new(tracker, "b");
+    *a.friend.borrow_mut() = Some(Rc::clone(&b));
+    *b.friend.borrow_mut() = Some(Rc::clone(&a)); // The cycle is closed, a -> b -> a
+    ((Rc::strong_count(&a), Rc::strong_count(&b)), Rc::downgrade(&a), Rc::downgrade(&b))
+} // a and b go out of scope here, each count goes from 2 to 1
+
+// Builds the cycle, drops both variables and watches nothing get dropped
+pub fn rc_cycle_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let tracker = Tracker::new();
+    let mut pair = None;
+    let unfreed = unfreed(|| pair = Some(leak_pair(&tracker)));
+    let Some(((a_count, b_count), a, b)) = pair else {
+        return Err(LessonError::BadInput("leak_pair didn't run".to_string()));
+    };
+    reporter.event(&OwnershipEvent::cloned("b", "a.friend")); // Rc::clone, a second owner of b
+    reporter.event(&OwnershipEvent::cloned("a", "b.friend"));
+    reporter.message(&format!("a.friend and b.friend are owners too, strong counts inside: a {a_count}, b {b_count}"));
+    reporter.message(&format!("a goes out of scope, strong count {a_count} → {}, not dropped", a.strong_count()));
+    reporter.message(&format!("b goes out of scope, strong count {b_count} → {}, not dropped", b.strong_count()));
+    tracker.report(reporter); // Logs nothing, neither String was dropped
+    reporter.message("the variables are gone, but there's no \"a.name dropped\" or \"b.name dropped\": each is still owned by the other");
+    reporter.message(&unfreed_message(unfreed));
+    Ok(())
+}
+
+// A person who knows another without owning them
+pub struct Acquaintance {
+    pub name: Tracked<String>,
+    pub friend: RefCell<Option<Rc<Acquaintance>>>,
+    pub knows: RefCell<Weak<Acquaintance>>,
+}
+
+impl Acquaintance {
+    pub fn new(tracker: &Tracker, name: &str) -> Rc<Acquaintance> {
+        Rc::new(Acquaintance { name: tracker.track(&format!("{name}.name"), name.to_string()), friend: RefCell::new(None), knows: RefCell::new(Weak::new()) })
+    }
+}
+
+// The same pair, a owning b and b knowing a through a Weak
+fn weak_pair(tracker: &Tracker) -> DemoOutput {
+    let mut output = DemoOutput::new();
+    let a = Acquaintance::new(tracker, "a");
+    let b = Acquaintance::new(tracker, "b");
+    *a.friend.borrow_mut() = Some(Rc::clone(&b));
+    *b.knows.borrow_mut() = Rc::downgrade(&a); // Weak count 1, the strong count stays 1
+    output.message(format!("a: strong {}, weak {}", Rc::strong_count(&a), Rc::weak_count(&a)));
+    output.message(format!("b: strong {}, weak {}", Rc::strong_count(&b), Rc::weak_count(&b)));
+    if let Some(known) = b.knows.borrow().upgrade() {
+        output.message(format!("b.knows.upgrade(): Some({}), strong count of a while it's held: {}", *known.name, Rc::strong_count(&a)));
+    }
+    output
+} // a's count reaches 0, a is dropped and with it its Rc of b, so b's reaches 0 too
+
+// The cycle with one side weak, both values are dropped, then upgrade after the owner is gone
+pub fn weak_fix_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let tracker = Tracker::new();
+    let unfreed = unfreed(|| {
+        // Reported inside, so the logged drops are counted as freed too
+        reporter.output(&weak_pair(&tracker));
+        reporter.event(&OwnershipEvent::dropped("a"));
+        reporter.event(&OwnershipEvent::dropped("b"));
+        tracker.report(reporter); // Both Strings are dropped this time
+    });
+    reporter.message(&unfreed_message(unfreed));
+
+    let owner = Rc::new(String::from("ferris"));
+    let weak = Rc::downgrade(&owner);
+    reporter.message(&format!("upgrade while owner is alive: {:?}", weak.upgrade()));
+    drop(owner);
+    reporter.event(&OwnershipEvent::dropped("owner"));
+    reporter.message(&format!("upgrade after owner is dropped: {:?}", weak.upgrade()));
+    Ok(())
+}
+
+// A tree node, owning its children and only pointing at its parent
+pub struct TreeNode {
+    pub value: Tracked<i32>,
+    pub parent: RefCell<Weak<TreeNode>>,
+    pub children: RefCell<Vec<Rc<TreeNode>>>,
+}
+
+impl TreeNode {
+    pub fn new(tracker: &Tracker, name: &str, value: i32) -> Rc<TreeNode> {
+        Rc::new(TreeNode { value: tracker.track(&format!("{name}.value"), value), parent: RefCell::new(Weak::new()), children: RefCell::new(Vec::new()) })
+    }
+
+    // Makes `child` one of this node's children and this node its parent
+    pub fn adopt(parent: &Rc<TreeNode>, child: &Rc<TreeNode>) {
+        parent.children.borrow_mut().push(Rc::clone(child));
+        *child.parent.borrow_mut() = Rc::downgrade(parent);
+    }
+}
+
+// The node's counts and what its parent pointer finds
+fn counts(name: &str, node: &Rc<TreeNode>) -> String {
+    let parent = node.parent.borrow().upgrade().map(|parent| *parent.value);
+    format!("{name}: strong {}, weak {}, parent {parent:?}", Rc::strong_count(node), Rc::weak_count(node))
+}
+
+// The book's leaf and branch, with the counts as the branch comes and goes
+pub fn weak_tree_demo(reporter: &mut dyn Reporter) -> Result<(), LessonError> {
+    let tracker = Tracker::new();
+    let leaf = TreeNode::new(&tracker, "leaf", 3);
+    reporter.message(&counts("leaf", &leaf));
+    {
+        let branch = TreeNode::new(&tracker, "branch", 5);
+        TreeNode::adopt(&branch, &leaf); // branch owns a second Rc of leaf, leaf has a Weak to branch
+        reporter.event(&OwnershipEvent::cloned("leaf", "branch.children[0]"));
+        reporter.message(&counts("branch", &branch));
+        reporter.message(&counts("leaf", &leaf));
+    } // branch dropped here, its strong count was 1, the Weak in leaf didn't count
+    reporter.event(&OwnershipEvent::dropped("branch"));
+    tracker.report(reporter);
+    reporter.message(&counts("leaf", &leaf));
+
+    let unfreed = unfreed(|| {
+        let root = TreeNode::new(&tracker, "root", 1);
+        for (name, value) in [("left", 2), ("right", 3)] {
+            TreeNode::adopt(&root, &TreeNode::new(&tracker, name, value));
+        }
+        drop(root); // root goes, and its children with it
+        reporter.event(&OwnershipEvent::dropped("root"));
+        tracker.report(reporter);
+    });
+    reporter.message(&unfreed_message(unfreed));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unfreed_message_with_and_without_the_counter() {
+        assert_eq!(unfreed_message(Some(4)), "allocations never freed: 4");
+        assert!(unfreed_message(None).contains("--features count-allocations"));
+    }
+
+    #[test]
+    fn rc_cycle_demo_drops_nothing() {
+        let mut output = DemoOutput::new();
+        rc_cycle_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages[..3],
+            [
+                "a.friend and b.friend are owners too, strong counts inside: a 2, b 2",
+                "a goes out of scope, strong count 2 → 1, not dropped",
+                "b goes out of scope, strong count 2 → 1, not dropped",
+            ]
+        );
+        assert_eq!(output.events, [OwnershipEvent::cloned("b", "a.friend"), OwnershipEvent::cloned("a", "b.friend")]);
+    }
+
+    #[test]
+    fn weak_fix_demo_drops_both_and_upgrade_fails_after() {
+        let mut output = DemoOutput::new();
+        weak_fix_demo(&mut output).unwrap();
+        assert_eq!(output.messages[..3], ["a: strong 1, weak 1", "b: strong 2, weak 0", "b.knows.upgrade(): Some(a), strong count of a while it's held: 2"]);
+        assert_eq!(output.messages[output.messages.len() - 2..], ["upgrade while owner is alive: Some(\"ferris\")", "upgrade after owner is dropped: None"]);
+        let dropped = ["a", "b", "a.name", "b.name", "owner"].map(OwnershipEvent::dropped);
+        assert_eq!(output.events, dropped);
+    }
+
+    #[test]
+    fn adopt_points_the_child_back_at_its_parent() {
+        let tracker = Tracker::new();
+        let parent = TreeNode::new(&tracker, "parent", 1);
+        let child = TreeNode::new(&tracker, "child", 2);
+        TreeNode::adopt(&parent, &child);
+        assert_eq!(counts("child", &child), "child: strong 2, weak 0, parent Some(1)");
+        assert_eq!(counts("parent", &parent), "parent: strong 1, weak 1, parent None");
+        drop(parent);
+        assert_eq!(counts("child", &child), "child: strong 1, weak 0, parent None");
+    }
+
+    #[test]
+    fn weak_tree_demo_drops_the_branch_but_not_the_leaf() {
+        let mut output = DemoOutput::new();
+        weak_tree_demo(&mut output).unwrap();
+        assert_eq!(
+            output.messages[..4],
+            ["leaf: strong 1, weak 0, parent None", "branch: strong 1, weak 1, parent None", "leaf: strong 2, weak 0, parent Some(5)", "leaf: strong 1, weak 0, parent None"]
+        );
+        assert_eq!(output.events[..3], [OwnershipEvent::cloned("leaf", "branch.children[0]"), OwnershipEvent::dropped("branch"), OwnershipEvent::dropped("branch.value")]);
+        assert!(!output.events.contains(&OwnershipEvent::dropped("leaf.value")));
+    }
+}